@@ -0,0 +1,190 @@
+//! Module containing the functions executed by the GUI to receive the traffic captured by a remote agent.
+
+use std::io::BufReader;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rustls::{ClientConnection, StreamOwned};
+
+use crate::agent::tls::{get_client_config, get_server_name};
+use crate::agent::types::agent_message::{
+    read_message, write_message, AgentHello, AgentUpdate, AgentWelcome, MAX_MESSAGE_LEN,
+};
+use crate::agent::types::agent_params::AgentParams;
+use crate::InfoTraffic;
+
+/// Encrypted connection with an agent
+pub type AgentStream = BufReader<StreamOwned<ClientConnection, TcpStream>>;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// If no update is received within this interval, the agent is considered unreachable
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Connects and authenticates to the agent.
+///
+/// Returns the established connection and the name of the adapter the agent is capturing from
+pub fn connect_to_agent(params: &AgentParams) -> Result<(AgentStream, String), String> {
+    let address = params
+        .address
+        .trim()
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("cannot resolve {}", params.address.trim()))?;
+    let tcp_stream =
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    tcp_stream
+        .set_read_timeout(Some(CONNECT_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let connection =
+        ClientConnection::new(get_client_config(&params.fingerprint), get_server_name())
+            .map_err(|e| e.to_string())?;
+    let mut stream = BufReader::new(StreamOwned::new(connection, tcp_stream));
+
+    let hello = AgentHello {
+        token: params.token.clone(),
+    };
+    write_message(stream.get_mut(), &hello)?;
+    let welcome: AgentWelcome = read_message(&mut stream, MAX_MESSAGE_LEN)?;
+    if !welcome.authenticated {
+        return Err("the agent rejected the supplied token".to_string());
+    }
+    stream
+        .get_ref()
+        .sock
+        .set_read_timeout(Some(UPDATE_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    Ok((stream, welcome.adapter))
+}
+
+/// The calling thread keeps receiving updates from the agent and merges them into the shared
+/// traffic statistics, until the connection is lost or a capture other than `capture_id` is started
+pub fn receive_updates_loop(
+    current_capture_id: &Arc<Mutex<u16>>,
+    capture_id: u16,
+    mut stream: AgentStream,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+) -> Result<(), String> {
+    loop {
+        let update: AgentUpdate = read_message(&mut stream, MAX_MESSAGE_LEN)?;
+        if *current_capture_id.lock().unwrap() != capture_id {
+            return Ok(());
+        }
+        update.apply_to(&mut info_traffic_mutex.lock().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Instant;
+
+    use crate::agent::server::{spawn_agent_server, MAX_AGENT_CLIENTS};
+    use crate::cli::AgentArgs;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+
+    use super::*;
+
+    /// Starts an agent with a new certificate and `secret` as token,
+    /// returning its address and the certificate fingerprint
+    fn spawn_test_agent(name: &str, agent_traffic: &Arc<Mutex<InfoTraffic>>) -> (String, String) {
+        let listen = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let dir = std::env::temp_dir();
+        let cert: PathBuf = dir.join(format!("sniffnet_test_agent_{name}_cert.pem"));
+        let key: PathBuf = dir.join(format!("sniffnet_test_agent_{name}_key.pem"));
+        let certificate =
+            rcgen::generate_simple_self_signed(vec!["sniffnet-agent".to_string()]).unwrap();
+        std::fs::write(&cert, certificate.serialize_pem().unwrap()).unwrap();
+        std::fs::write(&key, certificate.serialize_private_key_pem()).unwrap();
+        let args = AgentArgs {
+            listen,
+            token: "secret".to_string(),
+            cert: Some(cert),
+            key: Some(key),
+        };
+        let fingerprint = spawn_agent_server(&args, "eth0", agent_traffic).unwrap();
+        (listen.to_string(), fingerprint)
+    }
+
+    fn params(address: &str, token: &str, fingerprint: &str) -> AgentParams {
+        AgentParams {
+            address: address.to_string(),
+            token: token.to_string(),
+            fingerprint: fingerprint.to_string(),
+        }
+    }
+
+    /// Waits until the condition is satisfied, failing if it takes too long
+    fn wait_until(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition not met in time");
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_gui_receives_traffic_from_agent() {
+        let agent_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+        agent_traffic
+            .lock()
+            .unwrap()
+            .add_packet(1500, TrafficDirection::Incoming);
+        let (address, fingerprint) = spawn_test_agent("e2e", &agent_traffic);
+
+        assert!(connect_to_agent(&params(&address, "wrong", &fingerprint)).is_err());
+        assert!(connect_to_agent(&params(&address, "secret", "AA:BB")).is_err());
+
+        let (stream, adapter) =
+            connect_to_agent(&params(&address, "secret", &fingerprint.to_lowercase())).unwrap();
+        assert_eq!(adapter, "eth0");
+        let current_capture_id = Arc::new(Mutex::new(0));
+        let gui_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+        let gui_traffic2 = gui_traffic.clone();
+        let current_capture_id2 = current_capture_id.clone();
+        let receiver = thread::spawn(move || {
+            receive_updates_loop(&current_capture_id2, 0, stream, &gui_traffic2)
+        });
+
+        wait_until(|| gui_traffic.lock().unwrap().tot_received_bytes == 1500);
+        agent_traffic
+            .lock()
+            .unwrap()
+            .add_packet(500, TrafficDirection::Outgoing);
+        wait_until(|| gui_traffic.lock().unwrap().tot_sent_bytes == 500);
+        assert_eq!(gui_traffic.lock().unwrap().tot_received_packets, 1);
+
+        // a new capture stops the updates
+        *current_capture_id.lock().unwrap() += 1;
+        wait_until(|| receiver.is_finished());
+        assert_eq!(receiver.join().unwrap(), Ok(()));
+        agent_traffic
+            .lock()
+            .unwrap()
+            .add_packet(500, TrafficDirection::Outgoing);
+        assert_eq!(gui_traffic.lock().unwrap().tot_sent_bytes, 500);
+    }
+
+    #[test]
+    fn test_agent_refuses_too_many_guis() {
+        let agent_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+        let (address, fingerprint) = spawn_test_agent("limit", &agent_traffic);
+        let params = params(&address, "secret", &fingerprint);
+
+        let mut streams: Vec<AgentStream> = (0..MAX_AGENT_CLIENTS)
+            .map(|_| connect_to_agent(&params).unwrap().0)
+            .collect();
+        assert!(connect_to_agent(&params).is_err());
+
+        // a slot is freed when a GUI disconnects
+        drop(streams.pop());
+        wait_until(|| connect_to_agent(&params).is_ok());
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(not(target_arch = "powerpc64"))]
+pub mod client;
+#[cfg(not(target_arch = "powerpc64"))]
+pub mod server;
+#[cfg(not(target_arch = "powerpc64"))]
+pub mod tls;
+pub mod types;
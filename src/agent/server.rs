@@ -0,0 +1,127 @@
+//! Module containing the functions executed by the agent to stream the captured traffic
+//! to the connected GUI instances.
+
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rustls::{ServerConfig, ServerConnection, StreamOwned};
+
+use crate::agent::tls::{get_fingerprint, get_server_config, load_certificate};
+use crate::agent::types::agent_message::{read_message, write_message, AgentHello, AgentWelcome};
+use crate::agent::types::agent_tracker::AgentTracker;
+use crate::cli::AgentArgs;
+use crate::InfoTraffic;
+
+/// Interval between two consecutive updates sent to a GUI
+pub const AGENT_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum length of the authentication message
+const MAX_HELLO_LEN: u64 = 4096;
+
+/// Maximum number of GUIs connected at the same time; further connections are refused
+pub const MAX_AGENT_CLIENTS: usize = 8;
+
+/// A GUI that doesn't read or send data within this interval is disconnected
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Starts listening for GUI connections in a separate thread.
+///
+/// Returns the fingerprint of the certificate in use, to be supplied to the GUI
+pub fn spawn_agent_server(
+    args: &AgentArgs,
+    adapter: &str,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+) -> Result<String, String> {
+    let (certs, key) = load_certificate(args.cert.as_deref(), args.key.as_deref())?;
+    let fingerprint = get_fingerprint(&certs[0]);
+    let config = get_server_config(certs, key)?;
+    let listener = TcpListener::bind(args.listen)
+        .map_err(|e| format!("cannot listen on {}: {e}", args.listen))?;
+
+    let token = args.token.clone();
+    let adapter = adapter.to_string();
+    let info_traffic_mutex = info_traffic_mutex.clone();
+    let connected_clients = Arc::new(AtomicUsize::new(0));
+    thread::Builder::new()
+        .name("thread_agent_server".to_string())
+        .spawn(move || {
+            for tcp_stream in listener.incoming().flatten() {
+                let peer = tcp_stream
+                    .peer_addr()
+                    .map(|address| address.to_string())
+                    .unwrap_or_default();
+                if connected_clients.fetch_add(1, Ordering::SeqCst) >= MAX_AGENT_CLIENTS {
+                    connected_clients.fetch_sub(1, Ordering::SeqCst);
+                    eprintln!("sniffnet: connection with {peer} refused: too many clients");
+                    continue;
+                }
+                let config = config.clone();
+                let token = token.clone();
+                let adapter = adapter.clone();
+                let info_traffic_mutex = info_traffic_mutex.clone();
+                let clients = connected_clients.clone();
+                let spawned = thread::Builder::new()
+                    .name("thread_agent_connection".to_string())
+                    .spawn(move || {
+                        if let Err(e) =
+                            serve_gui(tcp_stream, config, &token, &adapter, &info_traffic_mutex)
+                        {
+                            eprintln!("sniffnet: connection with {peer} closed: {e}");
+                        }
+                        clients.fetch_sub(1, Ordering::SeqCst);
+                    });
+                if spawned.is_err() {
+                    connected_clients.fetch_sub(1, Ordering::SeqCst);
+                }
+            }
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(fingerprint)
+}
+
+/// Authenticates a GUI and keeps sending it traffic updates until the connection is closed
+fn serve_gui(
+    tcp_stream: TcpStream,
+    config: Arc<ServerConfig>,
+    token: &str,
+    adapter: &str,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+) -> Result<(), String> {
+    tcp_stream
+        .set_read_timeout(Some(SOCKET_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    tcp_stream
+        .set_write_timeout(Some(SOCKET_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let connection = ServerConnection::new(config).map_err(|e| e.to_string())?;
+    let mut stream = BufReader::new(StreamOwned::new(connection, tcp_stream));
+
+    let hello: AgentHello = read_message(&mut stream, MAX_HELLO_LEN)?;
+    let authenticated =
+        ring::constant_time::verify_slices_are_equal(hello.token.as_bytes(), token.as_bytes())
+            .is_ok();
+    let welcome = AgentWelcome {
+        authenticated,
+        adapter: if authenticated {
+            adapter.to_string()
+        } else {
+            String::new()
+        },
+    };
+    write_message(stream.get_mut(), &welcome)?;
+    if !authenticated {
+        return Err("invalid token".to_string());
+    }
+
+    let mut tracker = AgentTracker::default();
+    loop {
+        let update = tracker.next_update(&info_traffic_mutex.lock().unwrap());
+        write_message(stream.get_mut(), &update)?;
+        thread::sleep(AGENT_UPDATE_INTERVAL);
+    }
+}
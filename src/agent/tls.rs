@@ -0,0 +1,202 @@
+//! Module containing the TLS configuration used by the agent and by the GUI connecting to it.
+//!
+//! The agent usually runs with a self-signed certificate: the GUI doesn't validate it against
+//! certificate authorities, but checks that its SHA-256 fingerprint matches the one supplied by the user.
+
+use std::fmt::Write;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Write as _};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use ring::digest::{digest, SHA256};
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, PrivateKey, ServerConfig, ServerName};
+use rustls_pemfile::Item;
+
+use crate::utils::formatted_strings::{get_agent_cert_path, get_agent_key_path};
+
+/// Server name used in the TLS handshake, and included in the self-signed certificate
+pub const AGENT_SERVER_NAME: &str = "sniffnet-agent";
+
+/// Loads the certificate chain and private key of the agent.
+///
+/// If no paths are specified, a self-signed certificate stored in the configuration
+/// directory is used, generating it the first time
+pub fn load_certificate(
+    cert_path: Option<&Path>,
+    key_path: Option<&Path>,
+) -> Result<(Vec<Certificate>, PrivateKey), String> {
+    if let (Some(cert_path), Some(key_path)) = (cert_path, key_path) {
+        return read_certificate(cert_path, key_path);
+    }
+    let (cert_path, key_path) = (get_agent_cert_path(), get_agent_key_path());
+    if !cert_path.exists() || !key_path.exists() {
+        generate_certificate(&cert_path, &key_path)?;
+    }
+    read_certificate(&cert_path, &key_path)
+}
+
+/// Generates a new self-signed certificate and writes it to the given paths
+fn generate_certificate(cert_path: &Path, key_path: &Path) -> Result<(), String> {
+    let certificate = rcgen::generate_simple_self_signed(vec![AGENT_SERVER_NAME.to_string()])
+        .map_err(|e| e.to_string())?;
+    let cert_pem = certificate.serialize_pem().map_err(|e| e.to_string())?;
+    std::fs::write(cert_path, cert_pem).map_err(|e| e.to_string())?;
+    write_private_key(key_path, &certificate.serialize_private_key_pem()).map_err(|e| e.to_string())
+}
+
+/// Writes the private key to the given path, making it readable only by the current user
+fn write_private_key(key_path: &Path, key_pem: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(key_path)?;
+    #[cfg(unix)]
+    {
+        // the mode is only applied when the file is created
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(key_pem.as_bytes())
+}
+
+/// Reads a PEM certificate chain and the corresponding PEM private key
+fn read_certificate(
+    cert_path: &Path,
+    key_path: &Path,
+) -> Result<(Vec<Certificate>, PrivateKey), String> {
+    let read_items = |path: &Path| {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        rustls_pemfile::read_all(&mut BufReader::new(file))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    };
+    let certs: Vec<Certificate> = read_items(cert_path)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::X509Certificate(der) => Some(Certificate(der)),
+            _ => None,
+        })
+        .collect();
+    if certs.is_empty() {
+        return Err(format!("{}: no certificate found", cert_path.display()));
+    }
+    let key = read_items(key_path)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(der) | Item::RSAKey(der) | Item::ECKey(der) => Some(PrivateKey(der)),
+            _ => None,
+        })
+        .ok_or_else(|| format!("{}: no private key found", key_path.display()))?;
+    Ok((certs, key))
+}
+
+/// Returns the SHA-256 fingerprint of the certificate, as colon-separated uppercase hex bytes
+pub fn get_fingerprint(certificate: &Certificate) -> String {
+    let mut fingerprint = String::new();
+    for (i, byte) in digest(&SHA256, &certificate.0).as_ref().iter().enumerate() {
+        if i > 0 {
+            fingerprint.push(':');
+        }
+        let _ = write!(fingerprint, "{byte:02X}");
+    }
+    fingerprint
+}
+
+/// Removes separators and whitespaces, so that fingerprints can be compared regardless of their format
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+pub fn get_server_config(
+    certs: Vec<Certificate>,
+    key: PrivateKey,
+) -> Result<Arc<ServerConfig>, String> {
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| e.to_string())?;
+    Ok(Arc::new(config))
+}
+
+/// Client configuration accepting only the agent certificate with the given fingerprint
+pub fn get_client_config(fingerprint: &str) -> Arc<ClientConfig> {
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(FingerprintVerifier {
+            fingerprint: normalize_fingerprint(fingerprint),
+        }))
+        .with_no_client_auth();
+    Arc::new(config)
+}
+
+pub fn get_server_name() -> ServerName {
+    ServerName::try_from(AGENT_SERVER_NAME).unwrap()
+}
+
+/// Certificate verifier pinning the agent certificate by its fingerprint
+struct FingerprintVerifier {
+    fingerprint: String,
+}
+
+impl ServerCertVerifier for FingerprintVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if normalize_fingerprint(&get_fingerprint(end_entity)).eq(&self.fingerprint) {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::General(
+                "the agent certificate doesn't match the expected fingerprint".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_certificate_is_readable() {
+        let dir = std::env::temp_dir();
+        let cert_path = dir.join("sniffnet_test_agent_cert.pem");
+        let key_path = dir.join("sniffnet_test_agent_key.pem");
+        generate_certificate(&cert_path, &key_path).unwrap();
+        let (certs, _key) = load_certificate(Some(&cert_path), Some(&key_path)).unwrap();
+        assert_eq!(certs.len(), 1);
+
+        let fingerprint = get_fingerprint(&certs[0]);
+        assert_eq!(fingerprint.len(), 32 * 3 - 1);
+        assert_eq!(
+            normalize_fingerprint(&fingerprint.to_lowercase().replace(':', " ")),
+            normalize_fingerprint(&fingerprint)
+        );
+
+        assert!(read_certificate(&key_path, &key_path).is_err());
+        assert!(read_certificate(&cert_path, &cert_path).is_err());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&key_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Module defining the messages exchanged between a Sniffnet agent and the GUI connected to it.
+//!
+//! Each message is serialized as a single line of JSON.
+
+use std::io::{BufRead, Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_info_host::DataInfoHost;
+use crate::networking::types::filters::Filters;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::{AppProtocol, InfoTraffic};
+
+/// First message sent by the GUI to authenticate itself
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AgentHello {
+    /// Token shared with the agent
+    pub token: String,
+}
+
+/// Reply of the agent to the `AgentHello` message
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AgentWelcome {
+    /// Whether the supplied token was accepted
+    pub authenticated: bool,
+    /// Name of the network adapter the agent is capturing from (empty if not authenticated)
+    pub adapter: String,
+}
+
+/// Aggregated traffic statistics periodically sent by the agent.
+///
+/// Counters are sent in full, while connections, resolved addresses, and hosts
+/// only include the entries that are new or changed since the previous update
+#[derive(Serialize, Deserialize, Default)]
+pub struct AgentUpdate {
+    pub tot_received_bytes: u128,
+    pub tot_sent_bytes: u128,
+    pub tot_received_packets: u128,
+    pub tot_sent_packets: u128,
+    pub all_packets: u128,
+    pub all_bytes: u128,
+    pub dropped_packets: u32,
+    pub connections: Vec<(AddressPortPair, InfoAddressPortPair)>,
+    pub app_protocols: Vec<(AppProtocol, DataInfo)>,
+    pub addresses_resolved: Vec<(String, (String, Host))>,
+    pub hosts: Vec<(Host, DataInfoHost)>,
+}
+
+impl AgentUpdate {
+    /// Merges the update into the local traffic statistics, so that they can be displayed
+    /// as if the traffic was captured locally
+    pub fn apply_to(self, info_traffic: &mut InfoTraffic) {
+        info_traffic.tot_received_bytes = self.tot_received_bytes;
+        info_traffic.tot_sent_bytes = self.tot_sent_bytes;
+        info_traffic.tot_received_packets = self.tot_received_packets;
+        info_traffic.tot_sent_packets = self.tot_sent_packets;
+        info_traffic.all_packets = self.all_packets;
+        info_traffic.all_bytes = self.all_bytes;
+        info_traffic.dropped_packets = self.dropped_packets;
+
+        for (address, resolved) in self.addresses_resolved {
+            info_traffic.addresses_resolved.insert(address, resolved);
+        }
+
+        let updated_hosts: Vec<Host> = self.hosts.iter().map(|(host, _)| host.clone()).collect();
+        for (host, data_info_host) in self.hosts {
+            info_traffic.hosts.insert(host, data_info_host);
+        }
+        // favorites and ignore list are the ones of the GUI side
+        info_traffic.update_hosts_status();
+        for host in updated_hosts {
+            if info_traffic.hosts.get(&host).is_some_and(|data_info_host| {
+                data_info_host.is_favorite && !data_info_host.is_ignored
+            }) {
+                info_traffic.favorites_last_interval.insert(host);
+            }
+        }
+
+        for (key, mut info) in self.connections {
+            let address_to_lookup = get_address_to_lookup(&key, info.traffic_direction);
+            let is_ignored = info_traffic.is_ignored(&address_to_lookup, info.app_protocol);
+            let index = info_traffic
+                .map
+                .get_index_of(&key)
+                .unwrap_or(info_traffic.map.len());
+            info.index = index;
+            info_traffic.map.insert(key, info);
+            info_traffic.addresses_last_interval.insert(index);
+            if let Some((_, host)) = info_traffic
+                .addresses_resolved
+                .get(&address_to_lookup)
+                .filter(|_| !is_ignored)
+            {
+                if info_traffic
+                    .hosts
+                    .get(host)
+                    .is_some_and(|data_info_host| data_info_host.is_favorite)
+                {
+                    info_traffic.favorites_last_interval.insert(host.clone());
+                }
+            }
+        }
+
+        for (app_protocol, data_info) in self.app_protocols {
+            info_traffic.app_protocols.insert(app_protocol, data_info);
+        }
+
+        // the statistics sent by the agent don't take into account the display filters of the GUI
+        if info_traffic.display_filters.ne(&Filters::default()) {
+            info_traffic.apply_display_filters();
+        }
+    }
+}
+
+/// Maximum length of a message line; longer lines are considered malformed
+pub const MAX_MESSAGE_LEN: u64 = 64 * 1024 * 1024;
+
+/// Writes a message as a single line of JSON
+pub fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> Result<(), String> {
+    let mut line = serde_json::to_string(message).map_err(|e| e.to_string())?;
+    line.push('\n');
+    writer
+        .write_all(line.as_bytes())
+        .and_then(|()| writer.flush())
+        .map_err(|e| e.to_string())
+}
+
+/// Reads a message from a single line of JSON, at most `max_len` bytes long
+pub fn read_message<T: DeserializeOwned>(
+    reader: &mut impl BufRead,
+    max_len: u64,
+) -> Result<T, String> {
+    let mut line = String::new();
+    let len = reader
+        .take(max_len)
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+    if len == 0 {
+        return Err("connection closed".to_string());
+    }
+    if !line.ends_with('\n') {
+        return Err("malformed message".to_string());
+    }
+    serde_json::from_str(&line).map_err(|e| e.to_string())
+}
@@ -0,0 +1,10 @@
+/// Parameters used by the GUI to connect to a remote Sniffnet agent
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct AgentParams {
+    /// Address of the agent, in the form `host:port`
+    pub address: String,
+    /// Token shared with the agent
+    pub token: String,
+    /// SHA-256 fingerprint of the agent's certificate, as printed by the agent at startup
+    pub fingerprint: String,
+}
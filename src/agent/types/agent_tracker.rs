@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::agent::types::agent_message::AgentUpdate;
+use crate::networking::types::host::Host;
+use crate::InfoTraffic;
+
+/// Keeps track of what has already been sent to a connected GUI,
+/// so that each update only includes new or changed entries
+#[derive(Default)]
+pub struct AgentTracker {
+    /// Number of packets of each connection (by index) at the time of the last update
+    connections_packets: Vec<u128>,
+    /// Addresses whose resolution has already been sent
+    addresses_resolved: HashSet<String>,
+    /// Number of packets of each host at the time of the last update
+    hosts_packets: HashMap<Host, u128>,
+}
+
+impl AgentTracker {
+    /// Builds the next update to send, based on the current traffic statistics
+    pub fn next_update(&mut self, info_traffic: &InfoTraffic) -> AgentUpdate {
+        let mut connections = Vec::new();
+        for (index, (key, info)) in info_traffic.map.iter().enumerate() {
+            if index >= self.connections_packets.len() {
+                self.connections_packets.push(info.transmitted_packets);
+            } else if self.connections_packets[index] != info.transmitted_packets {
+                self.connections_packets[index] = info.transmitted_packets;
+            } else {
+                continue;
+            }
+            connections.push((key.clone(), info.clone()));
+        }
+
+        let mut addresses_resolved = Vec::new();
+        for (address, resolved) in &info_traffic.addresses_resolved {
+            if self.addresses_resolved.insert(address.clone()) {
+                addresses_resolved.push((address.clone(), resolved.clone()));
+            }
+        }
+
+        let mut hosts = Vec::new();
+        for (host, data_info_host) in &info_traffic.hosts {
+            let packets = data_info_host.data_info.tot_packets();
+            if self.hosts_packets.insert(host.clone(), packets) != Some(packets) {
+                hosts.push((host.clone(), data_info_host.clone()));
+            }
+        }
+
+        AgentUpdate {
+            tot_received_bytes: info_traffic.tot_received_bytes,
+            tot_sent_bytes: info_traffic.tot_sent_bytes,
+            tot_received_packets: info_traffic.tot_received_packets,
+            tot_sent_packets: info_traffic.tot_sent_packets,
+            all_packets: info_traffic.all_packets,
+            all_bytes: info_traffic.all_bytes,
+            dropped_packets: info_traffic.dropped_packets,
+            connections,
+            app_protocols: info_traffic
+                .app_protocols
+                .iter()
+                .map(|(app_protocol, data_info)| (*app_protocol, *data_info))
+                .collect(),
+            addresses_resolved,
+            hosts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::{AppProtocol, TransProtocol};
+
+    use super::*;
+
+    fn key(port: u16) -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.2".to_string(),
+            port,
+            "8.8.8.8".to_string(),
+            443,
+            TransProtocol::TCP,
+        )
+    }
+
+    fn host() -> Host {
+        Host {
+            domain: "dns.google".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        }
+    }
+
+    fn add_packet(info_traffic: &mut InfoTraffic, port: u16) {
+        info_traffic.add_packet(100, TrafficDirection::Outgoing);
+        let index = info_traffic
+            .map
+            .get_index_of(&key(port))
+            .unwrap_or(info_traffic.map.len());
+        let info = info_traffic
+            .map
+            .entry(key(port))
+            .or_insert(InfoAddressPortPair {
+                index,
+                traffic_direction: TrafficDirection::Outgoing,
+                app_protocol: AppProtocol::HTTPS,
+                ..InfoAddressPortPair::default()
+            });
+        info.transmitted_packets += 1;
+        info.transmitted_bytes += 100;
+        info_traffic
+            .hosts
+            .entry(host())
+            .or_default()
+            .data_info
+            .add_packet(100, TrafficDirection::Outgoing);
+        info_traffic
+            .app_protocols
+            .entry(AppProtocol::HTTPS)
+            .or_default()
+            .add_packet(100, TrafficDirection::Outgoing);
+    }
+
+    /// Sends an update from the agent side to the GUI side through JSON, as over the network
+    fn transfer(tracker: &mut AgentTracker, agent: &InfoTraffic, gui: &mut InfoTraffic) -> usize {
+        let update = tracker.next_update(agent);
+        let sent_connections = update.connections.len();
+        let json = serde_json::to_string(&update).unwrap();
+        serde_json::from_str::<AgentUpdate>(&json)
+            .unwrap()
+            .apply_to(gui);
+        sent_connections
+    }
+
+    #[test]
+    fn test_updates_are_incremental_and_reproduce_the_agent_traffic() {
+        let mut agent = InfoTraffic::new();
+        let mut gui = InfoTraffic::new();
+        gui.favorite_hosts.insert(host());
+        let mut tracker = AgentTracker::default();
+
+        add_packet(&mut agent, 5000);
+        add_packet(&mut agent, 5001);
+        agent
+            .addresses_resolved
+            .insert("8.8.8.8".to_string(), ("dns.google".to_string(), host()));
+        assert_eq!(transfer(&mut tracker, &agent, &mut gui), 2);
+
+        // nothing changed: no connection is sent again
+        gui.favorites_last_interval.clear();
+        assert_eq!(transfer(&mut tracker, &agent, &mut gui), 0);
+        assert!(gui.favorites_last_interval.is_empty());
+
+        add_packet(&mut agent, 5001);
+        add_packet(&mut agent, 5002);
+        assert_eq!(transfer(&mut tracker, &agent, &mut gui), 2);
+        assert!(gui.favorites_last_interval.contains(&host()));
+
+        assert_eq!(gui.tot_sent_packets, 4);
+        assert_eq!(gui.tot_sent_bytes, 400);
+        assert_eq!(gui.map.len(), 3);
+        for (index, (key, info)) in agent.map.iter().enumerate() {
+            let (gui_index, _, gui_info) = gui.map.get_full(key).unwrap();
+            assert_eq!(gui_index, index);
+            assert_eq!(gui_info.index, index);
+            assert_eq!(gui_info.transmitted_packets, info.transmitted_packets);
+        }
+        assert_eq!(gui.addresses_last_interval, HashSet::from([0, 1, 2]));
+        let gui_host: &DataInfoHost = gui.hosts.get(&host()).unwrap();
+        assert_eq!(gui_host.data_info.tot_packets(), 4);
+        assert!(gui_host.is_favorite);
+        assert_eq!(
+            gui.app_protocols
+                .get(&AppProtocol::HTTPS)
+                .map(DataInfo::tot_bytes),
+            Some(400)
+        );
+        assert_eq!(gui.addresses_resolved.get("8.8.8.8").unwrap().1, host());
+    }
+}
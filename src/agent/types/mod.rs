@@ -0,0 +1,3 @@
+pub mod agent_message;
+pub mod agent_params;
+pub mod agent_tracker;
@@ -0,0 +1,562 @@
+//! Module containing the functions executed by the thread exposing the local JSON API,
+//! used to query the state of the capture and to control it from other tools.
+
+use std::cmp::Reverse;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use pcap::Device;
+use serde_json::{json, Value};
+
+use crate::api::types::api_command::ApiCommand;
+use crate::api::types::throughput::{Throughput, TrafficTotals};
+use crate::cli::ApiArgs;
+use crate::gui::types::status::Status;
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::search_parameters::SearchParameters;
+use crate::networking::types::search_query::SearchQuery;
+use crate::report::get_report_entries::get_searched_connections;
+use crate::{InfoTraffic, ReportSortType};
+
+/// Default number of connections returned per page
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// Maximum number of connections returned per page
+const MAX_PAGE_SIZE: usize = 1000;
+
+/// Shared state the API answers about
+pub struct ApiContext {
+    /// Token to be supplied as bearer authorization
+    pub token: String,
+    pub current_capture_id: Arc<Mutex<u16>>,
+    pub info_traffic: Arc<Mutex<InfoTraffic>>,
+    pub status_pair: Arc<(Mutex<Status>, Condvar)>,
+    /// Capture controls waiting to be applied; None if the capture can't be controlled (headless mode)
+    pub api_commands: Option<Arc<Mutex<Vec<ApiCommand>>>>,
+    /// Most recent throughput measurement
+    pub throughput: Arc<Mutex<Throughput>>,
+}
+
+/// HTTP request received by the API
+struct ApiRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    authorization: Option<String>,
+}
+
+/// The calling thread listens on the given address and replies to the API requests
+pub fn serve_api_loop(args: &ApiArgs, context: ApiContext) {
+    let listener = match TcpListener::bind(args.address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("sniffnet: cannot expose the API on {}: {e}", args.address);
+            return;
+        }
+    };
+
+    let info_traffic = context.info_traffic.clone();
+    let throughput = context.throughput.clone();
+    thread::Builder::new()
+        .name("thread_api_throughput".to_string())
+        .spawn(move || sample_throughput_loop(&info_traffic, &throughput))
+        .unwrap();
+
+    for stream in listener.incoming().flatten() {
+        let _ = handle_connection(stream, &context);
+    }
+}
+
+/// Measures the throughput every second
+fn sample_throughput_loop(
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    throughput: &Arc<Mutex<Throughput>>,
+) {
+    let mut previous = (
+        Instant::now(),
+        TrafficTotals::new(&info_traffic_mutex.lock().unwrap()),
+    );
+    loop {
+        thread::sleep(Duration::from_secs(1));
+        let current = (
+            Instant::now(),
+            TrafficTotals::new(&info_traffic_mutex.lock().unwrap()),
+        );
+        *throughput.lock().unwrap() = current
+            .1
+            .throughput_since(&previous.1, current.0.duration_since(previous.0));
+        previous = current;
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, context: &ApiContext) -> std::io::Result<()> {
+    // requests are served one at a time, so a client can't stall the server for long
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    let Some(request) = read_request(&mut BufReader::new(&stream))? else {
+        return Ok(());
+    };
+    let (status, body) = handle_request(&request, context);
+    let body = format!("{body}\n");
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+/// Parses the request line and headers; the request body is ignored
+fn read_request(reader: &mut impl BufRead) -> std::io::Result<Option<ApiRequest>> {
+    let mut request_line = String::new();
+    reader.take(8192).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        if reader.take(8192).read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    Ok(Some(ApiRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        authorization,
+    }))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                    decoded.push(byte);
+                    i += 2;
+                } else {
+                    decoded.push(b'%');
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Compares two strings in constant time with respect to their content
+fn tokens_match(supplied: &str, expected: &str) -> bool {
+    supplied.len() == expected.len()
+        && supplied
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Returns the HTTP status and the JSON body replying to the given request
+fn handle_request(request: &ApiRequest, context: &ApiContext) -> (&'static str, Value) {
+    let authorized = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| tokens_match(token.trim(), &context.token));
+    if !authorized {
+        return (
+            "401 Unauthorized",
+            json!({"error": "missing or invalid token"}),
+        );
+    }
+
+    match (request.method.as_str(), request.path.trim_end_matches('/')) {
+        ("GET", "/api/status") => ("200 OK", get_status(context)),
+        ("GET", "/api/throughput") => ("200 OK", json!(*context.throughput.lock().unwrap())),
+        ("GET", "/api/connections") => get_connections(&request.query, context),
+        ("GET", "/api/hosts") => ("200 OK", get_hosts(context)),
+        ("GET", "/api/app_protocols") => ("200 OK", get_app_protocols(context)),
+        ("POST", "/api/capture/start") => {
+            let adapter = get_param(&request.query, "adapter").map(ToString::to_string);
+            if let Some(name) = &adapter {
+                let exists = Device::list()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|device| device.name.eq(name));
+                if !exists {
+                    return (
+                        "404 Not Found",
+                        json!({"error": format!("network adapter '{name}' not found")}),
+                    );
+                }
+            }
+            push_command(context, ApiCommand::Start(adapter))
+        }
+        ("POST", "/api/capture/stop") => push_command(context, ApiCommand::Stop),
+        ("POST", "/api/capture/reset") => push_command(context, ApiCommand::Reset),
+        _ => ("404 Not Found", json!({"error": "not found"})),
+    }
+}
+
+fn get_param<'a>(query: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    query
+        .iter()
+        .find(|(k, _)| k.eq(key))
+        .map(|(_, value)| value.as_str())
+}
+
+fn push_command(context: &ApiContext, command: ApiCommand) -> (&'static str, Value) {
+    let Some(api_commands) = &context.api_commands else {
+        return (
+            "409 Conflict",
+            json!({"error": "the capture can only be controlled when the graphical interface is running"}),
+        );
+    };
+    let name = match command {
+        ApiCommand::Start(_) => "start",
+        ApiCommand::Stop => "stop",
+        ApiCommand::Reset => "reset",
+    };
+    api_commands.lock().unwrap().push(command);
+    ("202 Accepted", json!({ "accepted": name }))
+}
+
+fn get_status(context: &ApiContext) -> Value {
+    let status = match *context.status_pair.0.lock().unwrap() {
+        Status::Init => "init",
+        Status::Running => "running",
+    };
+    let capture_id = *context.current_capture_id.lock().unwrap();
+    let info_traffic = context.info_traffic.lock().unwrap();
+    json!({
+        "status": status,
+        "capture_id": capture_id,
+        "received_bytes": info_traffic.tot_received_bytes,
+        "sent_bytes": info_traffic.tot_sent_bytes,
+        "received_packets": info_traffic.tot_received_packets,
+        "sent_packets": info_traffic.tot_sent_packets,
+        "all_bytes": info_traffic.all_bytes,
+        "all_packets": info_traffic.all_packets,
+        "dropped_packets": info_traffic.dropped_packets,
+        "connections": info_traffic.map.len(),
+        "hosts": info_traffic.hosts.len(),
+        "throughput": *context.throughput.lock().unwrap(),
+    })
+}
+
+fn get_connections(query: &[(String, String)], context: &ApiContext) -> (&'static str, Value) {
+    let search = SearchParameters {
+        app: get_param(query, "app").unwrap_or_default().to_string(),
+        domain: get_param(query, "domain").unwrap_or_default().to_string(),
+        country: get_param(query, "country").unwrap_or_default().to_string(),
+        as_name: get_param(query, "as_name").unwrap_or_default().to_string(),
+        only_favorites: get_param(query, "only_favorites").is_some_and(|v| v.eq("true")),
+        address: get_param(query, "address").unwrap_or_default().to_string(),
+        source: get_param(query, "source").unwrap_or_default().to_string(),
+        destination: get_param(query, "destination")
+            .unwrap_or_default()
+            .to_string(),
+        port: get_param(query, "port").unwrap_or_default().to_string(),
+        query: get_param(query, "query").unwrap_or_default().to_string(),
+        host: None,
+    };
+    if let Some(error) = search.invalid_address_filter() {
+        return ("400 Bad Request", json!({"error": error}));
+    }
+    if let Err(error) = SearchQuery::parse(&search.query) {
+        return (
+            "400 Bad Request",
+            json!({"error": format!("invalid query: {error}")}),
+        );
+    }
+    let sort_type = match get_param(query, "sort").unwrap_or("recent") {
+        "recent" => ReportSortType::MostRecent,
+        "bytes" => ReportSortType::MostBytes,
+        "packets" => ReportSortType::MostPackets,
+        other => {
+            return (
+                "400 Bad Request",
+                json!({"error": format!("invalid sort '{other}'")}),
+            )
+        }
+    };
+    let (Ok(page), Ok(page_size)) = (
+        get_param(query, "page").unwrap_or("1").parse::<usize>(),
+        get_param(query, "page_size").map_or(Ok(DEFAULT_PAGE_SIZE), str::parse::<usize>),
+    ) else {
+        return ("400 Bad Request", json!({"error": "invalid page"}));
+    };
+    let (page, page_size) = (page.max(1), page_size.clamp(1, MAX_PAGE_SIZE));
+    let Some(skipped) = (page - 1).checked_mul(page_size) else {
+        return ("400 Bad Request", json!({"error": "invalid page"}));
+    };
+
+    let info_traffic = context.info_traffic.lock().unwrap();
+    let results = get_searched_connections(&info_traffic, &search, sort_type);
+    let connections: Vec<Value> = results
+        .iter()
+        .skip(skipped)
+        .take(page_size)
+        .map(|(key, info)| {
+            let host = info_traffic
+                .addresses_resolved
+                .get(&get_address_to_lookup(key, info.traffic_direction))
+                .map(|(_, host)| host);
+            json!({"connection": key, "info": info, "host": host})
+        })
+        .collect();
+    (
+        "200 OK",
+        json!({"total": results.len(), "page": page, "connections": connections}),
+    )
+}
+
+fn get_hosts(context: &ApiContext) -> Value {
+    let info_traffic = context.info_traffic.lock().unwrap();
+    let mut hosts: Vec<_> = info_traffic.hosts.iter().collect();
+    hosts.sort_by_key(|(_, data_info_host)| Reverse(data_info_host.data_info.tot_bytes()));
+    Value::Array(
+        hosts
+            .into_iter()
+            .map(|(host, data_info_host)| json!({"host": host, "data_info_host": data_info_host}))
+            .collect(),
+    )
+}
+
+fn get_app_protocols(context: &ApiContext) -> Value {
+    let info_traffic = context.info_traffic.lock().unwrap();
+    let mut app_protocols: Vec<_> = info_traffic.app_protocols.iter().collect();
+    app_protocols.sort_by_key(|(_, data_info)| Reverse(data_info.tot_bytes()));
+    Value::Array(
+        app_protocols
+            .into_iter()
+            .map(|(app_protocol, data_info)| {
+                json!({"app_protocol": app_protocol, "data_info": data_info})
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::{AppProtocol, TransProtocol};
+
+    use super::*;
+
+    fn context(api_commands: Option<Arc<Mutex<Vec<ApiCommand>>>>) -> ApiContext {
+        let mut info_traffic = InfoTraffic::new();
+        for (port, app_protocol, bytes) in
+            [(443, AppProtocol::HTTPS, 1000), (53, AppProtocol::DNS, 100)]
+        {
+            info_traffic.add_packet(bytes, TrafficDirection::Outgoing);
+            info_traffic.app_protocols.insert(
+                app_protocol,
+                DataInfo::new_with_first_packet(bytes, TrafficDirection::Outgoing),
+            );
+            let key = AddressPortPair::new(
+                "192.168.1.2".to_string(),
+                50000,
+                "8.8.8.8".to_string(),
+                port,
+                TransProtocol::UDP,
+            );
+            let index = info_traffic.map.len();
+            info_traffic.map.insert(
+                key,
+                InfoAddressPortPair {
+                    transmitted_bytes: bytes,
+                    transmitted_packets: 1,
+                    app_protocol,
+                    traffic_direction: TrafficDirection::Outgoing,
+                    index,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+        }
+        let host = Host {
+            domain: "dns.google".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        info_traffic.addresses_resolved.insert(
+            "8.8.8.8".to_string(),
+            ("dns.google".to_string(), host.clone()),
+        );
+        info_traffic.hosts.insert(host, DataInfoHost::default());
+        ApiContext {
+            token: "secret".to_string(),
+            current_capture_id: Arc::new(Mutex::new(0)),
+            info_traffic: Arc::new(Mutex::new(info_traffic)),
+            status_pair: Arc::new((Mutex::new(Status::Running), Condvar::new())),
+            api_commands,
+            throughput: Arc::new(Mutex::new(Throughput::default())),
+        }
+    }
+
+    fn request(raw: &str) -> ApiRequest {
+        read_request(&mut raw.as_bytes()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = request(
+            "GET /api/connections?domain=dns%2Egoogle&as_name=a+b HTTP/1.1\r\n\
+            Host: localhost\r\nauthorization: Bearer secret\r\n\r\n",
+        );
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/api/connections");
+        assert_eq!(
+            request.query,
+            vec![
+                ("domain".to_string(), "dns.google".to_string()),
+                ("as_name".to_string(), "a b".to_string())
+            ]
+        );
+        assert_eq!(request.authorization, Some("Bearer secret".to_string()));
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_requests_require_token() {
+        let context = context(None);
+        for authorization in [
+            "",
+            "Authorization: Bearer wrong\r\n",
+            "Authorization: secret\r\n",
+        ] {
+            let (status, _) = handle_request(
+                &request(&format!("GET /api/status HTTP/1.1\r\n{authorization}\r\n")),
+                &context,
+            );
+            assert_eq!(status, "401 Unauthorized");
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        let context = context(None);
+        let get = |target: &str| {
+            handle_request(
+                &request(&format!(
+                    "GET {target} HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n"
+                )),
+                &context,
+            )
+        };
+
+        let (status, body) = get("/api/status");
+        assert_eq!(status, "200 OK");
+        assert_eq!(body["status"], "running");
+        assert_eq!(body["sent_bytes"], 1100);
+        assert_eq!(body["connections"], 2);
+
+        let (_, body) = get("/api/connections?sort=bytes");
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["connections"][0]["connection"]["port2"], 443);
+        assert_eq!(body["connections"][0]["host"]["domain"], "dns.google");
+
+        let (_, body) = get("/api/connections?app=dns&country=us");
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["connections"][0]["info"]["app_protocol"], "DNS");
+
+        let (_, body) = get("/api/connections?sort=bytes&page=2&page_size=1");
+        assert_eq!(body["connections"][0]["connection"]["port2"], 53);
+
+        let (_, body) = get("/api/connections?query=port%3A443+or+%28dir%3Aout+and+bytes%3C100%29");
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["connections"][0]["connection"]["port2"], 443);
+
+        let (_, body) = get("/api/connections?address=8.8.8.0%2F24&port=50-60");
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["connections"][0]["connection"]["port2"], 53);
+        let (_, body) = get("/api/connections?source=192.168.1.1-192.168.1.9&destination=8.8.8.8");
+        assert_eq!(body["total"], 2);
+        assert_eq!(
+            get("/api/connections?destination=192.168.1.2").1["total"],
+            0
+        );
+        assert_eq!(get("/api/connections?port=443-80").0, "400 Bad Request");
+
+        let (status, body) = get("/api/connections?query=bytes%3Efoo");
+        assert_eq!(status, "400 Bad Request");
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid query: invalid number 'foo'"));
+
+        assert_eq!(get("/api/connections?sort=foo").0, "400 Bad Request");
+        assert_eq!(
+            get(&format!("/api/connections?page={}", usize::MAX)).0,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            get("/api/connections?page=1000").1["connections"],
+            json!([])
+        );
+        assert_eq!(get("/api/hosts").1[0]["host"]["asn"]["number"], 15169);
+        assert_eq!(get("/api/app_protocols").1[0]["app_protocol"], "HTTPS");
+        assert_eq!(get("/api/foo").0, "404 Not Found");
+    }
+
+    #[test]
+    fn test_capture_control() {
+        let post = |context: &ApiContext, target: &str| {
+            handle_request(
+                &request(&format!(
+                    "POST {target} HTTP/1.1\r\nAuthorization: Bearer secret\r\n\r\n"
+                )),
+                context,
+            )
+            .0
+        };
+
+        // capture can't be controlled in headless mode
+        assert_eq!(post(&context(None), "/api/capture/stop"), "409 Conflict");
+
+        let api_commands = Arc::new(Mutex::new(Vec::new()));
+        let context = context(Some(api_commands.clone()));
+        assert_eq!(post(&context, "/api/capture/start"), "202 Accepted");
+        assert_eq!(post(&context, "/api/capture/stop"), "202 Accepted");
+        assert_eq!(post(&context, "/api/capture/reset/"), "202 Accepted");
+        assert_eq!(
+            *api_commands.lock().unwrap(),
+            vec![ApiCommand::Start(None), ApiCommand::Stop, ApiCommand::Reset]
+        );
+    }
+}
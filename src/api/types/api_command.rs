@@ -0,0 +1,10 @@
+/// Capture control requested through the API, applied by the GUI at its next tick
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiCommand {
+    /// Start capturing, on the given adapter or on the selected one
+    Start(Option<String>),
+    /// Stop capturing and go back to the initial page
+    Stop,
+    /// Discard the collected data and start a new capture on the same adapter
+    Reset,
+}
@@ -0,0 +1,2 @@
+pub mod api_command;
+pub mod throughput;
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::InfoTraffic;
+
+/// Amount of data exchanged per second
+#[derive(Serialize, Default, Debug, Clone, Copy, PartialEq)]
+pub struct Throughput {
+    pub received_bytes: f64,
+    pub sent_bytes: f64,
+    pub received_packets: f64,
+    pub sent_packets: f64,
+}
+
+/// Filtered traffic totals at a given moment
+#[derive(Default, Clone, Copy)]
+pub struct TrafficTotals {
+    received_bytes: u128,
+    sent_bytes: u128,
+    received_packets: u128,
+    sent_packets: u128,
+}
+
+impl TrafficTotals {
+    pub fn new(info_traffic: &InfoTraffic) -> Self {
+        Self {
+            received_bytes: info_traffic.tot_received_bytes,
+            sent_bytes: info_traffic.tot_sent_bytes,
+            received_packets: info_traffic.tot_received_packets,
+            sent_packets: info_traffic.tot_sent_packets,
+        }
+    }
+
+    /// Computes the throughput from a previous sample taken `elapsed` time before.
+    ///
+    /// If totals decreased (i.e., a new capture started) the throughput is computed from zero
+    #[allow(clippy::cast_precision_loss)]
+    pub fn throughput_since(&self, previous: &TrafficTotals, elapsed: Duration) -> Throughput {
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        let rate = |now: u128, before: u128| {
+            let delta = if now >= before { now - before } else { now };
+            delta as f64 / secs
+        };
+        Throughput {
+            received_bytes: rate(self.received_bytes, previous.received_bytes),
+            sent_bytes: rate(self.sent_bytes, previous.sent_bytes),
+            received_packets: rate(self.received_packets, previous.received_packets),
+            sent_packets: rate(self.sent_packets, previous.sent_packets),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::networking::types::traffic_direction::TrafficDirection;
+
+    use super::*;
+
+    #[test]
+    fn test_throughput_since() {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.add_packet(1000, TrafficDirection::Incoming);
+        let previous = TrafficTotals::new(&info_traffic);
+        info_traffic.add_packet(3000, TrafficDirection::Incoming);
+        info_traffic.add_packet(500, TrafficDirection::Outgoing);
+        let current = TrafficTotals::new(&info_traffic);
+        assert_eq!(
+            current.throughput_since(&previous, Duration::from_secs(2)),
+            Throughput {
+                received_bytes: 1500.0,
+                sent_bytes: 250.0,
+                received_packets: 0.5,
+                sent_packets: 0.5,
+            }
+        );
+
+        // a new capture started in the meantime
+        let restarted = TrafficTotals::new(&InfoTraffic::new());
+        assert_eq!(
+            restarted.throughput_since(&current, Duration::from_secs(1)),
+            Throughput::default()
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Module to export the charts as SVG or PNG images, both from the GUI and from the command line.
+
+use std::path::Path;
+use std::sync::Once;
+
+use plotters::prelude::{BitMapBackend, IntoDrawingArea, SVGBackend};
+use plotters::style::{register_font, FontStyle};
+use plotters_iced::Chart;
+
+use crate::gui::styles::style_constants::{SARASA_MONO_BOLD_BYTES, SARASA_MONO_BYTES};
+use crate::gui::styles::types::palette::to_rgb_color;
+use crate::gui::types::message::Message;
+use crate::{get_colors, StyleType};
+
+/// Format of the exported chart images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChartImageFormat {
+    Svg,
+    Png,
+}
+
+impl ChartImageFormat {
+    pub(crate) const ALL: [ChartImageFormat; 2] = [ChartImageFormat::Svg, ChartImageFormat::Png];
+
+    /// Format corresponding to the extension of the given path, if supported
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "svg" => Some(ChartImageFormat::Svg),
+            "png" => Some(ChartImageFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn get_extension(self) -> &'static str {
+        match self {
+            ChartImageFormat::Svg => "svg",
+            ChartImageFormat::Png => "png",
+        }
+    }
+
+    pub fn get_label(self) -> &'static str {
+        match self {
+            ChartImageFormat::Svg => "SVG",
+            ChartImageFormat::Png => "PNG",
+        }
+    }
+}
+
+/// Resolutions available for the exported chart images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChartResolution {
+    Hd,
+    #[default]
+    FullHd,
+    Uhd,
+}
+
+impl ChartResolution {
+    pub(crate) const ALL: [ChartResolution; 3] = [
+        ChartResolution::Hd,
+        ChartResolution::FullHd,
+        ChartResolution::Uhd,
+    ];
+
+    /// Width and height of the image, in pixels
+    pub fn size(self) -> (u32, u32) {
+        match self {
+            ChartResolution::Hd => (1280, 720),
+            ChartResolution::FullHd => (1920, 1080),
+            ChartResolution::Uhd => (3840, 2160),
+        }
+    }
+
+    pub fn get_picklist_label(self) -> &'static str {
+        match self {
+            ChartResolution::Hd => "1280 × 720",
+            ChartResolution::FullHd => "1920 × 1080",
+            ChartResolution::Uhd => "3840 × 2160",
+        }
+    }
+
+    pub fn all_strings() -> Vec<&'static str> {
+        ChartResolution::ALL
+            .iter()
+            .map(|resolution| resolution.get_picklist_label())
+            .collect()
+    }
+}
+
+/// Renders the given chart to an image file, with the format deduced from the path extension.
+///
+/// The background is filled with the primary color of the given style,
+/// so that the image looks like the chart displayed in the app
+pub fn export_chart<C: Chart<Message>>(
+    chart: &C,
+    style: StyleType,
+    path: &Path,
+    size: (u32, u32),
+) -> Result<(), String>
+where
+    C::State: Default,
+{
+    let format = ChartImageFormat::from_path(path).ok_or_else(|| {
+        format!(
+            "unsupported image format for '{}' (use .svg or .png)",
+            path.display()
+        )
+    })?;
+    register_chart_fonts();
+    let background = to_rgb_color(get_colors(style).primary);
+    let state = C::State::default();
+    match format {
+        ChartImageFormat::Svg => {
+            let root = SVGBackend::new(path, size).into_drawing_area();
+            root.fill(&background).map_err(|e| e.to_string())?;
+            chart.draw_chart(&state, root.clone());
+            root.present().map_err(|e| e.to_string())
+        }
+        ChartImageFormat::Png => {
+            let root = BitMapBackend::new(path, size).into_drawing_area();
+            root.fill(&background).map_err(|e| e.to_string())?;
+            chart.draw_chart(&state, root.clone());
+            root.present().map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Makes the app font available to plotters, which needs it to lay out the text of the images
+fn register_chart_fonts() {
+    static REGISTER_FONTS: Once = Once::new();
+    REGISTER_FONTS.call_once(|| {
+        register_font("Sarasa Mono SC", FontStyle::Normal, SARASA_MONO_BYTES).ok();
+        register_font("Sarasa Mono SC", FontStyle::Bold, SARASA_MONO_BOLD_BYTES).ok();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chart::manage_chart_data::update_charts_data;
+    use crate::{Language, RunTimeData, TrafficChart};
+
+    use super::*;
+
+    #[test]
+    fn test_export_chart() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::EN);
+        let mut runtime_data = RunTimeData::new();
+        for second in 0..10 {
+            runtime_data.tot_received_bytes += 1000 * second;
+            runtime_data.tot_sent_bytes += 500;
+            update_charts_data(&mut runtime_data, &mut traffic_chart);
+        }
+        let dir = std::env::temp_dir();
+
+        let svg_path = dir.join("sniffnet_test_chart.svg");
+        export_chart(&traffic_chart, StyleType::default(), &svg_path, (640, 360)).unwrap();
+        let svg = std::fs::read_to_string(&svg_path).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("width=\"640\""));
+
+        let png_path = dir.join("sniffnet_test_chart.png");
+        export_chart(&traffic_chart, StyleType::default(), &png_path, (640, 360)).unwrap();
+        let png = std::fs::read(&png_path).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        assert!(export_chart(
+            &traffic_chart,
+            StyleType::default(),
+            &dir.join("chart.jpg"),
+            (640, 360)
+        )
+        .is_err());
+    }
+}
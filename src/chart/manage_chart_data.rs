@@ -1,6 +1,9 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::{RunTimeData, TrafficChart};
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::traffic_series::SERIES_SECONDS;
+use crate::{InfoTraffic, RunTimeData, TrafficChart};
 
 /// This function is invoked every second by the application subscription
 ///
@@ -9,58 +12,220 @@ pub fn update_charts_data(runtime_data: &mut RunTimeData, traffic_chart: &mut Tr
     let tot_seconds = traffic_chart.ticks;
     traffic_chart.ticks += 1;
 
-    let sent_bytes_entry = runtime_data.tot_sent_bytes - runtime_data.tot_sent_bytes_prev;
-    let received_bytes_entry =
-        runtime_data.tot_received_bytes - runtime_data.tot_received_bytes_prev;
-    let sent_packets_entry = runtime_data.tot_sent_packets - runtime_data.tot_sent_packets_prev;
-    let received_packets_entry =
-        runtime_data.tot_received_packets - runtime_data.tot_received_packets_prev;
+    // totals can decrease when recomputed (e.g., when a newly resolved host is ignored)
+    let sent_bytes_entry = runtime_data
+        .tot_sent_bytes
+        .saturating_sub(runtime_data.tot_sent_bytes_prev);
+    let received_bytes_entry = runtime_data
+        .tot_received_bytes
+        .saturating_sub(runtime_data.tot_received_bytes_prev);
+    let sent_packets_entry = runtime_data
+        .tot_sent_packets
+        .saturating_sub(runtime_data.tot_sent_packets_prev);
+    let received_packets_entry = runtime_data
+        .tot_received_packets
+        .saturating_sub(runtime_data.tot_received_packets_prev);
 
     // update sent bytes traffic data
-    if traffic_chart.sent_bytes.len() >= 30 {
-        traffic_chart.sent_bytes.pop_front();
-    }
     traffic_chart.sent_bytes.push_back((
         tot_seconds,
         -<u128 as TryInto<i64>>::try_into(sent_bytes_entry).unwrap(),
     ));
-    traffic_chart.min_sent_bytes = get_min(&traffic_chart.sent_bytes);
     runtime_data.tot_sent_bytes_prev = runtime_data.tot_sent_bytes;
     // update received bytes traffic data
-    if traffic_chart.received_bytes.len() >= 30 {
-        traffic_chart.received_bytes.pop_front();
-    }
     traffic_chart
         .received_bytes
         .push_back((tot_seconds, received_bytes_entry.try_into().unwrap()));
-    traffic_chart.max_received_bytes = get_max(&traffic_chart.received_bytes);
     runtime_data.tot_received_bytes_prev = runtime_data.tot_received_bytes;
 
     // update sent packets traffic data
-    if traffic_chart.sent_packets.len() >= 30 {
-        traffic_chart.sent_packets.pop_front();
-    }
     traffic_chart.sent_packets.push_back((
         tot_seconds,
         -<u128 as TryInto<i64>>::try_into(sent_packets_entry).unwrap(),
     ));
-    traffic_chart.min_sent_packets = get_min(&traffic_chart.sent_packets);
     runtime_data.tot_sent_packets_prev = runtime_data.tot_sent_packets;
     // update received packets traffic data
-    if traffic_chart.received_packets.len() >= 30 {
-        traffic_chart.received_packets.pop_front();
-    }
     traffic_chart
         .received_packets
         .push_back((tot_seconds, received_packets_entry.try_into().unwrap()));
-    traffic_chart.max_received_packets = get_max(&traffic_chart.received_packets);
     runtime_data.tot_received_packets_prev = runtime_data.tot_received_packets;
+
+    update_chart_bounds(traffic_chart);
+}
+
+/// This function is invoked every second by the application subscription, before `update_charts_data`
+///
+/// It records the data exchanged in the last interval by each application protocol, host and country
+pub fn update_breakdown_data(info_traffic: &InfoTraffic, traffic_chart: &mut TrafficChart) {
+    let tick = traffic_chart.ticks;
+    let (app_protocols, hosts, countries) = get_breakdown_totals(info_traffic);
+    traffic_chart
+        .app_protocols_history
+        .update(tick, app_protocols);
+    traffic_chart.hosts_history.update(tick, hosts);
+    traffic_chart.countries_history.update(tick, countries);
+}
+
+/// Invoked when the traffic statistics are recomputed (e.g., after a change of the display filters)
+///
+/// It rebuilds the recent chart intervals from the traffic of the connections currently displayed,
+/// and resets the starting point of the next interval to the recomputed totals,
+/// so that the chart keeps going without a spike or a drop.
+///
+/// The per-second traffic of the connections is only kept for the last [`SERIES_SECONDS`] seconds
+/// (`now` being the current one), so only the last [`SERIES_SECONDS`] intervals can be rebuilt:
+/// the older ones are discarded, instead of showing traffic recorded with the previous filters.
+pub fn rebase_charts_data(
+    info_traffic: &InfoTraffic,
+    now: i64,
+    runtime_data: &mut RunTimeData,
+    traffic_chart: &mut TrafficChart,
+) {
+    rebuild_recent_intervals(info_traffic, now, traffic_chart);
+
+    runtime_data.tot_sent_bytes = info_traffic.tot_sent_bytes;
+    runtime_data.tot_received_bytes = info_traffic.tot_received_bytes;
+    runtime_data.tot_sent_packets = info_traffic.tot_sent_packets;
+    runtime_data.tot_received_packets = info_traffic.tot_received_packets;
+    runtime_data.tot_sent_bytes_prev = info_traffic.tot_sent_bytes;
+    runtime_data.tot_received_bytes_prev = info_traffic.tot_received_bytes;
+    runtime_data.tot_sent_packets_prev = info_traffic.tot_sent_packets;
+    runtime_data.tot_received_packets_prev = info_traffic.tot_received_packets;
+
+    let (app_protocols, hosts, countries) = get_breakdown_totals(info_traffic);
+    traffic_chart.app_protocols_history.rebase(app_protocols);
+    traffic_chart.hosts_history.rebase(hosts);
+    traffic_chart.countries_history.rebase(countries);
+}
+
+/// Recomputes the last [`SERIES_SECONDS`] chart intervals from the traffic series of the
+/// displayed connections, discarding the older ones.
+///
+/// The last interval is matched with the current second, and the previous ones with the
+/// previous seconds, so that no traffic is counted twice or skipped by the next interval
+fn rebuild_recent_intervals(
+    info_traffic: &InfoTraffic,
+    now: i64,
+    traffic_chart: &mut TrafficChart,
+) {
+    let Some(last_tick) = traffic_chart.ticks.checked_sub(1) else {
+        return;
+    };
+    let first_tick = traffic_chart.ticks.saturating_sub(SERIES_SECONDS);
+    let first_second = now - i64::from(last_tick - first_tick);
+
+    let mut totals: BTreeMap<u32, DataInfo> = (first_tick..=last_tick)
+        .map(|tick| (tick, DataInfo::default()))
+        .collect();
+    let mut app_protocols: HashMap<String, BTreeMap<u32, DataInfo>> = HashMap::new();
+    let mut hosts: HashMap<String, BTreeMap<u32, DataInfo>> = HashMap::new();
+    let mut countries: HashMap<String, BTreeMap<u32, DataInfo>> = HashMap::new();
+    for (key, val) in &info_traffic.map {
+        let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
+        if !info_traffic.is_displayed(key, val.app_protocol)
+            || info_traffic.is_ignored(&address_to_lookup, val.app_protocol)
+        {
+            continue;
+        }
+        let host = info_traffic
+            .addresses_resolved
+            .get(&address_to_lookup)
+            .map(|(_, host)| host);
+        for (second, data_info) in val.traffic_series.recent_samples(now) {
+            if *second < first_second {
+                continue;
+            }
+            let tick = first_tick + u32::try_from(second - first_second).unwrap_or_default();
+            *totals.entry(tick).or_default() += *data_info;
+            *app_protocols
+                .entry(val.app_protocol.to_string())
+                .or_default()
+                .entry(tick)
+                .or_default() += *data_info;
+            if let Some(host) = host {
+                *hosts
+                    .entry(host.domain.clone())
+                    .or_default()
+                    .entry(tick)
+                    .or_default() += *data_info;
+                *countries
+                    .entry(format!("{:?}", host.country))
+                    .or_default()
+                    .entry(tick)
+                    .or_default() += *data_info;
+            }
+        }
+    }
+
+    let to_sample = |amount: u128| i64::try_from(amount).unwrap_or(i64::MAX);
+    traffic_chart.sent_bytes = totals
+        .iter()
+        .map(|(tick, data_info)| (*tick, -to_sample(data_info.outgoing_bytes)))
+        .collect();
+    traffic_chart.received_bytes = totals
+        .iter()
+        .map(|(tick, data_info)| (*tick, to_sample(data_info.incoming_bytes)))
+        .collect();
+    traffic_chart.sent_packets = totals
+        .iter()
+        .map(|(tick, data_info)| (*tick, -to_sample(data_info.outgoing_packets)))
+        .collect();
+    traffic_chart.received_packets = totals
+        .iter()
+        .map(|(tick, data_info)| (*tick, to_sample(data_info.incoming_packets)))
+        .collect();
+    traffic_chart
+        .app_protocols_history
+        .replace_series(app_protocols);
+    traffic_chart.hosts_history.replace_series(hosts);
+    traffic_chart.countries_history.replace_series(countries);
+    update_chart_bounds(traffic_chart);
+}
+
+/// Total data exchanged so far by each application protocol, host and country
+fn get_breakdown_totals(
+    info_traffic: &InfoTraffic,
+) -> (
+    HashMap<String, DataInfo>,
+    HashMap<String, DataInfo>,
+    HashMap<String, DataInfo>,
+) {
+    let app_protocols = info_traffic
+        .app_protocols
+        .iter()
+        .filter(|(app_protocol, _)| !info_traffic.ignored_app_protocols.contains(app_protocol))
+        .map(|(app_protocol, data_info)| (app_protocol.to_string(), *data_info))
+        .collect();
+
+    let mut hosts: HashMap<String, DataInfo> = HashMap::new();
+    let mut countries: HashMap<String, DataInfo> = HashMap::new();
+    for (host, data_info_host) in info_traffic
+        .hosts
+        .iter()
+        .filter(|(_, data_info_host)| info_traffic.is_host_displayed(data_info_host))
+    {
+        *hosts.entry(host.domain.clone()).or_default() += data_info_host.data_info;
+        *countries.entry(format!("{:?}", host.country)).or_default() += data_info_host.data_info;
+    }
+    (app_protocols, hosts, countries)
+}
+
+/// Updates the minimum and maximum y values, based on the displayed time range
+pub fn update_chart_bounds(traffic_chart: &mut TrafficChart) {
+    traffic_chart.min_sent_bytes =
+        get_min(traffic_chart.displayed_samples(&traffic_chart.sent_bytes));
+    traffic_chart.max_received_bytes =
+        get_max(traffic_chart.displayed_samples(&traffic_chart.received_bytes));
+    traffic_chart.min_sent_packets =
+        get_min(traffic_chart.displayed_samples(&traffic_chart.sent_packets));
+    traffic_chart.max_received_packets =
+        get_max(traffic_chart.displayed_samples(&traffic_chart.received_packets));
 }
 
 /// Finds the minimum y value to be displayed in chart
-fn get_min(deque: &VecDeque<(u32, i64)>) -> i64 {
+fn get_min<'a>(samples: impl IntoIterator<Item = &'a (u32, i64)>) -> i64 {
     let mut min = 0;
-    for (_, x) in deque {
+    for (_, x) in samples {
         if *x < min {
             min = *x;
         }
@@ -69,9 +234,9 @@ fn get_min(deque: &VecDeque<(u32, i64)>) -> i64 {
 }
 
 /// Finds the maximum y value to be displayed in chart
-fn get_max(deque: &VecDeque<(u32, i64)>) -> i64 {
+fn get_max<'a>(samples: impl IntoIterator<Item = &'a (u32, i64)>) -> i64 {
     let mut max = 0;
-    for (_, x) in deque {
+    for (_, x) in samples {
         if *x > max {
             max = *x;
         }
@@ -81,10 +246,22 @@ fn get_max(deque: &VecDeque<(u32, i64)>) -> i64 {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::VecDeque;
+    use std::collections::{HashMap, VecDeque};
 
-    use crate::chart::manage_chart_data::{get_max, get_min, update_charts_data};
-    use crate::{ChartType, Language, RunTimeData, StyleType, TrafficChart};
+    use crate::chart::manage_chart_data::{
+        get_max, get_min, rebase_charts_data, update_breakdown_data, update_charts_data,
+    };
+    use crate::chart::types::chart_breakdown::{BreakdownKey, ChartBreakdown};
+    use crate::chart::types::chart_window::{ChartViewport, ChartWindow};
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::notifications::types::notifications::ThresholdState;
+    use crate::{
+        AppProtocol, ChartType, InfoTraffic, Language, RunTimeData, StyleType, TrafficChart,
+        TransProtocol,
+    };
 
     #[test]
     fn test_chart_data_updates() {
@@ -165,6 +342,7 @@ fn test_chart_data_updates() {
             language: Language::default(),
             chart_type: ChartType::Packets,
             style: StyleType::default(),
+            ..TrafficChart::new(StyleType::default(), Language::default())
         };
         let mut runtime_data = RunTimeData {
             all_bytes: 0,
@@ -180,6 +358,10 @@ fn test_chart_data_updates() {
             tot_received_packets_prev: tot_received,
             logged_notifications: Default::default(),
             tot_emitted_notifications: 0,
+            rules_state: HashMap::new(),
+            packets_threshold_state: ThresholdState::default(),
+            bytes_threshold_state: ThresholdState::default(),
+            favorites_last_emitted: HashMap::new(),
         };
 
         assert_eq!(get_min(&sent), -1000);
@@ -225,23 +407,19 @@ fn test_chart_data_updates() {
         runtime_data.tot_sent_packets += 220;
         update_charts_data(&mut runtime_data, &mut traffic_chart);
 
-        sent_bytes.pop_front();
-        sent_bytes.pop_front();
         sent_bytes.push_back((30, -99));
         sent_bytes.push_back((31, -77));
-        received_packets.pop_front();
-        received_packets.pop_front();
         received_packets.push_back((30, 990));
         received_packets.push_back((31, 1));
-        sent_packets.pop_front();
-        sent_packets.pop_front();
         sent_packets.push_back((30, 0));
         sent_packets.push_back((31, -220));
-        received_bytes.pop_front();
-        received_bytes.pop_front();
         received_bytes.push_back((30, 2));
         received_bytes.push_back((31, 0));
 
+        // the whole history is kept, but only the last 30 seconds are displayed
+        assert_eq!(traffic_chart.displayed_range(), (2, 31));
+        assert_eq!(traffic_chart.sent_bytes.len(), 32);
+
         // traffic_chart correctly updated?
         assert_eq!(traffic_chart.ticks, 32);
         assert_eq!(traffic_chart.min_sent_bytes, -1111);
@@ -253,4 +431,172 @@ fn test_chart_data_updates() {
         assert_eq!(traffic_chart.sent_packets, sent_packets);
         assert_eq!(traffic_chart.received_bytes, received_bytes);
     }
+    #[test]
+    fn test_chart_windows_zoom_and_pan() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+        for second in 0..4000 {
+            // a spike happened at second 1000
+            runtime_data.tot_received_bytes += if second == 1000 { 5000 } else { 10 };
+            update_charts_data(&mut runtime_data, &mut traffic_chart);
+        }
+        assert_eq!(traffic_chart.displayed_range(), (3970, 3999));
+        assert_eq!(traffic_chart.max_received_bytes, 10);
+
+        traffic_chart.change_window(ChartWindow::WholeSession);
+        assert_eq!(traffic_chart.displayed_range(), (0, 3999));
+        assert_eq!(traffic_chart.max_received_bytes, 5000);
+        // long ranges are downsampled, keeping the spike
+        let points = traffic_chart.displayed_points(&traffic_chart.received_bytes);
+        assert_eq!(points.len(), 286);
+        assert!(points.contains(&(994, 5000)));
+
+        // pan back to the spike and zoom in
+        traffic_chart.change_window(ChartWindow::Last5Minutes);
+        traffic_chart.change_viewport(ChartViewport {
+            span: None,
+            end: Some(1100),
+        });
+        assert_eq!(traffic_chart.displayed_range(), (801, 1100));
+        assert_eq!(traffic_chart.max_received_bytes, 5000);
+        traffic_chart.change_viewport(ChartViewport {
+            span: Some(1),
+            end: Some(1100),
+        });
+        assert_eq!(traffic_chart.displayed_range(), (1091, 1100));
+        assert_eq!(traffic_chart.max_received_bytes, 10);
+
+        // the view stays in place while new data arrives
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert_eq!(traffic_chart.displayed_range(), (1091, 1100));
+
+        // panning to the latest data follows it again
+        traffic_chart.change_viewport(ChartViewport {
+            span: Some(1_000_000),
+            end: Some(5000),
+        });
+        assert_eq!(traffic_chart.viewport.end, None);
+        assert_eq!(traffic_chart.displayed_range(), (0, 4000));
+    }
+    #[test]
+    fn test_breakdown_data_updates() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+        let mut info_traffic = InfoTraffic::new();
+        for (app_protocol, bytes) in [(AppProtocol::HTTPS, 900), (AppProtocol::DNS, 100)] {
+            info_traffic.app_protocols.insert(
+                app_protocol,
+                DataInfo::new_with_first_packet(bytes, TrafficDirection::Outgoing),
+            );
+        }
+        update_breakdown_data(&info_traffic, &mut traffic_chart);
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert!(traffic_chart.breakdown_keys().is_empty());
+
+        traffic_chart.change_breakdown(ChartBreakdown::AppProtocol);
+        let https = BreakdownKey::Single("HTTPS".to_string());
+        let dns = BreakdownKey::Single("DNS".to_string());
+        assert_eq!(
+            traffic_chart.breakdown_keys(),
+            vec![https.clone(), dns.clone()]
+        );
+
+        traffic_chart.toggle_series(https.clone());
+        assert!(traffic_chart.hidden_series.contains(&https));
+        // hidden series are still listed in the legend
+        assert_eq!(traffic_chart.breakdown_keys(), vec![https.clone(), dns]);
+        traffic_chart.toggle_series(https.clone());
+        assert!(traffic_chart.hidden_series.is_empty());
+
+        traffic_chart.toggle_series(https);
+        traffic_chart.change_breakdown(ChartBreakdown::Host);
+        assert!(traffic_chart.hidden_series.is_empty());
+        assert!(traffic_chart.breakdown_keys().is_empty());
+    }
+
+    #[test]
+    fn test_rebase_charts_data() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.tot_sent_packets = 1;
+        runtime_data.tot_sent_packets = 1;
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert_eq!(traffic_chart.sent_packets.back(), Some(&(0, -1)));
+
+        // totals recomputed with more traffic don't show up as a spike in the chart
+        info_traffic.tot_sent_packets = 10;
+        rebase_charts_data(&info_traffic, 0, &mut runtime_data, &mut traffic_chart);
+        assert_eq!(runtime_data.tot_sent_packets, 10);
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert_eq!(traffic_chart.sent_packets.back(), Some(&(1, 0)));
+
+        // totals recomputed with less traffic don't underflow
+        info_traffic.tot_sent_packets = 4;
+        rebase_charts_data(&info_traffic, 0, &mut runtime_data, &mut traffic_chart);
+        runtime_data.tot_sent_packets += 2;
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert_eq!(traffic_chart.sent_packets.back(), Some(&(2, -2)));
+
+        // totals decreased without a rebase don't underflow either
+        runtime_data.tot_sent_packets = 1;
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+        assert_eq!(traffic_chart.sent_packets.back(), Some(&(3, 0)));
+    }
+
+    #[test]
+    fn test_rebase_charts_data_rebuilds_recent_intervals() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+        let mut info_traffic = InfoTraffic::new();
+        let mut info = InfoAddressPortPair {
+            app_protocol: AppProtocol::HTTPS,
+            traffic_direction: TrafficDirection::Outgoing,
+            ..InfoAddressPortPair::default()
+        };
+        info.traffic_series
+            .add_packet(998, 100, TrafficDirection::Outgoing);
+        info.traffic_series
+            .add_packet(1000, 40, TrafficDirection::Incoming);
+        info.traffic_series
+            .add_packet(1000, 60, TrafficDirection::Incoming);
+        info_traffic.map.insert(
+            AddressPortPair::new(
+                "10.0.0.1".to_string(),
+                50000,
+                "10.0.0.2".to_string(),
+                443,
+                TransProtocol::TCP,
+            ),
+            info,
+        );
+        for _ in 0..3 {
+            update_charts_data(&mut runtime_data, &mut traffic_chart);
+        }
+
+        // the last interval is matched with the current second
+        rebase_charts_data(&info_traffic, 1000, &mut runtime_data, &mut traffic_chart);
+        assert_eq!(
+            traffic_chart.sent_bytes,
+            VecDeque::from([(0, -100), (1, 0), (2, 0)])
+        );
+        assert_eq!(
+            traffic_chart.received_bytes,
+            VecDeque::from([(0, 0), (1, 0), (2, 100)])
+        );
+        assert_eq!(
+            traffic_chart.received_packets,
+            VecDeque::from([(0, 0), (1, 0), (2, 2)])
+        );
+        assert_eq!(traffic_chart.min_sent_bytes, -100);
+        assert_eq!(
+            traffic_chart.app_protocols_history.values(
+                &BreakdownKey::Single("HTTPS".to_string()),
+                &[],
+                (0, 2),
+                ChartType::Bytes
+            ),
+            vec![(0, 100), (0, 0), (100, 0)]
+        );
+    }
 }
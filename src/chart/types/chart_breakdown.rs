@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, HashMap};
+
+use plotters::style::{Color, Palette, Palette99, RGBAColor, RGBColor};
+
+use crate::networking::types::data_info::DataInfo;
+use crate::translations::translations::application_protocol_translation;
+use crate::translations::translations_2::{country_translation, host_translation};
+use crate::translations::translations_3::{other_translation, total_traffic_translation};
+use crate::{ChartType, Language};
+
+/// Maximum number of series displayed individually; the others are grouped together
+pub const MAX_BREAKDOWN_SERIES: usize = 6;
+
+/// Enum representing the criteria to break down the traffic displayed in the chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChartBreakdown {
+    /// Only the total incoming and outgoing traffic
+    #[default]
+    Total,
+    AppProtocol,
+    Host,
+    Country,
+}
+
+impl ChartBreakdown {
+    pub(crate) const ALL: [ChartBreakdown; 4] = [
+        ChartBreakdown::Total,
+        ChartBreakdown::AppProtocol,
+        ChartBreakdown::Host,
+        ChartBreakdown::Country,
+    ];
+
+    pub fn get_picklist_label(self, language: Language) -> &'static str {
+        match self {
+            ChartBreakdown::Total => total_traffic_translation(language),
+            ChartBreakdown::AppProtocol => application_protocol_translation(language),
+            ChartBreakdown::Host => host_translation(language),
+            ChartBreakdown::Country => country_translation(language),
+        }
+    }
+
+    pub fn all_strings(language: Language) -> Vec<&'static str> {
+        ChartBreakdown::ALL
+            .iter()
+            .map(|breakdown| breakdown.get_picklist_label(language))
+            .collect()
+    }
+}
+
+/// A series of a broken down chart
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BreakdownKey {
+    /// Traffic of a single application protocol, host or country
+    Single(String),
+    /// Traffic of all the remaining application protocols, hosts or countries
+    Other,
+}
+
+impl BreakdownKey {
+    pub fn get_label(&self, language: Language) -> String {
+        match self {
+            BreakdownKey::Single(key) => key.clone(),
+            BreakdownKey::Other => other_translation(language).to_string(),
+        }
+    }
+}
+
+/// Color of the series at the given position
+pub fn get_breakdown_color(index: usize) -> RGBColor {
+    let RGBAColor(r, g, b, _) = Palette99::pick(index).to_rgba();
+    RGBColor(r, g, b)
+}
+
+/// Traffic exchanged in each time interval by each application protocol, host or country
+#[derive(Default)]
+pub struct BreakdownHistory {
+    /// Data exchanged by each key up to the last recorded interval
+    previous: HashMap<String, DataInfo>,
+    /// Data exchanged by each key in the intervals where it had some traffic
+    series: HashMap<String, Vec<(u32, DataInfo)>>,
+}
+
+impl BreakdownHistory {
+    /// Records the data exchanged in the given interval, given the total data exchanged so far by each key
+    pub fn update(&mut self, tick: u32, totals: HashMap<String, DataInfo>) {
+        for (key, total) in &totals {
+            let previous = self.previous.get(key).copied().unwrap_or_default();
+            let difference = DataInfo {
+                incoming_packets: total
+                    .incoming_packets
+                    .saturating_sub(previous.incoming_packets),
+                outgoing_packets: total
+                    .outgoing_packets
+                    .saturating_sub(previous.outgoing_packets),
+                incoming_bytes: total.incoming_bytes.saturating_sub(previous.incoming_bytes),
+                outgoing_bytes: total.outgoing_bytes.saturating_sub(previous.outgoing_bytes),
+            };
+            if difference.tot_packets() > 0 {
+                self.series
+                    .entry(key.clone())
+                    .or_default()
+                    .push((tick, difference));
+            }
+        }
+        self.previous = totals;
+    }
+
+    /// Replaces the data exchanged so far by each key, without recording any interval
+    /// (used when the totals are recomputed, so that the difference doesn't show up as traffic)
+    pub fn rebase(&mut self, totals: HashMap<String, DataInfo>) {
+        self.previous = totals;
+    }
+
+    /// Replaces the recorded intervals with the given ones (used when they're recomputed)
+    pub fn replace_series(&mut self, series: HashMap<String, BTreeMap<u32, DataInfo>>) {
+        self.series = series
+            .into_iter()
+            .map(|(key, samples)| (key, samples.into_iter().collect()))
+            .collect();
+    }
+
+    /// Intervals with some traffic for the given key, within the given range
+    fn samples_in(&self, key: &str, start: u32, end: u32) -> &[(u32, DataInfo)] {
+        let Some(samples) = self.series.get(key) else {
+            return &[];
+        };
+        let first = samples.partition_point(|(tick, _)| *tick < start);
+        let last = samples.partition_point(|(tick, _)| *tick <= end);
+        &samples[first..last]
+    }
+
+    /// Keys with the most traffic within the given range, followed by [`BreakdownKey::Other`]
+    /// if the remaining keys have some traffic
+    pub fn top_keys(&self, start: u32, end: u32, chart_type: ChartType) -> Vec<BreakdownKey> {
+        let mut amounts: Vec<(&String, u128)> = self
+            .series
+            .keys()
+            .map(|key| {
+                let amount = self
+                    .samples_in(key, start, end)
+                    .iter()
+                    .map(|(_, data_info)| get_amount(data_info, chart_type))
+                    .sum();
+                (key, amount)
+            })
+            .filter(|(_, amount)| *amount > 0)
+            .collect();
+        amounts.sort_by(|(key_a, amount_a), (key_b, amount_b)| {
+            amount_b.cmp(amount_a).then(key_a.cmp(key_b))
+        });
+        let mut keys: Vec<BreakdownKey> = amounts
+            .iter()
+            .take(MAX_BREAKDOWN_SERIES)
+            .map(|(key, _)| BreakdownKey::Single((*key).clone()))
+            .collect();
+        if amounts.len() > MAX_BREAKDOWN_SERIES {
+            keys.push(BreakdownKey::Other);
+        }
+        keys
+    }
+
+    /// Incoming and outgoing amounts of the given series for each interval within the given range;
+    /// `others` are the keys to be excluded when computing [`BreakdownKey::Other`]
+    pub fn values(
+        &self,
+        key: &BreakdownKey,
+        others: &[BreakdownKey],
+        (start, end): (u32, u32),
+        chart_type: ChartType,
+    ) -> Vec<(u128, u128)> {
+        let mut values = vec![(0, 0); (end - start + 1) as usize];
+        let mut add_samples = |samples: &[(u32, DataInfo)]| {
+            for (tick, data_info) in samples {
+                let value = &mut values[(tick - start) as usize];
+                if chart_type.eq(&ChartType::Packets) {
+                    value.0 += data_info.incoming_packets;
+                    value.1 += data_info.outgoing_packets;
+                } else {
+                    value.0 += data_info.incoming_bytes;
+                    value.1 += data_info.outgoing_bytes;
+                }
+            }
+        };
+        match key {
+            BreakdownKey::Single(key) => add_samples(self.samples_in(key, start, end)),
+            BreakdownKey::Other => {
+                for key in self.series.keys() {
+                    if !others.contains(&BreakdownKey::Single(key.clone())) {
+                        add_samples(self.samples_in(key, start, end));
+                    }
+                }
+            }
+        }
+        values
+    }
+}
+
+fn get_amount(data_info: &DataInfo, chart_type: ChartType) -> u128 {
+    if chart_type.eq(&ChartType::Packets) {
+        data_info.tot_packets()
+    } else {
+        data_info.tot_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::networking::types::traffic_direction::TrafficDirection;
+
+    use super::*;
+
+    fn totals(values: &[(&str, u128)]) -> HashMap<String, DataInfo> {
+        values
+            .iter()
+            .map(|(key, bytes)| {
+                let mut data_info = DataInfo::default();
+                for _ in 0..*bytes / 100 {
+                    data_info.add_packet(100, TrafficDirection::Incoming);
+                }
+                (key.to_string(), data_info)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_breakdown_history() {
+        let mut history = BreakdownHistory::default();
+        history.update(0, totals(&[("HTTPS", 1000), ("DNS", 100)]));
+        history.update(1, totals(&[("HTTPS", 1000), ("DNS", 300)]));
+        history.update(2, totals(&[("HTTPS", 6000), ("DNS", 300), ("NTP", 100)]));
+
+        let keys = history.top_keys(0, 2, ChartType::Bytes);
+        let https = BreakdownKey::Single("HTTPS".to_string());
+        let dns = BreakdownKey::Single("DNS".to_string());
+        assert_eq!(
+            keys,
+            vec![
+                https.clone(),
+                dns.clone(),
+                BreakdownKey::Single("NTP".to_string())
+            ]
+        );
+        assert_eq!(
+            history.values(&https, &keys, (0, 2), ChartType::Bytes),
+            vec![(1000, 0), (0, 0), (5000, 0)]
+        );
+        assert_eq!(
+            history.values(&dns, &keys, (1, 2), ChartType::Packets),
+            vec![(2, 0), (0, 0)]
+        );
+        // within the first two intervals DNS has more traffic
+        assert_eq!(history.top_keys(1, 1, ChartType::Bytes), vec![dns]);
+        // the remaining keys are grouped together
+        assert_eq!(
+            history.values(&BreakdownKey::Other, &[https], (0, 2), ChartType::Bytes),
+            vec![(100, 0), (200, 0), (100, 0)]
+        );
+    }
+}
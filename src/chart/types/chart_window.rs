@@ -0,0 +1,56 @@
+use crate::translations::translations_3::whole_session_translation;
+use crate::Language;
+
+/// Enum representing the time windows that can be displayed in the traffic chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChartWindow {
+    #[default]
+    Last30Seconds,
+    Last5Minutes,
+    LastHour,
+    WholeSession,
+}
+
+impl ChartWindow {
+    pub(crate) const ALL: [ChartWindow; 4] = [
+        ChartWindow::Last30Seconds,
+        ChartWindow::Last5Minutes,
+        ChartWindow::LastHour,
+        ChartWindow::WholeSession,
+    ];
+
+    /// Number of seconds displayed, or `None` if the whole session is displayed
+    pub fn seconds(self) -> Option<u32> {
+        match self {
+            ChartWindow::Last30Seconds => Some(30),
+            ChartWindow::Last5Minutes => Some(300),
+            ChartWindow::LastHour => Some(3600),
+            ChartWindow::WholeSession => None,
+        }
+    }
+
+    pub fn get_picklist_label(self, language: Language) -> &'static str {
+        match self {
+            ChartWindow::Last30Seconds => "30 s",
+            ChartWindow::Last5Minutes => "5 min",
+            ChartWindow::LastHour => "1 h",
+            ChartWindow::WholeSession => whole_session_translation(language),
+        }
+    }
+
+    pub fn all_strings(language: Language) -> Vec<&'static str> {
+        ChartWindow::ALL
+            .iter()
+            .map(|window| window.get_picklist_label(language))
+            .collect()
+    }
+}
+
+/// Portion of the selected time window currently displayed, as modified by zooming and panning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChartViewport {
+    /// Number of displayed seconds; if `None`, it's the one of the selected time window
+    pub span: Option<u32>,
+    /// Last displayed second; if `None`, the chart follows the latest data
+    pub end: Option<u32>,
+}
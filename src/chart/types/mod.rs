@@ -1,2 +1,5 @@
+pub mod chart_breakdown;
 pub mod chart_type;
+pub mod chart_window;
+pub mod sparkline_chart;
 pub mod traffic_chart;
@@ -0,0 +1,125 @@
+//! This module defines the `SparklineChart` struct, a small chart of the recent traffic
+//! of a single connection or host
+
+use iced::widget::Container;
+use iced::{Element, Length, Renderer};
+use plotters::prelude::*;
+use plotters_iced::{Chart, ChartBuilder, ChartWidget, DrawingBackend};
+
+use crate::gui::styles::style_constants::{get_alpha_chart_badge, get_font_weight};
+use crate::gui::styles::types::palette::to_rgb_color;
+use crate::gui::types::message::Message;
+use crate::networking::types::traffic_series::TrafficSeries;
+use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
+use crate::{get_colors, StyleType};
+
+/// Chart of the bytes exchanged per second by a connection or host, with incoming traffic
+/// above the x axis and outgoing traffic below it
+pub struct SparklineChart {
+    /// Incoming and outgoing bytes for each second, from the oldest
+    values: Vec<(u128, u128)>,
+    /// Whether to display the y axis labels
+    with_labels: bool,
+    /// Style of the chart
+    style: StyleType,
+}
+
+impl SparklineChart {
+    pub fn new(
+        traffic_series: &TrafficSeries,
+        now: i64,
+        with_labels: bool,
+        style: StyleType,
+    ) -> Self {
+        Self {
+            values: traffic_series.last_seconds(now),
+            with_labels,
+            style,
+        }
+    }
+
+    pub fn view(self, width: f32, height: f32) -> Element<'static, Message, Renderer<StyleType>> {
+        Container::new(
+            ChartWidget::new(self)
+                .width(Length::Fixed(width))
+                .height(Length::Fixed(height)),
+        )
+        .into()
+    }
+
+    fn points(&self, outgoing: bool) -> Vec<(u32, i64)> {
+        (0..)
+            .zip(&self.values)
+            .map(|(second, (incoming_bytes, outgoing_bytes))| {
+                if outgoing {
+                    (second, -i64::try_from(*outgoing_bytes).unwrap_or(i64::MAX))
+                } else {
+                    (second, i64::try_from(*incoming_bytes).unwrap_or(i64::MAX))
+                }
+            })
+            .collect()
+    }
+}
+
+impl Chart<Message> for SparklineChart {
+    type State = ();
+
+    fn build_chart<DB: DrawingBackend>(
+        &self,
+        _state: &Self::State,
+        mut chart_builder: ChartBuilder<DB>,
+    ) {
+        let incoming = self.points(false);
+        let outgoing = self.points(true);
+        // keep the x axis in the middle, so that bursts in both directions are comparable
+        let max = incoming
+            .iter()
+            .chain(&outgoing)
+            .map(|(_, bytes)| bytes.abs())
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        let last_second = u32::try_from(self.values.len()).unwrap_or_default().max(2) - 1;
+
+        let colors = get_colors(self.style);
+        let color_incoming = to_rgb_color(colors.secondary);
+        let color_outgoing = to_rgb_color(colors.outgoing);
+        let color_font = to_rgb_color(colors.text_body);
+        let color_mix = get_alpha_chart_badge(self.style);
+
+        if self.with_labels {
+            chart_builder.set_label_area_size(LabelAreaPosition::Left, 60);
+        }
+        let mut chart = chart_builder
+            .build_cartesian_2d(0..last_second, -max..max)
+            .expect("Error drawing sparkline");
+
+        if self.with_labels {
+            chart
+                .configure_mesh()
+                .disable_mesh()
+                .disable_x_axis()
+                .label_style(
+                    ("Sarasa Mono SC", 11)
+                        .into_font()
+                        .style(get_font_weight(self.style))
+                        .color(&color_font),
+                )
+                .y_labels(3)
+                .y_label_formatter(&|bytes| {
+                    get_formatted_bytes_string_with_b(u128::from(bytes.unsigned_abs()))
+                })
+                .draw()
+                .expect("Error drawing sparkline");
+        }
+
+        for (points, color) in [(incoming, color_incoming), (outgoing, color_outgoing)] {
+            chart
+                .draw_series(
+                    AreaSeries::new(points, 0, color.mix(color_mix.into()))
+                        .border_style(ShapeStyle::from(&color).stroke_width(1)),
+                )
+                .expect("Error drawing sparkline");
+        }
+    }
+}
@@ -1,13 +1,22 @@
 //! This module defines the behavior of the `TrafficChart` struct, used to display chart in GUI run page
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use iced::alignment::{Horizontal, Vertical};
+use iced::event::Status;
+use iced::mouse::{Cursor, Interaction, ScrollDelta};
+use iced::widget::canvas::Event;
 use iced::widget::{Column, Container};
-use iced::{Element, Renderer};
+use iced::{mouse, Element, Renderer};
+use plotters::coord::ReverseCoordTranslate;
 use plotters::prelude::*;
 use plotters_iced::{Chart, ChartBuilder, ChartWidget, DrawingBackend};
 
+use crate::chart::manage_chart_data::update_chart_bounds;
+use crate::chart::types::chart_breakdown::{
+    get_breakdown_color, BreakdownHistory, BreakdownKey, ChartBreakdown,
+};
+use crate::chart::types::chart_window::{ChartViewport, ChartWindow};
 use crate::gui::styles::style_constants::{
     get_alpha_chart_badge, get_font_weight, CHARTS_LINE_BORDER,
 };
@@ -17,25 +26,32 @@
 use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
 use crate::{get_colors, ChartType, Language, StyleType};
 
+/// Minimum number of seconds displayed when zooming in
+const MIN_CHART_SPAN: u32 = 10;
+/// Maximum number of points drawn for each series; longer ranges are downsampled
+const MAX_CHART_POINTS: usize = 300;
+/// Horizontal space taken by the y labels and the right margin of the chart
+const CHART_HORIZONTAL_PADDING: f32 = 90.0;
+
 /// Struct defining the chart to be displayed in gui run page
 pub struct TrafficChart {
     /// Current time interval number
     pub ticks: u32,
-    /// Sent bytes filtered and their time occurrence
+    /// Sent bytes filtered and their time occurrence (for the whole session)
     pub sent_bytes: VecDeque<(u32, i64)>,
-    /// Received bytes filtered and their time occurrence
+    /// Received bytes filtered and their time occurrence (for the whole session)
     pub received_bytes: VecDeque<(u32, i64)>,
-    /// Sent packets filtered and their time occurrence
+    /// Sent packets filtered and their time occurrence (for the whole session)
     pub sent_packets: VecDeque<(u32, i64)>,
-    /// Received packets filtered and their time occurrence
+    /// Received packets filtered and their time occurrence (for the whole session)
     pub received_packets: VecDeque<(u32, i64)>,
-    /// Minimum number of sent bytes per time interval (computed on last 30 intervals)
+    /// Minimum number of sent bytes per time interval (computed on the displayed intervals)
     pub min_sent_bytes: i64,
-    /// Minimum number of received bytes per time interval (computed on last 30 intervals)
+    /// Minimum number of received bytes per time interval (computed on the displayed intervals)
     pub max_received_bytes: i64,
-    /// Minimum number of sent packets per time interval (computed on last 30 intervals)
+    /// Minimum number of sent packets per time interval (computed on the displayed intervals)
     pub min_sent_packets: i64,
-    /// Minimum number of received packets per time interval (computed on last 30 intervals)
+    /// Minimum number of received packets per time interval (computed on the displayed intervals)
     pub max_received_packets: i64,
     /// Language used for the chart legend
     pub language: Language,
@@ -43,6 +59,29 @@ pub struct TrafficChart {
     pub chart_type: ChartType,
     /// Style of the chart
     pub style: StyleType,
+    /// Selected time window
+    pub window: ChartWindow,
+    /// Zoom and pan applied to the selected time window
+    pub viewport: ChartViewport,
+    /// Criterion used to break down the displayed traffic
+    pub breakdown: ChartBreakdown,
+    /// Traffic of each application protocol over time
+    pub app_protocols_history: BreakdownHistory,
+    /// Traffic of each host over time
+    pub hosts_history: BreakdownHistory,
+    /// Traffic of each country over time
+    pub countries_history: BreakdownHistory,
+    /// Series of the broken down chart hidden by the user
+    pub hidden_series: HashSet<BreakdownKey>,
+}
+
+/// State of the interactions with the chart
+#[derive(Default)]
+pub struct ChartState {
+    /// Position of the cursor relative to the chart, if hovering it
+    cursor: Option<iced::Point>,
+    /// Horizontal position where dragging started, and last displayed second at that moment
+    drag_start: Option<(f32, u32)>,
 }
 
 impl TrafficChart {
@@ -60,6 +99,13 @@ pub fn new(style: StyleType, language: Language) -> Self {
             language,
             chart_type: ChartType::Bytes,
             style,
+            window: ChartWindow::default(),
+            viewport: ChartViewport::default(),
+            breakdown: ChartBreakdown::default(),
+            app_protocols_history: BreakdownHistory::default(),
+            hosts_history: BreakdownHistory::default(),
+            countries_history: BreakdownHistory::default(),
+            hidden_series: HashSet::new(),
         }
     }
 
@@ -81,14 +127,161 @@ pub fn change_language(&mut self, language: Language) {
     pub fn change_style(&mut self, style: StyleType) {
         self.style = style;
     }
+
+    pub fn change_window(&mut self, window: ChartWindow) {
+        self.window = window;
+        self.viewport = ChartViewport::default();
+        update_chart_bounds(self);
+    }
+
+    pub fn change_breakdown(&mut self, breakdown: ChartBreakdown) {
+        self.breakdown = breakdown;
+        self.hidden_series.clear();
+    }
+
+    /// Shows or hides a series of the broken down chart
+    pub fn toggle_series(&mut self, key: BreakdownKey) {
+        if !self.hidden_series.remove(&key) {
+            self.hidden_series.insert(key);
+        }
+    }
+
+    fn breakdown_history(&self) -> Option<&BreakdownHistory> {
+        match self.breakdown {
+            ChartBreakdown::Total => None,
+            ChartBreakdown::AppProtocol => Some(&self.app_protocols_history),
+            ChartBreakdown::Host => Some(&self.hosts_history),
+            ChartBreakdown::Country => Some(&self.countries_history),
+        }
+    }
+
+    /// Series of the broken down chart in the displayed range, from the one with most traffic
+    pub fn breakdown_keys(&self) -> Vec<BreakdownKey> {
+        let (start, end) = self.displayed_range();
+        self.breakdown_history()
+            .map(|history| history.top_keys(start, end, self.chart_type))
+            .unwrap_or_default()
+    }
+
+    /// Visible series of the broken down chart, each stacked on top of the previous ones,
+    /// with the index of their color
+    fn stacked_series(&self, history: &BreakdownHistory) -> Vec<(usize, Vec<(u128, u128)>)> {
+        let range = self.displayed_range();
+        let keys = self.breakdown_keys();
+        let mut cumulative = vec![(0, 0); (range.1 - range.0 + 1) as usize];
+        let mut stacks = Vec::new();
+        for (index, key) in keys.iter().enumerate() {
+            if self.hidden_series.contains(key) {
+                continue;
+            }
+            let values = history.values(key, &keys, range, self.chart_type);
+            for (sum, (incoming, outgoing)) in cumulative.iter_mut().zip(values) {
+                sum.0 += incoming;
+                sum.1 += outgoing;
+            }
+            stacks.push((index, cumulative.clone()));
+        }
+        stacks
+    }
+
+    /// Zooms or pans the chart, keeping the displayed range within the session
+    pub fn change_viewport(&mut self, viewport: ChartViewport) {
+        let last = self.ticks.saturating_sub(1);
+        self.viewport = ChartViewport {
+            span: viewport
+                .span
+                .map(|span| span.clamp(MIN_CHART_SPAN, self.ticks.max(MIN_CHART_SPAN))),
+            end: viewport.end.filter(|end| *end < last),
+        };
+        update_chart_bounds(self);
+    }
+
+    /// Number of seconds currently displayed
+    pub fn displayed_span(&self) -> u32 {
+        self.viewport
+            .span
+            .or(self.window.seconds())
+            .unwrap_or(self.ticks)
+            .max(MIN_CHART_SPAN)
+    }
+
+    /// First and last seconds currently displayed
+    pub fn displayed_range(&self) -> (u32, u32) {
+        let last = self.ticks.saturating_sub(1);
+        let end = self.viewport.end.map_or(last, |end| end.min(last));
+        (end.saturating_sub(self.displayed_span() - 1), end)
+    }
+
+    /// Samples of the given series falling in the displayed range
+    pub fn displayed_samples<'a>(
+        &self,
+        samples: &'a VecDeque<(u32, i64)>,
+    ) -> impl Iterator<Item = &'a (u32, i64)> {
+        let (start, end) = self.displayed_range();
+        let first = samples.partition_point(|(second, _)| *second < start);
+        let last = samples.partition_point(|(second, _)| *second <= end);
+        samples.range(first..last)
+    }
+
+    /// Points of the given series to be drawn.
+    ///
+    /// Long ranges are downsampled keeping the peak of each group of samples,
+    /// so that spikes are still visible
+    pub fn displayed_points(&self, samples: &VecDeque<(u32, i64)>) -> Vec<(u32, i64)> {
+        let samples: Vec<(u32, i64)> = self.displayed_samples(samples).copied().collect();
+        let group_size = samples.len().div_ceil(MAX_CHART_POINTS).max(1);
+        samples
+            .chunks(group_size)
+            .map(|group| {
+                let peak = group
+                    .iter()
+                    .map(|(_, value)| *value)
+                    .max_by_key(|value| value.unsigned_abs())
+                    .unwrap_or_default();
+                (group[0].0, peak)
+            })
+            .collect()
+    }
+
+    /// Indexes of the values to be drawn: long ranges are downsampled keeping the peak
+    /// of each group of values
+    fn peak_indexes(values: &[(u128, u128)]) -> Vec<usize> {
+        let group_size = values.len().div_ceil(MAX_CHART_POINTS).max(1);
+        (0..values.len())
+            .step_by(group_size)
+            .map(|first| {
+                (first..(first + group_size).min(values.len()))
+                    .max_by_key(|index| values[*index].0 + values[*index].1)
+                    .unwrap_or(first)
+            })
+            .collect()
+    }
+
+    fn format_value(&self, value: i64) -> String {
+        if self.chart_type.eq(&ChartType::Packets) {
+            value.unsigned_abs().to_string()
+        } else {
+            get_formatted_bytes_string_with_b(u128::from(value.unsigned_abs()))
+        }
+    }
+}
+
+/// Formats a second of the session as minutes and seconds (and hours, if needed)
+pub fn format_chart_time(second: u32) -> String {
+    let (hours, minutes, seconds) = (second / 3600, second % 3600 / 60, second % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
 }
 
 impl Chart<Message> for TrafficChart {
-    type State = ();
+    type State = ChartState;
 
     fn build_chart<DB: DrawingBackend>(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         mut chart_builder: ChartBuilder<DB>,
     ) {
         let font_weight = get_font_weight(self.style);
@@ -96,8 +289,12 @@ fn build_chart<DB: DrawingBackend>(
         if self.ticks == 0 {
             return;
         }
-        let tot_seconds = self.ticks - 1;
-        let first_time_displayed = if self.ticks > 30 { self.ticks - 30 } else { 0 };
+        let (first_time_displayed, tot_seconds) = self.displayed_range();
+        let (received, sent) = if self.chart_type.eq(&ChartType::Packets) {
+            (&self.received_packets, &self.sent_packets)
+        } else {
+            (&self.received_bytes, &self.sent_bytes)
+        };
 
         let colors = get_colors(self.style);
         let color_incoming = to_rgb_color(colors.secondary);
@@ -132,6 +329,7 @@ fn build_chart<DB: DrawingBackend>(
                     .color(&color_font),
             )
             .y_labels(7)
+            .x_label_formatter(&|second| format_chart_time(*second))
             .y_label_formatter(if self.chart_type.eq(&ChartType::Packets) {
                 &|packets| packets.abs().to_string()
             } else {
@@ -140,59 +338,221 @@ fn build_chart<DB: DrawingBackend>(
             .draw()
             .unwrap();
 
-        // Incoming series
-        chart
-            .draw_series(
-                AreaSeries::new(
-                    if self.chart_type.eq(&ChartType::Packets) {
-                        self.received_packets.iter().copied()
-                    } else {
-                        self.received_bytes.iter().copied()
-                    },
-                    0,
-                    color_incoming.mix(color_mix.into()),
+        if let Some(history) = self.breakdown_history() {
+            // Stacked series (incoming above and outgoing below the x axis), drawing the tallest first
+            let stacks = self.stacked_series(history);
+            let indexes = stacks
+                .last()
+                .map(|(_, top)| Self::peak_indexes(top))
+                .unwrap_or_default();
+            let to_i64 = |value: u128| i64::try_from(value).unwrap_or(i64::MAX);
+            for (color_index, values) in stacks.iter().rev() {
+                let color = get_breakdown_color(*color_index);
+                let points = |outgoing: bool| {
+                    indexes.iter().map(move |index| {
+                        let (incoming_value, outgoing_value) = values[*index];
+                        #[allow(clippy::cast_possible_truncation)]
+                        let second = first_time_displayed + *index as u32;
+                        if outgoing {
+                            (second, -to_i64(outgoing_value))
+                        } else {
+                            (second, to_i64(incoming_value))
+                        }
+                    })
+                };
+                for outgoing in [false, true] {
+                    chart
+                        .draw_series(
+                            AreaSeries::new(points(outgoing), 0, color.filled())
+                                .border_style(ShapeStyle::from(&color).stroke_width(1)),
+                        )
+                        .expect("Error drawing graph");
+                }
+            }
+        } else {
+            // Incoming series
+            chart
+                .draw_series(
+                    AreaSeries::new(
+                        self.displayed_points(received),
+                        0,
+                        color_incoming.mix(color_mix.into()),
+                    )
+                    .border_style(
+                        ShapeStyle::from(&color_incoming).stroke_width(CHARTS_LINE_BORDER),
+                    ),
                 )
-                .border_style(ShapeStyle::from(&color_incoming).stroke_width(CHARTS_LINE_BORDER)),
-            )
-            .expect("Error drawing graph")
-            .label(incoming_translation(self.language))
-            .legend(move |(x, y)| {
-                Rectangle::new([(x, y - 5), (x + 25, y + 5)], color_incoming.filled())
-            });
+                .expect("Error drawing graph")
+                .label(incoming_translation(self.language))
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 25, y + 5)], color_incoming.filled())
+                });
 
-        // Outgoing series
-        chart
-            .draw_series(
-                AreaSeries::new(
-                    if self.chart_type.eq(&ChartType::Packets) {
-                        self.sent_packets.iter().copied()
-                    } else {
-                        self.sent_bytes.iter().copied()
-                    },
-                    0,
-                    color_outgoing.mix(color_mix.into()),
+            // Outgoing series
+            chart
+                .draw_series(
+                    AreaSeries::new(
+                        self.displayed_points(sent),
+                        0,
+                        color_outgoing.mix(color_mix.into()),
+                    )
+                    .border_style(
+                        ShapeStyle::from(&color_outgoing).stroke_width(CHARTS_LINE_BORDER),
+                    ),
                 )
-                .border_style(ShapeStyle::from(&color_outgoing).stroke_width(CHARTS_LINE_BORDER)),
-            )
-            .expect("Error drawing graph")
-            .label(outgoing_translation(self.language))
-            .legend(move |(x, y)| {
-                Rectangle::new([(x, y - 5), (x + 25, y + 5)], color_outgoing.filled())
-            });
+                .expect("Error drawing graph")
+                .label(outgoing_translation(self.language))
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 25, y + 5)], color_outgoing.filled())
+                });
 
-        // Legend
+            // Legend
+            chart
+                .configure_series_labels()
+                .position(SeriesLabelPosition::UpperRight)
+                .background_style(BLACK.mix(0.3))
+                .border_style(BLACK.mix(0.6))
+                .label_font(
+                    ("Sarasa Mono SC", 13.5)
+                        .into_font()
+                        .style(font_weight)
+                        .color(&color_font),
+                )
+                .draw()
+                .expect("Error drawing graph");
+        }
+
+        // Tooltip with the exact values at the hovered second
+        let Some(cursor) = state.cursor else {
+            return;
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let Some((second, _)) = chart
+            .as_coord_spec()
+            .reverse_translate((cursor.x as i32, cursor.y as i32))
+        else {
+            return;
+        };
+        let value_at = |samples: &VecDeque<(u32, i64)>| {
+            samples
+                .binary_search_by_key(&second, |(s, _)| *s)
+                .map_or(0, |index| samples[index].1)
+        };
+        let (y_min, y_max) = if self.chart_type.eq(&ChartType::Packets) {
+            (self.min_sent_packets, self.max_received_packets)
+        } else {
+            (self.min_sent_bytes, self.max_received_bytes)
+        };
         chart
-            .configure_series_labels()
-            .position(SeriesLabelPosition::UpperRight)
-            .background_style(BLACK.mix(0.3))
-            .border_style(BLACK.mix(0.6))
-            .label_font(
-                ("Sarasa Mono SC", 13.5)
-                    .into_font()
-                    .style(font_weight)
-                    .color(&color_font),
+            .draw_series(std::iter::once(PathElement::new(
+                vec![(second, y_min), (second, y_max)],
+                color_font,
+            )))
+            .expect("Error drawing graph");
+        let lines = [
+            format_chart_time(second),
+            format!(
+                "{}: {}",
+                incoming_translation(self.language),
+                self.format_value(value_at(received))
+            ),
+            format!(
+                "{}: {}",
+                outgoing_translation(self.language),
+                self.format_value(value_at(sent))
+            ),
+        ];
+        let text_style = ("Sarasa Mono SC", 12.5)
+            .into_font()
+            .style(font_weight)
+            .color(&color_font);
+        // show the tooltip on the left of the cursor when in the right half of the chart
+        let x_offset = if second - first_time_displayed > (tot_seconds - first_time_displayed) / 2 {
+            -170
+        } else {
+            10
+        };
+        let tooltip = EmptyElement::at((second, y_max))
+            + Rectangle::new(
+                [(x_offset, 0), (x_offset + 160, 55)],
+                BLACK.mix(0.6).filled(),
             )
-            .draw()
+            + Text::new(lines[0].clone(), (x_offset + 8, 5), text_style.clone())
+            + Text::new(lines[1].clone(), (x_offset + 8, 22), text_style.clone())
+            + Text::new(lines[2].clone(), (x_offset + 8, 39), text_style);
+        chart
+            .plotting_area()
+            .draw(&tooltip)
             .expect("Error drawing graph");
     }
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: Event,
+        bounds: iced::Rectangle,
+        cursor: Cursor,
+    ) -> (Status, Option<Message>) {
+        state.cursor = cursor.position_in(bounds);
+        let Event::Mouse(mouse_event) = event else {
+            return (Status::Ignored, None);
+        };
+        match (mouse_event, state.cursor) {
+            // zoom in scrolling up, zoom out scrolling down
+            (mouse::Event::WheelScrolled { delta }, Some(_)) => {
+                let (ScrollDelta::Lines { y, .. } | ScrollDelta::Pixels { y, .. }) = delta;
+                let span = self.displayed_span();
+                let span = if y > 0.0 {
+                    span * 4 / 5
+                } else {
+                    span * 5 / 4 + 1
+                };
+                let viewport = ChartViewport {
+                    span: Some(span),
+                    ..self.viewport
+                };
+                (Status::Captured, Some(Message::ChartViewport(viewport)))
+            }
+            (mouse::Event::ButtonPressed(mouse::Button::Left), Some(position)) => {
+                state.drag_start = Some((position.x, self.displayed_range().1));
+                (Status::Captured, None)
+            }
+            (mouse::Event::ButtonReleased(mouse::Button::Left), _) => {
+                state.drag_start = None;
+                (Status::Ignored, None)
+            }
+            // dragging to the right moves back in time
+            (mouse::Event::CursorMoved { .. }, Some(position)) => {
+                let Some((start_x, start_end)) = state.drag_start else {
+                    return (Status::Ignored, None);
+                };
+                let span = self.displayed_span();
+                let plot_width = (bounds.width - CHART_HORIZONTAL_PADDING).max(1.0);
+                #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+                let shift = ((position.x - start_x) / plot_width * span as f32).round() as i64;
+                let end = (i64::from(start_end) - shift).max(i64::from(span - 1));
+                let viewport = ChartViewport {
+                    end: Some(u32::try_from(end).unwrap_or_default()),
+                    ..self.viewport
+                };
+                (Status::Captured, Some(Message::ChartViewport(viewport)))
+            }
+            _ => (Status::Ignored, None),
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        _bounds: iced::Rectangle,
+        _cursor: Cursor,
+    ) -> Interaction {
+        if state.drag_start.is_some() {
+            Interaction::Grabbing
+        } else if state.cursor.is_some() {
+            Interaction::Crosshair
+        } else {
+            Interaction::Idle
+        }
+    }
 }
@@ -0,0 +1,107 @@
+//! Module containing the capture executed when Sniffnet runs without graphical interface.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use pcap::{Active, Capture, Device};
+
+use crate::chart::export_chart::export_chart;
+use crate::chart::manage_chart_data::{update_breakdown_data, update_charts_data};
+use crate::chart::types::chart_window::ChartWindow;
+use crate::cli::ChartExportArgs;
+use crate::gui::types::status::Status;
+use crate::networking::manage_packets::get_capture_result;
+use crate::networking::types::filters::Filters;
+use crate::networking::types::my_device::MyDevice;
+use crate::secondary_threads::parse_packets::parse_packets;
+use crate::utils::formatted_strings::get_report_path;
+use crate::{InfoTraffic, Language, RunTimeData, StyleType, TrafficChart};
+
+/// Number of seconds between two exports of the traffic chart image
+const CHART_EXPORT_INTERVAL: u32 = 5;
+
+/// Captures the traffic of the given network adapter until the process is terminated.
+///
+/// The shared traffic statistics keep being updated, so that the report file and
+/// the other consumers (e.g., the metrics endpoint) work as when the GUI is running
+pub fn run_headless(
+    adapter: &str,
+    current_capture_id: &Arc<Mutex<u16>>,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    status_pair: &Arc<(Mutex<Status>, Condvar)>,
+) {
+    let (device, cap) = open_adapter_or_exit(adapter);
+
+    *status_pair.0.lock().unwrap() = Status::Running;
+    status_pair.1.notify_all();
+    println!(
+        "Capturing on '{adapter}' (report: {}), press Ctrl+C to stop",
+        get_report_path().display()
+    );
+
+    parse_packets(
+        current_capture_id,
+        &device,
+        cap,
+        Filters::default(),
+        info_traffic_mutex,
+    );
+}
+
+/// Opens a capture on the network adapter with the given name,
+/// exiting the process if it's not possible
+pub fn open_adapter_or_exit(adapter: &str) -> (MyDevice, Capture<Active>) {
+    let Some(device) = Device::list()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|device| device.name.eq(adapter))
+    else {
+        eprintln!("sniffnet: network adapter '{adapter}' not found");
+        std::process::exit(1);
+    };
+    let device = MyDevice {
+        name: device.name,
+        desc: device.desc,
+        addresses: Arc::new(Mutex::new(device.addresses)),
+    };
+    let (pcap_error, cap) = get_capture_result(&device);
+    if let Some(error) = pcap_error {
+        eprintln!("sniffnet: cannot capture on '{adapter}': {error}");
+        std::process::exit(1);
+    }
+    (device, cap.unwrap())
+}
+
+/// Keeps the traffic chart updated every second, like the GUI does,
+/// and periodically exports it to the image file supplied from the command line.
+///
+/// The whole session is displayed, so that the image can be attached to incident reports
+pub fn export_chart_loop(
+    export_args: &ChartExportArgs,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    style: StyleType,
+    language: Language,
+) {
+    let mut runtime_data = RunTimeData::new();
+    let mut traffic_chart = TrafficChart::new(style, language);
+    traffic_chart.change_window(ChartWindow::WholeSession);
+    loop {
+        thread::sleep(Duration::from_secs(1));
+        let info_traffic = info_traffic_mutex.lock().unwrap();
+        runtime_data.tot_sent_packets = info_traffic.tot_sent_packets;
+        runtime_data.tot_received_packets = info_traffic.tot_received_packets;
+        runtime_data.tot_sent_bytes = info_traffic.tot_sent_bytes;
+        runtime_data.tot_received_bytes = info_traffic.tot_received_bytes;
+        update_breakdown_data(&info_traffic, &mut traffic_chart);
+        drop(info_traffic);
+        update_charts_data(&mut runtime_data, &mut traffic_chart);
+
+        if traffic_chart.ticks.is_multiple_of(CHART_EXPORT_INTERVAL) {
+            if let Err(e) = export_chart(&traffic_chart, style, &export_args.path, export_args.size)
+            {
+                eprintln!("sniffnet: cannot export the traffic chart: {e}");
+            }
+        }
+    }
+}
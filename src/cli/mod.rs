@@ -1,29 +1,255 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::chart::export_chart::{ChartImageFormat, ChartResolution};
 use crate::utils::formatted_strings::APP_VERSION;
 
+pub mod headless;
+
+/// Options supplied from the command line
+#[derive(Debug, Default, PartialEq)]
+pub struct CliArgs {
+    /// Address where the Prometheus metrics endpoint is exposed, if enabled
+    pub metrics: Option<SocketAddr>,
+    /// Whether to capture without graphical interface
+    pub headless: bool,
+    /// Whether to capture with the terminal user interface
+    pub tui: bool,
+    /// Network adapter to capture from in headless or terminal mode (the last used one if not specified)
+    pub adapter: Option<String>,
+    /// Options of the agent mode, if enabled
+    pub agent: Option<AgentArgs>,
+    /// Options of the local JSON API, if enabled
+    pub api: Option<ApiArgs>,
+    /// Options of the traffic chart image periodically exported in headless mode, if enabled
+    pub export_chart: Option<ChartExportArgs>,
+}
+
+/// Options of the agent mode, streaming the captured traffic to remote GUI instances
+#[derive(Debug, PartialEq)]
+pub struct AgentArgs {
+    /// Address where the agent listens for GUI connections
+    pub listen: SocketAddr,
+    /// Token the GUI has to supply to connect
+    pub token: String,
+    /// Certificate file (PEM) used for TLS
+    pub cert: Option<PathBuf>,
+    /// Private key file (PEM) used for TLS
+    pub key: Option<PathBuf>,
+}
+
+/// Options of the local JSON API used to query and control the capture
+#[derive(Debug, PartialEq)]
+pub struct ApiArgs {
+    /// Loopback address where the API is exposed
+    pub address: SocketAddr,
+    /// Token to be supplied as bearer authorization with each request
+    pub token: String,
+}
+
+/// Options of the traffic chart image exported in headless mode
+#[derive(Debug, PartialEq)]
+pub struct ChartExportArgs {
+    /// Image file (SVG or PNG, depending on the extension)
+    pub path: PathBuf,
+    /// Width and height of the image, in pixels
+    pub size: (u32, u32),
+}
+
+/// Default address where the agent listens for GUI connections
+const AGENT_DEFAULT_LISTEN: &str = "0.0.0.0:9797";
+
+/// Environment variable that can be used to supply the agent token
+const AGENT_TOKEN_VAR: &str = "SNIFFNET_AGENT_TOKEN";
+
+/// Environment variable that can be used to supply the API token
+const API_TOKEN_VAR: &str = "SNIFFNET_API_TOKEN";
+
 /// Parse CLI arguments, and exit if `--help`, `--version`, or an
 /// unknown argument was supplied
-pub fn parse_cli_args() {
-    let args = std::env::args().skip(1);
-    for arg in args {
+pub fn parse_cli_args() -> CliArgs {
+    match parse_args(std::env::args().skip(1), |var| std::env::var(var).ok()) {
+        Ok(cli_args) => cli_args,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_args(
+    args: impl Iterator<Item = String>,
+    env_var: impl Fn(&str) -> Option<String>,
+) -> Result<CliArgs, String> {
+    let mut args = args.peekable();
+    let mut cli_args = CliArgs::default();
+    let is_agent = args.next_if(|arg| arg.eq("agent")).is_some();
+    let mut listen = AGENT_DEFAULT_LISTEN.parse::<SocketAddr>().unwrap();
+    let (mut token, mut cert, mut key) = (env_var(AGENT_TOKEN_VAR), None, None);
+    let (mut api_address, mut api_token) = (None, env_var(API_TOKEN_VAR));
+    let (mut chart_path, mut chart_size) = (None, ChartResolution::default().size());
+    while let Some(arg) = args.next() {
         match arg.as_str() {
-            "--help" | "-h" => print_help(),
-            "--version" | "-v" => print_version(),
-            _ => {
-                unknown_argument(&arg);
-                std::process::exit(1);
+            "--help" | "-h" => {
+                print_help();
+                std::process::exit(0);
+            }
+            "--version" | "-v" => {
+                print_version();
+                std::process::exit(0);
+            }
+            "--metrics" => {
+                let value = args.next().ok_or_else(|| missing_value(&arg))?;
+                cli_args.metrics = Some(
+                    value
+                        .parse::<SocketAddr>()
+                        .map_err(|_| invalid_value(&arg, &value))?,
+                );
+            }
+            "--headless" => cli_args.headless = true,
+            "--tui" => cli_args.tui = true,
+            "--api" => {
+                let value = args.next().ok_or_else(|| missing_value(&arg))?;
+                let address = value
+                    .parse::<SocketAddr>()
+                    .map_err(|_| invalid_value(&arg, &value))?;
+                if !address.ip().is_loopback() {
+                    return Err(
+                        "sniffnet: the API can only be exposed on a loopback address\n\
+                        For more information, try 'sniffnet --help'"
+                            .to_string(),
+                    );
+                }
+                api_address = Some(address);
+            }
+            "--api-token" => {
+                api_token = Some(args.next().ok_or_else(|| missing_value(&arg))?);
+            }
+            "--adapter" => {
+                cli_args.adapter = Some(args.next().ok_or_else(|| missing_value(&arg))?);
+            }
+            "--export-chart" => {
+                let value = args.next().ok_or_else(|| missing_value(&arg))?;
+                let path = PathBuf::from(&value);
+                if ChartImageFormat::from_path(&path).is_none() {
+                    return Err(invalid_value(&arg, &value));
+                }
+                chart_path = Some(path);
+            }
+            "--chart-size" => {
+                let value = args.next().ok_or_else(|| missing_value(&arg))?;
+                chart_size = value
+                    .split_once('x')
+                    .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                    .filter(|(width, height)| *width > 0 && *height > 0)
+                    .ok_or_else(|| invalid_value(&arg, &value))?;
             }
+            "--listen" if is_agent => {
+                let value = args.next().ok_or_else(|| missing_value(&arg))?;
+                listen = value
+                    .parse::<SocketAddr>()
+                    .map_err(|_| invalid_value(&arg, &value))?;
+            }
+            "--token" if is_agent => {
+                token = Some(args.next().ok_or_else(|| missing_value(&arg))?);
+            }
+            "--cert" if is_agent => {
+                cert = Some(PathBuf::from(
+                    args.next().ok_or_else(|| missing_value(&arg))?,
+                ));
+            }
+            "--key" if is_agent => {
+                key = Some(PathBuf::from(
+                    args.next().ok_or_else(|| missing_value(&arg))?,
+                ));
+            }
+            _ => return Err(unknown_argument(&arg)),
         }
-        std::process::exit(0);
     }
+    if let Some(address) = api_address {
+        let token = api_token.filter(|token| !token.is_empty()).ok_or_else(|| {
+            format!(
+                "sniffnet: the API requires a token ('--api-token' or {API_TOKEN_VAR})\n\
+                For more information, try 'sniffnet --help'"
+            )
+        })?;
+        cli_args.api = Some(ApiArgs { address, token });
+    }
+    if is_agent {
+        let token = token.filter(|token| !token.is_empty()).ok_or_else(|| {
+            format!(
+                "sniffnet: agent mode requires a token ('--token' or {AGENT_TOKEN_VAR})\n\
+                For more information, try 'sniffnet --help'"
+            )
+        })?;
+        if cert.is_some() != key.is_some() {
+            return Err(
+                "sniffnet: options '--cert' and '--key' must be used together\n\
+                For more information, try 'sniffnet --help'"
+                    .to_string(),
+            );
+        }
+        cli_args.headless = true;
+        cli_args.agent = Some(AgentArgs {
+            listen,
+            token,
+            cert,
+            key,
+        });
+    }
+    if let Some(path) = chart_path {
+        if !cli_args.headless {
+            return Err("sniffnet: option '--export-chart' requires '--headless'\n\
+                For more information, try 'sniffnet --help'"
+                .to_string());
+        }
+        cli_args.export_chart = Some(ChartExportArgs {
+            path,
+            size: chart_size,
+        });
+    }
+    if cli_args.tui && cli_args.headless {
+        return Err(
+            "sniffnet: option '--tui' cannot be used with '--headless' or in agent mode\n\
+            For more information, try 'sniffnet --help'"
+                .to_string(),
+        );
+    }
+    if cli_args.adapter.is_some() && !cli_args.headless && !cli_args.tui {
+        return Err(
+            "sniffnet: option '--adapter' requires '--headless' or '--tui'\n\
+            For more information, try 'sniffnet --help'"
+                .to_string(),
+        );
+    }
+    Ok(cli_args)
 }
 
 fn print_help() {
     println!(
         "Application to comfortably monitor your Internet traffic\n\
         Usage: sniffnet [OPTIONS]\n\
+        \x20      sniffnet agent [OPTIONS]\n\
         Options:\n\
-        \t-h, --help      Print help\n\
-        \t-v, --version   Print version info\n\
+        \t-h, --help                Print help\n\
+        \t-v, --version             Print version info\n\
+        \t--metrics <ADDRESS>       Expose Prometheus metrics at http://<ADDRESS>/metrics\n\
+        \t                          (e.g. 127.0.0.1:9898)\n\
+        \t--api <ADDRESS>           Expose a JSON API to query and control the capture\n\
+        \t                          on a loopback address (e.g. 127.0.0.1:9899)\n\
+        \t--api-token <TOKEN>       Token required by the API (or SNIFFNET_API_TOKEN variable)\n\
+        \t--headless                Capture without graphical interface\n\
+        \t--tui                     Capture with a terminal user interface (e.g. over SSH)\n\
+        \t--adapter <NAME>          Network adapter to capture from in headless or terminal mode\n\
+        \t                          (defaults to the last used one)\n\
+        \t--export-chart <FILE>     Periodically export the traffic chart in headless mode\n\
+        \t                          (SVG or PNG image, depending on the extension)\n\
+        \t--chart-size <SIZE>       Size of the exported chart (default: 1920x1080)\n\
+        Agent options (capture without graphical interface and stream the traffic to a remote GUI):\n\
+        \t--listen <ADDRESS>        Address to listen for GUI connections (default: 0.0.0.0:9797)\n\
+        \t--token <TOKEN>           Token required to connect (or SNIFFNET_AGENT_TOKEN variable)\n\
+        \t--cert <FILE>             TLS certificate, PEM (default: self-signed, generated once)\n\
+        \t--key <FILE>              TLS private key, PEM\n\
         (Run without options to start the app)"
     );
 }
@@ -32,9 +258,209 @@ fn print_version() {
     println!("sniffnet {APP_VERSION}");
 }
 
-fn unknown_argument(arg: &str) {
-    eprintln!(
+fn unknown_argument(arg: &str) -> String {
+    format!(
         "sniffnet: unknown option '{arg}'\n\
         For more information, try 'sniffnet --help'"
-    );
+    )
+}
+
+fn missing_value(arg: &str) -> String {
+    format!(
+        "sniffnet: option '{arg}' requires a value\n\
+        For more information, try 'sniffnet --help'"
+    )
+}
+
+fn invalid_value(arg: &str, value: &str) -> String {
+    format!(
+        "sniffnet: invalid value '{value}' for option '{arg}'\n\
+        For more information, try 'sniffnet --help'"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        parse_args(args.iter().map(ToString::to_string), |_| None)
+    }
+
+    #[test]
+    fn test_no_args() {
+        assert_eq!(parse(&[]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn test_headless_with_metrics() {
+        assert_eq!(
+            parse(&[
+                "--headless",
+                "--metrics",
+                "127.0.0.1:9898",
+                "--adapter",
+                "eth0"
+            ]),
+            Ok(CliArgs {
+                metrics: Some("127.0.0.1:9898".parse().unwrap()),
+                headless: true,
+                tui: false,
+                adapter: Some("eth0".to_string()),
+                agent: None,
+                api: None,
+                export_chart: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tui() {
+        assert_eq!(
+            parse(&["--tui", "--adapter", "eth0"]),
+            Ok(CliArgs {
+                tui: true,
+                adapter: Some("eth0".to_string()),
+                ..CliArgs::default()
+            })
+        );
+        assert!(parse(&["--tui", "--headless"]).is_err());
+        assert!(parse(&["agent", "--token", "secret", "--tui"]).is_err());
+    }
+
+    #[test]
+    fn test_api() {
+        assert_eq!(
+            parse(&["--api", "127.0.0.1:9899", "--api-token", "secret"])
+                .unwrap()
+                .api,
+            Some(ApiArgs {
+                address: "127.0.0.1:9899".parse().unwrap(),
+                token: "secret".to_string(),
+            })
+        );
+        let cli_args = parse_args(
+            ["--api", "[::1]:9899"].iter().map(ToString::to_string),
+            |var| (var == API_TOKEN_VAR).then(|| "from_env".to_string()),
+        )
+        .unwrap();
+        assert_eq!(cli_args.api.unwrap().token, "from_env");
+
+        // a token is required
+        assert!(parse(&["--api", "127.0.0.1:9899"]).is_err());
+        // only loopback addresses are allowed
+        assert!(parse(&["--api", "0.0.0.0:9899", "--api-token", "secret"]).is_err());
+    }
+
+    #[test]
+    fn test_agent() {
+        assert_eq!(
+            parse(&["agent", "--token", "secret", "--adapter", "eth0"]),
+            Ok(CliArgs {
+                metrics: None,
+                headless: true,
+                tui: false,
+                adapter: Some("eth0".to_string()),
+                agent: Some(AgentArgs {
+                    listen: "0.0.0.0:9797".parse().unwrap(),
+                    token: "secret".to_string(),
+                    cert: None,
+                    key: None,
+                }),
+                api: None,
+                export_chart: None,
+            })
+        );
+        let cli_args = parse_args(
+            [
+                "agent",
+                "--listen",
+                "127.0.0.1:8000",
+                "--cert",
+                "c.pem",
+                "--key",
+                "k.pem",
+            ]
+            .iter()
+            .map(ToString::to_string),
+            |var| (var == AGENT_TOKEN_VAR).then(|| "from_env".to_string()),
+        )
+        .unwrap();
+        assert_eq!(
+            cli_args.agent,
+            Some(AgentArgs {
+                listen: "127.0.0.1:8000".parse().unwrap(),
+                token: "from_env".to_string(),
+                cert: Some(PathBuf::from("c.pem")),
+                key: Some(PathBuf::from("k.pem")),
+            })
+        );
+
+        // a token is required
+        assert!(parse(&["agent"]).is_err());
+        assert!(parse(&["agent", "--token", ""]).is_err());
+        assert!(parse(&["agent", "--token", "secret", "--cert", "c.pem"]).is_err());
+        // agent options are not available outside of agent mode
+        assert_eq!(
+            parse(&["--token", "secret"]),
+            Err(unknown_argument("--token"))
+        );
+        assert_eq!(
+            parse(&["--headless", "agent"]),
+            Err(unknown_argument("agent"))
+        );
+    }
+
+    #[test]
+    fn test_export_chart() {
+        assert_eq!(
+            parse(&["--headless", "--export-chart", "chart.svg"])
+                .unwrap()
+                .export_chart,
+            Some(ChartExportArgs {
+                path: PathBuf::from("chart.svg"),
+                size: (1920, 1080),
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "agent",
+                "--token",
+                "secret",
+                "--export-chart",
+                "chart.PNG",
+                "--chart-size",
+                "800x600"
+            ])
+            .unwrap()
+            .export_chart,
+            Some(ChartExportArgs {
+                path: PathBuf::from("chart.PNG"),
+                size: (800, 600),
+            })
+        );
+
+        // only available in headless mode
+        assert!(parse(&["--export-chart", "chart.svg"]).is_err());
+        assert_eq!(
+            parse(&["--headless", "--export-chart", "chart.jpg"]),
+            Err(invalid_value("--export-chart", "chart.jpg"))
+        );
+        assert_eq!(
+            parse(&["--headless", "--chart-size", "800"]),
+            Err(invalid_value("--chart-size", "800"))
+        );
+        assert!(parse(&["--headless", "--chart-size", "0x600"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        assert_eq!(parse(&["--foo"]), Err(unknown_argument("--foo")));
+        assert_eq!(parse(&["--metrics"]), Err(missing_value("--metrics")));
+        assert_eq!(
+            parse(&["--metrics", "localhost"]),
+            Err(invalid_value("--metrics", "localhost"))
+        );
+        assert!(parse(&["--adapter", "eth0"]).is_err());
+    }
 }
@@ -4,14 +4,113 @@
 use serde::{Deserialize, Serialize};
 
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::networking::types::host_pattern::HostPattern;
+use crate::networking::types::ignore_pattern::IgnorePattern;
+use crate::networking::types::label::Label;
 use crate::notifications::types::notifications::Notifications;
+use crate::report::types::report_column::ReportColumns;
 use crate::{Language, StyleType};
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct ConfigSettings {
     pub color_gradient: GradientType,
     pub language: Language,
+    // the following lists are declared before the tables and skipped when empty,
+    // since the TOML serializer doesn't support values (like an empty list) after tables
+    /// Favorite hosts and patterns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub favorites: Vec<HostPattern>,
+    /// Hosts and application protocols hidden from the views and notifications
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored: Vec<IgnorePattern>,
+    /// Aliases, tags and notes assigned to addresses and hosts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<Label>,
     pub notifications: Notifications,
+    #[serde(default)]
+    pub report_columns: ReportColumns,
     // StyleType should be last in order to deserialize as a table properly
     pub style: StyleType,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::gui::styles::types::tag_color::TagColor;
+    use crate::networking::types::address_filter::IpFilter;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::label::{LabelTarget, Tag};
+    use crate::AppProtocol;
+
+    use super::*;
+
+    #[test]
+    fn test_favorites_and_ignore_list_serialization() {
+        let mut config_settings = ConfigSettings::default();
+        for (favorites, ignored) in [
+            (vec![], vec![]),
+            (vec![HostPattern::Asn(15169)], vec![]),
+            (vec![], vec![IgnorePattern::AppProtocol(AppProtocol::mDNS)]),
+            (
+                vec![HostPattern::Domain("*.example.com".to_string())],
+                vec![
+                    IgnorePattern::AppProtocol(AppProtocol::SSDP),
+                    IgnorePattern::Host(HostPattern::Address(
+                        IpFilter::from_str("10.0.0.0/8").unwrap(),
+                    )),
+                ],
+            ),
+        ] {
+            config_settings.favorites = favorites;
+            config_settings.ignored = ignored;
+            let serialized = toml::to_string(&config_settings).unwrap();
+            let deserialized: ConfigSettings = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized.favorites, config_settings.favorites);
+            assert_eq!(deserialized.ignored, config_settings.ignored);
+        }
+    }
+
+    #[test]
+    fn test_labels_serialization() {
+        let host = Host {
+            domain: "example.com".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let mut config_settings = ConfigSettings {
+            favorites: vec![HostPattern::Host(host.clone())],
+            ..ConfigSettings::default()
+        };
+        for labels in [
+            vec![],
+            vec![Label::new(LabelTarget::Mac(
+                "aa:bb:cc:dd:ee:ff".to_string(),
+            ))],
+            vec![
+                Label {
+                    alias: "NAS".to_string(),
+                    note: "Backups every night".to_string(),
+                    tags: vec![Tag {
+                        name: "home".to_string(),
+                        color: TagColor::Green,
+                    }],
+                    target: LabelTarget::Address("192.168.1.5".to_string()),
+                },
+                Label {
+                    alias: "Example".to_string(),
+                    ..Label::new(LabelTarget::Host(host.clone()))
+                },
+            ],
+        ] {
+            config_settings.labels = labels;
+            let serialized = toml::to_string(&config_settings).unwrap();
+            let deserialized: ConfigSettings = toml::from_str(&serialized).unwrap();
+            assert_eq!(deserialized.labels, config_settings.labels);
+            assert_eq!(deserialized.favorites, config_settings.favorites);
+        }
+    }
+}
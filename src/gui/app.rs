@@ -13,9 +13,11 @@
 
 use crate::gui::components::footer::footer;
 use crate::gui::components::header::header;
+use crate::gui::components::label::get_label_overlay;
 use crate::gui::components::modal::{get_clear_all_overlay, get_exit_overlay, Modal};
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::pages::connection_details_page::connection_details_page;
+use crate::gui::pages::hosts_page::hosts_page;
 use crate::gui::pages::initial_page::initial_page;
 use crate::gui::pages::inspect_page::inspect_page;
 use crate::gui::pages::notifications_page::notifications_page;
@@ -89,6 +91,7 @@ fn view(&self) -> Element<Message, Renderer<StyleType>> {
             Status::Running => match self.running_page {
                 RunningPage::Overview => overview_page(self),
                 RunningPage::Inspect => inspect_page(self),
+                RunningPage::Hosts => hosts_page(self),
                 RunningPage::Notifications => notifications_page(self),
             },
         };
@@ -133,6 +136,7 @@ fn view(&self) -> Element<Message, Renderer<StyleType>> {
                     MyModal::ConnectionDetails(connection_index) => {
                         connection_details_page(self, connection_index)
                     }
+                    MyModal::EditLabel(_) => get_label_overlay(self),
                 };
 
                 Modal::new(content, overlay)
@@ -168,6 +172,8 @@ fn subscription(&self) -> Subscription<Message> {
                     KeyCode::Enter => Some(Message::ReturnKeyPressed),
                     KeyCode::Escape => Some(Message::EscKeyPressed),
                     KeyCode::Tab => Some(Message::SwitchPage(true)),
+                    KeyCode::Up => Some(Message::VerticalArrowPressed(false)),
+                    KeyCode::Down => Some(Message::VerticalArrowPressed(true)),
                     _ => None,
                 },
                 _ => None,
@@ -0,0 +1,281 @@
+//! Module defining the widgets to display and edit the labels assigned to addresses and hosts.
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::{
+    button, horizontal_space, vertical_space, Button, Column, Container, PickList, Row, Text,
+    TextInput,
+};
+use iced::{Alignment, Font, Length, Renderer};
+
+use crate::gui::components::modal::get_modal_header;
+use crate::gui::styles::button::ButtonType;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::style_constants::{get_font, get_font_headers, FONT_SIZE_FOOTER};
+use crate::gui::styles::text::TextType;
+use crate::gui::styles::types::tag_color::TagColor;
+use crate::gui::types::message::Message;
+use crate::networking::types::label::{get_label, with_alias, Label, LabelDraft, LabelTarget, Tag};
+use crate::translations::translations_3::{
+    add_tag_translation, alias_translation, cancel_translation, edit_label_translation,
+    notes_translation, remove_label_translation, save_translation, tags_translation,
+};
+use crate::{Language, Sniffer, StyleType};
+
+/// Returns a row with the given text preceded by the alias of the label,
+/// and followed by the tags of the label, each displayed with its color
+pub fn get_labeled_text(
+    label: Option<&Label>,
+    text: &str,
+    font: Font,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Row::new()
+        .spacing(3)
+        .align_items(Alignment::Center)
+        .push(Text::new(with_alias(label, text)).font(font));
+    for tag in label.map(|label| label.tags.as_slice()).unwrap_or_default() {
+        ret_val = ret_val.push(get_tag_chip(tag, font));
+    }
+    ret_val
+}
+
+/// Returns the tags and the notes of the given label, if any
+pub fn get_label_details(
+    label: Option<&Label>,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Column::new().spacing(2).padding([0, 0, 0, 20]);
+    if let Some(label) = label {
+        if !label.tags.is_empty() {
+            let mut tags_row = Row::new().spacing(3);
+            for tag in &label.tags {
+                tags_row = tags_row.push(get_tag_chip(tag, font));
+            }
+            ret_val = ret_val.push(tags_row);
+        }
+        if !label.note.trim().is_empty() {
+            ret_val = ret_val.push(
+                Text::new(label.note.clone())
+                    .font(font)
+                    .size(FONT_SIZE_FOOTER),
+            );
+        }
+    }
+    ret_val
+}
+
+/// Returns a button opening the label editor for the given target
+pub fn get_edit_label_button(
+    target: LabelTarget,
+    language: Language,
+    font: Font,
+) -> Button<'static, Message, Renderer<StyleType>> {
+    button(
+        Text::new(edit_label_translation(language))
+            .font(font)
+            .size(FONT_SIZE_FOOTER)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([2, 8])
+    .on_press(Message::EditLabel(target))
+}
+
+fn get_tag_chip(tag: &Tag, font: Font) -> Container<'static, Message, Renderer<StyleType>> {
+    Container::new(
+        Text::new(tag.name.clone())
+            .font(font)
+            .size(FONT_SIZE_FOOTER),
+    )
+    .padding([0, 6])
+    .style(ContainerType::Tag(tag.color))
+}
+
+/// Computes the label editor modal
+pub fn get_label_overlay(sniffer: &Sniffer) -> Container<'static, Message, Renderer<StyleType>> {
+    let font = get_font(sniffer.style);
+    let font_headers = get_font_headers(sniffer.style);
+    let language = sniffer.language;
+    let draft = sniffer.label_draft.clone().unwrap_or_else(|| {
+        // the editor is never displayed without a draft
+        LabelDraft::new(Label::new(LabelTarget::Address(String::new())))
+    });
+
+    let alias_input = TextInput::new(alias_translation(language), &draft.label.alias)
+        .on_input({
+            let draft = draft.clone();
+            move |alias| {
+                let mut draft = draft.clone();
+                draft.label.alias = alias;
+                Message::UpdateLabelDraft(draft)
+            }
+        })
+        .on_submit(Message::SaveLabel)
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fill);
+
+    let notes_input = TextInput::new(notes_translation(language), &draft.label.note)
+        .on_input({
+            let draft = draft.clone();
+            move |note| {
+                let mut draft = draft.clone();
+                draft.label.note = note;
+                Message::UpdateLabelDraft(draft)
+            }
+        })
+        .on_submit(Message::SaveLabel)
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fill);
+
+    let mut tags_row = Row::new().spacing(5).align_items(Alignment::Center);
+    for (index, tag) in draft.label.tags.iter().enumerate() {
+        let mut new_draft = draft.clone();
+        new_draft.label.tags.remove(index);
+        tags_row = tags_row.push(
+            Row::new()
+                .align_items(Alignment::Center)
+                .push(get_tag_chip(tag, font))
+                .push(
+                    button(
+                        Text::new("×")
+                            .font(font)
+                            .size(FONT_SIZE_FOOTER)
+                            .vertical_alignment(Vertical::Center)
+                            .horizontal_alignment(Horizontal::Center),
+                    )
+                    .padding(0)
+                    .height(Length::Fixed(18.0))
+                    .width(Length::Fixed(18.0))
+                    .style(ButtonType::Neutral)
+                    .on_press(Message::UpdateLabelDraft(new_draft)),
+                ),
+        );
+    }
+
+    let tag_input = TextInput::new(tags_translation(language), &draft.tag_name)
+        .on_input({
+            let draft = draft.clone();
+            move |tag_name| {
+                let mut draft = draft.clone();
+                draft.tag_name = tag_name;
+                Message::UpdateLabelDraft(draft)
+            }
+        })
+        .on_submit(Message::UpdateLabelDraft(draft.clone().with_new_tag()))
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fixed(200.0));
+
+    let color_list_str = TagColor::all_strings(language);
+    let picklist_color = PickList::new(
+        color_list_str.clone(),
+        Some(draft.tag_color.get_picklist_label(language)),
+        {
+            let draft = draft.clone();
+            move |selected_str| {
+                let index = color_list_str
+                    .iter()
+                    .position(|str| *str == selected_str)
+                    .unwrap_or_default();
+                let mut draft = draft.clone();
+                draft.tag_color = TagColor::ALL[index];
+                Message::UpdateLabelDraft(draft)
+            }
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
+    let mut add_tag_button = get_button(add_tag_translation(language), font);
+    if !draft.tag_name.trim().is_empty() {
+        add_tag_button =
+            add_tag_button.on_press(Message::UpdateLabelDraft(draft.clone().with_new_tag()));
+    }
+
+    let mut remove_button = get_button(remove_label_translation(language), font);
+    if get_label(&sniffer.labels, &draft.label.target).is_some() {
+        remove_button = remove_button
+            .style(ButtonType::Alert)
+            .on_press(Message::RemoveLabel(draft.label.target.clone()));
+    }
+
+    let buttons_row = Row::new()
+        .spacing(10)
+        .push(
+            get_button(save_translation(language), font)
+                .style(ButtonType::BorderedRound)
+                .on_press(Message::SaveLabel),
+        )
+        .push(remove_button)
+        .push(get_button(cancel_translation(language), font).on_press(Message::HideModal));
+
+    let body = Column::new()
+        .padding([10, 20])
+        .spacing(8)
+        .width(Length::Fill)
+        .push(
+            Text::new(draft.label.target.to_string())
+                .font(font)
+                .style(TextType::Title),
+        )
+        .push(
+            Text::new(format!("{}:", alias_translation(language)))
+                .font(font)
+                .style(TextType::Subtitle),
+        )
+        .push(alias_input)
+        .push(
+            Text::new(format!("{}:", notes_translation(language)))
+                .font(font)
+                .style(TextType::Subtitle),
+        )
+        .push(notes_input)
+        .push(
+            Text::new(format!("{}:", tags_translation(language)))
+                .font(font)
+                .style(TextType::Subtitle),
+        )
+        .push(tags_row)
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(tag_input)
+                .push(picklist_color)
+                .push(add_tag_button),
+        )
+        .push(vertical_space(Length::Fill))
+        .push(
+            Row::new()
+                .push(horizontal_space(Length::Fill))
+                .push(buttons_row)
+                .push(horizontal_space(Length::Fill)),
+        );
+
+    Container::new(
+        Column::new()
+            .width(Length::Fill)
+            .push(get_modal_header(
+                font,
+                font_headers,
+                sniffer.color_gradient,
+                language,
+                edit_label_translation(language).to_string(),
+            ))
+            .push(body),
+    )
+    .height(Length::Fixed(420.0))
+    .width(Length::Fixed(550.0))
+    .style(ContainerType::Modal)
+}
+
+fn get_button(label: &'static str, font: Font) -> Button<'static, Message, Renderer<StyleType>> {
+    button(
+        Text::new(label)
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15])
+}
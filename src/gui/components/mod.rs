@@ -1,6 +1,8 @@
 pub mod footer;
 pub mod header;
+pub mod label;
 pub mod modal;
 pub mod radio;
+pub mod resize_handle;
 pub mod tab;
 pub mod types;
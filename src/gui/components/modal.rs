@@ -88,7 +88,7 @@ pub fn get_clear_all_overlay(
         .style(ContainerType::Modal)
 }
 
-fn get_modal_header(
+pub fn get_modal_header(
     font: Font,
     font_headers: Font,
     color_gradient: GradientType,
@@ -5,6 +5,10 @@
 use crate::gui::styles::style_constants::FONT_SIZE_SUBTITLE;
 use crate::gui::styles::text::TextType;
 use crate::gui::types::message::Message;
+use crate::notifications::types::notification_hook::{HookKind, NotificationHook};
+use crate::notifications::types::notification_rule::{
+    NotificationRuleDraft, RuleConditionKind, RuleMatcherKind,
+};
 use crate::notifications::types::notifications::{
     BytesNotification, FavoriteNotification, Notification, PacketsNotification,
 };
@@ -195,6 +199,92 @@ pub fn sound_favorite_radios(
     ret_val
 }
 
+pub fn rule_matcher_radios(
+    rule_draft: &NotificationRuleDraft,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Row::new().spacing(15);
+    for option in RuleMatcherKind::ALL {
+        let rule_draft = rule_draft.clone();
+        ret_val = ret_val.push(
+            Radio::new(
+                option.get_radio_label(language),
+                option,
+                Some(rule_draft.matcher_kind),
+                |value| {
+                    Message::UpdateRuleDraft(NotificationRuleDraft {
+                        matcher_kind: value,
+                        ..rule_draft
+                    })
+                },
+            )
+            .spacing(7)
+            .font(font)
+            .size(15),
+        );
+    }
+    ret_val
+}
+
+pub fn rule_condition_radios(
+    rule_draft: &NotificationRuleDraft,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Row::new().spacing(15);
+    for option in RuleConditionKind::ALL {
+        let rule_draft = rule_draft.clone();
+        ret_val = ret_val.push(
+            Radio::new(
+                option.get_radio_label(language),
+                option,
+                Some(rule_draft.condition_kind),
+                |value| {
+                    Message::UpdateRuleDraft(NotificationRuleDraft {
+                        condition_kind: value,
+                        ..rule_draft
+                    })
+                },
+            )
+            .spacing(7)
+            .font(font)
+            .size(15),
+        );
+    }
+    ret_val
+}
+
+pub fn sound_rule_radios(
+    rule_draft: &NotificationRuleDraft,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Row::new()
+        .spacing(20)
+        .push(Text::new(format!("{}:", sound_translation(language))).font(font));
+    for option in Sound::ALL {
+        let rule_draft = rule_draft.clone();
+        ret_val = ret_val.push(
+            Radio::new(
+                option.get_radio_label(language),
+                option,
+                Some(rule_draft.sound),
+                |value| {
+                    Message::UpdateRuleDraft(NotificationRuleDraft {
+                        sound: value,
+                        ..rule_draft
+                    })
+                },
+            )
+            .spacing(7)
+            .font(font)
+            .size(15),
+        );
+    }
+    ret_val
+}
+
 pub fn chart_radios(
     active: ChartType,
     font: Font,
@@ -219,3 +309,34 @@ pub fn chart_radios(
     }
     ret_val
 }
+
+pub fn hook_kind_radios(
+    hook: &NotificationHook,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Row::new().spacing(15);
+    for option in HookKind::ALL {
+        let hook = hook.clone();
+        ret_val = ret_val.push(
+            Radio::new(
+                option.get_radio_label(language),
+                option,
+                Some(hook.kind),
+                |value| {
+                    Message::UpdateNotificationSettings(
+                        Notification::Hook(NotificationHook {
+                            kind: value,
+                            ..hook
+                        }),
+                        false,
+                    )
+                },
+            )
+            .spacing(7)
+            .font(font)
+            .size(15),
+        );
+    }
+    ret_val
+}
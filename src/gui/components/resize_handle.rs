@@ -0,0 +1,180 @@
+//! Widget that can be dragged horizontally to resize the element next to it
+//! (e.g., the columns of the connections table).
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::widget::{self, tree, Tree, Widget};
+use iced::advanced::{self, overlay, renderer, Clipboard, Shell};
+use iced::{event, mouse, Element, Event, Length, Rectangle};
+
+pub struct ResizeHandle<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_drag: Box<dyn Fn(f32) -> Message + 'a>,
+    on_release: Message,
+}
+
+impl<'a, Message, Renderer> ResizeHandle<'a, Message, Renderer> {
+    /// Creates a handle displaying the given content; while dragging it, `on_drag` is called
+    /// with the horizontal movement of the cursor, and `on_release` is produced when the drag ends
+    pub fn new(
+        content: impl Into<Element<'a, Message, Renderer>>,
+        on_drag: impl Fn(f32) -> Message + 'a,
+        on_release: Message,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            on_drag: Box::new(on_drag),
+            on_release,
+        }
+    }
+}
+
+/// Horizontal position of the cursor when it was last moved while dragging the handle
+#[derive(Default)]
+struct State {
+    drag_position: Option<f32>,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for ResizeHandle<'a, Message, Renderer>
+where
+    Renderer: advanced::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(position) = cursor.position_over(layout.bounds()) {
+                    state.drag_position = Some(position.x);
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position })
+                if state.drag_position.is_some() =>
+            {
+                let drag_position = state
+                    .drag_position
+                    .replace(position.x)
+                    .unwrap_or(position.x);
+                shell.publish((self.on_drag)(position.x - drag_position));
+                return event::Status::Captured;
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+                if state.drag_position.take().is_some() =>
+            {
+                shell.publish(self.on_release.clone());
+                return event::Status::Captured;
+            }
+            _ => {}
+        }
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+        if state.drag_position.is_some() || cursor.is_over(layout.bounds()) {
+            mouse::Interaction::ResizingHorizontally
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &<Renderer as advanced::Renderer>::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+}
+
+impl<'a, Message, Renderer> From<ResizeHandle<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + advanced::Renderer,
+{
+    fn from(handle: ResizeHandle<'a, Message, Renderer>) -> Self {
+        Element::new(handle)
+    }
+}
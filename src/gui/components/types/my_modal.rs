@@ -7,4 +7,6 @@ pub enum MyModal {
     ClearAll,
     /// Connection details modal.
     ConnectionDetails(usize),
+    /// Label editor modal, with the index of the connection details modal to return to (if any).
+    EditLabel(Option<usize>),
 }
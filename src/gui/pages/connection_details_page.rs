@@ -1,5 +1,6 @@
 use std::net::IpAddr;
 
+use chrono::Local;
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::tooltip::Position;
 use iced::widget::{button, horizontal_space, lazy, vertical_space, Rule};
@@ -7,8 +8,10 @@
 use iced::Length::Fixed;
 use iced::{Alignment, Font, Length, Renderer};
 
+use crate::chart::types::sparkline_chart::SparklineChart;
 use crate::countries::country_utils::{get_computer_tooltip, get_flag_tooltip};
 use crate::countries::flags_pictures::FLAGS_WIDTH_BIG;
+use crate::gui::components::label::{get_edit_label_button, get_label_details};
 use crate::gui::styles::container::ContainerType;
 use crate::gui::styles::style_constants::{get_font, get_font_headers, FONT_SIZE_TITLE};
 use crate::gui::styles::text::TextType;
@@ -18,6 +21,7 @@
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::label::{get_label, with_alias, Label, LabelTarget};
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::translations::translations::{
     application_protocol_translation, hide_translation, incoming_translation, outgoing_translation,
@@ -37,7 +41,11 @@ pub fn connection_details_page(
     connection_index: usize,
 ) -> Container<Message, Renderer<StyleType>> {
     Container::new(lazy(
-        sniffer.runtime_data.tot_sent_packets + sniffer.runtime_data.tot_received_packets,
+        (
+            sniffer.runtime_data.tot_sent_packets + sniffer.runtime_data.tot_received_packets,
+            sniffer.traffic_chart.ticks,
+            sniffer.labels.clone(),
+        ),
         move |_| page_content(sniffer, connection_index),
     ))
 }
@@ -89,7 +97,7 @@ fn page_content(
     );
     let mut host_info_col = Column::new();
     if let Some((r_dns, host)) = host_option {
-        host_info_col = get_host_info_col(&r_dns, &host, font, sniffer.language);
+        host_info_col = get_host_info_col(&r_dns, &host, &sniffer.labels, font, sniffer.language);
         let host_info = host_info_option.unwrap_or_default();
         let flag = get_flag_tooltip(
             host.country,
@@ -114,6 +122,7 @@ fn page_content(
         &key.address1,
         key.port1,
         &val.mac_address1,
+        &sniffer.labels,
         font,
         sniffer.language,
     );
@@ -122,6 +131,7 @@ fn page_content(
         &key.address2,
         key.port2,
         &val.mac_address2,
+        &sniffer.labels,
         font,
         sniffer.language,
     );
@@ -132,13 +142,13 @@ fn page_content(
         dest_col = dest_col.push(host_info_col);
     }
 
-    let col_info = col_info(&key, &val, font, sniffer.language);
+    let col_info = col_info(&key, &val, font, sniffer.language, sniffer.style);
 
     let content = assemble_widgets(col_info, source_col, dest_col);
 
     Container::new(header_and_content.push(content))
         .width(Length::Fixed(1000.0))
-        .height(Length::Fixed(500.0))
+        .height(Length::Fixed(550.0))
         .style(ContainerType::Modal)
 }
 
@@ -195,6 +205,7 @@ fn col_info(
     val: &InfoAddressPortPair,
     font: Font,
     language: Language,
+    style: StyleType,
 ) -> Column<'static, Message, Renderer<StyleType>> {
     Column::new()
         .spacing(10)
@@ -239,12 +250,17 @@ fn col_info(
             ),
             font,
         ))
+        .push(
+            SparklineChart::new(&val.traffic_series, Local::now().timestamp(), true, style)
+                .view(250.0, 80.0),
+        )
         .push(vertical_space(Length::FillPortion(1)))
 }
 
 fn get_host_info_col(
     r_dns: &str,
     host: &Host,
+    labels: &[Label],
     font: Font,
     language: Language,
 ) -> Column<'static, Message, Renderer<StyleType>> {
@@ -253,11 +269,19 @@ fn get_host_info_col(
         host_info_col = host_info_col.push(Rule::horizontal(10.0));
     }
     if r_dns.parse::<IpAddr>().is_err() {
-        host_info_col = host_info_col.push(TextType::highlighted_subtitle_with_desc(
-            fqdn_translation(language),
-            r_dns,
-            font,
-        ));
+        let host_label = get_label(labels, &LabelTarget::Host(host.clone()));
+        host_info_col = host_info_col
+            .push(with_edit_label_button(
+                TextType::highlighted_subtitle_with_desc(
+                    fqdn_translation(language),
+                    &with_alias(host_label, r_dns),
+                    font,
+                ),
+                LabelTarget::Host(host.clone()),
+                language,
+                font,
+            ))
+            .push(get_label_details(host_label, font));
     }
     if !host.asn.name.is_empty() && host.asn.number > 0 {
         host_info_col = host_info_col.push(TextType::highlighted_subtitle_with_desc(
@@ -303,10 +327,12 @@ fn get_src_or_dest_col(
     ip: &String,
     port: u16,
     mac: &str,
+    labels: &[Label],
     font: Font,
     language: Language,
 ) -> Column<'static, Message, Renderer<StyleType>> {
-    Column::new()
+    let address_label = get_label(labels, &LabelTarget::Address(ip.clone()));
+    let mut ret_val = Column::new()
         .spacing(4)
         .push(
             Container::new(caption)
@@ -314,16 +340,53 @@ fn get_src_or_dest_col(
                 .align_x(Horizontal::Center),
         )
         .push(Rule::horizontal(10.0))
-        .push(TextType::highlighted_subtitle_with_desc(
-            socket_address_translation(language),
-            &get_socket_address(ip, port),
+        .push(with_edit_label_button(
+            TextType::highlighted_subtitle_with_desc(
+                socket_address_translation(language),
+                &with_alias(address_label, &get_socket_address(ip, port)),
+                font,
+            ),
+            LabelTarget::Address(ip.clone()),
+            language,
             font,
         ))
-        .push(TextType::highlighted_subtitle_with_desc(
+        .push(get_label_details(address_label, font));
+    // MAC addresses are not available on all the network adapters
+    if let Some(target @ LabelTarget::Mac(_)) = LabelTarget::from_str(mac) {
+        let mac_label = get_label(labels, &target);
+        ret_val = ret_val
+            .push(with_edit_label_button(
+                TextType::highlighted_subtitle_with_desc(
+                    mac_address_translation(language),
+                    &with_alias(mac_label, mac),
+                    font,
+                ),
+                target,
+                language,
+                font,
+            ))
+            .push(get_label_details(mac_label, font));
+    } else {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
             mac_address_translation(language),
             mac,
             font,
-        ))
+        ));
+    }
+    ret_val
+}
+
+fn with_edit_label_button(
+    content: Column<'static, Message, Renderer<StyleType>>,
+    target: LabelTarget,
+    language: Language,
+    font: Font,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    Row::new()
+        .align_items(Alignment::Center)
+        .push(content)
+        .push(horizontal_space(Length::Fill))
+        .push(get_edit_label_button(target, language, font))
 }
 
 fn assemble_widgets(
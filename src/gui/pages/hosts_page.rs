@@ -0,0 +1,348 @@
+//! Module defining the hosts page of the application.
+//!
+//! It lists all the hosts exchanging traffic, with search, sorting and pagination;
+//! clicking a host shows its connections in the inspect page.
+
+use iced::widget::scrollable::Direction;
+use iced::widget::{
+    button, horizontal_space, lazy, vertical_space, Column, Container, PickList, Row, Rule,
+    Scrollable, Text, TextInput,
+};
+use iced::{Alignment, Font, Length, Renderer};
+
+use crate::countries::country_utils::get_flag_tooltip;
+use crate::countries::flags_pictures::FLAGS_WIDTH_BIG;
+use crate::gui::components::label::get_labeled_text;
+use crate::gui::components::tab::get_pages_tabs;
+use crate::gui::pages::inspect_page::get_change_page_row;
+use crate::gui::pages::overview_page::{get_bars_length, get_host_buttons};
+use crate::gui::styles::button::ButtonType;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::rule::RuleType;
+use crate::gui::styles::scrollbar::ScrollbarType;
+use crate::gui::styles::style_constants::{get_font, get_font_headers, FONT_SIZE_TITLE};
+use crate::gui::styles::text::TextType;
+use crate::gui::styles::text_input::TextInputType;
+use crate::gui::types::message::Message;
+use crate::networking::types::label::get_host_label_entry;
+use crate::networking::types::search_parameters::SearchParameters;
+use crate::report::get_report_entries::get_searched_hosts;
+use crate::report::types::host_grouping::HostGrouping;
+use crate::report::types::host_sort_type::HostSortType;
+use crate::translations::translations::{
+    incoming_translation, outgoing_translation, packets_translation,
+};
+use crate::translations::translations_2::{
+    host_translation, no_search_results_translation, sort_by_translation,
+};
+use crate::translations::translations_3::{
+    group_by_translation, hosts_translation, search_hosts_translation,
+};
+use crate::utils::formatted_strings::{get_formatted_bytes_string_with_b, get_host_label};
+use crate::utils::types::icon::Icon;
+use crate::{ChartType, Language, RunningPage, Sniffer, StyleType};
+
+/// Width of the column with the in/out traffic bars of each host
+const HOST_BAR_WIDTH: f32 = 477.5;
+
+/// Width of the columns with the incoming and outgoing traffic of each host
+const TRAFFIC_COLUMN_WIDTH: f32 = 150.0;
+
+/// Computes the body of gui hosts page
+pub fn hosts_page(sniffer: &Sniffer) -> Container<'_, Message, Renderer<StyleType>> {
+    let font = get_font(sniffer.style);
+    let font_headers = get_font_headers(sniffer.style);
+
+    let tabs = get_pages_tabs(
+        RunningPage::Hosts,
+        font,
+        font_headers,
+        sniffer.language,
+        sniffer.unread_notifications,
+    );
+
+    let hosts = lazy(
+        (
+            sniffer.runtime_data.tot_sent_packets + sniffer.runtime_data.tot_received_packets,
+            sniffer.style,
+            sniffer.language,
+            sniffer.hosts_search.clone(),
+            sniffer.hosts_sort_type,
+            sniffer.host_grouping,
+            sniffer.hosts_page_number,
+            sniffer.ignored.len(),
+            sniffer.labels.clone(),
+        ),
+        move |_| lazy_hosts(sniffer),
+    );
+
+    let body = Column::new()
+        .width(Length::Fill)
+        .padding(10)
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(search_row(sniffer, font))
+        .push(hosts);
+
+    Container::new(Column::new().push(tabs).push(body)).height(Length::Fill)
+}
+
+fn search_row(sniffer: &Sniffer, font: Font) -> Container<'static, Message, Renderer<StyleType>> {
+    let language = sniffer.language;
+
+    let search_input = TextInput::new(search_hosts_translation(language), &sniffer.hosts_search)
+        .on_input(Message::SearchHosts)
+        .padding([0, 5])
+        .font(font)
+        .width(Length::Fixed(250.0))
+        .style(if sniffer.hosts_search.is_empty() {
+            TextInputType::Standard
+        } else {
+            TextInputType::Badge
+        });
+
+    let sort_active_str = sniffer.hosts_sort_type.get_picklist_label(language);
+    let sort_list_str: Vec<&str> = HostSortType::all_strings(language);
+    let picklist_sort = PickList::new(
+        sort_list_str.clone(),
+        Some(sort_active_str),
+        move |selected_str| {
+            let index = sort_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::HostsSortSelection(HostSortType::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
+    let grouping_list_str: Vec<&str> = HostGrouping::all_strings(language);
+    let picklist_grouping = PickList::new(
+        grouping_list_str.clone(),
+        Some(sniffer.host_grouping.get_picklist_label(language)),
+        move |selected_str| {
+            let index = grouping_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::HostGroupingSelection(HostGrouping::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
+    Container::new(
+        Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(
+                Text::new(hosts_translation(language))
+                    .font(font)
+                    .style(TextType::Title)
+                    .size(FONT_SIZE_TITLE),
+            )
+            .push(horizontal_space(Length::Fixed(10.0)))
+            .push(search_input)
+            .push(horizontal_space(Length::Fixed(20.0)))
+            .push(Text::new(format!("{}:", sort_by_translation(language))).font(font))
+            .push(picklist_sort)
+            .push(horizontal_space(Length::Fixed(20.0)))
+            .push(Text::new(format!("{}:", group_by_translation(language))).font(font))
+            .push(picklist_grouping),
+    )
+    .width(Length::Fixed(1042.0))
+    .padding(10)
+    .style(ContainerType::BorderedRound)
+}
+
+fn lazy_hosts(sniffer: &Sniffer) -> Container<'static, Message, Renderer<StyleType>> {
+    let font = get_font(sniffer.style);
+    let language = sniffer.language;
+    let chart_type = if sniffer.hosts_sort_type.eq(&HostSortType::MostPackets) {
+        ChartType::Packets
+    } else {
+        ChartType::Bytes
+    };
+
+    let (entries, results_number) = get_searched_hosts(
+        &sniffer.info_traffic.lock().unwrap(),
+        &sniffer.hosts_search,
+        sniffer.hosts_sort_type,
+        sniffer.host_grouping,
+        sniffer.hosts_page_number,
+    );
+
+    let mut col_hosts = Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_items(Alignment::Center);
+
+    if results_number == 0 {
+        col_hosts = col_hosts
+            .padding(20)
+            .push(vertical_space(Length::FillPortion(1)))
+            .push(Icon::Funnel.to_text().size(60))
+            .push(vertical_space(Length::Fixed(15.0)))
+            .push(Text::new(no_search_results_translation(language)).font(font))
+            .push(vertical_space(Length::FillPortion(2)));
+    } else {
+        // the bars are proportional to the host with most traffic in this page
+        let max_data_info = entries
+            .iter()
+            .map(|(_, data_info_host)| data_info_host.data_info)
+            .max_by_key(|data_info| match chart_type {
+                ChartType::Bytes => data_info.tot_bytes(),
+                ChartType::Packets => data_info.tot_packets(),
+            })
+            .unwrap_or_default();
+
+        let mut scroll_hosts = Column::new().spacing(2);
+        for (host, data_info_host) in &entries {
+            let data_info = &data_info_host.data_info;
+            let (incoming_bar_len, outgoing_bar_len) =
+                get_bars_length(HOST_BAR_WIDTH, chart_type, &max_data_info, data_info);
+
+            let host_bar = Column::new()
+                .width(Length::Fixed(HOST_BAR_WIDTH))
+                .spacing(1)
+                .push(get_labeled_text(
+                    get_host_label_entry(&sniffer.labels, host),
+                    &get_host_label(host),
+                    font,
+                ))
+                .push(
+                    Row::new()
+                        .push(traffic_bar(incoming_bar_len, RuleType::Incoming))
+                        .push(traffic_bar(outgoing_bar_len, RuleType::Outgoing)),
+                );
+
+            let content = Row::new()
+                .align_items(Alignment::Center)
+                .spacing(5)
+                .push(get_host_buttons(
+                    sniffer.host_grouping,
+                    data_info_host.is_favorite,
+                    host,
+                ))
+                .push(get_flag_tooltip(
+                    host.country,
+                    FLAGS_WIDTH_BIG,
+                    data_info_host.is_local,
+                    data_info_host.traffic_type,
+                    language,
+                    font,
+                ))
+                .push(host_bar)
+                .push(horizontal_space(Length::Fixed(10.0)))
+                .push(traffic_cell(
+                    data_info.incoming_bytes,
+                    data_info.incoming_packets,
+                    TextType::Incoming,
+                    font,
+                    language,
+                ))
+                .push(traffic_cell(
+                    data_info.outgoing_bytes,
+                    data_info.outgoing_packets,
+                    TextType::Outgoing,
+                    font,
+                    language,
+                ));
+
+            scroll_hosts = scroll_hosts.push(
+                button(content)
+                    .padding([5, 15, 5, 10])
+                    .on_press(Message::Search(SearchParameters {
+                        host: Some((sniffer.host_grouping, host.clone())),
+                        ..SearchParameters::default()
+                    }))
+                    .style(ButtonType::Neutral),
+            );
+        }
+
+        let start_entry_num = (sniffer.hosts_page_number - 1) * 20 + 1;
+        let end_entry_num = start_entry_num + entries.len() - 1;
+        col_hosts = col_hosts
+            .push(header_row(font, language))
+            .push(Rule::horizontal(5))
+            .push(
+                Scrollable::new(scroll_hosts)
+                    .height(Length::FillPortion(17))
+                    .width(Length::Fill)
+                    .direction(Direction::Vertical(ScrollbarType::properties())),
+            )
+            .push(Rule::horizontal(5))
+            .push(get_change_page_row(
+                font,
+                language,
+                sniffer.hosts_page_number,
+                start_entry_num,
+                end_entry_num,
+                results_number,
+                Message::UpdateHostsPageNumber,
+            ));
+    }
+
+    Container::new(col_hosts)
+        .padding([10, 7, 7, 7])
+        .width(Length::Fixed(1042.0))
+        .style(ContainerType::BorderedRound)
+}
+
+fn header_row(font: Font, language: Language) -> Row<'static, Message, Renderer<StyleType>> {
+    Row::new()
+        .padding([0, 15, 0, 10])
+        .spacing(5)
+        .push(horizontal_space(Length::Fixed(
+            3.0 * FLAGS_WIDTH_BIG + 10.0,
+        )))
+        .push(
+            Text::new(host_translation(language))
+                .font(font)
+                .width(Length::Fixed(HOST_BAR_WIDTH + 10.0)),
+        )
+        .push(
+            Text::new(incoming_translation(language))
+                .font(font)
+                .style(TextType::Incoming)
+                .width(Length::Fixed(TRAFFIC_COLUMN_WIDTH)),
+        )
+        .push(
+            Text::new(outgoing_translation(language))
+                .font(font)
+                .style(TextType::Outgoing)
+                .width(Length::Fixed(TRAFFIC_COLUMN_WIDTH)),
+        )
+}
+
+fn traffic_bar(length: f32, style: RuleType) -> Row<'static, Message, Renderer<StyleType>> {
+    if length > 0.0 {
+        Row::new()
+            .width(Length::Fixed(length))
+            .push(Rule::horizontal(1).style(style))
+    } else {
+        Row::new()
+    }
+}
+
+fn traffic_cell(
+    bytes: u128,
+    packets: u128,
+    text_type: TextType,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    Column::new()
+        .width(Length::Fixed(TRAFFIC_COLUMN_WIDTH))
+        .push(
+            Text::new(get_formatted_bytes_string_with_b(bytes))
+                .font(font)
+                .style(text_type),
+        )
+        .push(
+            Text::new(format!("{packets} {}", packets_translation(language)))
+                .font(font)
+                .style(text_type),
+        )
+}
@@ -6,12 +6,13 @@
 use iced::widget::tooltip::Position;
 use iced::widget::{
     button, horizontal_space, vertical_space, Button, Column, Container, PickList, Row, Scrollable,
-    Text, Tooltip,
+    Text, TextInput, Tooltip,
 };
 use iced::Length::FillPortion;
 use iced::{alignment, Alignment, Font, Length, Renderer};
 use pcap::Device;
 
+use crate::agent::types::agent_params::AgentParams;
 use crate::gui::components::radio::{ip_version_radios, transport_protocol_radios};
 use crate::gui::styles::button::ButtonType;
 use crate::gui::styles::container::ContainerType;
@@ -25,6 +26,10 @@
     address_translation, addresses_translation, all_translation, application_protocol_translation,
     choose_adapters_translation, select_filters_translation, start_translation,
 };
+use crate::translations::translations_3::{
+    certificate_fingerprint_translation, connect_translation, remote_agent_translation,
+    token_translation,
+};
 use crate::utils::types::icon::Icon;
 use crate::{AppProtocol, Language, StyleType};
 
@@ -80,7 +85,7 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>
         )
         .push(picklist_app);
 
-    let filters = Column::new()
+    let mut filters = Column::new()
         .width(FillPortion(6))
         .padding(10)
         .spacing(15)
@@ -100,6 +105,9 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>
                 .push(col_transport)
                 .push(col_app),
         );
+    if cfg!(not(target_arch = "powerpc64")) {
+        filters = filters.push(col_agent(&sniffer.agent_params, font, sniffer.language));
+    }
 
     let body = Column::new().push(vertical_space(Length::Fixed(5.0))).push(
         Row::new()
@@ -137,6 +145,79 @@ fn button_start(
         .style(ContainerType::Tooltip)
 }
 
+fn col_agent(
+    agent_params: &AgentParams,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let params = agent_params.clone();
+    let address_input = TextInput::new("192.168.1.10:9797", &agent_params.address)
+        .on_input(move |address| {
+            Message::UpdateAgentParams(AgentParams {
+                address,
+                ..params.clone()
+            })
+        })
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::FillPortion(2));
+    let params = agent_params.clone();
+    let token_input = TextInput::new(token_translation(language), &agent_params.token)
+        .on_input(move |token| {
+            Message::UpdateAgentParams(AgentParams {
+                token,
+                ..params.clone()
+            })
+        })
+        .password()
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::FillPortion(1));
+    let params = agent_params.clone();
+    let fingerprint_input = TextInput::new(
+        certificate_fingerprint_translation(language),
+        &agent_params.fingerprint,
+    )
+    .on_input(move |fingerprint| {
+        Message::UpdateAgentParams(AgentParams {
+            fingerprint,
+            ..params.clone()
+        })
+    })
+    .padding([0, 0, 0, 10])
+    .font(font);
+
+    let mut connect_button = button(
+        Text::new(connect_translation(language))
+            .font(font)
+            .vertical_alignment(alignment::Vertical::Center),
+    )
+    .padding([2, 15]);
+    if !agent_params.address.trim().is_empty()
+        && !agent_params.token.is_empty()
+        && !agent_params.fingerprint.trim().is_empty()
+    {
+        connect_button = connect_button.on_press(Message::ConnectToAgent);
+    }
+
+    Column::new()
+        .spacing(10)
+        .push(
+            Text::new(remote_agent_translation(language))
+                .font(font)
+                .style(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(Row::new().spacing(10).push(address_input).push(token_input))
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(fingerprint_input)
+                .push(connect_button),
+        )
+}
+
 fn get_col_adapter(sniffer: &Sniffer, font: Font) -> Column<Message, Renderer<StyleType>> {
     let mut dev_str_list = vec![];
     for dev in Device::list().expect("Error retrieving device list\r\n") {
@@ -3,31 +3,50 @@
 use iced::widget::tooltip::Position;
 use iced::widget::{button, horizontal_space, vertical_space, Rule};
 use iced::widget::{
-    lazy, Button, Checkbox, Column, Container, PickList, Row, Scrollable, Text, TextInput, Tooltip,
+    lazy, Button, Checkbox, Column, Container, Row, Scrollable, Text, TextInput, Tooltip,
 };
 use iced::{alignment, Alignment, Font, Length, Renderer};
 
+use crate::gui::components::resize_handle::ResizeHandle;
 use crate::gui::components::tab::get_pages_tabs;
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::styles::button::ButtonType;
 use crate::gui::styles::container::ContainerType;
 use crate::gui::styles::scrollbar::ScrollbarType;
-use crate::gui::styles::style_constants::{get_font, get_font_headers, FONT_SIZE_TITLE};
+use crate::gui::styles::style_constants::{
+    get_font, get_font_headers, FONT_SIZE_BODY, FONT_SIZE_TITLE,
+};
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::text_input::TextInputType;
 use crate::gui::types::message::Message;
-use crate::networking::types::search_parameters::{FilterInputType, SearchParameters};
+use crate::networking::types::search_parameters::{
+    Autocomplete, FilterInputType, SearchParameters,
+};
+use crate::networking::types::search_query::SearchQuery;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::report::get_report_entries::get_searched_entries;
-use crate::translations::translations::application_protocol_translation;
+use crate::report::types::report_column::{
+    ReportColumn, ReportColumnSettings, ReportColumns, ReportSort,
+};
+use crate::translations::translations::{address_translation, application_protocol_translation};
 use crate::translations::translations_2::{
-    administrative_entity_translation, country_translation, domain_name_translation,
-    no_search_results_translation, only_show_favorites_translation, search_filters_translation,
-    showing_results_translation, sort_by_translation,
+    administrative_entity_translation, country_translation, destination_translation,
+    domain_name_translation, host_translation, no_search_results_translation,
+    only_show_favorites_translation, search_filters_translation, showing_results_translation,
+    source_translation,
 };
-use crate::utils::formatted_strings::get_open_report_tooltip;
+use crate::translations::translations_3::{
+    columns_translation, port_translation, search_query_translation,
+};
+use crate::utils::formatted_strings::{get_host_label, get_open_report_tooltip};
 use crate::utils::types::icon::Icon;
-use crate::{Language, ReportSortType, RunningPage, Sniffer, StyleType};
+use crate::{Language, RunningPage, Sniffer, StyleType};
+
+/// Width of the separators between the header cells, which can be dragged to resize the columns
+const RESIZE_HANDLE_WIDTH: f32 = 9.0;
+
+/// Width of a character of the (monospaced) font used in the connections table
+const CHAR_WIDTH: f32 = FONT_SIZE_BODY / 2.0;
 
 /// Computes the body of gui inspect page
 pub fn inspect_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>> {
@@ -52,34 +71,23 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>
 
     tab_and_body = tab_and_body.push(tabs);
 
-    let sort_active_str = sniffer
-        .report_sort_type
-        .get_picklist_label(sniffer.language);
-    let sort_list_str: Vec<&str> = ReportSortType::all_strings(sniffer.language);
-    let picklist_sort = PickList::new(
-        sort_list_str.clone(),
-        Some(sort_active_str),
-        move |selected_str| {
-            if selected_str == *sort_list_str.first().unwrap_or(&"") {
-                Message::ReportSortSelection(ReportSortType::MostRecent)
-            } else if selected_str == *sort_list_str.get(1).unwrap_or(&"") {
-                Message::ReportSortSelection(ReportSortType::MostBytes)
-            } else {
-                Message::ReportSortSelection(ReportSortType::MostPackets)
-            }
-        },
-    )
-    .padding([3, 7])
-    .font(font);
+    let suggestions = sniffer.autocomplete.map(|autocomplete| {
+        (
+            autocomplete,
+            sniffer.filter_suggestions(autocomplete.filter),
+        )
+    });
 
     let report = lazy(
         (
             sniffer.runtime_data.tot_sent_packets + sniffer.runtime_data.tot_received_packets,
             sniffer.style,
             sniffer.language,
-            sniffer.report_sort_type,
+            sniffer.report_sort,
+            sniffer.report_columns.clone(),
             sniffer.search.clone(),
             sniffer.page_number,
+            sniffer.labels.clone(),
         ),
         move |_| lazy_report(sniffer),
     );
@@ -88,21 +96,15 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>
         .push(
             Container::new(
                 Row::new()
-                    .push(filters_col(&sniffer.search, font, sniffer.language))
+                    .push(filters_col(
+                        &sniffer.search,
+                        &suggestions,
+                        font,
+                        sniffer.language,
+                    ))
                     .push(Rule::vertical(25))
-                    .push(
-                        Column::new()
-                            .spacing(10)
-                            .push(
-                                Text::new(sort_by_translation(sniffer.language))
-                                    .font(font)
-                                    .style(TextType::Title)
-                                    .size(FONT_SIZE_TITLE),
-                            )
-                            .push(picklist_sort),
-                    ),
+                    .push(columns_col(&sniffer.report_columns, font, sniffer.language)),
             )
-            .height(Length::Fixed(165.0))
             .padding(10)
             .style(ContainerType::BorderedRound),
         )
@@ -121,6 +123,13 @@ fn lazy_report(sniffer: &Sniffer) -> Row<'static, Message, Renderer<StyleType>>
         .width(Length::Fill)
         .align_items(Alignment::Center);
 
+    let columns = sniffer.report_columns.visible();
+    let table_width = columns
+        .iter()
+        .map(|settings| f32::from(settings.width) + RESIZE_HANDLE_WIDTH)
+        .sum::<f32>()
+        + 4.0;
+
     let mut scroll_report = Column::new();
     let start_entry_num = (sniffer.page_number - 1) * 20 + 1;
     let end_entry_num = start_entry_num + search_results.len() - 1;
@@ -130,19 +139,32 @@ fn lazy_report(sniffer: &Sniffer) -> Row<'static, Message, Renderer<StyleType>>
         } else {
             TextType::Incoming
         };
-        let entry_row = Row::new()
-            .align_items(Alignment::Center)
-            .push(
-                Text::new(format!(
-                    "  {}{}  ",
-                    report_entry.key.print_gui(),
-                    report_entry.val.print_gui()
-                ))
-                .style(entry_text_type)
-                .font(font),
-            )
-            .push(report_entry.tooltip)
-            .push(Text::new("  "));
+        let mut flag = Some(report_entry.tooltip);
+        let mut entry_row = Row::new().align_items(Alignment::Center);
+        for settings in &columns {
+            let text = Text::new(fit_to_width(
+                settings.column.get_cell_text(
+                    &report_entry.key,
+                    &report_entry.val,
+                    &report_entry.host,
+                    &sniffer.labels,
+                    sniffer.language,
+                ),
+                settings.width,
+            ))
+            .style(entry_text_type)
+            .font(font);
+            let mut cell = Row::new().spacing(5).align_items(Alignment::Center);
+            if settings.column.eq(&ReportColumn::Country) {
+                if let Some(flag) = flag.take() {
+                    cell = cell.push(flag);
+                }
+            }
+            cell = cell.push(text);
+            entry_row = entry_row.push(Container::new(cell).width(Length::Fixed(
+                f32::from(settings.width) + RESIZE_HANDLE_WIDTH,
+            )));
+        }
 
         scroll_report = scroll_report.push(
             button(entry_row)
@@ -154,21 +176,29 @@ fn lazy_report(sniffer: &Sniffer) -> Row<'static, Message, Renderer<StyleType>>
         );
     }
     if results_number > 0 {
-        col_report = col_report
-            .push(Text::new("      Src IP address       Src port      Dst IP address       Dst port  Layer4   Layer7     Packets     Bytes   Country").vertical_alignment(Vertical::Center).height(Length::FillPortion(2)).font(font))
+        let table = Column::new()
+            .width(Length::Fixed(table_width))
+            .push(table_header(
+                &columns,
+                sniffer.report_sort,
+                font,
+                sniffer.language,
+            ))
             .push(Rule::horizontal(5))
             .push(
                 Scrollable::new(scroll_report)
-                    .height(Length::FillPortion(15))
+                    .height(Length::Fill)
                     .width(Length::Fill)
-                    .direction(Direction::Both {
-                        vertical: ScrollbarType::properties(),
-                        horizontal: ScrollbarType::properties(),
-                    })
-            )
+                    .direction(Direction::Vertical(ScrollbarType::properties())),
+            );
+        col_report = col_report
             .push(
-                Rule::horizontal(5)
+                Scrollable::new(table)
+                    .height(Length::FillPortion(17))
+                    .width(Length::Fill)
+                    .direction(Direction::Horizontal(ScrollbarType::properties())),
             )
+            .push(Rule::horizontal(5))
             .push(get_change_page_row(
                 font,
                 sniffer.language,
@@ -176,6 +206,7 @@ fn lazy_report(sniffer: &Sniffer) -> Row<'static, Message, Renderer<StyleType>>
                 start_entry_num,
                 end_entry_num,
                 results_number,
+                Message::UpdatePageNumber,
             ));
     } else {
         col_report = col_report.push(
@@ -209,8 +240,93 @@ fn lazy_report(sniffer: &Sniffer) -> Row<'static, Message, Renderer<StyleType>>
         )
 }
 
+// Header of the connections table: clicking a column sorts by it, dragging a separator resizes it
+fn table_header(
+    columns: &[ReportColumnSettings],
+    sort: ReportSort,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let mut header = Row::new()
+        .padding([0, 2])
+        .height(Length::Fixed(35.0))
+        .align_items(Alignment::Center);
+    for settings in columns {
+        let column = settings.column;
+        let mut label = column.get_label(language).to_string();
+        if sort.column.eq(&column) {
+            label.push_str(if sort.descending { " v" } else { " ^" });
+        }
+        header = header
+            .push(
+                button(Text::new(fit_to_width(label, settings.width)).font(font))
+                    .padding(0)
+                    .width(Length::Fixed(f32::from(settings.width)))
+                    .style(ButtonType::Neutral)
+                    .on_press(Message::SortReport(column)),
+            )
+            .push(ResizeHandle::new(
+                Container::new(Rule::vertical(1))
+                    .width(Length::Fixed(RESIZE_HANDLE_WIDTH))
+                    .height(Length::Fixed(20.0))
+                    .align_x(Horizontal::Center),
+                move |delta| Message::ResizeReportColumn(column, delta),
+                Message::SaveReportColumns,
+            ));
+    }
+    header
+}
+
+/// Truncates the text so that it fits in the given width
+fn fit_to_width(text: String, width: u16) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let max_chars = (f32::from(width) / CHAR_WIDTH) as usize;
+    if text.chars().count() > max_chars {
+        text.chars().take(max_chars).collect()
+    } else {
+        text
+    }
+}
+
+// Checkboxes to choose the columns displayed in the connections table
+fn columns_col(
+    report_columns: &ReportColumns,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut grid = Row::new().spacing(15);
+    for chunk in ReportColumn::ALL.chunks(5) {
+        let mut col = Column::new().spacing(3);
+        for column in chunk {
+            let column = *column;
+            col = col.push(
+                Checkbox::new(
+                    column.get_label(language),
+                    report_columns.get(column).visible,
+                    move |_| Message::ToggleReportColumn(column),
+                )
+                .spacing(5)
+                .size(16)
+                .font(font),
+            );
+        }
+        grid = grid.push(col);
+    }
+
+    Column::new()
+        .spacing(10)
+        .push(
+            Text::new(columns_translation(language))
+                .font(font)
+                .style(TextType::Title)
+                .size(FONT_SIZE_TITLE),
+        )
+        .push(grid)
+}
+
 fn filters_col(
     search_params: &SearchParameters,
+    suggestions: &Option<(Autocomplete, Vec<String>)>,
     font: Font,
     language: Language,
 ) -> Column<'static, Message, Renderer<StyleType>> {
@@ -226,10 +342,38 @@ fn filters_col(
         title_row = title_row.push(button_clear_filter(SearchParameters::default(), font));
     }
 
-    Column::new()
+    let mut ret_val = Column::new()
         .spacing(3)
         .push(title_row)
-        .push(vertical_space(Length::Fixed(10.0)))
+        .push(vertical_space(Length::Fixed(10.0)));
+    if let Some((_, host)) = &search_params.host {
+        ret_val = ret_val.push(
+            Container::new(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Text::new(format!(
+                            "{}: {}",
+                            host_translation(language),
+                            get_host_label(host)
+                        ))
+                        .font(font),
+                    )
+                    .push(button_clear_filter(
+                        SearchParameters {
+                            host: None,
+                            ..search_params.clone()
+                        },
+                        font,
+                    )),
+            )
+            .padding([5, 8])
+            .style(ContainerType::Badge),
+        );
+    }
+
+    ret_val
         .push(
             Container::new(
                 Checkbox::new(
@@ -253,23 +397,133 @@ fn filters_col(
                 ContainerType::Neutral
             }),
         )
+        .push(
+            Row::new()
+                .align_items(Alignment::Start)
+                .spacing(10)
+                .push(with_suggestions(
+                    filter_input(
+                        FilterInputType::App,
+                        &search_params.app,
+                        application_protocol_translation(language),
+                        60.0,
+                        search_params.clone(),
+                        font,
+                    ),
+                    FilterInputType::App,
+                    suggestions,
+                    font,
+                ))
+                .push(with_suggestions(
+                    filter_input(
+                        FilterInputType::Country,
+                        &search_params.country,
+                        country_translation(language),
+                        30.0,
+                        search_params.clone(),
+                        font,
+                    ),
+                    FilterInputType::Country,
+                    suggestions,
+                    font,
+                )),
+        )
+        .push(
+            Row::new()
+                .align_items(Alignment::Start)
+                .spacing(10)
+                .push(with_suggestions(
+                    filter_input(
+                        FilterInputType::Domain,
+                        &search_params.domain,
+                        domain_name_translation(language),
+                        120.0,
+                        search_params.clone(),
+                        font,
+                    ),
+                    FilterInputType::Domain,
+                    suggestions,
+                    font,
+                ))
+                .push(with_suggestions(
+                    filter_input(
+                        FilterInputType::AS,
+                        &search_params.as_name.clone(),
+                        administrative_entity_translation(language),
+                        120.0,
+                        search_params.clone(),
+                        font,
+                    ),
+                    FilterInputType::AS,
+                    suggestions,
+                    font,
+                )),
+        )
+        .push(address_filters_col(search_params, font, language))
+        .push(query_input(search_params, font, language))
+}
+
+/// Displays the values suggested for the given filter (if it's the one being edited) below its input
+fn with_suggestions(
+    input: Container<'static, Message, Renderer<StyleType>>,
+    filter_input_type: FilterInputType,
+    suggestions: &Option<(Autocomplete, Vec<String>)>,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let col = Column::new().spacing(2).push(input);
+    let Some((autocomplete, values)) = suggestions else {
+        return col;
+    };
+    if autocomplete.filter.ne(&filter_input_type) || values.is_empty() {
+        return col;
+    }
+    let mut list = Column::new();
+    for (i, value) in values.iter().enumerate() {
+        list = list.push(
+            button(Text::new(value.clone()).font(font))
+                .padding([2, 6])
+                .width(Length::Fill)
+                .style(if autocomplete.selected.eq(&Some(i)) {
+                    ButtonType::BorderedRoundSelected
+                } else {
+                    ButtonType::Neutral
+                })
+                .on_press(Message::ApplySuggestion(filter_input_type, value.clone())),
+        );
+    }
+    col.push(
+        Container::new(list)
+            .padding(3)
+            .width(Length::Fixed(250.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+/// Inputs of the address and port filters, with the description of the first invalid value (if any) below them
+fn address_filters_col(
+    search_params: &SearchParameters,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut col = Column::new()
+        .spacing(3)
         .push(
             Row::new()
                 .align_items(Alignment::Center)
                 .spacing(10)
                 .push(filter_input(
-                    FilterInputType::App,
-                    &search_params.app,
-                    application_protocol_translation(language),
-                    60.0,
+                    FilterInputType::Address,
+                    &search_params.address,
+                    address_translation(language),
+                    180.0,
                     search_params.clone(),
                     font,
                 ))
                 .push(filter_input(
-                    FilterInputType::Country,
-                    &search_params.country,
-                    country_translation(language),
-                    30.0,
+                    FilterInputType::Port,
+                    &search_params.port,
+                    port_translation(language),
+                    90.0,
                     search_params.clone(),
                     font,
                 )),
@@ -279,22 +533,56 @@ fn filters_col(
                 .align_items(Alignment::Center)
                 .spacing(10)
                 .push(filter_input(
-                    FilterInputType::Domain,
-                    &search_params.domain,
-                    domain_name_translation(language),
-                    120.0,
+                    FilterInputType::Source,
+                    &search_params.source,
+                    source_translation(language),
+                    180.0,
                     search_params.clone(),
                     font,
                 ))
                 .push(filter_input(
-                    FilterInputType::AS,
-                    &search_params.as_name.clone(),
-                    administrative_entity_translation(language),
-                    120.0,
+                    FilterInputType::Destination,
+                    &search_params.destination,
+                    destination_translation(language),
+                    180.0,
                     search_params.clone(),
                     font,
                 )),
-        )
+        );
+    if let Some(error) = search_params.invalid_address_filter() {
+        col = col.push(
+            Text::new(error)
+                .font(font)
+                .style(TextType::Danger)
+                .width(Length::Fixed(400.0)),
+        );
+    }
+    col
+}
+
+/// Input of the search expression, with the description of its error (if any) below it
+fn query_input(
+    search_params: &SearchParameters,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut col = Column::new().spacing(3).push(filter_input(
+        FilterInputType::Query,
+        &search_params.query,
+        search_query_translation(language),
+        330.0,
+        search_params.clone(),
+        font,
+    ));
+    if let Err(error) = SearchQuery::parse(&search_params.query) {
+        col = col.push(
+            Text::new(error.to_string())
+                .font(font)
+                .style(TextType::Danger)
+                .width(Length::Fixed(400.0)),
+        );
+    }
+    col
 }
 
 fn filter_input(
@@ -325,11 +613,36 @@ fn filter_input(
                 as_name: String::new(),
                 ..search_params.clone()
             },
+            FilterInputType::Address => SearchParameters {
+                address: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::Source => SearchParameters {
+                source: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::Destination => SearchParameters {
+                destination: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::Port => SearchParameters {
+                port: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::Query => SearchParameters {
+                query: String::new(),
+                ..search_params.clone()
+            },
         },
         font,
     );
 
-    let input = TextInput::new("-", filter_value)
+    let placeholder = if filter_input_type.eq(&FilterInputType::Query) {
+        "country:US and bytes>10MB and port:443"
+    } else {
+        "-"
+    };
+    let input = TextInput::new(placeholder, filter_value)
         .on_input(move |new_value| {
             Message::Search(match filter_input_type {
                 FilterInputType::App => SearchParameters {
@@ -348,6 +661,27 @@ fn filter_input(
                     as_name: new_value.trim().to_string(),
                     ..search_params.clone()
                 },
+                FilterInputType::Address => SearchParameters {
+                    address: new_value.trim().to_string(),
+                    ..search_params.clone()
+                },
+                FilterInputType::Source => SearchParameters {
+                    source: new_value.trim().to_string(),
+                    ..search_params.clone()
+                },
+                FilterInputType::Destination => SearchParameters {
+                    destination: new_value.trim().to_string(),
+                    ..search_params.clone()
+                },
+                FilterInputType::Port => SearchParameters {
+                    port: new_value.trim().to_string(),
+                    ..search_params.clone()
+                },
+                // spaces separate the terms of search expressions
+                FilterInputType::Query => SearchParameters {
+                    query: new_value,
+                    ..search_params.clone()
+                },
             })
         })
         .padding([0, 5])
@@ -377,7 +711,10 @@ fn filter_input(
         })
 }
 
-fn get_button_change_page(increment: bool) -> Button<'static, Message, Renderer<StyleType>> {
+fn get_button_change_page(
+    increment: bool,
+    on_change: fn(bool) -> Message,
+) -> Button<'static, Message, Renderer<StyleType>> {
     button(
         if increment {
             Icon::ArrowRight
@@ -392,23 +729,26 @@ fn get_button_change_page(increment: bool) -> Button<'static, Message, Renderer<
     .padding(2)
     .height(Length::Fixed(20.0))
     .width(Length::Fixed(25.0))
-    .on_press(Message::UpdatePageNumber(increment))
+    .on_press(on_change(increment))
 }
 
-fn get_change_page_row(
+/// Row to move through the pages of results (20 per page); `on_change` builds the message
+/// to go to the next (previous) page if true (false)
+pub fn get_change_page_row(
     font: Font,
     language: Language,
     page_number: usize,
     start_entry_num: usize,
     end_entry_num: usize,
     results_number: usize,
+    on_change: fn(bool) -> Message,
 ) -> Row<'static, Message, Renderer<StyleType>> {
     Row::new()
         .height(Length::FillPortion(2))
         .align_items(Alignment::Center)
         .spacing(10)
         .push(if page_number > 1 {
-            Container::new(get_button_change_page(false).width(25.0))
+            Container::new(get_button_change_page(false, on_change).width(25.0))
         } else {
             Container::new(horizontal_space(25.0))
         })
@@ -422,7 +762,7 @@ fn get_change_page_row(
             .font(font),
         )
         .push(if page_number < (results_number + 20 - 1) / 20 {
-            Container::new(get_button_change_page(true).width(25.0))
+            Container::new(get_button_change_page(true, on_change).width(25.0))
         } else {
             Container::new(horizontal_space(25.0))
         })
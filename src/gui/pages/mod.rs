@@ -1,4 +1,5 @@
 pub mod connection_details_page;
+pub mod hosts_page;
 pub mod initial_page;
 pub mod inspect_page;
 pub mod notifications_page;
@@ -1,7 +1,7 @@
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Direction;
 use iced::widget::tooltip::Position;
-use iced::widget::{button, vertical_space};
+use iced::widget::{button, horizontal_space, vertical_space, TextInput};
 use iced::widget::{lazy, Column, Container, Row, Scrollable, Text, Tooltip};
 use iced::Length::FillPortion;
 use iced::{Alignment, Font, Length, Renderer};
@@ -19,7 +19,9 @@
 use crate::gui::types::message::Message;
 use crate::notifications::types::logged_notification::{
     BytesThresholdExceeded, FavoriteTransmitted, LoggedNotification, PacketsThresholdExceeded,
+    RuleTriggered,
 };
+use crate::notifications::types::notification_record::NotificationRecord;
 use crate::translations::translations::{
     bytes_exceeded_translation, bytes_exceeded_value_translation, clear_all_translation,
     favorite_transmitted_translation, incoming_translation, no_notifications_received_translation,
@@ -27,13 +29,18 @@
     packets_exceeded_translation, packets_exceeded_value_translation, per_second_translation,
     threshold_translation,
 };
+use crate::translations::translations_3::{
+    export_translation, filter_translation, history_entries_translation,
+    notifications_history_translation, recent_notifications_translation,
+    rule_triggered_translation,
+};
 use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
 use crate::utils::types::icon::Icon;
 use crate::{Language, RunningPage, Sniffer, StyleType};
 
 /// Computes the body of gui notifications page
 pub fn notifications_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>> {
-    let notifications = sniffer.notifications;
+    let notifications = &sniffer.notifications;
     let font = get_font(sniffer.style);
     let font_headers = get_font_headers(sniffer.style);
 
@@ -53,9 +60,13 @@ pub fn notifications_page(sniffer: &Sniffer) -> Container<Message, Renderer<Styl
         .push(tabs)
         .push(vertical_space(Length::Fixed(15.0)));
 
-    if notifications.packets_notification.threshold.is_none()
+    if let Some(history) = &sniffer.notifications_history {
+        let body = body_history(sniffer, history, font);
+        tab_and_body = tab_and_body.push(body);
+    } else if notifications.packets_notification.threshold.is_none()
         && notifications.bytes_notification.threshold.is_none()
         && !notifications.favorite_notification.notify_on_favorite
+        && notifications.rules.is_empty()
         && sniffer.runtime_data.logged_notifications.is_empty()
     {
         let body = body_no_notifications_set(font, sniffer.language);
@@ -92,11 +103,16 @@ pub fn notifications_page(sniffer: &Sniffer) -> Container<Message, Renderer<Styl
                     .direction(Direction::Vertical(ScrollbarType::properties())),
             )
             .push(
-                Container::new(get_button_clear_all(font, sniffer.language))
-                    .width(Length::FillPortion(1))
-                    .height(Length::Fill)
-                    .align_x(Horizontal::Center)
-                    .align_y(Vertical::Center),
+                Container::new(
+                    Column::new()
+                        .spacing(10)
+                        .push(get_button_history(font, sniffer.language))
+                        .push(get_button_clear_all(font, sniffer.language)),
+                )
+                .width(Length::FillPortion(1))
+                .height(Length::Fill)
+                .align_x(Horizontal::Center)
+                .align_y(Vertical::Center),
             );
         tab_and_body = tab_and_body.push(body_row);
     }
@@ -124,6 +140,7 @@ fn body_no_notifications_set(
             language,
             SettingsPage::Notifications,
         ))
+        .push(get_button_history(font, language))
         .push(vertical_space(FillPortion(2)))
 }
 
@@ -144,11 +161,13 @@ fn body_no_notifications_received(
                 .font(font),
         )
         .push(Text::new(waiting.to_owned()).font(font).size(50))
+        .push(get_button_history(font, language))
         .push(vertical_space(FillPortion(2)))
 }
 
 fn packets_notification_log(
     logged_notification: PacketsThresholdExceeded,
+    time_span: String,
     language: Language,
     font: Font,
 ) -> Container<'static, Message, Renderer<StyleType>> {
@@ -187,7 +206,7 @@ fn packets_notification_log(
                     Row::new()
                         .spacing(5)
                         .push(Icon::Clock.to_text())
-                        .push(Text::new(logged_notification.timestamp).font(font)),
+                        .push(Text::new(time_span).font(font)),
                 )
                 .push(
                     Text::new(packets_exceeded_translation(language))
@@ -223,6 +242,7 @@ fn packets_notification_log(
 
 fn bytes_notification_log(
     logged_notification: BytesThresholdExceeded,
+    time_span: String,
     language: Language,
     font: Font,
 ) -> Container<'static, Message, Renderer<StyleType>> {
@@ -266,7 +286,7 @@ fn bytes_notification_log(
                     Row::new()
                         .spacing(5)
                         .push(Icon::Clock.to_text())
-                        .push(Text::new(logged_notification.timestamp).font(font)),
+                        .push(Text::new(time_span).font(font)),
                 )
                 .push(
                     Text::new(bytes_exceeded_translation(language))
@@ -304,17 +324,12 @@ fn bytes_notification_log(
 
 fn favorite_notification_log(
     logged_notification: FavoriteTransmitted,
+    time_span: String,
     language: Language,
     font: Font,
 ) -> Container<'static, Message, Renderer<StyleType>> {
-    let domain = logged_notification.host.domain;
     let country = logged_notification.host.country;
-    let asn = logged_notification.host.asn;
-
-    let mut domain_asn_str = domain;
-    if !asn.name.is_empty() {
-        domain_asn_str.push_str(&format!(" - {}", asn.name));
-    }
+    let domain_asn_str = logged_notification.get_host_description();
 
     let row_flag_details = Row::new()
         .align_items(Alignment::Center)
@@ -350,7 +365,7 @@ fn favorite_notification_log(
                     Row::new()
                         .spacing(5)
                         .push(Icon::Clock.to_text())
-                        .push(Text::new(logged_notification.timestamp).font(font)),
+                        .push(Text::new(time_span).font(font)),
                 )
                 .push(
                     Text::new(favorite_transmitted_translation(language))
@@ -371,6 +386,251 @@ fn favorite_notification_log(
         .style(ContainerType::BorderedRound)
 }
 
+fn rule_notification_log(
+    logged_notification: RuleTriggered,
+    time_span: String,
+    language: Language,
+    font: Font,
+) -> Container<'static, Message, Renderer<StyleType>> {
+    let value_str = logged_notification.get_value_description(language);
+    let content = Row::new()
+        .spacing(30)
+        .align_items(Alignment::Center)
+        .height(Length::Fill)
+        .push(
+            Tooltip::new(
+                Icon::Funnel.to_text().size(80),
+                rule_triggered_translation(language),
+                Position::FollowCursor,
+            )
+            .font(font)
+            .style(ContainerType::Tooltip),
+        )
+        .push(
+            Column::new()
+                .width(Length::Fixed(250.0))
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(time_span).font(font)),
+                )
+                .push(
+                    Text::new(rule_triggered_translation(language))
+                        .style(TextType::Title)
+                        .font(font),
+                )
+                .push(
+                    Text::new(logged_notification.rule.get_description(language))
+                        .style(TextType::Subtitle)
+                        .size(FONT_SIZE_FOOTER)
+                        .font(font),
+                ),
+        )
+        .push(
+            Column::new()
+                .spacing(7)
+                .width(Length::Fill)
+                .push(Text::new(value_str).font(font)),
+        );
+    Container::new(content)
+        .height(Length::Fixed(120.0))
+        .width(Length::Fixed(800.0))
+        .padding(10)
+        .style(ContainerType::BorderedRound)
+}
+
+fn body_history<'a>(
+    sniffer: &'a Sniffer,
+    history: &[NotificationRecord],
+    font: Font,
+) -> Column<'a, Message, Renderer<StyleType>> {
+    let language = sniffer.language;
+    let back_button = Tooltip::new(
+        button(
+            Icon::ArrowBack
+                .to_text()
+                .size(20)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .padding(0)
+        .height(Length::Fixed(40.0))
+        .width(Length::Fixed(60.0))
+        .on_press(Message::ShowNotificationsHistory(false)),
+        recent_notifications_translation(language),
+        Position::Right,
+    )
+    .font(font)
+    .style(ContainerType::Tooltip);
+    let export_button = Tooltip::new(
+        button(
+            Icon::File
+                .to_text()
+                .size(20)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .padding(0)
+        .height(Length::Fixed(40.0))
+        .width(Length::Fixed(60.0))
+        .on_press(Message::ExportNotificationsHistory),
+        export_translation(language),
+        Position::Left,
+    )
+    .font(font)
+    .style(ContainerType::Tooltip);
+    let filter_input = TextInput::new(filter_translation(language), &sniffer.history_filter)
+        .on_input(Message::FilterNotificationsHistory)
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fixed(300.0));
+
+    let header = Row::new()
+        .padding([0, 20])
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(back_button)
+        .push(
+            Text::new(notifications_history_translation(language))
+                .style(TextType::Title)
+                .font(font),
+        )
+        .push(horizontal_space(Length::Fill))
+        .push(Icon::Funnel.to_text())
+        .push(filter_input)
+        .push(export_button);
+
+    let matching = history
+        .iter()
+        .filter(|record| record.matches(&sniffer.history_filter))
+        .count();
+    let entries = lazy(
+        (
+            history.len(),
+            sniffer.history_filter.clone(),
+            sniffer.language,
+            sniffer.style,
+        ),
+        move |_| lazy_history_entries(sniffer),
+    );
+
+    Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(header)
+        .push(
+            Text::new(history_entries_translation(
+                language,
+                matching.min(HISTORY_MAX_ENTRIES),
+                matching,
+            ))
+            .style(TextType::Subtitle)
+            .size(FONT_SIZE_FOOTER)
+            .font(font),
+        )
+        .push(Scrollable::new(entries).direction(Direction::Vertical(ScrollbarType::properties())))
+}
+
+/// Maximum number of entries of the notifications history displayed at once
+const HISTORY_MAX_ENTRIES: usize = 500;
+
+fn lazy_history_entries(sniffer: &Sniffer) -> Column<'static, Message, Renderer<StyleType>> {
+    let font = get_font(sniffer.style);
+    let mut ret_val = Column::new()
+        .width(Length::Fixed(830.0))
+        .padding(5)
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+    let Some(history) = &sniffer.notifications_history else {
+        return ret_val;
+    };
+    for record in history
+        .iter()
+        .rev()
+        .filter(|record| record.matches(&sniffer.history_filter))
+        .take(HISTORY_MAX_ENTRIES)
+    {
+        ret_val = ret_val.push(history_entry(record, font));
+    }
+    ret_val
+}
+
+fn history_entry(
+    record: &NotificationRecord,
+    font: Font,
+) -> Container<'static, Message, Renderer<StyleType>> {
+    let icon = match record.kind.as_str() {
+        "packets_threshold_exceeded" => Icon::PacketsThreshold,
+        "bytes_threshold_exceeded" => Icon::BytesThreshold,
+        "favorite_transmitted" => Icon::Star,
+        _ => Icon::Funnel,
+    };
+    // RFC 3339 timestamp without the time zone, e.g. 2023-09-01 03:12:45
+    let date_time = record
+        .timestamp
+        .get(0..19)
+        .unwrap_or(&record.timestamp)
+        .replace('T', " ");
+    let content = Row::new()
+        .spacing(30)
+        .align_items(Alignment::Center)
+        .push(icon.to_text().size(40))
+        .push(
+            Column::new()
+                .width(Length::Fixed(250.0))
+                .spacing(7)
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .push(Icon::Clock.to_text())
+                        .push(Text::new(date_time).font(font)),
+                )
+                .push(
+                    Text::new(record.summary.clone())
+                        .style(TextType::Title)
+                        .font(font),
+                ),
+        )
+        .push(
+            Text::new(record.body.clone())
+                .font(font)
+                .width(Length::Fill),
+        );
+    Container::new(content)
+        .width(Length::Fixed(800.0))
+        .padding(10)
+        .style(ContainerType::BorderedRound)
+}
+
+fn get_button_history(
+    font: Font,
+    language: Language,
+) -> Tooltip<'static, Message, Renderer<StyleType>> {
+    let content = button(
+        Icon::Clock
+            .to_text()
+            .size(20)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(10)
+    .height(Length::Fixed(50.0))
+    .width(Length::Fixed(75.0))
+    .on_press(Message::ShowNotificationsHistory(true));
+
+    Tooltip::new(
+        content,
+        notifications_history_translation(language),
+        Position::Top,
+    )
+    .gap(5)
+    .font(font)
+    .style(ContainerType::Tooltip)
+}
+
 fn get_button_clear_all(
     font: Font,
     language: Language,
@@ -401,16 +661,35 @@ fn lazy_logged_notifications(sniffer: &Sniffer) -> Column<'static, Message, Rend
         .spacing(10)
         .align_items(Alignment::Center);
 
-    for logged_notification in &sniffer.runtime_data.logged_notifications {
-        ret_val = ret_val.push(match logged_notification {
+    for group in &sniffer.runtime_data.logged_notifications {
+        let time_span = group.get_time_span();
+        ret_val = ret_val.push(match &group.notification {
             LoggedNotification::PacketsThresholdExceeded(packet_threshold_exceeded) => {
-                packets_notification_log(packet_threshold_exceeded.clone(), sniffer.language, font)
+                packets_notification_log(
+                    packet_threshold_exceeded.clone(),
+                    time_span,
+                    sniffer.language,
+                    font,
+                )
             }
             LoggedNotification::BytesThresholdExceeded(byte_threshold_exceeded) => {
-                bytes_notification_log(byte_threshold_exceeded.clone(), sniffer.language, font)
+                bytes_notification_log(
+                    byte_threshold_exceeded.clone(),
+                    time_span,
+                    sniffer.language,
+                    font,
+                )
             }
             LoggedNotification::FavoriteTransmitted(favorite_transmitted) => {
-                favorite_notification_log(favorite_transmitted.clone(), sniffer.language, font)
+                favorite_notification_log(
+                    favorite_transmitted.clone(),
+                    time_span,
+                    sniffer.language,
+                    font,
+                )
+            }
+            LoggedNotification::RuleTriggered(rule_triggered) => {
+                rule_notification_log(rule_triggered.clone(), time_span, sniffer.language, font)
             }
         });
     }
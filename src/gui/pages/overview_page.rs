@@ -3,17 +3,24 @@
 //! It contains elements to display traffic statistics: chart, detailed connections data
 //! and overall statistics about the filtered traffic.
 
+use chrono::Local;
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Direction;
 use iced::widget::{
-    button, lazy, vertical_space, Button, Column, Container, Row, Scrollable, Text,
+    button, lazy, vertical_space, Button, Column, Container, PickList, Row, Scrollable, Text,
 };
 use iced::widget::{horizontal_space, Rule};
 use iced::Length::{Fill, FillPortion};
-use iced::{Alignment, Font, Length, Renderer};
+use iced::{Alignment, Color, Element, Font, Length, Renderer};
+use plotters::style::RGBColor;
 
+use crate::chart::export_chart::{ChartImageFormat, ChartResolution};
+use crate::chart::types::chart_breakdown::{get_breakdown_color, ChartBreakdown};
+use crate::chart::types::chart_window::ChartWindow;
+use crate::chart::types::sparkline_chart::SparklineChart;
 use crate::countries::country_utils::get_flag_tooltip;
 use crate::countries::flags_pictures::FLAGS_WIDTH_BIG;
+use crate::gui::components::label::get_labeled_text;
 use crate::gui::components::radio::chart_radios;
 use crate::gui::components::tab::get_pages_tabs;
 use crate::gui::styles::button::ButtonType;
@@ -27,11 +34,13 @@
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
+use crate::networking::types::label::get_host_label_entry;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::search_parameters::SearchParameters;
 use crate::report::get_report_entries::{get_app_entries, get_host_entries};
+use crate::report::types::host_grouping::HostGrouping;
 use crate::translations::translations::{
-    application_protocol_translation, bytes_chart_translation, error_translation,
+    all_translation, application_protocol_translation, bytes_chart_translation, error_translation,
     filtered_bytes_translation, filtered_packets_translation, network_adapter_translation,
     no_addresses_translation, none_translation, of_total_translation, packets_chart_translation,
     some_observed_translation, traffic_rate_translation, waiting_translation,
@@ -40,11 +49,19 @@
     data_representation_translation, dropped_packets_translation, host_translation,
     only_top_30_hosts_translation,
 };
+use crate::translations::translations_3::{
+    chart_hint_translation, display_filters_translation, export_translation, group_by_translation,
+    show_all_hosts_translation,
+};
 use crate::utils::formatted_strings::{
-    get_active_filters_col, get_formatted_bytes_string_with_b, get_percentage_string,
+    get_active_filters_col, get_formatted_bytes_string_with_b, get_host_label,
+    get_percentage_string,
 };
 use crate::utils::types::icon::Icon;
-use crate::{AppProtocol, ChartType, Language, RunningPage, StyleType};
+use crate::{AppProtocol, ChartType, IpVersion, Language, RunningPage, StyleType, TransProtocol};
+
+/// Width of the sparklines showing the recent traffic of each host
+const SPARKLINE_WIDTH: f32 = 80.0;
 
 /// Computes the body of gui overview page
 pub fn overview_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType>> {
@@ -65,9 +82,16 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType
         match (observed, filtered) {
             (0, 0) => {
                 //no packets observed at all
-                body = body_no_packets(&sniffer.device, font, sniffer.language, &sniffer.waiting);
+                body = body_no_packets(
+                    &sniffer.device,
+                    sniffer.remote_adapter.as_ref(),
+                    font,
+                    sniffer.language,
+                    &sniffer.waiting,
+                );
             }
-            (observed, 0) => {
+            // with active display filters, the page is kept to let the user change them
+            (observed, 0) if sniffer.display_filters.eq(&Filters::default()) => {
                 //no packets have been filtered but some have been observed
                 body = body_no_observed(
                     sniffer.filters,
@@ -96,18 +120,23 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType
                         sniffer.style,
                         sniffer.language,
                         sniffer.traffic_chart.chart_type,
+                        sniffer.display_filters,
                     ),
                     move |_| lazy_col_info(total, filtered, dropped, sniffer),
                 );
 
-                let num_favorites = sniffer.info_traffic.lock().unwrap().favorite_hosts.len();
                 let container_report = lazy(
                     (
                         filtered,
-                        num_favorites,
+                        sniffer.favorites.len(),
+                        sniffer.ignored.len(),
+                        sniffer.labels.clone(),
+                        sniffer.traffic_chart.ticks,
                         sniffer.style,
                         sniffer.language,
                         sniffer.traffic_chart.chart_type,
+                        sniffer.host_grouping,
+                        sniffer.display_filters,
                     ),
                     move |_| lazy_row_report(sniffer),
                 );
@@ -142,26 +171,28 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message, Renderer<StyleType
 
 fn body_no_packets(
     device: &MyDevice,
+    remote_adapter: Option<&String>,
     font: Font,
     language: Language,
     waiting: &str,
 ) -> Column<'static, Message, Renderer<StyleType>> {
-    let adapter_name = device.name.clone();
-    let (icon_text, nothing_to_see_text) = if device.addresses.lock().unwrap().is_empty() {
-        (
-            Icon::Warning.to_text().size(60),
-            no_addresses_translation(language, &adapter_name)
-                .horizontal_alignment(Horizontal::Center)
-                .font(font),
-        )
-    } else {
-        (
-            Icon::get_hourglass(waiting.len()).size(60),
-            waiting_translation(language, &adapter_name)
-                .horizontal_alignment(Horizontal::Center)
-                .font(font),
-        )
-    };
+    let adapter_name = remote_adapter.unwrap_or(&device.name).clone();
+    let (icon_text, nothing_to_see_text) =
+        if remote_adapter.is_none() && device.addresses.lock().unwrap().is_empty() {
+            (
+                Icon::Warning.to_text().size(60),
+                no_addresses_translation(language, &adapter_name)
+                    .horizontal_alignment(Horizontal::Center)
+                    .font(font),
+            )
+        } else {
+            (
+                Icon::get_hourglass(waiting.len()).size(60),
+                waiting_translation(language, &adapter_name)
+                    .horizontal_alignment(Horizontal::Center)
+                    .font(font),
+            )
+        };
 
     Column::new()
         .width(Length::Fill)
@@ -253,32 +284,29 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, Renderer<
     let mut scroll_host = Column::new()
         .width(Length::Fixed(width))
         .align_items(Alignment::Center);
-    let entries = get_host_entries(&sniffer.info_traffic, chart_type);
+    let entries = get_host_entries(&sniffer.info_traffic, chart_type, sniffer.host_grouping);
+    let now = Local::now().timestamp();
+    // leave room for the ignore button
+    let bar_width = width - SPARKLINE_WIDTH - FLAGS_WIDTH_BIG - 10.0;
 
     for (host, data_info_host) in &entries {
         let (incoming_bar_len, outgoing_bar_len) = get_bars_length(
-            width * 0.86,
+            bar_width * 0.86,
             chart_type,
             &entries.get(0).unwrap().1.data_info.clone(),
             &data_info_host.data_info,
         );
 
-        let star_button = get_star_button(data_info_host.is_favorite, host.clone());
-
         let host_bar = Column::new()
-            .width(Length::Fixed(width))
+            .width(Length::Fixed(bar_width))
             .spacing(1)
             .push(
                 Row::new()
-                    .push(Text::new(host.domain.clone()).font(font))
-                    .push(
-                        Text::new(if host.asn.name.is_empty() {
-                            String::new()
-                        } else {
-                            format!(" - {}", host.asn.name)
-                        })
-                        .font(font),
-                    )
+                    .push(get_labeled_text(
+                        get_host_label_entry(&sniffer.labels, host),
+                        &get_host_label(host),
+                        font,
+                    ))
                     .push(horizontal_space(Length::FillPortion(1)))
                     .push(
                         Text::new(if chart_type.eq(&ChartType::Packets) {
@@ -310,7 +338,11 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, Renderer<
         let content = Row::new()
             .align_items(Alignment::Center)
             .spacing(5)
-            .push(star_button)
+            .push(get_host_buttons(
+                sniffer.host_grouping,
+                data_info_host.is_favorite,
+                host,
+            ))
             .push(get_flag_tooltip(
                 host.country,
                 FLAGS_WIDTH_BIG,
@@ -319,7 +351,11 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, Renderer<
                 sniffer.language,
                 font,
             ))
-            .push(host_bar);
+            .push(host_bar)
+            .push(
+                SparklineChart::new(&data_info_host.traffic_series, now, false, sniffer.style)
+                    .view(SPARKLINE_WIDTH, 26.0),
+            );
 
         scroll_host = scroll_host.push(
             button(content)
@@ -335,20 +371,53 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, Renderer<
     }
 
     if entries.len() >= 30 {
-        scroll_host = scroll_host.push(vertical_space(Length::Fixed(25.0))).push(
-            Text::new(only_top_30_hosts_translation(sniffer.language))
-                .font(font)
-                .horizontal_alignment(Horizontal::Center),
-        );
+        scroll_host = scroll_host
+            .push(vertical_space(Length::Fixed(25.0)))
+            .push(
+                Text::new(only_top_30_hosts_translation(sniffer.language))
+                    .font(font)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .push(vertical_space(Length::Fixed(10.0)))
+            .push(
+                button(Text::new(show_all_hosts_translation(sniffer.language)).font(font))
+                    .padding([2, 8])
+                    .style(ButtonType::Standard)
+                    .on_press(Message::ChangeRunningPage(RunningPage::Hosts)),
+            );
     }
 
+    let grouping_list_str: Vec<&str> = HostGrouping::all_strings(sniffer.language);
+    let picklist_grouping = PickList::new(
+        grouping_list_str.clone(),
+        Some(sniffer.host_grouping.get_picklist_label(sniffer.language)),
+        move |selected_str| {
+            let index = grouping_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::HostGroupingSelection(HostGrouping::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
     Column::new()
         .width(Length::Fixed(width + 11.0))
         .push(
-            Text::new(host_translation(sniffer.language))
-                .font(font)
-                .style(TextType::Title)
-                .size(FONT_SIZE_TITLE),
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(host_translation(sniffer.language))
+                        .font(font)
+                        .style(TextType::Title)
+                        .size(FONT_SIZE_TITLE),
+                )
+                .push(horizontal_space(Length::Fill))
+                .push(Text::new(format!("{}:", group_by_translation(sniffer.language))).font(font))
+                .push(picklist_grouping)
+                .push(horizontal_space(Length::Fixed(15.0))),
         )
         .push(vertical_space(Length::Fixed(10.0)))
         .push(
@@ -452,8 +521,13 @@ fn lazy_col_info(
         sniffer.runtime_data.tot_sent_bytes + sniffer.runtime_data.tot_received_bytes;
     let all_bytes = sniffer.runtime_data.all_bytes;
 
-    let col_device_filters =
-        col_device_filters(sniffer.language, font, sniffer.filters, &sniffer.device);
+    let col_device_filters = col_device_filters(
+        sniffer.language,
+        font,
+        sniffer.filters,
+        &sniffer.device,
+        sniffer.remote_adapter.as_ref(),
+    );
 
     let col_data_representation =
         col_data_representation(sniffer.language, font, sniffer.traffic_chart.chart_type);
@@ -483,6 +557,12 @@ fn lazy_col_info(
                 .push(col_data_representation),
         )
         .push(Rule::horizontal(25))
+        .push(col_display_filters(
+            sniffer.language,
+            font,
+            sniffer.display_filters,
+        ))
+        .push(Rule::horizontal(25))
         .push(
             Scrollable::new(col_bytes_packets)
                 .width(Length::Fill)
@@ -509,6 +589,38 @@ fn container_chart(sniffer: &Sniffer, font: Font) -> Container<Message, Renderer
     });
     chart_info_string.push(')');
 
+    let window_active_str = traffic_chart.window.get_picklist_label(language);
+    let window_list_str: Vec<&str> = ChartWindow::all_strings(language);
+    let picklist_window = PickList::new(
+        window_list_str.clone(),
+        Some(window_active_str),
+        move |selected_str| {
+            let index = window_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::ChartWindowSelection(ChartWindow::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
+    let breakdown_active_str = traffic_chart.breakdown.get_picklist_label(language);
+    let breakdown_list_str: Vec<&str> = ChartBreakdown::all_strings(language);
+    let picklist_breakdown = PickList::new(
+        breakdown_list_str.clone(),
+        Some(breakdown_active_str),
+        move |selected_str| {
+            let index = breakdown_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::ChartBreakdownSelection(ChartBreakdown::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
     Container::new(
         Column::new()
             .align_items(Alignment::Center)
@@ -527,9 +639,27 @@ fn container_chart(sniffer: &Sniffer, font: Font) -> Container<Message, Renderer
                         Text::new(chart_info_string)
                             .style(TextType::Subtitle)
                             .font(font),
+                    )
+                    .push(horizontal_space(Length::Fixed(20.0)))
+                    .push(picklist_window)
+                    .push(picklist_breakdown)
+                    .push(
+                        Text::new(chart_hint_translation(language))
+                            .style(TextType::Subtitle)
+                            .size(12)
+                            .font(font),
                     ),
             )
-            .push(traffic_chart.view()),
+            .push(traffic_chart.view())
+            .push(
+                Row::new()
+                    .padding([0, 10])
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(breakdown_legend(sniffer, font))
+                    .push(horizontal_space(Fill))
+                    .push(chart_export_row(sniffer, font)),
+            ),
     )
     .width(Fill)
     .align_x(Horizontal::Center)
@@ -537,11 +667,85 @@ fn container_chart(sniffer: &Sniffer, font: Font) -> Container<Message, Renderer
     .style(ContainerType::BorderedRound)
 }
 
+// Series of the broken down chart, which can be clicked to show or hide them
+fn breakdown_legend(sniffer: &Sniffer, font: Font) -> Row<'static, Message, Renderer<StyleType>> {
+    let traffic_chart = &sniffer.traffic_chart;
+    let mut legend = Row::new()
+        .padding([5, 0])
+        .spacing(5)
+        .align_items(Alignment::Center);
+    for (index, key) in traffic_chart.breakdown_keys().into_iter().enumerate() {
+        let RGBColor(r, g, b) = get_breakdown_color(index);
+        let is_hidden = traffic_chart.hidden_series.contains(&key);
+        let color = Color {
+            a: if is_hidden { 0.2 } else { 1.0 },
+            ..Color::from_rgb8(r, g, b)
+        };
+        legend = legend.push(
+            button(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Container::new(Rule::horizontal(20).style(RuleType::PaletteColor(color)))
+                            .width(Length::Fixed(20.0))
+                            .height(Length::Fixed(12.0)),
+                    )
+                    .push(Text::new(key.get_label(sniffer.language)).font(font)),
+            )
+            .padding([2, 8])
+            .style(ButtonType::Neutral)
+            .on_press(Message::ToggleChartSeries(key)),
+        );
+    }
+    legend
+}
+
+// Resolution and format of the image the chart can be exported to
+fn chart_export_row(sniffer: &Sniffer, font: Font) -> Row<'static, Message, Renderer<StyleType>> {
+    let resolution_active_str = sniffer.chart_resolution.get_picklist_label();
+    let resolution_list_str: Vec<&str> = ChartResolution::all_strings();
+    let picklist_resolution = PickList::new(
+        resolution_list_str.clone(),
+        Some(resolution_active_str),
+        move |selected_str| {
+            let index = resolution_list_str
+                .iter()
+                .position(|str| *str == selected_str)
+                .unwrap_or_default();
+            Message::ChartResolutionSelection(ChartResolution::ALL[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font);
+
+    let mut row = Row::new()
+        .padding([5, 0])
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .push(
+            Text::new(format!("{}:", export_translation(sniffer.language)))
+                .style(TextType::Subtitle)
+                .font(font),
+        )
+        .push(picklist_resolution);
+    for format in ChartImageFormat::ALL {
+        row = row.push(
+            button(Text::new(format.get_label()).font(font))
+                .padding([2, 8])
+                .style(ButtonType::Neutral)
+                .on_press(Message::ExportChart(format)),
+        );
+    }
+    row
+}
+
 fn col_device_filters(
     language: Language,
     font: Font,
     filters: Filters,
     device: &MyDevice,
+    remote_adapter: Option<&String>,
 ) -> Column<'static, Message, Renderer<StyleType>> {
     #[cfg(not(target_os = "windows"))]
     let adapter_info = &device.name;
@@ -549,6 +753,7 @@ fn col_device_filters(
     let adapter_name = &device.name;
     #[cfg(target_os = "windows")]
     let adapter_info = device.desc.as_ref().unwrap_or(adapter_name);
+    let adapter_info = remote_adapter.unwrap_or(adapter_info);
 
     Column::new()
         .width(Length::FillPortion(1))
@@ -561,6 +766,99 @@ fn col_device_filters(
         .push(get_active_filters_col(filters, language, font))
 }
 
+fn col_display_filters(
+    language: Language,
+    font: Font,
+    display_filters: Filters,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let picklist_ip = get_display_filter_picklist(
+        &IpVersion::ALL,
+        display_filters.ip,
+        |ip| match ip {
+            IpVersion::Other => "IPv4 + IPv6".to_string(),
+            ip => ip.to_string(),
+        },
+        move |ip| {
+            Message::DisplayFiltersSelection(Filters {
+                ip,
+                ..display_filters
+            })
+        },
+        font,
+    );
+    let picklist_transport = get_display_filter_picklist(
+        &TransProtocol::ALL,
+        display_filters.transport,
+        |transport| match transport {
+            TransProtocol::Other => "TCP + UDP".to_string(),
+            transport => transport.to_string(),
+        },
+        move |transport| {
+            Message::DisplayFiltersSelection(Filters {
+                transport,
+                ..display_filters
+            })
+        },
+        font,
+    );
+    let picklist_app = get_display_filter_picklist(
+        &AppProtocol::ALL,
+        display_filters.application,
+        move |application| match application {
+            AppProtocol::Other => all_translation(language).to_string(),
+            application => application.to_string(),
+        },
+        move |application| {
+            Message::DisplayFiltersSelection(Filters {
+                application,
+                ..display_filters
+            })
+        },
+        font,
+    );
+
+    Column::new()
+        .width(Length::Fill)
+        .spacing(5)
+        .push(
+            Text::new(format!("{}:", display_filters_translation(language)))
+                .style(TextType::Subtitle)
+                .font(font),
+        )
+        .push(
+            Row::new()
+                .spacing(5)
+                .push(picklist_ip)
+                .push(picklist_transport)
+                .push(picklist_app),
+        )
+}
+
+/// Pick list to select the value of one of the display filters
+fn get_display_filter_picklist<T: Copy + 'static>(
+    values: &[T],
+    selected: T,
+    label: impl Fn(T) -> String,
+    on_select: impl Fn(T) -> Message + 'static,
+    font: Font,
+) -> PickList<'static, String, Message, Renderer<StyleType>> {
+    let labels: Vec<String> = values.iter().map(|value| label(*value)).collect();
+    let values = values.to_vec();
+    PickList::new(
+        labels.clone(),
+        Some(label(selected)),
+        move |selected_label| {
+            let index = labels
+                .iter()
+                .position(|label| *label == selected_label)
+                .unwrap_or_default();
+            on_select(values[index])
+        },
+    )
+    .padding([3, 7])
+    .font(font)
+}
+
 fn col_data_representation(
     language: Language,
     font: Font,
@@ -627,7 +925,7 @@ fn col_bytes_packets(
         ))
 }
 
-fn get_bars_length(
+pub fn get_bars_length(
     tot_width: f32,
     chart_type: ChartType,
     first_entry: &DataInfo,
@@ -656,7 +954,44 @@ fn get_bars_length(
     (incoming_bar_len, outgoing_bar_len)
 }
 
-fn get_star_button(is_favorite: bool, host: Host) -> Button<'static, Message, Renderer<StyleType>> {
+/// Returns the star and ignore buttons of a host, or an empty space of the same width for groups of hosts
+/// (favorites and ignored hosts are single hosts)
+pub fn get_host_buttons(
+    host_grouping: HostGrouping,
+    is_favorite: bool,
+    host: &Host,
+) -> Element<'static, Message, Renderer<StyleType>> {
+    if host_grouping.eq(&HostGrouping::None) {
+        Row::new()
+            .align_items(Alignment::Center)
+            .spacing(5)
+            .push(get_star_button(is_favorite, host.clone()))
+            .push(get_ignore_button(host.clone()))
+            .into()
+    } else {
+        horizontal_space(Length::Fixed(2.0 * FLAGS_WIDTH_BIG + 5.0)).into()
+    }
+}
+
+fn get_ignore_button(host: Host) -> Button<'static, Message, Renderer<StyleType>> {
+    button(
+        Icon::Forbidden
+            .to_text()
+            .size(15)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(0)
+    .height(Length::Fixed(FLAGS_WIDTH_BIG * 0.75))
+    .width(Length::Fixed(FLAGS_WIDTH_BIG))
+    .style(ButtonType::NotStarred)
+    .on_press(Message::IgnoreHost(host))
+}
+
+pub fn get_star_button(
+    is_favorite: bool,
+    host: Host,
+) -> Button<'static, Message, Renderer<StyleType>> {
     button(
         Icon::Star
             .to_text()
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Direction;
 use iced::widget::tooltip::Position;
@@ -9,8 +11,10 @@
 use iced::Length::Fixed;
 use iced::{Alignment, Font, Length, Renderer};
 
+use crate::gui::components::label::{get_edit_label_button, get_labeled_text};
 use crate::gui::components::radio::{
-    sound_bytes_threshold_radios, sound_favorite_radios, sound_packets_threshold_radios,
+    hook_kind_radios, rule_condition_radios, rule_matcher_radios, sound_bytes_threshold_radios,
+    sound_favorite_radios, sound_packets_threshold_radios, sound_rule_radios,
 };
 use crate::gui::components::tab::get_settings_tabs;
 use crate::gui::pages::types::settings_page::SettingsPage;
@@ -22,14 +26,31 @@
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::gradient_type::GradientType;
 use crate::gui::types::message::Message;
+use crate::networking::types::host_pattern::HostPattern;
+use crate::networking::types::ignore_pattern::IgnorePattern;
+use crate::networking::types::label::{Label, LabelTarget};
+use crate::notifications::types::notification_hook::{HookKind, NotificationHook};
+use crate::notifications::types::notification_rule::{
+    NotificationRule, NotificationRuleDraft, RuleConditionKind,
+};
 use crate::notifications::types::notifications::{
-    BytesNotification, FavoriteNotification, Notification, PacketsNotification,
+    BytesNotification, DesktopNotifications, FavoriteNotification, Notification,
+    PacketsNotification,
 };
 use crate::translations::translations::{
     bytes_threshold_translation, favorite_notification_translation, hide_translation,
     notifications_title_translation, packets_threshold_translation, per_second_translation,
     settings_translation, specify_multiples_translation, threshold_translation, volume_translation,
 };
+use crate::translations::translations_3::{
+    add_favorite_translation, add_rule_translation, condition_translation, cooldown_translation,
+    custom_rules_translation, desktop_notifications_translation, edit_label_translation,
+    favorite_hosts_translation, history_file_translation, ignore_translation,
+    ignored_traffic_translation, ip_or_mac_address_translation, labels_translation,
+    match_translation, notification_hook_translation, retries_translation, sustain_translation,
+    timeout_translation,
+};
+use crate::utils::formatted_strings::get_notifications_history_path;
 use crate::utils::types::icon::Icon;
 use crate::{Language, Sniffer, StyleType};
 
@@ -87,6 +108,45 @@ pub fn settings_notifications_page(sniffer: &Sniffer) -> Container<Message, Rend
                         sniffer.notifications.favorite_notification,
                         sniffer.language,
                         font,
+                    ))
+                    .push(get_favorites(
+                        &sniffer.favorites,
+                        &sniffer.favorite_draft,
+                        sniffer.language,
+                        font,
+                    ))
+                    .push(get_ignored(
+                        &sniffer.ignored,
+                        &sniffer.ignore_draft,
+                        sniffer.language,
+                        font,
+                    ))
+                    .push(get_labels(
+                        &sniffer.labels,
+                        &sniffer.label_target_draft,
+                        sniffer.language,
+                        font,
+                    ))
+                    .push(get_rules_notify(
+                        &sniffer.notifications.rules,
+                        &sniffer.rule_draft,
+                        sniffer.language,
+                        font,
+                    ))
+                    .push(if cfg!(target_os = "linux") {
+                        get_desktop_notify(sniffer.notifications.desktop, sniffer.language, font)
+                    } else {
+                        Column::new()
+                    })
+                    .push(get_hook_notify(
+                        &sniffer.notifications.hook,
+                        sniffer.language,
+                        font,
+                    ))
+                    .push(get_history_file(
+                        &sniffer.notifications.history_path,
+                        sniffer.language,
+                        font,
                     )),
             )
             .direction(Direction::Vertical(ScrollbarType::properties())),
@@ -153,9 +213,26 @@ fn get_packets_notify(
                     font,
                     language,
                 ));
+        let throttling_row = throttling_row(
+            packets_notification.sustain,
+            packets_notification.cooldown,
+            move |sustain, cooldown| {
+                Message::UpdateNotificationSettings(
+                    Notification::Packets(PacketsNotification {
+                        sustain,
+                        cooldown,
+                        ..packets_notification
+                    }),
+                    false,
+                )
+            },
+            font,
+            language,
+        );
         ret_val = ret_val
             .push(vertical_space(Fixed(5.0)))
             .push(input_row)
+            .push(throttling_row)
             .push(sound_row);
         Column::new().padding(5).push(
             Container::new(ret_val)
@@ -219,9 +296,26 @@ fn get_bytes_notify(
                     font,
                     language,
                 ));
+        let throttling_row = throttling_row(
+            bytes_notification.sustain,
+            bytes_notification.cooldown,
+            move |sustain, cooldown| {
+                Message::UpdateNotificationSettings(
+                    Notification::Bytes(BytesNotification {
+                        sustain,
+                        cooldown,
+                        ..bytes_notification
+                    }),
+                    false,
+                )
+            },
+            font,
+            language,
+        );
         ret_val = ret_val
             .push(vertical_space(Fixed(5.0)))
             .push(input_row)
+            .push(throttling_row)
             .push(sound_row);
         Column::new().padding(5).push(
             Container::new(ret_val)
@@ -243,9 +337,15 @@ fn get_favorite_notify(
         move |toggled| {
             Message::UpdateNotificationSettings(
                 if toggled {
-                    Notification::Favorite(FavoriteNotification::on(favorite_notification.sound))
+                    Notification::Favorite(FavoriteNotification::on(
+                        favorite_notification.sound,
+                        favorite_notification.cooldown,
+                    ))
                 } else {
-                    Notification::Favorite(FavoriteNotification::off(favorite_notification.sound))
+                    Notification::Favorite(FavoriteNotification::off(
+                        favorite_notification.sound,
+                        favorite_notification.cooldown,
+                    ))
                 },
                 false,
             )
@@ -257,10 +357,31 @@ fn get_favorite_notify(
     let mut ret_val = Column::new().spacing(5).push(checkbox);
 
     if favorite_notification.notify_on_favorite {
+        let cooldown_row = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .push(horizontal_space(Fixed(40.0)))
+            .push(Text::new(format!("{}:", cooldown_translation(language))).font(font))
+            .push(number_input(
+                favorite_notification.cooldown,
+                font,
+                move |cooldown| {
+                    Message::UpdateNotificationSettings(
+                        Notification::Favorite(FavoriteNotification {
+                            cooldown,
+                            ..favorite_notification
+                        }),
+                        false,
+                    )
+                },
+            ));
         let sound_row = Row::new()
             .push(horizontal_space(Fixed(50.0)))
             .push(sound_favorite_radios(favorite_notification, font, language));
-        ret_val = ret_val.push(vertical_space(Fixed(5.0))).push(sound_row);
+        ret_val = ret_val
+            .push(vertical_space(Fixed(5.0)))
+            .push(cooldown_row)
+            .push(sound_row);
         Column::new().padding(5).push(
             Container::new(ret_val)
                 .padding(10)
@@ -277,6 +398,581 @@ fn get_favorite_notify(
     }
 }
 
+fn get_favorites(
+    favorites: &[HostPattern],
+    favorite_draft: &str,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    get_patterns_list(
+        favorite_hosts_translation(language),
+        favorites.iter().map(ToString::to_string).collect(),
+        PatternsInput {
+            draft: favorite_draft,
+            placeholder: "*.example.com, AS15169, US, 10.0.0.0/8",
+            is_valid: HostPattern::from_str(favorite_draft).is_some(),
+            add_label: add_favorite_translation(language),
+            on_input: Message::UpdateFavoriteDraft,
+            on_add: Message::AddFavoritePattern,
+            on_remove: Message::RemoveFavoritePattern,
+        },
+        font,
+    )
+}
+
+fn get_ignored(
+    ignored: &[IgnorePattern],
+    ignore_draft: &str,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    get_patterns_list(
+        ignored_traffic_translation(language),
+        ignored.iter().map(ToString::to_string).collect(),
+        PatternsInput {
+            draft: ignore_draft,
+            placeholder: "mDNS, SSDP, *.windowsupdate.com, AS8075, 224.0.0.0/4",
+            is_valid: IgnorePattern::from_str(ignore_draft).is_some(),
+            add_label: ignore_translation(language),
+            on_input: Message::UpdateIgnoreDraft,
+            on_add: Message::AddIgnorePattern,
+            on_remove: Message::RemoveIgnorePattern,
+        },
+        font,
+    )
+}
+
+fn get_labels(
+    labels: &[Label],
+    label_target_draft: &str,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Column::new()
+        .spacing(5)
+        .push(Text::new(labels_translation(language)).font(font));
+
+    for label in labels {
+        ret_val = ret_val.push(
+            Row::new()
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(horizontal_space(Fixed(45.0)))
+                .push(Row::new().width(Length::Fill).push(get_labeled_text(
+                    Some(label),
+                    &label.target.to_string(),
+                    font,
+                )))
+                .push(get_edit_label_button(label.target.clone(), language, font))
+                .push(
+                    button(
+                        Icon::Bin
+                            .to_text()
+                            .size(15)
+                            .horizontal_alignment(Horizontal::Center)
+                            .vertical_alignment(Vertical::Center),
+                    )
+                    .padding(2)
+                    .height(Fixed(25.0))
+                    .width(Fixed(35.0))
+                    .on_press(Message::RemoveLabel(label.target.clone())),
+                ),
+        );
+    }
+
+    let target = LabelTarget::from_str(label_target_draft);
+    let mut target_input =
+        TextInput::new(ip_or_mac_address_translation(language), label_target_draft)
+            .on_input(Message::UpdateLabelTargetDraft)
+            .padding([0, 0, 0, 10])
+            .font(font)
+            .width(Length::Fixed(300.0));
+    let mut add_button = button(
+        Text::new(edit_label_translation(language))
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15]);
+    if let Some(target) = target {
+        target_input = target_input.on_submit(Message::EditLabel(target.clone()));
+        add_button = add_button.on_press(Message::EditLabel(target));
+    }
+
+    ret_val = ret_val.push(vertical_space(Fixed(5.0))).push(
+        Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(horizontal_space(Fixed(40.0)))
+            .push(target_input)
+            .push(add_button),
+    );
+
+    Column::new().padding(5).push(
+        Container::new(ret_val)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+/// Input used to add patterns to a list
+struct PatternsInput<'a> {
+    draft: &'a str,
+    placeholder: &'a str,
+    is_valid: bool,
+    add_label: &'static str,
+    on_input: fn(String) -> Message,
+    on_add: Message,
+    on_remove: fn(usize) -> Message,
+}
+
+/// Returns a list of patterns that can be removed, followed by the input to add new ones
+fn get_patterns_list(
+    title: &'static str,
+    patterns: Vec<String>,
+    input: PatternsInput,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Column::new().spacing(5).push(Text::new(title).font(font));
+
+    for (index, pattern) in patterns.into_iter().enumerate() {
+        ret_val = ret_val.push(
+            Row::new()
+                .align_items(Alignment::Center)
+                .push(horizontal_space(Fixed(50.0)))
+                .push(Text::new(pattern).font(font).width(Length::Fill))
+                .push(
+                    button(
+                        Icon::Bin
+                            .to_text()
+                            .size(15)
+                            .horizontal_alignment(Horizontal::Center)
+                            .vertical_alignment(Vertical::Center),
+                    )
+                    .padding(2)
+                    .height(Fixed(25.0))
+                    .width(Fixed(35.0))
+                    .on_press((input.on_remove)(index)),
+                ),
+        );
+    }
+
+    let pattern_input = TextInput::new(input.placeholder, input.draft)
+        .on_input(input.on_input)
+        .on_submit(input.on_add.clone())
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fixed(300.0));
+
+    let mut add_button = button(
+        Text::new(input.add_label)
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15]);
+    if input.is_valid {
+        add_button = add_button.on_press(input.on_add);
+    }
+
+    ret_val = ret_val.push(vertical_space(Fixed(5.0))).push(
+        Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(horizontal_space(Fixed(40.0)))
+            .push(pattern_input)
+            .push(add_button),
+    );
+
+    Column::new().padding(5).push(
+        Container::new(ret_val)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+fn get_rules_notify(
+    rules: &[NotificationRule],
+    rule_draft: &NotificationRuleDraft,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Column::new()
+        .spacing(5)
+        .push(Text::new(custom_rules_translation(language)).font(font));
+
+    for (index, rule) in rules.iter().enumerate() {
+        ret_val = ret_val.push(
+            Row::new()
+                .align_items(Alignment::Center)
+                .push(horizontal_space(Fixed(50.0)))
+                .push(
+                    Text::new(format!(
+                        "{}   ({}, {} s)",
+                        rule.get_description(language),
+                        rule.sound.get_radio_label(language),
+                        rule.cooldown
+                    ))
+                    .font(font)
+                    .width(Length::Fill),
+                )
+                .push(
+                    button(
+                        Icon::Bin
+                            .to_text()
+                            .size(15)
+                            .horizontal_alignment(Horizontal::Center)
+                            .vertical_alignment(Vertical::Center),
+                    )
+                    .padding(2)
+                    .height(Fixed(25.0))
+                    .width(Fixed(35.0))
+                    .on_press(Message::RemoveNotificationRule(index)),
+                ),
+        );
+    }
+
+    let draft = rule_draft.clone();
+    let matcher_input = TextInput::new(
+        rule_draft.matcher_kind.get_placeholder(),
+        &rule_draft.matcher_value,
+    )
+    .on_input(move |value| {
+        Message::UpdateRuleDraft(NotificationRuleDraft {
+            matcher_value: value,
+            ..draft.clone()
+        })
+    })
+    .padding([0, 0, 0, 10])
+    .font(font)
+    .width(Length::Fixed(300.0));
+
+    let mut condition_row = Row::new()
+        .spacing(10)
+        .push(rule_condition_radios(rule_draft, font, language));
+    if rule_draft
+        .condition_kind
+        .eq(&RuleConditionKind::BytesPerSecond)
+    {
+        let draft = rule_draft.clone();
+        let mut info_str = per_second_translation(language).to_string();
+        info_str.push_str(specify_multiples_translation(language));
+        condition_row = condition_row
+            .push(
+                TextInput::new("0", &rule_draft.threshold)
+                    .on_input(move |value| {
+                        Message::UpdateRuleDraft(NotificationRuleDraft {
+                            threshold: value,
+                            ..draft.clone()
+                        })
+                    })
+                    .padding([0, 0, 0, 10])
+                    .font(font)
+                    .width(Length::Fixed(100.0)),
+            )
+            .push(
+                Text::new(info_str)
+                    .font(font)
+                    .vertical_alignment(Vertical::Center)
+                    .size(FONT_SIZE_FOOTER),
+            );
+    }
+
+    let draft = rule_draft.clone();
+    let cooldown_input = TextInput::new("0", &rule_draft.cooldown)
+        .on_input(move |value| {
+            Message::UpdateRuleDraft(NotificationRuleDraft {
+                cooldown: value,
+                ..draft.clone()
+            })
+        })
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fixed(100.0));
+
+    let mut add_button = button(
+        Text::new(add_rule_translation(language))
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15]);
+    if rule_draft.to_rule().is_some() {
+        add_button = add_button.on_press(Message::AddNotificationRule);
+    }
+
+    let editor = Column::new()
+        .spacing(10)
+        .push(Text::new(format!("{}:", match_translation(language))).font(font))
+        .push(rule_matcher_radios(rule_draft, font, language))
+        .push(matcher_input)
+        .push(Text::new(format!("{}:", condition_translation(language))).font(font))
+        .push(condition_row)
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(format!("{}:", cooldown_translation(language))).font(font))
+                .push(cooldown_input),
+        )
+        .push(sound_rule_radios(rule_draft, font, language))
+        .push(add_button);
+
+    ret_val = ret_val
+        .push(vertical_space(Fixed(5.0)))
+        .push(Row::new().push(horizontal_space(Fixed(50.0))).push(editor));
+
+    Column::new().padding(5).push(
+        Container::new(ret_val)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+fn get_desktop_notify(
+    desktop: DesktopNotifications,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let ret_val = Column::new()
+        .spacing(5)
+        .push(Text::new(desktop_notifications_translation(language)).font(font))
+        .push(vertical_space(Fixed(5.0)))
+        .push(
+            Row::new().push(horizontal_space(Fixed(50.0))).push(
+                Column::new()
+                    .spacing(5)
+                    .push(
+                        Checkbox::new(
+                            packets_threshold_translation(language),
+                            desktop.packets,
+                            move |packets| {
+                                Message::UpdateNotificationSettings(
+                                    Notification::Desktop(DesktopNotifications {
+                                        packets,
+                                        ..desktop
+                                    }),
+                                    false,
+                                )
+                            },
+                        )
+                        .size(18)
+                        .font(font),
+                    )
+                    .push(
+                        Checkbox::new(
+                            bytes_threshold_translation(language),
+                            desktop.bytes,
+                            move |bytes| {
+                                Message::UpdateNotificationSettings(
+                                    Notification::Desktop(DesktopNotifications {
+                                        bytes,
+                                        ..desktop
+                                    }),
+                                    false,
+                                )
+                            },
+                        )
+                        .size(18)
+                        .font(font),
+                    )
+                    .push(
+                        Checkbox::new(
+                            favorite_notification_translation(language),
+                            desktop.favorites,
+                            move |favorites| {
+                                Message::UpdateNotificationSettings(
+                                    Notification::Desktop(DesktopNotifications {
+                                        favorites,
+                                        ..desktop
+                                    }),
+                                    false,
+                                )
+                            },
+                        )
+                        .size(18)
+                        .font(font),
+                    )
+                    .push(
+                        Checkbox::new(
+                            custom_rules_translation(language),
+                            desktop.rules,
+                            move |rules| {
+                                Message::UpdateNotificationSettings(
+                                    Notification::Desktop(DesktopNotifications {
+                                        rules,
+                                        ..desktop
+                                    }),
+                                    false,
+                                )
+                            },
+                        )
+                        .size(18)
+                        .font(font),
+                    ),
+            ),
+        );
+    Column::new().padding(5).push(
+        Container::new(ret_val)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+fn get_hook_notify(
+    hook: &NotificationHook,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let mut ret_val = Column::new()
+        .spacing(5)
+        .push(Text::new(notification_hook_translation(language)).font(font))
+        .push(vertical_space(Fixed(5.0)))
+        .push(
+            Row::new()
+                .push(horizontal_space(Fixed(50.0)))
+                .push(hook_kind_radios(hook, font, language)),
+        );
+
+    if hook.kind.ne(&HookKind::None) {
+        let hook_target = hook.clone();
+        let target_input = TextInput::new(hook.kind.get_placeholder(), &hook.target)
+            .on_input(move |target| {
+                Message::UpdateNotificationSettings(
+                    Notification::Hook(NotificationHook {
+                        target,
+                        ..hook_target.clone()
+                    }),
+                    false,
+                )
+            })
+            .padding([0, 0, 0, 10])
+            .font(font)
+            .width(Length::Fixed(400.0));
+        let hook_retries = hook.clone();
+        let hook_timeout = hook.clone();
+        let attempts_row = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .push(horizontal_space(Fixed(40.0)))
+            .push(Text::new(format!("{}:", retries_translation(language))).font(font))
+            .push(number_input(hook.retries, font, move |retries| {
+                Message::UpdateNotificationSettings(
+                    Notification::Hook(NotificationHook {
+                        retries,
+                        ..hook_retries.clone()
+                    }),
+                    false,
+                )
+            }))
+            .push(horizontal_space(Fixed(10.0)))
+            .push(Text::new(format!("{}:", timeout_translation(language))).font(font))
+            .push(number_input(hook.timeout, font, move |timeout| {
+                Message::UpdateNotificationSettings(
+                    Notification::Hook(NotificationHook {
+                        timeout,
+                        ..hook_timeout.clone()
+                    }),
+                    false,
+                )
+            }));
+        ret_val = ret_val
+            .push(
+                Row::new()
+                    .push(horizontal_space(Fixed(50.0)))
+                    .push(target_input),
+            )
+            .push(attempts_row);
+    }
+
+    Column::new().padding(5).push(
+        Container::new(ret_val)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+fn get_history_file(
+    history_path: &Path,
+    language: Language,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let default_path = get_notifications_history_path();
+    let path_input = TextInput::new(
+        &default_path.to_string_lossy(),
+        &history_path.to_string_lossy(),
+    )
+    .on_input(|path| {
+        Message::UpdateNotificationSettings(Notification::HistoryPath(PathBuf::from(path)), false)
+    })
+    .padding([0, 0, 0, 10])
+    .font(font)
+    .width(Length::Fixed(400.0));
+
+    let content = Column::new()
+        .spacing(5)
+        .push(Text::new(history_file_translation(language)).font(font))
+        .push(vertical_space(Fixed(5.0)))
+        .push(
+            Row::new()
+                .push(horizontal_space(Fixed(50.0)))
+                .push(path_input),
+        );
+
+    Column::new().padding(5).push(
+        Container::new(content)
+            .padding(10)
+            .width(Fixed(700.0))
+            .style(ContainerType::BorderedRound),
+    )
+}
+
+fn throttling_row(
+    sustain: u64,
+    cooldown: u64,
+    on_change: impl Fn(u64, u64) -> Message + Copy + 'static,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    Row::new()
+        .align_items(Alignment::Center)
+        .spacing(10)
+        .push(horizontal_space(Fixed(40.0)))
+        .push(Text::new(format!("{}:", sustain_translation(language))).font(font))
+        .push(number_input(sustain, font, move |sustain| {
+            on_change(sustain, cooldown)
+        }))
+        .push(horizontal_space(Fixed(10.0)))
+        .push(Text::new(format!("{}:", cooldown_translation(language))).font(font))
+        .push(number_input(cooldown, font, move |cooldown| {
+            on_change(sustain, cooldown)
+        }))
+}
+
+fn number_input(
+    value: u64,
+    font: Font,
+    on_input: impl Fn(u64) -> Message + 'static,
+) -> TextInput<'static, Message, Renderer<StyleType>> {
+    let value_str = value.to_string();
+    TextInput::new("0", if value == 0 { "" } else { &value_str })
+        .on_input(move |new_value| {
+            if new_value.is_empty() {
+                on_input(0)
+            } else {
+                on_input(new_value.trim().parse::<u64>().unwrap_or(value))
+            }
+        })
+        .padding([0, 0, 0, 10])
+        .font(font)
+        .width(Length::Fixed(60.0))
+}
+
 fn input_group_packets(
     packets_notification: PacketsNotification,
     font: Font,
@@ -1,7 +1,9 @@
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Direction;
 use iced::widget::{button, horizontal_space, vertical_space, Rule};
-use iced::widget::{Button, Column, Container, Row, Scrollable, Space, Text};
+use iced::widget::{
+    Button, Checkbox, Column, Container, Row, Scrollable, Slider, Space, Text, TextInput,
+};
 use iced::Length::Fixed;
 use iced::{Alignment, Element, Font, Length, Renderer};
 
@@ -17,13 +19,20 @@
 };
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::custom_palette::ExtraStyles;
+use crate::gui::styles::types::custom_theme::{get_contrast_issues, CustomTheme};
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::styles::types::theme_editor::{PaletteAlpha, PaletteColor, ThemeEditor};
 use crate::gui::types::message::Message;
 use crate::translations::translations::{
     appearance_title_translation, deep_sea_translation, mon_amour_translation,
     yeti_day_translation, yeti_night_translation,
 };
 use crate::translations::translations_2::color_gradients_translation;
+use crate::translations::translations_3::{
+    bold_text_translation, browse_translation, cancel_translation, custom_themes_translation,
+    import_translation, save_translation, theme_editor_translation, theme_file_path_translation,
+    theme_name_translation,
+};
 use crate::utils::types::icon::Icon;
 use crate::StyleType::{Day, DeepSea, MonAmour, Night};
 use crate::{Language, Sniffer, StyleType};
@@ -61,6 +70,15 @@ pub fn settings_style_page(sniffer: &Sniffer) -> Container<Message, Renderer<Sty
         ))
         .push(vertical_space(Length::Fixed(15.0)));
 
+    if let Some(theme_editor) = &sniffer.theme_editor {
+        let editor_scroll = Scrollable::new(theme_editor_col(theme_editor, sniffer, font))
+            .direction(Direction::Vertical(ScrollbarType::properties()));
+        return Container::new(content.push(editor_scroll))
+            .height(Length::Fixed(400.0))
+            .width(Length::Fixed(800.0))
+            .style(ContainerType::Modal);
+    }
+
     let mut styles_col = Column::new()
         .align_items(Alignment::Center)
         .width(Length::Fill)
@@ -98,9 +116,14 @@ pub fn settings_style_page(sniffer: &Sniffer) -> Container<Message, Renderer<Sty
                 )),
         )
         .push(vertical_space(Length::Fixed(10.0)));
-    for children in get_extra_palettes(ExtraStyles::all_styles(), sniffer.style) {
+    let extra_styles = ExtraStyles::all_styles()
+        .iter()
+        .map(|&style| (style.to_string(), StyleType::Custom(style)))
+        .collect();
+    for children in get_extra_palettes(extra_styles, sniffer.style) {
         styles_col = styles_col.push(children);
     }
+    styles_col = styles_col.push(custom_themes_col(sniffer, font));
 
     let styles_scroll =
         Scrollable::new(styles_col).direction(Direction::Vertical(ScrollbarType::properties()));
@@ -249,14 +272,12 @@ fn get_palette(
 
 // Buttons for each extra style arranged in rows of two
 fn get_extra_palettes(
-    styles: &[ExtraStyles],
+    styles: Vec<(String, StyleType)>,
     current_style: StyleType,
 ) -> Vec<Element<'static, Message, Renderer<StyleType>>> {
     // Map each extra style into a palette container
-    let mut styles = styles.iter().map(|&style| {
-        let name = style.to_string();
+    let mut styles = styles.into_iter().map(|(name, style)| {
         let description = String::new();
-        let style = StyleType::Custom(style);
         get_palette_container(current_style, name, description, style)
     });
 
@@ -291,3 +312,226 @@ fn get_extra_palettes(
 
     children
 }
+
+// Themes loaded from the themes directory, and input to import a new one
+fn custom_themes_col(
+    sniffer: &Sniffer,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let language = sniffer.language;
+    let custom_styles = sniffer
+        .custom_themes
+        .iter()
+        .map(|CustomTheme { name, palette }| {
+            (
+                name.clone(),
+                StyleType::Custom(ExtraStyles::CustomToml(*palette)),
+            )
+        })
+        .collect();
+
+    let mut col = Column::new()
+        .align_items(Alignment::Center)
+        .width(Length::Fill)
+        .push(
+            Text::new(custom_themes_translation(language))
+                .style(TextType::Subtitle)
+                .font(font),
+        )
+        .push(vertical_space(Length::Fixed(10.0)));
+    for children in get_extra_palettes(custom_styles, sniffer.style) {
+        col = col.push(children);
+    }
+    for error in &sniffer.custom_themes_errors {
+        col = col.push(Text::new(error.clone()).style(TextType::Danger).font(font));
+    }
+
+    let mut import_button = button(
+        Text::new(import_translation(language))
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15]);
+    if !sniffer.custom_theme_path.trim().is_empty() {
+        import_button = import_button.on_press(Message::ImportCustomTheme);
+    }
+    col.push(vertical_space(Length::Fixed(10.0)))
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(
+                        theme_file_path_translation(language),
+                        &sniffer.custom_theme_path,
+                    )
+                    .on_input(Message::UpdateCustomThemePath)
+                    .on_submit(Message::ImportCustomTheme)
+                    .padding([0, 0, 0, 10])
+                    .font(font)
+                    .width(Length::Fixed(500.0)),
+                )
+                .push(
+                    button(
+                        Text::new(browse_translation(language))
+                            .font(font)
+                            .vertical_alignment(Vertical::Center)
+                            .horizontal_alignment(Horizontal::Center),
+                    )
+                    .padding([5, 15])
+                    .on_press(Message::BrowseCustomTheme),
+                )
+                .push(import_button),
+        )
+        .push(vertical_space(Length::Fixed(10.0)))
+        .push(
+            button(
+                Text::new(theme_editor_translation(language))
+                    .font(font)
+                    .vertical_alignment(Vertical::Center)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .padding([5, 15])
+            .on_press(Message::OpenThemeEditor),
+        )
+        .push(vertical_space(Length::Fixed(10.0)))
+}
+
+// Editor of a new custom style, whose changes are previewed in the whole app
+fn theme_editor_col(
+    theme_editor: &ThemeEditor,
+    sniffer: &Sniffer,
+    font: Font,
+) -> Column<'static, Message, Renderer<StyleType>> {
+    let language = sniffer.language;
+
+    let editor = theme_editor.clone();
+    let mut col = Column::new()
+        .align_items(Alignment::Center)
+        .width(Length::Fill)
+        .spacing(10)
+        .push(
+            Text::new(theme_editor_translation(language))
+                .style(TextType::Subtitle)
+                .font(font),
+        )
+        .push(
+            TextInput::new(theme_name_translation(language), &theme_editor.name)
+                .on_input(move |name| Message::UpdateThemeEditor(editor.with_name(name)))
+                .padding([0, 0, 0, 10])
+                .font(font)
+                .width(Length::Fixed(300.0)),
+        )
+        .push(get_palette(theme_editor.style(), true));
+
+    for field in PaletteColor::ALL {
+        col = col.push(palette_color_row(theme_editor, field, font));
+    }
+    for field in PaletteAlpha::ALL {
+        let editor = theme_editor.clone();
+        let percentage = (field.get(&theme_editor.palette) * 100.0).round() as u8;
+        col = col.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(field.field_name()).font(font).width(Fixed(180.0)))
+                .push(
+                    Slider::new(0..=100, percentage, move |value| {
+                        Message::UpdateThemeEditor(
+                            editor.with_alpha(field, f32::from(value) / 100.0),
+                        )
+                    })
+                    .width(Fixed(300.0)),
+                )
+                .push(Text::new(format!("{percentage:^3}%")).font(font)),
+        );
+    }
+    let editor = theme_editor.clone();
+    col = col.push(
+        Checkbox::new(
+            bold_text_translation(language),
+            theme_editor.style().has_bold_text(),
+            move |toggled| Message::UpdateThemeEditor(editor.with_bold_text(toggled)),
+        )
+        .size(18)
+        .font(font),
+    );
+
+    let issues = get_contrast_issues(&theme_editor.palette);
+    for error in issues.iter().chain(&sniffer.custom_themes_errors) {
+        col = col.push(Text::new(error.clone()).style(TextType::Danger).font(font));
+    }
+
+    let mut save_button = button(
+        Text::new(save_translation(language))
+            .font(font)
+            .vertical_alignment(Vertical::Center)
+            .horizontal_alignment(Horizontal::Center),
+    )
+    .padding([5, 15]);
+    if issues.is_empty() && !theme_editor.name.trim().is_empty() {
+        save_button = save_button.on_press(Message::SaveThemeEditor);
+    }
+    col.push(
+        Row::new()
+            .spacing(10)
+            .push(
+                button(
+                    Text::new(cancel_translation(language))
+                        .font(font)
+                        .vertical_alignment(Vertical::Center)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .padding([5, 15])
+                .on_press(Message::CloseThemeEditor),
+            )
+            .push(save_button),
+    )
+    .push(vertical_space(Length::Fixed(10.0)))
+}
+
+// Swatch, hexadecimal input and RGB sliders of a color of the edited style
+fn palette_color_row(
+    theme_editor: &ThemeEditor,
+    field: PaletteColor,
+    font: Font,
+) -> Row<'static, Message, Renderer<StyleType>> {
+    let color = field.get(&theme_editor.palette);
+    let editor = theme_editor.clone();
+    let mut row = Row::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(Text::new(field.field_name()).font(font).width(Fixed(120.0)))
+        .push(
+            Container::new(Rule::horizontal(20).style(RuleType::PaletteColor(color)))
+                .width(Fixed(40.0))
+                .height(Fixed(20.0))
+                .style(ContainerType::Palette),
+        )
+        .push(
+            TextInput::new("#rrggbb", &theme_editor.hex_value(field))
+                .on_input(move |hex| Message::UpdateThemeEditor(editor.with_hex_input(field, hex)))
+                .padding([0, 0, 0, 10])
+                .font(font)
+                .width(Fixed(110.0)),
+        );
+    for channel in 0..3 {
+        let editor = theme_editor.clone();
+        let value = [color.r, color.g, color.b][channel];
+        row = row.push(
+            Slider::new(0..=255, (value * 255.0).round() as u8, move |value| {
+                let mut new_color = color;
+                let new_value = f32::from(value) / 255.0;
+                match channel {
+                    0 => new_color.r = new_value,
+                    1 => new_color.g = new_value,
+                    _ => new_color.b = new_value,
+                }
+                Message::UpdateThemeEditor(editor.with_color(field, new_color))
+            })
+            .width(Fixed(90.0)),
+        );
+    }
+    row
+}
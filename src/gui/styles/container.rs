@@ -10,6 +10,7 @@
     BORDER_ROUNDED_RADIUS, BORDER_WIDTH,
 };
 use crate::gui::styles::types::gradient_type::{get_gradient_headers, GradientType};
+use crate::gui::styles::types::tag_color::TagColor;
 use crate::{get_colors, StyleType};
 
 #[derive(Clone, Copy, Default)]
@@ -23,6 +24,7 @@ pub enum ContainerType {
     Neutral,
     Gradient(GradientType),
     Modal,
+    Tag(TagColor),
 }
 
 impl iced::widget::container::StyleSheet for StyleType {
@@ -54,6 +56,10 @@ fn appearance(&self, style: &Self::Style) -> Appearance {
                 ),
                 ContainerType::Modal => Background::Color(colors.primary),
                 ContainerType::Standard => Background::Color(Color::TRANSPARENT),
+                ContainerType::Tag(tag_color) => Background::Color(Color {
+                    a: 0.3,
+                    ..tag_color.to_color()
+                }),
             }),
             border_radius: match style {
                 ContainerType::BorderedRound => BORDER_ROUNDED_RADIUS.into(),
@@ -61,7 +67,7 @@ fn appearance(&self, style: &Self::Style) -> Appearance {
                     [0.0, 0.0, BORDER_ROUNDED_RADIUS, BORDER_ROUNDED_RADIUS].into()
                 }
                 ContainerType::Tooltip => 7.0.into(),
-                ContainerType::Badge => 100.0.into(),
+                ContainerType::Badge | ContainerType::Tag(_) => 100.0.into(),
                 _ => 0.0.into(),
             },
             border_width: match style {
@@ -75,6 +81,7 @@ fn appearance(&self, style: &Self::Style) -> Appearance {
             },
             border_color: match style {
                 ContainerType::Palette => Color::BLACK,
+                ContainerType::Tag(tag_color) => tag_color.to_color(),
                 _ => Color {
                     a: get_alpha_round_borders(*self),
                     ..colors.buttons
@@ -17,6 +17,7 @@ pub enum RuleType {
     PaletteSecondary(StyleType),
     PaletteOutgoing(StyleType),
     PaletteButtons(StyleType),
+    PaletteColor(Color),
     Incoming,
     Outgoing,
 }
@@ -34,6 +35,7 @@ fn appearance(&self, style: &Self::Style) -> iced::widget::rule::Appearance {
                 RuleType::PaletteSecondary(style) => get_colors(*style).secondary,
                 RuleType::PaletteOutgoing(style) => get_colors(*style).outgoing,
                 RuleType::PaletteButtons(style) => get_colors(*style).buttons,
+                RuleType::PaletteColor(color) => *color,
                 RuleType::Standard => Color {
                     a: get_alpha_round_borders(*self),
                     ..colors.buttons
@@ -48,6 +50,7 @@ fn appearance(&self, style: &Self::Style) -> iced::widget::rule::Appearance {
                     StyleType::Custom(_) => 25,
                     _ => 40,
                 },
+                RuleType::PaletteColor(_) => 20,
                 RuleType::Standard => 3,
             },
             radius: 0.0.into(),
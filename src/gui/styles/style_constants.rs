@@ -145,26 +145,26 @@
 };
 
 pub fn get_font(style: StyleType) -> Font {
-    if style.is_nightly() {
-        SARASA_MONO
-    } else {
+    if style.has_bold_text() {
         SARASA_MONO_BOLD
+    } else {
+        SARASA_MONO
     }
 }
 
 pub fn get_font_weight(style: StyleType) -> FontStyle {
-    if style.is_nightly() {
-        FontStyle::Normal
-    } else {
+    if style.has_bold_text() {
         FontStyle::Bold
+    } else {
+        FontStyle::Normal
     }
 }
 
 pub fn get_font_headers(style: StyleType) -> Font {
-    if style.is_nightly() {
-        SARASA_MONO_BOLD
-    } else {
+    if style.has_bold_text() {
         SARASA_MONO
+    } else {
+        SARASA_MONO_BOLD
     }
 }
 
@@ -0,0 +1,57 @@
+//! Module used to (de)serialize colors as hexadecimal strings (`#rrggbb` or `#rrggbbaa`),
+//! as they are written in custom themes files.
+
+use iced::Color;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&to_hex(*color))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    from_hex(&hex).ok_or_else(|| D::Error::custom(format!("invalid color '{hex}'")))
+}
+
+/// Returns the hexadecimal representation of a color; alpha is omitted if the color is opaque
+pub fn to_hex(color: Color) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let [r, g, b, a] = [color.r, color.g, color.b, color.a].map(|c| (c * 255.0).round() as u8);
+    if a == u8::MAX {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Parses a color in the form `#rrggbb` or `#rrggbbaa`
+pub fn from_hex(hex: &str) -> Option<Color> {
+    let digits = hex.trim().strip_prefix('#')?;
+    if !(digits.len() == 6 || digits.len() == 8) || !digits.is_ascii() {
+        return None;
+    }
+    let mut components = [u8::MAX; 4];
+    for (i, component) in components.iter_mut().enumerate().take(digits.len() / 2) {
+        *component = u8::from_str_radix(&digits[2 * i..2 * i + 2], 16).ok()?;
+    }
+    let [r, g, b, a] = components;
+    Some(Color::from_rgba8(r, g, b, f32::from(a) / 255.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_colors() {
+        assert_eq!(from_hex("#ffffff"), Some(Color::WHITE));
+        assert_eq!(from_hex(" #000000ff "), Some(Color::BLACK));
+        assert_eq!(to_hex(Color::from_rgb8(0x28, 0x2a, 0x36)), "#282a36");
+        let translucent = from_hex("#F1FA8Cb3").unwrap();
+        assert_eq!(to_hex(translucent), "#f1fa8cb3");
+        for invalid in ["ffffff", "#fff", "#gggggg", "#ffffff0", "#ààà"] {
+            assert_eq!(from_hex(invalid), None);
+        }
+    }
+}
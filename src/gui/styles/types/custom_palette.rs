@@ -1,12 +1,19 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use iced::Color;
 use serde::{Deserialize, Serialize};
 
 use crate::gui::styles::custom_themes::{dracula, gruvbox, nord, solarized};
+use crate::gui::styles::style_constants::{
+    get_alpha_chart_badge, get_alpha_round_borders, get_alpha_round_containers, get_starred_color,
+};
+use crate::gui::styles::types::color_hex;
 use crate::gui::styles::types::palette::Palette;
+use crate::{get_colors, StyleType};
 
 /// Custom style with any relevant metadata
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CustomPalette {
     /// Color scheme's palette
     pub(crate) palette: Palette,
@@ -15,8 +22,10 @@ pub struct CustomPalette {
 }
 
 /// Extension color for themes.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PaletteExtension {
     /// Color of favorites star
+    #[serde(with = "color_hex")]
     pub starred: Color,
     /// Badge/logo alpha
     pub chart_badge_alpha: f32,
@@ -24,6 +33,77 @@ pub struct PaletteExtension {
     pub round_borders_alpha: f32,
     /// Round containers alpha
     pub round_containers_alpha: f32,
+    /// Whether texts are bold; if not specified, only day styles use bold texts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bold_text: Option<bool>,
+}
+
+impl CustomPalette {
+    /// Colors of the given style, to be used as a starting point for a new custom style
+    pub fn from_style(style: StyleType) -> Self {
+        CustomPalette {
+            palette: get_colors(style),
+            extension: PaletteExtension {
+                starred: get_starred_color(style),
+                chart_badge_alpha: get_alpha_chart_badge(style),
+                round_borders_alpha: get_alpha_round_borders(style),
+                round_containers_alpha: get_alpha_round_containers(style),
+                bold_text: Some(style.has_bold_text()),
+            },
+        }
+    }
+
+    /// Theme is a night/dark style, i.e., its background is dark
+    pub fn is_nightly(&self) -> bool {
+        relative_luminance(self.palette.primary) < 0.5
+    }
+}
+
+impl Hash for CustomPalette {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let palette = &self.palette;
+        let extension = &self.extension;
+        for color in [
+            palette.primary,
+            palette.secondary,
+            palette.outgoing,
+            palette.buttons,
+            palette.text_headers,
+            palette.text_body,
+            extension.starred,
+        ] {
+            for component in [color.r, color.g, color.b, color.a] {
+                component.to_bits().hash(state);
+            }
+        }
+        for alpha in [
+            extension.chart_badge_alpha,
+            extension.round_borders_alpha,
+            extension.round_containers_alpha,
+        ] {
+            alpha.to_bits().hash(state);
+        }
+        extension.bold_text.hash(state);
+    }
+}
+
+/// Relative luminance of a color, as defined by WCAG 2
+pub fn relative_luminance(color: Color) -> f32 {
+    let linear = |c: f32| {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+/// Contrast ratio between two colors, as defined by WCAG 2 (from 1 to 21)
+pub fn contrast_ratio(color_1: Color, color_2: Color) -> f32 {
+    let l1 = relative_luminance(color_1);
+    let l2 = relative_luminance(color_2);
+    (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
 }
 
 /// Built in extra styles
@@ -38,6 +118,8 @@ pub enum ExtraStyles {
     NordLight,
     SolarizedDark,
     SolarizedLight,
+    /// Style loaded from a TOML file
+    CustomToml(CustomPalette),
 }
 
 impl ExtraStyles {
@@ -52,6 +134,7 @@ pub fn to_palette(self) -> Palette {
             ExtraStyles::NordDark => nord::nord_dark().palette,
             ExtraStyles::SolarizedDark => solarized::solarized_dark().palette,
             ExtraStyles::SolarizedLight => solarized::solarized_light().palette,
+            ExtraStyles::CustomToml(custom_palette) => custom_palette.palette,
         }
     }
 
@@ -66,11 +149,12 @@ pub fn to_ext(self) -> PaletteExtension {
             ExtraStyles::NordDark => nord::nord_dark().extension,
             ExtraStyles::SolarizedDark => solarized::solarized_dark().extension,
             ExtraStyles::SolarizedLight => solarized::solarized_light().extension,
+            ExtraStyles::CustomToml(custom_palette) => custom_palette.extension,
         }
     }
 
     /// Theme is a night/dark style
-    pub const fn is_nightly(self) -> bool {
+    pub fn is_nightly(self) -> bool {
         match self {
             ExtraStyles::DraculaDark
             | ExtraStyles::GruvboxDark
@@ -80,6 +164,7 @@ pub const fn is_nightly(self) -> bool {
             | ExtraStyles::GruvboxLight
             | ExtraStyles::NordLight
             | ExtraStyles::SolarizedLight => false,
+            ExtraStyles::CustomToml(custom_palette) => custom_palette.is_nightly(),
         }
     }
 
@@ -109,6 +194,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ExtraStyles::NordDark => write!(f, "Nord (Night)"),
             ExtraStyles::SolarizedLight => write!(f, "Solarized (Day)"),
             ExtraStyles::SolarizedDark => write!(f, "Solarized (Night)"),
+            ExtraStyles::CustomToml(_) => write!(f, "Custom"),
         }
     }
 }
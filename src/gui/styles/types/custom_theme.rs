@@ -0,0 +1,332 @@
+//! Module defining the `CustomTheme` struct, representing a style loaded from a TOML file.
+//!
+//! A theme file defines a [`Palette`] and a [`PaletteExtension`], for example:
+//!
+//! ```toml
+//! name = "Midnight"
+//!
+//! [palette]
+//! primary = "#1e1e2e"
+//! secondary = "#f5c2e7"
+//! outgoing = "#89dceb"
+//! buttons = "#45475a"
+//! text_headers = "#1e1e2e"
+//! text_body = "#cdd6f4"
+//!
+//! [extension]
+//! starred = "#f9e2afb3"
+//! chart_badge_alpha = 0.15
+//! round_borders_alpha = 0.1
+//! round_containers_alpha = 0.04
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::gui::styles::types::custom_palette::{contrast_ratio, CustomPalette, PaletteExtension};
+use crate::gui::styles::types::palette::Palette;
+
+/// Minimum contrast ratio required between texts and their background
+pub const MIN_CONTRAST_RATIO: f32 = 2.5;
+
+/// Style loaded from a TOML file
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomTheme {
+    /// Name of the theme (the file name, if not specified in the file itself)
+    pub name: String,
+    /// Colors of the theme
+    pub palette: CustomPalette,
+}
+
+/// Content of a theme file
+#[derive(Serialize, Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    palette: Palette,
+    extension: PaletteExtension,
+}
+
+impl CustomTheme {
+    /// Reads and validates the theme defined in the given TOML file
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let file_stem = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        Self::from_toml(&content, file_stem)
+    }
+
+    /// Parses and validates a theme, using `default_name` if the theme doesn't specify one
+    pub fn from_toml(content: &str, default_name: String) -> Result<Self, String> {
+        let theme_file: ThemeFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        let palette = CustomPalette {
+            palette: theme_file.palette,
+            extension: theme_file.extension,
+        };
+        check_contrast(&palette)?;
+        for alpha in [
+            palette.extension.chart_badge_alpha,
+            palette.extension.round_borders_alpha,
+            palette.extension.round_containers_alpha,
+        ] {
+            if !(0.0..=1.0).contains(&alpha) {
+                return Err(format!(
+                    "alpha values must be between 0 and 1 (found {alpha})"
+                ));
+            }
+        }
+        Ok(Self {
+            name: theme_file
+                .name
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(default_name),
+            palette,
+        })
+    }
+
+    /// Writes the theme to a TOML file in the given directory, named after the theme.
+    ///
+    /// Returns the path of the written file
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, String> {
+        check_contrast(&self.palette)?;
+        let file_name: String = self
+            .name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        if file_name.is_empty() {
+            return Err("the theme name cannot be empty".to_string());
+        }
+        let theme_file = ThemeFile {
+            name: Some(self.name.trim().to_string()),
+            palette: self.palette.palette,
+            extension: self.palette.extension,
+        };
+        let content = toml::to_string(&theme_file).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let path = dir.join(format!("{file_name}.toml"));
+        std::fs::write(&path, content).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+}
+
+/// Checks that texts are readable on top of their backgrounds
+fn check_contrast(custom_palette: &CustomPalette) -> Result<(), String> {
+    match get_contrast_issues(custom_palette).into_iter().next() {
+        Some(issue) => Err(issue),
+        None => Ok(()),
+    }
+}
+
+/// Returns a description of each pair of text and background colors with insufficient contrast
+pub fn get_contrast_issues(custom_palette: &CustomPalette) -> Vec<String> {
+    let palette = &custom_palette.palette;
+    let mut issues = Vec::new();
+    for (text, text_name, background, background_name) in [
+        (palette.text_body, "text_body", palette.primary, "primary"),
+        (palette.text_body, "text_body", palette.buttons, "buttons"),
+        (
+            palette.text_headers,
+            "text_headers",
+            palette.secondary,
+            "secondary",
+        ),
+    ] {
+        let ratio = contrast_ratio(text, background);
+        if ratio < MIN_CONTRAST_RATIO {
+            issues.push(format!(
+                "insufficient contrast between '{text_name}' and '{background_name}' \
+                ({ratio:.1}:1, at least {MIN_CONTRAST_RATIO}:1 is required)"
+            ));
+        }
+    }
+    issues
+}
+
+/// Loads all the themes contained in the given directory, sorted by name.
+///
+/// Returns the valid themes, and a description of the errors for the invalid ones
+pub fn load_custom_themes(dir: &Path) -> (Vec<CustomTheme>, Vec<String>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (themes, errors);
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            match CustomTheme::from_file(&path) {
+                Ok(theme) => themes.push(theme),
+                Err(e) => errors.push(format!(
+                    "{}: {e}",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                )),
+            }
+        }
+    }
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    errors.sort();
+    (themes, errors)
+}
+
+/// Validates the theme file at the given path and copies it into the themes directory
+pub fn import_custom_theme(path: &Path, dir: &Path) -> Result<CustomTheme, String> {
+    let theme = CustomTheme::from_file(path)?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| "invalid file name".to_string())?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let destination = dir.join(file_name).with_extension("toml");
+    if destination.ne(path) {
+        std::fs::copy(path, destination).map_err(|e| e.to_string())?;
+    }
+    Ok(theme)
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::Color;
+
+    use crate::gui::styles::types::custom_palette::ExtraStyles;
+    use crate::{get_colors, StyleType};
+
+    use super::*;
+
+    const THEME: &str = r##"
+        name = "Midnight"
+
+        [palette]
+        primary = "#1e1e2e"
+        secondary = "#f5c2e7"
+        outgoing = "#89dceb"
+        buttons = "#45475a"
+        text_headers = "#1e1e2e"
+        text_body = "#cdd6f4"
+
+        [extension]
+        starred = "#f9e2afb3"
+        chart_badge_alpha = 0.15
+        round_borders_alpha = 0.1
+        round_containers_alpha = 0.04
+    "##;
+
+    #[test]
+    fn test_parse_theme() {
+        let theme = CustomTheme::from_toml(THEME, "file".to_string()).unwrap();
+        assert_eq!(theme.name, "Midnight");
+        assert_eq!(
+            theme.palette.palette.primary,
+            Color::from_rgb8(0x1e, 0x1e, 0x2e)
+        );
+        assert!((theme.palette.extension.starred.a - 0.7).abs() < 0.01);
+        assert!(theme.palette.is_nightly());
+
+        let unnamed = THEME.replace("name = \"Midnight\"", "");
+        let theme = CustomTheme::from_toml(&unnamed, "file".to_string()).unwrap();
+        assert_eq!(theme.name, "file");
+    }
+
+    #[test]
+    fn test_invalid_themes() {
+        // missing color
+        let missing = THEME.replace("buttons = \"#45475a\"", "");
+        assert!(CustomTheme::from_toml(&missing, String::new()).is_err());
+        // invalid color
+        let invalid = THEME.replace("#45475a", "grey");
+        assert!(CustomTheme::from_toml(&invalid, String::new())
+            .unwrap_err()
+            .contains("invalid color 'grey'"));
+        // unreadable text
+        let low_contrast = THEME.replace("#cdd6f4", "#2e2e3e");
+        assert!(CustomTheme::from_toml(&low_contrast, String::new())
+            .unwrap_err()
+            .contains("insufficient contrast between 'text_body' and 'primary'"));
+        // invalid alpha
+        let invalid_alpha = THEME.replace("0.15", "1.5");
+        assert!(CustomTheme::from_toml(&invalid_alpha, String::new()).is_err());
+    }
+
+    #[test]
+    fn test_built_in_themes_have_enough_contrast() {
+        let styles = [
+            StyleType::Night,
+            StyleType::Day,
+            StyleType::DeepSea,
+            StyleType::MonAmour,
+        ]
+        .into_iter()
+        .chain(
+            ExtraStyles::all_styles()
+                .iter()
+                .map(|s| StyleType::Custom(*s)),
+        );
+        for style in styles {
+            let palette = CustomPalette {
+                palette: get_colors(style),
+                extension: ExtraStyles::DraculaDark.to_ext(),
+            };
+            assert_eq!(check_contrast(&palette), Ok(()), "{style:?}");
+        }
+    }
+
+    #[test]
+    fn test_custom_style_is_persisted() {
+        #[derive(serde::Serialize, Deserialize)]
+        struct Config {
+            style: StyleType,
+        }
+        let theme = CustomTheme::from_toml(THEME, String::new()).unwrap();
+        let config = Config {
+            style: StyleType::Custom(ExtraStyles::CustomToml(theme.palette)),
+        };
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.style, config.style);
+    }
+
+    #[test]
+    fn test_load_and_import_themes() {
+        let dir = std::env::temp_dir().join("sniffnet_test_custom_themes");
+        let _ = std::fs::remove_dir_all(&dir);
+        let source = std::env::temp_dir().join("sniffnet_test_theme_midnight.toml");
+        std::fs::write(&source, THEME).unwrap();
+        let imported = import_custom_theme(&source, &dir).unwrap();
+        std::fs::write(dir.join("broken.toml"), "name = 1").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a theme").unwrap();
+
+        let (themes, errors) = load_custom_themes(&dir);
+        assert_eq!(themes, vec![imported]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("broken.toml: "));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_save_theme() {
+        let dir = std::env::temp_dir().join("sniffnet_test_saved_themes");
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut theme = CustomTheme {
+            name: " My Day/Theme ".to_string(),
+            palette: CustomPalette::from_style(StyleType::Custom(ExtraStyles::NordLight)),
+        };
+        assert_eq!(theme.palette.extension.bold_text, Some(true));
+        let path = theme.save(&dir).unwrap();
+        assert_eq!(path, dir.join("my_day_theme.toml"));
+        theme.name = "My Day/Theme".to_string();
+        assert_eq!(CustomTheme::from_file(&path), Ok(theme.clone()));
+
+        theme.palette.palette.text_body = theme.palette.palette.primary;
+        assert_eq!(get_contrast_issues(&theme.palette).len(), 2);
+        assert!(theme.save(&dir).is_err());
+        theme.name = " ".to_string();
+        assert!(theme.save(&dir).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
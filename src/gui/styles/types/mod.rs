@@ -1,4 +1,8 @@
+pub mod color_hex;
 pub mod custom_palette;
+pub mod custom_theme;
 pub mod gradient_type;
 pub mod palette;
 pub mod style_type;
+pub mod tag_color;
+pub mod theme_editor;
@@ -2,10 +2,12 @@
 
 use iced::Color;
 use plotters::style::RGBColor;
+use serde::{Deserialize, Serialize};
 
 use crate::gui::styles::style_constants::{
     DAY_STYLE, DEEP_SEA_STYLE, MON_AMOUR_STYLE, NIGHT_STYLE,
 };
+use crate::gui::styles::types::color_hex;
 use crate::StyleType;
 
 /// Set of colors to apply to GUI
@@ -16,18 +18,25 @@
 /// - `secondary` and `outgoing` should be complementary colors if possible
 /// - `text_headers` should be black or white and must have a strong contrast with `secondary`
 /// - `text_body` should be black or white and must have a strong contrast with `primary`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Palette {
     /// Main color of the GUI (background, hovered buttons, active tab)
+    #[serde(with = "color_hex")]
     pub primary: Color,
     /// Secondary color of the GUI (incoming connections, header, footer, buttons' borders, radio selection)
+    #[serde(with = "color_hex")]
     pub secondary: Color,
     /// Color of outgoing connections
+    #[serde(with = "color_hex")]
     pub outgoing: Color,
     /// Color of active buttons (when not hovered) and inactive tabs
+    #[serde(with = "color_hex")]
     pub buttons: Color,
     /// Color of header and footer text
+    #[serde(with = "color_hex")]
     pub text_headers: Color,
     /// Color of body and buttons text
+    #[serde(with = "color_hex")]
     pub text_body: Color,
 }
 
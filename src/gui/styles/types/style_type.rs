@@ -42,4 +42,12 @@ pub fn is_nightly(self) -> bool {
             StyleType::Custom(style) => style.is_nightly(),
         }
     }
+
+    /// Whether texts are displayed in bold
+    pub fn has_bold_text(self) -> bool {
+        match self {
+            StyleType::Custom(style) => style.to_ext().bold_text.unwrap_or(!style.is_nightly()),
+            _ => !self.is_nightly(),
+        }
+    }
 }
@@ -0,0 +1,55 @@
+//! Module defining the `TagColor` enum, representing the colors available for the tags of the labels.
+
+use iced::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::translations::translations_3::tag_color_translation;
+use crate::Language;
+
+/// Color of a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum TagColor {
+    #[default]
+    Gray,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl TagColor {
+    pub(crate) const ALL: [TagColor; 7] = [
+        TagColor::Gray,
+        TagColor::Red,
+        TagColor::Orange,
+        TagColor::Yellow,
+        TagColor::Green,
+        TagColor::Blue,
+        TagColor::Purple,
+    ];
+
+    pub fn to_color(self) -> Color {
+        match self {
+            TagColor::Gray => Color::from_rgb8(128, 128, 128),
+            TagColor::Red => Color::from_rgb8(220, 60, 60),
+            TagColor::Orange => Color::from_rgb8(235, 140, 30),
+            TagColor::Yellow => Color::from_rgb8(220, 190, 20),
+            TagColor::Green => Color::from_rgb8(60, 170, 80),
+            TagColor::Blue => Color::from_rgb8(50, 120, 220),
+            TagColor::Purple => Color::from_rgb8(150, 80, 200),
+        }
+    }
+
+    pub fn get_picklist_label(self, language: Language) -> &'static str {
+        tag_color_translation(self, language)
+    }
+
+    pub fn all_strings(language: Language) -> Vec<&'static str> {
+        TagColor::ALL
+            .iter()
+            .map(|color| color.get_picklist_label(language))
+            .collect()
+    }
+}
@@ -0,0 +1,233 @@
+//! Module defining the `ThemeEditor` struct, which holds the custom style being composed
+//! in the style settings.
+
+use iced::Color;
+
+use crate::gui::styles::types::color_hex::{from_hex, to_hex};
+use crate::gui::styles::types::custom_palette::{CustomPalette, ExtraStyles};
+use crate::gui::styles::types::custom_theme::CustomTheme;
+use crate::StyleType;
+
+/// Color of a custom style that can be edited
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteColor {
+    Primary,
+    Secondary,
+    Outgoing,
+    Buttons,
+    TextHeaders,
+    TextBody,
+    Starred,
+}
+
+impl PaletteColor {
+    pub const ALL: [PaletteColor; 7] = [
+        PaletteColor::Primary,
+        PaletteColor::Secondary,
+        PaletteColor::Outgoing,
+        PaletteColor::Buttons,
+        PaletteColor::TextHeaders,
+        PaletteColor::TextBody,
+        PaletteColor::Starred,
+    ];
+
+    pub fn get(self, custom_palette: &CustomPalette) -> Color {
+        let palette = &custom_palette.palette;
+        match self {
+            PaletteColor::Primary => palette.primary,
+            PaletteColor::Secondary => palette.secondary,
+            PaletteColor::Outgoing => palette.outgoing,
+            PaletteColor::Buttons => palette.buttons,
+            PaletteColor::TextHeaders => palette.text_headers,
+            PaletteColor::TextBody => palette.text_body,
+            PaletteColor::Starred => custom_palette.extension.starred,
+        }
+    }
+
+    fn set(self, custom_palette: &mut CustomPalette, color: Color) {
+        let palette = &mut custom_palette.palette;
+        match self {
+            PaletteColor::Primary => palette.primary = color,
+            PaletteColor::Secondary => palette.secondary = color,
+            PaletteColor::Outgoing => palette.outgoing = color,
+            PaletteColor::Buttons => palette.buttons = color,
+            PaletteColor::TextHeaders => palette.text_headers = color,
+            PaletteColor::TextBody => palette.text_body = color,
+            PaletteColor::Starred => custom_palette.extension.starred = color,
+        }
+    }
+
+    /// Name of the color, as written in theme files
+    pub fn field_name(self) -> &'static str {
+        match self {
+            PaletteColor::Primary => "primary",
+            PaletteColor::Secondary => "secondary",
+            PaletteColor::Outgoing => "outgoing",
+            PaletteColor::Buttons => "buttons",
+            PaletteColor::TextHeaders => "text_headers",
+            PaletteColor::TextBody => "text_body",
+            PaletteColor::Starred => "starred",
+        }
+    }
+}
+
+/// Transparency value of a custom style that can be edited
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteAlpha {
+    ChartBadge,
+    RoundBorders,
+    RoundContainers,
+}
+
+impl PaletteAlpha {
+    pub const ALL: [PaletteAlpha; 3] = [
+        PaletteAlpha::ChartBadge,
+        PaletteAlpha::RoundBorders,
+        PaletteAlpha::RoundContainers,
+    ];
+
+    pub fn get(self, custom_palette: &CustomPalette) -> f32 {
+        let extension = &custom_palette.extension;
+        match self {
+            PaletteAlpha::ChartBadge => extension.chart_badge_alpha,
+            PaletteAlpha::RoundBorders => extension.round_borders_alpha,
+            PaletteAlpha::RoundContainers => extension.round_containers_alpha,
+        }
+    }
+
+    fn set(self, custom_palette: &mut CustomPalette, alpha: f32) {
+        let extension = &mut custom_palette.extension;
+        match self {
+            PaletteAlpha::ChartBadge => extension.chart_badge_alpha = alpha,
+            PaletteAlpha::RoundBorders => extension.round_borders_alpha = alpha,
+            PaletteAlpha::RoundContainers => extension.round_containers_alpha = alpha,
+        }
+    }
+
+    /// Name of the value, as written in theme files
+    pub fn field_name(self) -> &'static str {
+        match self {
+            PaletteAlpha::ChartBadge => "chart_badge_alpha",
+            PaletteAlpha::RoundBorders => "round_borders_alpha",
+            PaletteAlpha::RoundContainers => "round_containers_alpha",
+        }
+    }
+}
+
+/// Custom style being composed in the theme editor
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeEditor {
+    /// Name of the style
+    pub name: String,
+    /// Colors of the style
+    pub palette: CustomPalette,
+    /// Hexadecimal value being typed for a color, which may not be valid yet
+    pub hex_input: Option<(PaletteColor, String)>,
+    /// Style in use before opening the editor, restored if the editing is cancelled
+    pub previous_style: StyleType,
+}
+
+impl ThemeEditor {
+    /// Starts editing a new style, based on the colors of the given one
+    pub fn new(style: StyleType) -> Self {
+        Self {
+            name: String::new(),
+            palette: CustomPalette::from_style(style),
+            hex_input: None,
+            previous_style: style,
+        }
+    }
+
+    /// Style resulting from the current values, used as live preview
+    pub fn style(&self) -> StyleType {
+        StyleType::Custom(ExtraStyles::CustomToml(self.palette))
+    }
+
+    pub fn to_theme(&self) -> CustomTheme {
+        CustomTheme {
+            name: self.name.trim().to_string(),
+            palette: self.palette,
+        }
+    }
+
+    pub fn with_color(&self, field: PaletteColor, color: Color) -> Self {
+        let mut editor = self.clone();
+        field.set(&mut editor.palette, color);
+        editor.hex_input = None;
+        editor
+    }
+
+    /// Updates the hexadecimal value typed for a color, applying it if valid
+    pub fn with_hex_input(&self, field: PaletteColor, hex: String) -> Self {
+        let mut editor = self.clone();
+        if let Some(color) = from_hex(&hex) {
+            field.set(&mut editor.palette, color);
+        }
+        editor.hex_input = Some((field, hex));
+        editor
+    }
+
+    /// Hexadecimal value to display for a color
+    pub fn hex_value(&self, field: PaletteColor) -> String {
+        match &self.hex_input {
+            Some((input_field, hex)) if input_field.eq(&field) => hex.clone(),
+            _ => to_hex(field.get(&self.palette)),
+        }
+    }
+
+    pub fn with_alpha(&self, field: PaletteAlpha, alpha: f32) -> Self {
+        let mut editor = self.clone();
+        field.set(&mut editor.palette, alpha.clamp(0.0, 1.0));
+        editor
+    }
+
+    pub fn with_bold_text(&self, bold_text: bool) -> Self {
+        let mut editor = self.clone();
+        editor.palette.extension.bold_text = Some(bold_text);
+        editor
+    }
+
+    pub fn with_name(&self, name: String) -> Self {
+        Self {
+            name,
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_theme() {
+        let editor = ThemeEditor::new(StyleType::Night);
+        assert_eq!(editor.previous_style, StyleType::Night);
+        assert!(!editor.style().has_bold_text());
+
+        let editor = editor
+            .with_color(PaletteColor::Primary, Color::from_rgb8(0x10, 0x20, 0x30))
+            .with_alpha(PaletteAlpha::ChartBadge, 1.5)
+            .with_bold_text(true)
+            .with_name("Test".to_string());
+        assert_eq!(editor.hex_value(PaletteColor::Primary), "#102030");
+        assert_eq!(PaletteAlpha::ChartBadge.get(&editor.palette), 1.0);
+        assert!(editor.style().has_bold_text());
+        assert_eq!(editor.to_theme().name, "Test");
+
+        // an incomplete value is displayed but not applied
+        let editor = editor.with_hex_input(PaletteColor::Outgoing, "#ff00".to_string());
+        assert_eq!(editor.hex_value(PaletteColor::Outgoing), "#ff00");
+        assert_ne!(
+            PaletteColor::Outgoing.get(&editor.palette),
+            Color::from_rgb8(0xff, 0x00, 0xff)
+        );
+        let editor = editor.with_hex_input(PaletteColor::Outgoing, "#ff00ff".to_string());
+        assert_eq!(
+            PaletteColor::Outgoing.get(&editor.palette),
+            Color::from_rgb8(0xff, 0x00, 0xff)
+        );
+        // other colors display their current value
+        assert_eq!(editor.hex_value(PaletteColor::Primary), "#102030");
+    }
+}
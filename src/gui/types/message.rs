@@ -1,16 +1,25 @@
 use iced::font;
 
+use crate::agent::types::agent_params::AgentParams;
+use crate::chart::export_chart::{ChartImageFormat, ChartResolution};
+use crate::chart::types::chart_breakdown::{BreakdownKey, ChartBreakdown};
+use crate::chart::types::chart_window::{ChartViewport, ChartWindow};
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::pages::types::running_page::RunningPage;
 use crate::gui::pages::types::settings_page::SettingsPage;
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::styles::types::theme_editor::ThemeEditor;
+use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
-use crate::networking::types::search_parameters::SearchParameters;
+use crate::networking::types::label::{LabelDraft, LabelTarget};
+use crate::networking::types::search_parameters::{FilterInputType, SearchParameters};
+use crate::notifications::types::notification_rule::NotificationRuleDraft;
 use crate::notifications::types::notifications::Notification;
+use crate::report::types::host_grouping::HostGrouping;
+use crate::report::types::host_sort_type::HostSortType;
+use crate::report::types::report_column::ReportColumn;
 use crate::utils::types::web_page::WebPage;
-use crate::{
-    AppProtocol, ChartType, IpVersion, Language, ReportSortType, StyleType, TransProtocol,
-};
+use crate::{AppProtocol, ChartType, IpVersion, Language, StyleType, TransProtocol};
 
 #[derive(Debug, Clone)]
 /// Messages types that permit to react to application interactions/subscriptions
@@ -27,10 +36,30 @@ pub enum Message {
     TransportProtocolSelection(TransProtocol),
     /// Select application filter
     AppProtocolSelection(AppProtocol),
+    /// Select the display filters, applied to the traffic collected so far
+    DisplayFiltersSelection(Filters),
     /// Select chart type to be displayed
     ChartSelection(ChartType),
-    /// Select report type to be displayed
-    ReportSortSelection(ReportSortType),
+    /// Select time window to be displayed in the chart
+    ChartWindowSelection(ChartWindow),
+    /// Zoom or pan the chart
+    ChartViewport(ChartViewport),
+    /// Select the criterion to break down the traffic displayed in the chart
+    ChartBreakdownSelection(ChartBreakdown),
+    /// Show or hide a series of the broken down chart
+    ToggleChartSeries(BreakdownKey),
+    /// Select the resolution of the exported chart images
+    ChartResolutionSelection(ChartResolution),
+    /// Export the chart to an image file of the given format
+    ExportChart(ChartImageFormat),
+    /// Sort the connections table by the given column (or invert the order if already sorted by it)
+    SortReport(ReportColumn),
+    /// Show or hide a column of the connections table
+    ToggleReportColumn(ReportColumn),
+    /// Change the width of a column of the connections table by the given amount of pixels
+    ResizeReportColumn(ReportColumn, f32),
+    /// Save the settings of the connections table columns
+    SaveReportColumns,
     /// Adds or removes the given host into/from the favorites
     AddOrRemoveFavorite(Host, bool),
     /// Open Sniffnet's complete textual report
@@ -61,8 +90,44 @@ pub enum Message {
     LanguageSelection(Language),
     /// Set packets notification
     UpdateNotificationSettings(Notification, bool),
+    /// Update the custom notification rule being composed
+    UpdateRuleDraft(NotificationRuleDraft),
+    /// Add the composed custom notification rule
+    AddNotificationRule,
+    /// Remove the custom notification rule at the given index
+    RemoveNotificationRule(usize),
+    /// Update the favorite pattern being inserted in the notifications settings
+    UpdateFavoriteDraft(String),
+    /// Add the favorite pattern being inserted to the favorites
+    AddFavoritePattern,
+    /// Remove the favorite with the given index
+    RemoveFavoritePattern(usize),
+    /// Adds the given host to the ignore list
+    IgnoreHost(Host),
+    /// Update the ignore list pattern being inserted in the notifications settings
+    UpdateIgnoreDraft(String),
+    /// Add the ignore list pattern being inserted to the ignore list
+    AddIgnorePattern,
+    /// Remove the ignore list entry with the given index
+    RemoveIgnorePattern(usize),
+    /// Opens the label editor for the given address or host
+    EditLabel(LabelTarget),
+    /// Update the label being edited
+    UpdateLabelDraft(LabelDraft),
+    /// Save the label being edited
+    SaveLabel,
+    /// Remove the label of the given address or host
+    RemoveLabel(LabelTarget),
+    /// Update the IP or MAC address inserted in the settings to create a new label
+    UpdateLabelTargetDraft(String),
     /// Clear all received notifications
     ClearAllNotifications,
+    /// Show (true) or hide (false) the full history of notifications
+    ShowNotificationsHistory(bool),
+    /// Update the text used to filter the notifications history
+    FilterNotificationsHistory(String),
+    /// Export the filtered notifications history to a file
+    ExportNotificationsHistory,
     /// Set notifications volume
     ChangeVolume(u8),
     /// Quits the app. Used when Ctrl+Q keys are pressed.
@@ -79,14 +144,49 @@ pub enum Message {
     CtrlDPressed,
     /// Update search parameters of inspect page
     Search(SearchParameters),
+    /// Set the value of an inspect page filter to the selected suggestion
+    ApplySuggestion(FilterInputType, String),
     /// Update page result number in inspect
     UpdatePageNumber(bool),
+    /// Update the text searched in the hosts page
+    SearchHosts(String),
+    /// Select the ordering of the hosts page
+    HostsSortSelection(HostSortType),
+    /// Select how hosts are aggregated in the overview and hosts pages
+    HostGroupingSelection(HostGrouping),
+    /// Update page number of the hosts page
+    UpdateHostsPageNumber(bool),
     /// Left (false) or Right (true) arrow key has been pressed
     ArrowPressed(bool),
+    /// Up (false) or Down (true) arrow key has been pressed
+    VerticalArrowPressed(bool),
     /// Emit when the main window be focused
     WindowFocused,
     /// Result after loading a custom font
     FontLoaded(Result<(), font::Error>),
     /// Enable or disable gradients
     GradientsSelection(GradientType),
+    /// Update the parameters used to connect to a remote agent
+    UpdateAgentParams(AgentParams),
+    /// Connect to a remote agent and display its traffic
+    ConnectToAgent,
+    /// Result of the connection to a remote agent made for the given capture
+    /// (the adapter used by the agent, or an error)
+    AgentConnected(u16, Result<String, String>),
+    /// Update the path of the custom theme to be imported
+    UpdateCustomThemePath(String),
+    /// Import the custom theme at the specified path and apply it
+    ImportCustomTheme,
+    /// Open a file dialog to select the custom theme to be imported
+    BrowseCustomTheme,
+    /// Import the custom theme selected in the file dialog (if any) and apply it
+    CustomThemeFileSelected(Option<String>),
+    /// Open the theme editor, starting from the current style
+    OpenThemeEditor,
+    /// Update the style being composed in the theme editor, and preview it
+    UpdateThemeEditor(ThemeEditor),
+    /// Save the style composed in the theme editor as a new custom theme
+    SaveThemeEditor,
+    /// Close the theme editor, restoring the previous style
+    CloseThemeEditor,
 }
@@ -1,8 +1,12 @@
 //! Module defining the `RunTimeData` struct, useful to to generate chart and to display statistics about network traffic
 //!
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::time::Instant;
 
-use crate::notifications::types::logged_notification::LoggedNotification;
+use crate::networking::types::host::Host;
+use crate::notifications::types::logged_notification::NotificationGroup;
+use crate::notifications::types::notification_rule::{NotificationRule, RuleState};
+use crate::notifications::types::notifications::ThresholdState;
 
 /// Struct containing useful data to display statistics about network traffic and the relative notifications
 pub struct RunTimeData {
@@ -28,10 +32,18 @@ pub struct RunTimeData {
     pub tot_sent_packets_prev: u128,
     /// Total received packets filtered before the current time interval
     pub tot_received_packets_prev: u128,
-    /// Log of the received notifications
-    pub logged_notifications: VecDeque<LoggedNotification>,
+    /// Log of the received notifications, with similar events grouped together
+    pub logged_notifications: VecDeque<NotificationGroup>,
     /// The total number of emitted notifications
     pub tot_emitted_notifications: usize,
+    /// Traffic matched by each custom notification rule until the previous time interval
+    pub rules_state: HashMap<NotificationRule, RuleState>,
+    /// Hysteresis and cooldown state of the packets threshold
+    pub packets_threshold_state: ThresholdState,
+    /// Hysteresis and cooldown state of the bytes threshold
+    pub bytes_threshold_state: ThresholdState,
+    /// Last time a notification was emitted for each favorite host
+    pub favorites_last_emitted: HashMap<Host, Instant>,
 }
 
 impl RunTimeData {
@@ -51,6 +63,10 @@ pub fn new() -> Self {
             tot_received_packets_prev: 0,
             logged_notifications: VecDeque::default(),
             tot_emitted_notifications: 0,
+            rules_state: HashMap::new(),
+            packets_threshold_state: ThresholdState::default(),
+            bytes_threshold_state: ThresholdState::default(),
+            favorites_last_emitted: HashMap::new(),
         }
     }
 }
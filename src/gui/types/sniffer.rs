@@ -2,33 +2,63 @@
 //! to share data among the different threads.
 
 use std::collections::{HashSet, VecDeque};
+use std::path::Path;
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use chrono::Local;
+#[cfg(not(target_arch = "powerpc64"))]
+use iced::futures::channel::oneshot;
 use iced::{window, Command};
 use pcap::Device;
 
-use crate::chart::manage_chart_data::update_charts_data;
+#[cfg(not(target_arch = "powerpc64"))]
+use crate::agent::client::{connect_to_agent, receive_updates_loop};
+use crate::agent::types::agent_params::AgentParams;
+use crate::api::types::api_command::ApiCommand;
+use crate::chart::export_chart::{export_chart, ChartImageFormat, ChartResolution};
+use crate::chart::manage_chart_data::{
+    rebase_charts_data, update_breakdown_data, update_charts_data,
+};
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::pages::types::running_page::RunningPage;
 use crate::gui::pages::types::settings_page::SettingsPage;
+use crate::gui::styles::types::custom_palette::ExtraStyles;
+use crate::gui::styles::types::custom_theme::{
+    import_custom_theme, load_custom_themes, CustomTheme,
+};
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::styles::types::theme_editor::ThemeEditor;
 use crate::gui::types::message::Message;
 use crate::gui::types::status::Status;
 use crate::networking::manage_packets::get_capture_result;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
+use crate::networking::types::host_pattern::HostPattern;
+use crate::networking::types::ignore_pattern::IgnorePattern;
+use crate::networking::types::label::{get_label, Label, LabelDraft};
 use crate::networking::types::my_device::MyDevice;
-use crate::networking::types::search_parameters::SearchParameters;
+use crate::networking::types::search_parameters::{
+    Autocomplete, FilterInputType, SearchParameters,
+};
+use crate::notifications::history::{export_history, read_history};
 use crate::notifications::notify_and_log::notify_and_log;
+use crate::notifications::types::notification_record::NotificationRecord;
+use crate::notifications::types::notification_rule::NotificationRuleDraft;
 use crate::notifications::types::notifications::{Notification, Notifications};
 use crate::notifications::types::sound::{play, Sound};
-use crate::report::get_report_entries::get_searched_entries;
-use crate::report::types::report_sort_type::ReportSortType;
+use crate::report::get_report_entries::{
+    get_filter_suggestions, get_searched_entries, get_searched_hosts,
+};
+use crate::report::types::host_grouping::HostGrouping;
+use crate::report::types::host_sort_type::HostSortType;
+use crate::report::types::report_column::{ReportColumns, ReportSort};
 use crate::secondary_threads::parse_packets::parse_packets;
 use crate::translations::types::language::Language;
-use crate::utils::formatted_strings::get_report_path;
+use crate::utils::formatted_strings::{
+    get_chart_export_path, get_notifications_export_path, get_report_path, get_themes_dir,
+};
 use crate::utils::types::web_page::WebPage;
 use crate::{ConfigDevice, ConfigSettings, InfoTraffic, RunTimeData, StyleType, TrafficChart};
 
@@ -44,26 +74,42 @@ pub struct Sniffer {
     pub status_pair: Arc<(Mutex<Status>, Condvar)>,
     /// Reports if a newer release of the software is available on GitHub
     pub newer_release_available: Arc<Mutex<Result<bool, String>>>,
+    /// Capture controls requested through the API, waiting to be applied
+    pub api_commands: Arc<Mutex<Vec<ApiCommand>>>,
     /// Traffic data displayed in GUI
     pub runtime_data: RunTimeData,
     /// Network adapter to be analyzed
     pub device: MyDevice,
     /// Last network adapter name for which packets were observed; saved into config file
     pub last_device_name_sniffed: String,
-    /// Active filters on the observed traffic
+    /// Active filters on the observed traffic, applied when the capture starts
     pub filters: Filters,
+    /// Filters on the collected traffic, editable while the capture is running
+    pub display_filters: Filters,
     /// Signals if a pcap error occurred
     pub pcap_error: Option<String>,
     /// Application style
     pub style: StyleType,
     /// Wether gradients are enabled by the user
     pub color_gradient: GradientType,
+    /// Custom themes loaded from the themes directory
+    pub custom_themes: Vec<CustomTheme>,
+    /// Errors occurred loading or importing custom themes
+    pub custom_themes_errors: Vec<String>,
+    /// Path of the custom theme to be imported
+    pub custom_theme_path: String,
+    /// Custom style being composed; None if the theme editor is closed
+    pub theme_editor: Option<ThemeEditor>,
     /// Waiting string
     pub waiting: String,
     /// Chart displayed
     pub traffic_chart: TrafficChart,
-    /// Report type to be displayed
-    pub report_sort_type: ReportSortType,
+    /// Resolution of the exported chart images
+    pub chart_resolution: ChartResolution,
+    /// Criterion to sort the connections table
+    pub report_sort: ReportSort,
+    /// Widths and visibility of the connections table columns
+    pub report_columns: ReportColumns,
     /// Currently displayed modal; None if no modal is displayed
     pub modal: Option<MyModal>,
     /// Currently displayed settings page; None if settings is closed
@@ -72,6 +118,27 @@ pub struct Sniffer {
     pub last_opened_setting: SettingsPage,
     /// Contains the notifications configuration set by the user
     pub notifications: Notifications,
+    /// Custom notification rule being composed in the notifications settings
+    pub rule_draft: NotificationRuleDraft,
+    /// Favorite hosts and patterns
+    pub favorites: Vec<HostPattern>,
+    /// Favorite pattern being inserted by the user in the notifications settings page
+    pub favorite_draft: String,
+    /// Ignore list: hosts and application protocols hidden from the views and notifications
+    pub ignored: Vec<IgnorePattern>,
+    /// Ignore list pattern being inserted by the user in the notifications settings page
+    pub ignore_draft: String,
+    /// Aliases, tags and notes assigned to addresses and hosts
+    pub labels: Vec<Label>,
+    /// Label being edited; None if the label editor is closed
+    pub label_draft: Option<LabelDraft>,
+    /// IP or MAC address inserted by the user in the notifications settings page to create a new label
+    pub label_target_draft: String,
+    /// Full history of the emitted notifications, read from file when displayed
+    /// and then kept up to date with the new events
+    pub notifications_history: Option<Vec<NotificationRecord>>,
+    /// Text used to filter the notifications history
+    pub history_filter: String,
     /// Defines the current running page
     pub running_page: RunningPage,
     /// Language used in the GUI
@@ -80,12 +147,26 @@ pub struct Sniffer {
     pub unread_notifications: usize,
     /// Search parameters of inspect page
     pub search: SearchParameters,
+    /// Text searched in the hosts page
+    pub hosts_search: String,
+    /// Ordering of the hosts page
+    pub hosts_sort_type: HostSortType,
+    /// Aggregation of the hosts in the overview and hosts pages
+    pub host_grouping: HostGrouping,
+    /// Current page number of the hosts page
+    pub hosts_page_number: usize,
+    /// Filter input of inspect page whose suggestions are displayed, if any
+    pub autocomplete: Option<Autocomplete>,
     /// Current page number of inspect search results
     pub page_number: usize,
     /// Currently selected connection for inspection of its details
     pub selected_connection: usize,
     /// Record the timestamp of last window focus
     pub last_focus_time: std::time::Instant,
+    /// Parameters used to connect to a remote agent
+    pub agent_params: AgentParams,
+    /// Adapter and address of the remote agent whose traffic is displayed; None if capturing locally
+    pub remote_adapter: Option<String>,
 }
 
 impl Sniffer {
@@ -96,45 +177,93 @@ pub fn new(
         config_settings: &ConfigSettings,
         config_device: &ConfigDevice,
         newer_release_available: Arc<Mutex<Result<bool, String>>>,
+        api_commands: Arc<Mutex<Vec<ApiCommand>>>,
     ) -> Self {
         Self {
             current_capture_id,
             info_traffic,
             status_pair,
             newer_release_available,
+            api_commands,
             runtime_data: RunTimeData::new(),
             device: config_device.to_my_device(),
             last_device_name_sniffed: config_device.device_name.clone(),
             filters: Filters::default(),
+            display_filters: Filters::default(),
             pcap_error: None,
             style: config_settings.style,
             color_gradient: config_settings.color_gradient,
+            custom_themes: Vec::new(),
+            custom_themes_errors: Vec::new(),
+            custom_theme_path: String::new(),
+            theme_editor: None,
             waiting: ".".to_string(),
             traffic_chart: TrafficChart::new(config_settings.style, config_settings.language),
-            report_sort_type: ReportSortType::MostRecent,
+            chart_resolution: ChartResolution::default(),
+            report_sort: ReportSort::default(),
+            report_columns: config_settings.report_columns.clone(),
             modal: None,
             settings_page: None,
             last_opened_setting: SettingsPage::Notifications,
-            notifications: config_settings.notifications,
+            notifications: config_settings.notifications.clone(),
+            rule_draft: NotificationRuleDraft::default(),
+            favorites: config_settings.favorites.clone(),
+            favorite_draft: String::new(),
+            ignored: config_settings.ignored.clone(),
+            ignore_draft: String::new(),
+            labels: config_settings.labels.clone(),
+            label_draft: None,
+            label_target_draft: String::new(),
+            notifications_history: None,
+            history_filter: String::new(),
             running_page: RunningPage::Overview,
             language: config_settings.language,
             unread_notifications: 0,
             search: SearchParameters::default(),
+            hosts_search: String::new(),
+            hosts_sort_type: HostSortType::default(),
+            host_grouping: HostGrouping::default(),
+            hosts_page_number: 1,
+            autocomplete: None,
             page_number: 1,
             selected_connection: 0,
             last_focus_time: std::time::Instant::now(),
+            agent_params: AgentParams::default(),
+            remote_adapter: None,
         }
     }
 
     pub fn update(&mut self, message: Message) -> Command<Message> {
         match message {
-            Message::TickRun => return self.refresh_data(),
+            Message::TickInit => return self.apply_api_commands(),
+            Message::TickRun => {
+                return Command::batch([self.apply_api_commands(), self.refresh_data()])
+            }
             Message::AdapterSelection(name) => self.set_adapter(&name),
             Message::IpVersionSelection(version) => self.filters.ip = version,
             Message::TransportProtocolSelection(protocol) => self.filters.transport = protocol,
             Message::AppProtocolSelection(protocol) => self.filters.application = protocol,
+            Message::DisplayFiltersSelection(display_filters) => {
+                self.set_display_filters(display_filters);
+            }
             Message::ChartSelection(unit) => self.traffic_chart.change_kind(unit),
-            Message::ReportSortSelection(sort) => self.report_sort_type = sort,
+            Message::ChartWindowSelection(window) => self.traffic_chart.change_window(window),
+            Message::ChartViewport(viewport) => self.traffic_chart.change_viewport(viewport),
+            Message::ChartBreakdownSelection(breakdown) => {
+                self.traffic_chart.change_breakdown(breakdown);
+            }
+            Message::ToggleChartSeries(key) => self.traffic_chart.toggle_series(key),
+            Message::ChartResolutionSelection(resolution) => self.chart_resolution = resolution,
+            Message::ExportChart(format) => self.export_chart(format),
+            Message::SortReport(column) => self.report_sort = self.report_sort.toggle(column),
+            Message::ToggleReportColumn(column) => {
+                self.report_columns.toggle(column);
+                self.store_settings();
+            }
+            Message::ResizeReportColumn(column, delta) => {
+                self.report_columns.resize(column, delta);
+            }
+            Message::SaveReportColumns => self.store_settings(),
             Message::OpenReport => self.open_report_file(),
             Message::OpenWebPage(web_page) => Self::open_web(&web_page),
             Message::Start => self.start(),
@@ -145,20 +274,87 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             }
             Message::Waiting => self.update_waiting_dots(),
             Message::AddOrRemoveFavorite(host, add) => self.add_or_remove_favorite(&host, add),
+            Message::UpdateFavoriteDraft(favorite_draft) => self.favorite_draft = favorite_draft,
+            Message::AddFavoritePattern => {
+                if let Some(pattern) = HostPattern::from_str(&self.favorite_draft) {
+                    if !self.favorites.contains(&pattern) {
+                        self.favorites.push(pattern);
+                        self.update_favorites();
+                    }
+                    self.favorite_draft = String::new();
+                }
+            }
+            Message::RemoveFavoritePattern(index) if index < self.favorites.len() => {
+                self.favorites.remove(index);
+                self.update_favorites();
+            }
+            Message::IgnoreHost(host) => {
+                self.add_ignore_pattern(IgnorePattern::Host(HostPattern::Host(host)));
+                self.store_settings();
+            }
+            Message::UpdateIgnoreDraft(ignore_draft) => self.ignore_draft = ignore_draft,
+            Message::AddIgnorePattern => {
+                if let Some(pattern) = IgnorePattern::from_str(&self.ignore_draft) {
+                    self.add_ignore_pattern(pattern);
+                    self.ignore_draft = String::new();
+                }
+            }
+            Message::RemoveIgnorePattern(index) if index < self.ignored.len() => {
+                self.ignored.remove(index);
+                self.update_ignored();
+            }
+            Message::EditLabel(target) => {
+                let label = get_label(&self.labels, &target)
+                    .cloned()
+                    .unwrap_or_else(|| Label::new(target));
+                self.label_draft = Some(LabelDraft::new(label));
+                self.label_target_draft = String::new();
+                // the label editor can be opened from the connection details, to which it returns
+                let connection_index = match self.modal {
+                    Some(MyModal::ConnectionDetails(index)) => Some(index),
+                    _ => None,
+                };
+                self.modal = Some(MyModal::EditLabel(connection_index));
+            }
+            Message::UpdateLabelDraft(label_draft) => self.label_draft = Some(label_draft),
+            Message::SaveLabel => {
+                if let Some(label_draft) = self.label_draft.take() {
+                    // a tag still being inserted is saved as well
+                    self.set_label(label_draft.with_new_tag().label);
+                    return self.update(Message::HideModal);
+                }
+            }
+            Message::RemoveLabel(target) => {
+                self.set_label(Label::new(target));
+                if matches!(self.modal, Some(MyModal::EditLabel(_))) {
+                    return self.update(Message::HideModal);
+                }
+            }
+            Message::UpdateLabelTargetDraft(label_target_draft) => {
+                self.label_target_draft = label_target_draft;
+            }
             Message::ShowModal(modal) => {
                 if self.settings_page.is_none() && self.modal.is_none() {
                     self.modal = Some(modal);
                 }
             }
-            Message::HideModal => self.modal = None,
+            Message::HideModal => {
+                self.modal = match self.modal {
+                    Some(MyModal::EditLabel(Some(connection_index))) => {
+                        Some(MyModal::ConnectionDetails(connection_index))
+                    }
+                    _ => None,
+                };
+                self.label_draft = None;
+            }
             Message::OpenSettings(settings_page) => {
                 if self.modal.is_none() {
-                    self.settings_page = Some(settings_page);
+                    self.open_settings(settings_page);
                 }
             }
             Message::OpenLastSettings => {
                 if self.modal.is_none() && self.settings_page.is_none() {
-                    self.settings_page = Some(self.last_opened_setting);
+                    self.open_settings(self.last_opened_setting);
                 }
             }
             Message::CloseSettings => self.close_and_save_settings(),
@@ -175,6 +371,24 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::UpdateNotificationSettings(value, emit_sound) => {
                 self.update_notification_settings(value, emit_sound);
             }
+            Message::UpdateRuleDraft(rule_draft) => self.rule_draft = rule_draft,
+            Message::AddNotificationRule => {
+                if let Some(rule) = self.rule_draft.to_rule() {
+                    if !self.notifications.rules.contains(&rule) {
+                        self.notifications.rules.push(rule);
+                    }
+                    self.rule_draft = NotificationRuleDraft::default();
+                }
+            }
+            Message::RemoveNotificationRule(index) if index < self.notifications.rules.len() => {
+                self.notifications.rules.remove(index);
+            }
+            Message::ShowNotificationsHistory(show) => {
+                self.notifications_history =
+                    show.then(|| read_history(&self.notifications.history_path));
+            }
+            Message::FilterNotificationsHistory(filter) => self.history_filter = filter,
+            Message::ExportNotificationsHistory => self.export_notifications_history(),
             Message::ChangeVolume(volume) => {
                 play(Sound::Pop, volume);
                 self.notifications.volume = volume;
@@ -198,8 +412,20 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::Search(parameters) => {
                 self.page_number = 1;
                 self.running_page = RunningPage::Inspect;
+                self.autocomplete =
+                    self.search
+                        .edited_autocomplete_filter(&parameters)
+                        .map(|filter| Autocomplete {
+                            filter,
+                            selected: None,
+                        });
                 self.search = parameters;
             }
+            Message::ApplySuggestion(filter, value) => {
+                self.page_number = 1;
+                *self.search.get_mut(filter) = value;
+                self.autocomplete = None;
+            }
             Message::UpdatePageNumber(increment) => {
                 let new_page = if increment {
                     self.page_number.checked_add(1)
@@ -221,10 +447,75 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
                     } else if self.page_number > 1 {
                         return self.update(Message::UpdatePageNumber(increment));
                     }
+                } else if self.running_page.eq(&RunningPage::Hosts)
+                    && self.settings_page.is_none()
+                    && self.modal.is_none()
+                {
+                    if increment {
+                        if self.hosts_page_number < self.searched_hosts_number().div_ceil(20) {
+                            return self.update(Message::UpdateHostsPageNumber(increment));
+                        }
+                    } else if self.hosts_page_number > 1 {
+                        return self.update(Message::UpdateHostsPageNumber(increment));
+                    }
+                }
+            }
+            Message::SearchHosts(search) => {
+                self.hosts_search = search;
+                self.hosts_page_number = 1;
+            }
+            Message::HostsSortSelection(sort_type) => {
+                self.hosts_sort_type = sort_type;
+                self.hosts_page_number = 1;
+            }
+            Message::HostGroupingSelection(grouping) => {
+                self.host_grouping = grouping;
+                self.hosts_page_number = 1;
+            }
+            Message::UpdateHostsPageNumber(increment) => {
+                if increment {
+                    self.hosts_page_number += 1;
+                } else {
+                    self.hosts_page_number = self.hosts_page_number.saturating_sub(1).max(1);
+                }
+            }
+            Message::VerticalArrowPressed(next)
+                if self.running_page.eq(&RunningPage::Inspect)
+                    && self.settings_page.is_none()
+                    && self.modal.is_none() =>
+            {
+                if let Some(mut autocomplete) = self.autocomplete {
+                    let suggestions_len = self.filter_suggestions(autocomplete.filter).len();
+                    autocomplete.move_selection(next, suggestions_len);
+                    self.autocomplete = Some(autocomplete);
                 }
             }
             Message::WindowFocused => self.last_focus_time = std::time::Instant::now(),
             Message::GradientsSelection(gradient_type) => self.color_gradient = gradient_type,
+            Message::UpdateAgentParams(agent_params) => self.agent_params = agent_params,
+            #[cfg(not(target_arch = "powerpc64"))]
+            Message::ConnectToAgent => return self.connect_to_agent(),
+            Message::AgentConnected(capture_id, result) => self.agent_connected(capture_id, result),
+            Message::UpdateCustomThemePath(path) => self.custom_theme_path = path,
+            Message::ImportCustomTheme => self.import_custom_theme(),
+            Message::BrowseCustomTheme => {
+                return Command::perform(pick_custom_theme_file(), Message::CustomThemeFileSelected)
+            }
+            Message::CustomThemeFileSelected(Some(path)) => {
+                self.custom_theme_path = path;
+                self.import_custom_theme();
+            }
+            Message::OpenThemeEditor => {
+                self.custom_themes_errors = Vec::new();
+                self.theme_editor = Some(ThemeEditor::new(self.style));
+            }
+            Message::UpdateThemeEditor(theme_editor) => {
+                self.style = theme_editor.style();
+                self.traffic_chart.change_style(self.style);
+                self.theme_editor = Some(theme_editor);
+            }
+            Message::SaveThemeEditor => self.save_theme_editor(),
+            Message::CloseThemeEditor => self.close_theme_editor(),
             _ => {}
         }
         Command::none()
@@ -233,7 +524,10 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
     fn refresh_data(&mut self) -> Command<Message> {
         let info_traffic_lock = self.info_traffic.lock().unwrap();
         self.runtime_data.all_packets = info_traffic_lock.all_packets;
-        if info_traffic_lock.tot_received_packets + info_traffic_lock.tot_sent_packets == 0 {
+        // with active display filters, the page is updated even if none of the traffic is displayed
+        if info_traffic_lock.tot_received_packets + info_traffic_lock.tot_sent_packets == 0
+            && info_traffic_lock.display_filters.eq(&Filters::default())
+        {
             drop(info_traffic_lock);
             return self.update(Message::Waiting);
         }
@@ -243,17 +537,23 @@ fn refresh_data(&mut self) -> Command<Message> {
         self.runtime_data.tot_received_bytes = info_traffic_lock.tot_received_bytes;
         self.runtime_data.tot_sent_bytes = info_traffic_lock.tot_sent_bytes;
         self.runtime_data.dropped_packets = info_traffic_lock.dropped_packets;
+        update_breakdown_data(&info_traffic_lock, &mut self.traffic_chart);
         drop(info_traffic_lock);
-        let emitted_notifications = notify_and_log(
+        let emitted_records = notify_and_log(
             &mut self.runtime_data,
-            self.notifications,
+            &self.notifications,
             &self.info_traffic.clone(),
+            self.language,
         );
+        let emitted_notifications = emitted_records.len();
         self.info_traffic.lock().unwrap().favorites_last_interval = HashSet::new();
         self.runtime_data.tot_emitted_notifications += emitted_notifications;
         if self.running_page.ne(&RunningPage::Notifications) {
             self.unread_notifications += emitted_notifications;
         }
+        if let Some(history) = &mut self.notifications_history {
+            history.extend(emitted_records);
+        }
         update_charts_data(&mut self.runtime_data, &mut self.traffic_chart);
 
         let current_device_name = self.device.name.clone();
@@ -278,26 +578,101 @@ fn refresh_data(&mut self) -> Command<Message> {
         Command::none()
     }
 
+    /// Applies the capture controls requested through the API
+    fn apply_api_commands(&mut self) -> Command<Message> {
+        let api_commands = std::mem::take(&mut *self.api_commands.lock().unwrap());
+        let mut commands = Vec::new();
+        for api_command in api_commands {
+            let status = *self.status_pair.0.lock().unwrap();
+            match (api_command, status) {
+                (ApiCommand::Start(adapter), Status::Init) => {
+                    if let Some(name) = adapter {
+                        self.set_adapter(&name);
+                    }
+                    self.start();
+                }
+                (ApiCommand::Stop, Status::Running) => commands.push(self.reset()),
+                (ApiCommand::Reset, Status::Running) => {
+                    commands.push(self.reset());
+                    self.start();
+                }
+                _ => {}
+            }
+        }
+        Command::batch(commands)
+    }
+
     fn open_report_file(&mut self) {
         if self.status_pair.0.lock().unwrap().eq(&Status::Running) {
-            let report_path = get_report_path();
-            #[cfg(target_os = "windows")]
-            std::process::Command::new("explorer")
-                .arg(report_path)
-                .spawn()
-                .unwrap();
-            #[cfg(target_os = "macos")]
-            std::process::Command::new("open")
-                .arg("-t")
-                .arg(report_path)
-                .spawn()
-                .unwrap();
-            #[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
-            std::process::Command::new("xdg-open")
-                .arg(report_path)
-                .spawn()
-                .unwrap();
+            Self::open_file(&get_report_path());
+        }
+    }
+
+    fn export_notifications_history(&self) {
+        if let Some(history) = &self.notifications_history {
+            let export_path = get_notifications_export_path();
+            let records: Vec<&NotificationRecord> = history
+                .iter()
+                .filter(|record| record.matches(&self.history_filter))
+                .collect();
+            if export_history(&export_path, &records).is_ok() {
+                Self::open_file(&export_path);
+            }
+        }
+    }
+
+    /// Number of hosts matching the text searched in the hosts page
+    fn searched_hosts_number(&self) -> usize {
+        get_searched_hosts(
+            &self.info_traffic.lock().unwrap(),
+            &self.hosts_search,
+            self.hosts_sort_type,
+            self.host_grouping,
+            1,
+        )
+        .1
+    }
+
+    /// Values suggested for the given filter input of the inspect page, based on the text typed in it
+    pub fn filter_suggestions(&self, filter: FilterInputType) -> Vec<String> {
+        let typed = self.search.get(filter);
+        if typed.is_empty() {
+            return Vec::new();
         }
+        get_filter_suggestions(&self.info_traffic.lock().unwrap(), filter, typed)
+    }
+
+    fn export_chart(&self, format: ChartImageFormat) {
+        let export_path = get_chart_export_path(format);
+        if export_chart(
+            &self.traffic_chart,
+            self.style,
+            &export_path,
+            self.chart_resolution.size(),
+        )
+        .is_ok()
+        {
+            Self::open_file(&export_path);
+        }
+    }
+
+    fn open_file(path: &Path) {
+        #[cfg(target_os = "windows")]
+        std::process::Command::new("explorer")
+            .arg(path)
+            .spawn()
+            .unwrap();
+        #[cfg(target_os = "macos")]
+        std::process::Command::new("open")
+            .arg("-t")
+            .arg(path)
+            .spawn()
+            .unwrap();
+        #[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+        std::process::Command::new("xdg-open")
+            .arg(path)
+            .spawn()
+            .unwrap();
     }
 
     fn open_web(web_page: &WebPage) {
@@ -324,7 +699,8 @@ fn start(&mut self) {
         self.pcap_error = pcap_error.clone();
         *self.status_pair.0.lock().unwrap() = Status::Running;
         let info_traffic_mutex = self.info_traffic.clone();
-        *info_traffic_mutex.lock().unwrap() = InfoTraffic::new();
+        *info_traffic_mutex.lock().unwrap() =
+            InfoTraffic::with_user_lists(&self.favorites, &self.ignored, &self.labels);
         self.runtime_data = RunTimeData::new();
         self.traffic_chart = TrafficChart::new(self.style, self.language);
 
@@ -348,15 +724,60 @@ fn start(&mut self) {
         }
     }
 
+    /// Starts displaying the traffic captured by a remote agent
+    /// (the connection is made in the background, without freezing the UI)
+    #[cfg(not(target_arch = "powerpc64"))]
+    fn connect_to_agent(&mut self) -> Command<Message> {
+        *self.status_pair.0.lock().unwrap() = Status::Running;
+        let info_traffic_mutex = self.info_traffic.clone();
+        *info_traffic_mutex.lock().unwrap() =
+            InfoTraffic::with_user_lists(&self.favorites, &self.ignored, &self.labels);
+        self.runtime_data = RunTimeData::new();
+        self.traffic_chart = TrafficChart::new(self.style, self.language);
+        // the agent doesn't apply any filter
+        self.filters = Filters::default();
+
+        let capture_id = *self.current_capture_id.lock().unwrap();
+        Command::perform(
+            connect_to_agent_in_background(
+                self.agent_params.clone(),
+                capture_id,
+                self.current_capture_id.clone(),
+                info_traffic_mutex,
+            ),
+            move |result| Message::AgentConnected(capture_id, result),
+        )
+    }
+
+    /// Displays the outcome of the connection to a remote agent
+    fn agent_connected(&mut self, capture_id: u16, result: Result<String, String>) {
+        if *self.current_capture_id.lock().unwrap() != capture_id {
+            // the capture was reset in the meantime
+            return;
+        }
+        match result {
+            Ok(adapter) => {
+                self.pcap_error = None;
+                self.remote_adapter = Some(adapter);
+                self.status_pair.1.notify_all();
+            }
+            Err(error) => self.pcap_error = Some(error),
+        }
+    }
+
     fn reset(&mut self) -> Command<Message> {
         *self.status_pair.0.lock().unwrap() = Status::Init;
         self.running_page = RunningPage::Overview;
         *self.current_capture_id.lock().unwrap() += 1; //change capture id to kill previous capture and to rewrite output file
         self.pcap_error = None;
-        self.report_sort_type = ReportSortType::MostRecent;
+        self.remote_adapter = None;
+        self.report_sort = ReportSort::default();
         self.unread_notifications = 0;
         self.search = SearchParameters::default();
         self.page_number = 1;
+        self.hosts_search = String::new();
+        self.hosts_page_number = 1;
+        self.display_filters = Filters::default();
         self.update(Message::HideModal)
     }
 
@@ -384,32 +805,141 @@ fn update_waiting_dots(&mut self) {
     }
 
     fn add_or_remove_favorite(&mut self, host: &Host, add: bool) {
-        let mut info_traffic = self.info_traffic.lock().unwrap();
+        let favorite = HostPattern::Host(host.clone());
         if add {
-            info_traffic.favorite_hosts.insert(host.clone());
+            if !self.favorites.contains(&favorite) {
+                self.favorites.push(favorite);
+            }
         } else {
-            info_traffic.favorite_hosts.remove(host);
+            self.favorites.retain(|pattern| pattern.ne(&favorite));
+        }
+        self.update_favorites();
+        self.store_settings();
+    }
+
+    /// Applies the current favorites to the captured traffic
+    fn update_favorites(&mut self) {
+        self.info_traffic
+            .lock()
+            .unwrap()
+            .set_favorites(&self.favorites);
+    }
+
+    fn add_ignore_pattern(&mut self, pattern: IgnorePattern) {
+        if !self.ignored.contains(&pattern) {
+            self.ignored.push(pattern);
+            self.update_ignored();
+        }
+    }
+
+    /// Applies the current ignore list to the captured traffic
+    fn update_ignored(&mut self) {
+        let mut info_traffic = self.info_traffic.lock().unwrap();
+        info_traffic.set_ignored(&self.ignored);
+        rebase_charts_data(
+            &info_traffic,
+            Local::now().timestamp(),
+            &mut self.runtime_data,
+            &mut self.traffic_chart,
+        );
+    }
+
+    /// Applies the given display filters to the traffic collected so far
+    fn set_display_filters(&mut self, display_filters: Filters) {
+        self.display_filters = display_filters;
+        self.page_number = 1;
+        self.hosts_page_number = 1;
+        let mut info_traffic = self.info_traffic.lock().unwrap();
+        info_traffic.set_display_filters(display_filters);
+        rebase_charts_data(
+            &info_traffic,
+            Local::now().timestamp(),
+            &mut self.runtime_data,
+            &mut self.traffic_chart,
+        );
+    }
+
+    /// Replaces the label having the same target of the given one (removing it if the given one is empty)
+    fn set_label(&mut self, label: Label) {
+        match self.labels.iter().position(|l| l.target.eq(&label.target)) {
+            Some(index) if label.is_empty() => {
+                self.labels.remove(index);
+            }
+            Some(index) => self.labels[index] = label,
+            None if label.is_empty() => {}
+            None => self.labels.push(label),
         }
-        if let Some(host_info) = info_traffic.hosts.get_mut(host) {
-            host_info.is_favorite = add;
+        self.info_traffic.lock().unwrap().labels = self.labels.clone();
+        self.store_settings();
+    }
+
+    fn open_settings(&mut self, settings_page: SettingsPage) {
+        self.settings_page = Some(settings_page);
+        if settings_page.eq(&SettingsPage::Appearance) {
+            (self.custom_themes, self.custom_themes_errors) = load_custom_themes(&get_themes_dir());
+        }
+    }
+
+    fn import_custom_theme(&mut self) {
+        let path = self.custom_theme_path.trim().to_string();
+        if path.is_empty() {
+            return;
+        }
+        match import_custom_theme(Path::new(&path), &get_themes_dir()) {
+            Ok(theme) => {
+                self.custom_theme_path = String::new();
+                (self.custom_themes, self.custom_themes_errors) =
+                    load_custom_themes(&get_themes_dir());
+                self.style = StyleType::Custom(ExtraStyles::CustomToml(theme.palette));
+                self.traffic_chart.change_style(self.style);
+            }
+            Err(e) => self.custom_themes_errors = vec![format!("{path}: {e}")],
+        }
+    }
+
+    fn save_theme_editor(&mut self) {
+        if let Some(theme_editor) = &self.theme_editor {
+            match theme_editor.to_theme().save(&get_themes_dir()) {
+                Ok(_) => {
+                    self.theme_editor = None;
+                    (self.custom_themes, self.custom_themes_errors) =
+                        load_custom_themes(&get_themes_dir());
+                }
+                Err(e) => self.custom_themes_errors = vec![e],
+            }
+        }
+    }
+
+    fn close_theme_editor(&mut self) {
+        if let Some(theme_editor) = self.theme_editor.take() {
+            self.style = theme_editor.previous_style;
+            self.traffic_chart.change_style(self.style);
         }
-        drop(info_traffic);
     }
 
     fn close_and_save_settings(&mut self) {
+        self.close_theme_editor();
         if self.settings_page.is_some() {
             self.last_opened_setting = self.settings_page.unwrap();
             self.settings_page = None;
-            let store = ConfigSettings {
-                style: self.style,
-                notifications: self.notifications,
-                language: self.language,
-                color_gradient: self.color_gradient,
-            };
-            confy::store("sniffnet", "settings", store).unwrap_or(());
+            self.store_settings();
         }
     }
 
+    fn store_settings(&self) {
+        let store = ConfigSettings {
+            style: self.style,
+            notifications: self.notifications.clone(),
+            language: self.language,
+            favorites: self.favorites.clone(),
+            ignored: self.ignored.clone(),
+            labels: self.labels.clone(),
+            color_gradient: self.color_gradient,
+            report_columns: self.report_columns.clone(),
+        };
+        confy::store("sniffnet", "settings", store).unwrap_or(());
+    }
+
     fn update_notification_settings(&mut self, value: Notification, emit_sound: bool) {
         let sound = match value {
             Notification::Packets(packets_notification) => {
@@ -424,6 +954,18 @@ fn update_notification_settings(&mut self, value: Notification, emit_sound: bool
                 self.notifications.favorite_notification = favorite_notification;
                 favorite_notification.sound
             }
+            Notification::Desktop(desktop) => {
+                self.notifications.desktop = desktop;
+                Sound::None
+            }
+            Notification::Hook(hook) => {
+                self.notifications.hook = hook;
+                Sound::None
+            }
+            Notification::HistoryPath(history_path) => {
+                self.notifications.history_path = history_path;
+                Sound::None
+            }
         };
         if emit_sound {
             play(sound, self.notifications.volume);
@@ -463,6 +1005,17 @@ fn switch_page(&mut self, next: bool) {
     }
 
     fn shortcut_return(&mut self) -> Command<Message> {
+        if let Some(Autocomplete {
+            filter,
+            selected: Some(index),
+        }) = self.autocomplete
+        {
+            if self.modal.is_none() && self.settings_page.is_none() {
+                if let Some(value) = self.filter_suggestions(filter).get(index) {
+                    return self.update(Message::ApplySuggestion(filter, value.clone()));
+                }
+            }
+        }
         if self.status_pair.0.lock().unwrap().eq(&Status::Init)
             && self.settings_page.is_none()
             && self.modal.is_none()
@@ -482,6 +1035,7 @@ fn shortcut_esc(&mut self) -> Command<Message> {
         } else if self.settings_page.is_some() {
             return self.update(Message::CloseSettings);
         }
+        self.autocomplete = None;
         Command::none()
     }
 
@@ -510,6 +1064,46 @@ fn shortcut_ctrl_d(&mut self) -> Command<Message> {
     }
 }
 
+/// Connects to a remote agent in a new thread, which then keeps receiving its updates for the given capture.
+///
+/// Returns the adapter used by the agent, followed by its address
+#[cfg(not(target_arch = "powerpc64"))]
+async fn connect_to_agent_in_background(
+    agent_params: AgentParams,
+    capture_id: u16,
+    current_capture_id: Arc<Mutex<u16>>,
+    info_traffic_mutex: Arc<Mutex<InfoTraffic>>,
+) -> Result<String, String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::Builder::new()
+        .name("thread_agent_client".to_string())
+        .spawn(move || match connect_to_agent(&agent_params) {
+            Ok((stream, adapter)) => {
+                let _ = sender.send(Ok(format!("{adapter} ({})", agent_params.address.trim())));
+                let _ = receive_updates_loop(
+                    &current_capture_id,
+                    capture_id,
+                    stream,
+                    &info_traffic_mutex,
+                );
+            }
+            Err(error) => {
+                let _ = sender.send(Err(error));
+            }
+        })
+        .unwrap();
+    receiver.await.map_err(|e| e.to_string())?
+}
+
+/// Opens a file dialog to select a custom theme, returning its path (if one was selected)
+async fn pick_custom_theme_file() -> Option<String> {
+    let file = rfd::AsyncFileDialog::new()
+        .add_filter("TOML", &["toml"])
+        .pick_file()
+        .await?;
+    Some(file.path().to_string_lossy().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(unused_must_use)]
@@ -519,21 +1113,52 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
+    use chrono::Local;
+
+    use crate::chart::types::chart_breakdown::BreakdownKey;
     use crate::countries::types::country::Country;
     use crate::gui::components::types::my_modal::MyModal;
     use crate::gui::pages::types::settings_page::SettingsPage;
+    use crate::gui::styles::types::tag_color::TagColor;
     use crate::gui::types::message::Message;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::filters::Filters;
     use crate::networking::types::host::Host;
+    use crate::networking::types::host_pattern::HostPattern;
+    use crate::networking::types::ignore_pattern::IgnorePattern;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::label::{Label, LabelTarget};
+    use crate::networking::types::search_parameters::{
+        Autocomplete, FilterInputType, SearchParameters,
+    };
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::networking::types::traffic_series::TrafficSeries;
+    use crate::notifications::history::read_history;
+    use crate::notifications::notify_and_log::notify_and_log;
     use crate::notifications::types::logged_notification::{
-        LoggedNotification, PacketsThresholdExceeded,
+        LoggedNotification, NotificationGroup, PacketsThresholdExceeded,
+    };
+    use crate::notifications::types::notification_rule::{
+        NotificationRule, NotificationRuleDraft, RuleCondition, RuleConditionKind, RuleMatcher,
+        RuleMatcherKind,
     };
     use crate::notifications::types::notifications::{
         BytesNotification, FavoriteNotification, Notification, PacketsNotification,
     };
     use crate::notifications::types::sound::Sound;
+    use crate::report::get_report_entries::{
+        get_app_entries, get_host_entries, get_searched_connections, get_searched_hosts,
+    };
+    use crate::report::types::host_grouping::HostGrouping;
+    use crate::report::types::host_sort_type::HostSortType;
+    use crate::report::types::report_column::{ReportColumn, ReportSort};
+    use crate::report::types::report_sort_type::ReportSortType;
     use crate::{
-        AppProtocol, ByteMultiple, ChartType, InfoTraffic, IpVersion, Language, ReportSortType,
-        RunningPage, Sniffer, Status, StyleType, TransProtocol,
+        AppProtocol, ByteMultiple, ChartType, InfoTraffic, IpVersion, Language, RunningPage,
+        Sniffer, Status, StyleType, TransProtocol,
     };
 
     #[test]
@@ -545,6 +1170,7 @@ fn test_correctly_update_ip_version() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.filters.ip, IpVersion::Other);
@@ -567,6 +1193,7 @@ fn test_correctly_update_transport_protocol() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.filters.transport, TransProtocol::Other);
@@ -589,6 +1216,7 @@ fn test_correctly_update_application_protocol() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.filters.application, AppProtocol::Other);
@@ -611,6 +1239,7 @@ fn test_correctly_update_chart_kind() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.traffic_chart.chart_type, ChartType::Bytes);
@@ -631,17 +1260,34 @@ fn test_correctly_update_report_kind() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
-        assert_eq!(sniffer.report_sort_type, ReportSortType::MostRecent);
-        sniffer.update(Message::ReportSortSelection(ReportSortType::MostBytes));
-        assert_eq!(sniffer.report_sort_type, ReportSortType::MostBytes);
-        sniffer.update(Message::ReportSortSelection(ReportSortType::MostPackets));
-        assert_eq!(sniffer.report_sort_type, ReportSortType::MostPackets);
-        sniffer.update(Message::ReportSortSelection(ReportSortType::MostPackets));
-        assert_eq!(sniffer.report_sort_type, ReportSortType::MostPackets);
-        sniffer.update(Message::ReportSortSelection(ReportSortType::MostRecent));
-        assert_eq!(sniffer.report_sort_type, ReportSortType::MostRecent);
+        assert_eq!(sniffer.report_sort, ReportSort::default());
+        sniffer.update(Message::SortReport(ReportColumn::Bytes));
+        assert_eq!(
+            sniffer.report_sort,
+            ReportSort {
+                column: ReportColumn::Bytes,
+                descending: true
+            }
+        );
+        sniffer.update(Message::SortReport(ReportColumn::Bytes));
+        assert_eq!(
+            sniffer.report_sort,
+            ReportSort {
+                column: ReportColumn::Bytes,
+                descending: false
+            }
+        );
+        sniffer.update(Message::SortReport(ReportColumn::Domain));
+        assert_eq!(
+            sniffer.report_sort,
+            ReportSort {
+                column: ReportColumn::Domain,
+                descending: false
+            }
+        );
     }
 
     #[test]
@@ -653,6 +1299,7 @@ fn test_correctly_update_style() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         sniffer.update(Message::Style(StyleType::MonAmour));
@@ -667,6 +1314,75 @@ fn test_correctly_update_style() {
         assert_eq!(sniffer.style, StyleType::DeepSea);
     }
 
+    #[test]
+    fn test_import_invalid_custom_theme() {
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(InfoTraffic::new())),
+            Arc::new((Mutex::new(Status::Init), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let path = std::env::temp_dir().join("sniffnet_test_invalid_theme.toml");
+        std::fs::write(&path, "name = \"Broken\"").unwrap();
+
+        sniffer.update(Message::UpdateCustomThemePath(
+            path.to_string_lossy().to_string(),
+        ));
+        sniffer.update(Message::ImportCustomTheme);
+        assert_eq!(sniffer.style, StyleType::Night);
+        assert_eq!(sniffer.custom_themes_errors.len(), 1);
+        assert!(sniffer.custom_themes_errors[0].contains("missing field `palette`"));
+        // the path is kept to let the user fix it
+        assert!(!sniffer.custom_theme_path.is_empty());
+        // closing the file dialog without selecting a file changes nothing
+        sniffer.custom_themes_errors = Vec::new();
+        sniffer.update(Message::CustomThemeFileSelected(None));
+        assert!(sniffer.custom_themes_errors.is_empty());
+        // the file selected in the dialog is imported
+        sniffer.update(Message::CustomThemeFileSelected(Some(
+            path.to_string_lossy().to_string(),
+        )));
+        assert_eq!(sniffer.custom_themes_errors.len(), 1);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_theme_editor_preview() {
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(InfoTraffic::new())),
+            Arc::new((Mutex::new(Status::Init), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        sniffer.update(Message::Style(StyleType::DeepSea));
+        sniffer.update(Message::OpenThemeEditor);
+        let editor = sniffer.theme_editor.clone().unwrap();
+        assert_eq!(editor.previous_style, StyleType::DeepSea);
+
+        // changes are previewed in the whole app
+        let editor = editor.with_bold_text(true);
+        sniffer.update(Message::UpdateThemeEditor(editor.clone()));
+        assert_eq!(sniffer.style, editor.style());
+        assert!(sniffer.style.has_bold_text());
+
+        // the previous style is restored when the editor is closed without saving
+        sniffer.update(Message::CloseThemeEditor);
+        assert_eq!(sniffer.theme_editor, None);
+        assert_eq!(sniffer.style, StyleType::DeepSea);
+
+        sniffer.update(Message::OpenThemeEditor);
+        sniffer.update(Message::UpdateThemeEditor(editor));
+        sniffer.update(Message::CloseSettings);
+        assert_eq!(sniffer.theme_editor, None);
+        assert_eq!(sniffer.style, StyleType::DeepSea);
+    }
+
     #[test]
     fn test_waiting_dots_update() {
         let mut sniffer = Sniffer::new(
@@ -676,6 +1392,7 @@ fn test_waiting_dots_update() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.waiting, ".".to_string());
@@ -698,6 +1415,7 @@ fn test_modify_favorite_connections() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
         // remove 1
         sniffer.update(Message::AddOrRemoveFavorite(
@@ -894,6 +1612,7 @@ fn test_show_and_hide_modal_and_settings() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.modal, None);
@@ -988,6 +1707,7 @@ fn test_correctly_update_language() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         assert_eq!(sniffer.language, Language::EN);
@@ -1012,6 +1732,7 @@ fn test_correctly_update_notification_settings() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
 
         // initial default state
@@ -1021,7 +1742,9 @@ fn test_correctly_update_notification_settings() {
             PacketsNotification {
                 threshold: None,
                 sound: Sound::Gulp,
-                previous_threshold: 750
+                previous_threshold: 750,
+                sustain: 1,
+                cooldown: 0
             }
         );
         assert_eq!(
@@ -1030,7 +1753,9 @@ fn test_correctly_update_notification_settings() {
                 threshold: None,
                 byte_multiple: ByteMultiple::KB,
                 sound: Sound::Pop,
-                previous_threshold: 800000
+                previous_threshold: 800000,
+                sustain: 1,
+                cooldown: 0
             }
         );
         assert_eq!(
@@ -1038,6 +1763,7 @@ fn test_correctly_update_notification_settings() {
             FavoriteNotification {
                 notify_on_favorite: false,
                 sound: Sound::Swhoosh,
+                cooldown: 0,
             }
         );
         // change volume
@@ -1048,7 +1774,9 @@ fn test_correctly_update_notification_settings() {
             PacketsNotification {
                 threshold: None,
                 sound: Sound::Gulp,
-                previous_threshold: 750
+                previous_threshold: 750,
+                sustain: 1,
+                cooldown: 0
             }
         );
         assert_eq!(
@@ -1057,7 +1785,9 @@ fn test_correctly_update_notification_settings() {
                 threshold: None,
                 byte_multiple: ByteMultiple::KB,
                 sound: Sound::Pop,
-                previous_threshold: 800000
+                previous_threshold: 800000,
+                sustain: 1,
+                cooldown: 0
             }
         );
         assert_eq!(
@@ -1065,6 +1795,7 @@ fn test_correctly_update_notification_settings() {
             FavoriteNotification {
                 notify_on_favorite: false,
                 sound: Sound::Swhoosh,
+                cooldown: 0,
             }
         );
         // change packets notifications
@@ -1073,6 +1804,8 @@ fn test_correctly_update_notification_settings() {
                 threshold: Some(1122),
                 sound: Sound::None,
                 previous_threshold: 1122,
+                sustain: 2,
+                cooldown: 30,
             }),
             false,
         ));
@@ -1082,7 +1815,9 @@ fn test_correctly_update_notification_settings() {
             PacketsNotification {
                 threshold: Some(1122),
                 sound: Sound::None,
-                previous_threshold: 1122
+                previous_threshold: 1122,
+                sustain: 2,
+                cooldown: 30
             }
         );
         assert_eq!(
@@ -1091,7 +1826,9 @@ fn test_correctly_update_notification_settings() {
                 threshold: None,
                 byte_multiple: ByteMultiple::KB,
                 sound: Sound::Pop,
-                previous_threshold: 800000
+                previous_threshold: 800000,
+                sustain: 1,
+                cooldown: 0
             }
         );
         assert_eq!(
@@ -1099,6 +1836,7 @@ fn test_correctly_update_notification_settings() {
             FavoriteNotification {
                 notify_on_favorite: false,
                 sound: Sound::Swhoosh,
+                cooldown: 0,
             }
         );
         // change bytes notifications
@@ -1108,6 +1846,8 @@ fn test_correctly_update_notification_settings() {
                 byte_multiple: ByteMultiple::GB,
                 sound: Sound::None,
                 previous_threshold: 3,
+                sustain: 5,
+                cooldown: 0,
             }),
             true,
         ));
@@ -1117,7 +1857,9 @@ fn test_correctly_update_notification_settings() {
             PacketsNotification {
                 threshold: Some(1122),
                 sound: Sound::None,
-                previous_threshold: 1122
+                previous_threshold: 1122,
+                sustain: 2,
+                cooldown: 30
             }
         );
         assert_eq!(
@@ -1127,6 +1869,8 @@ fn test_correctly_update_notification_settings() {
                 byte_multiple: ByteMultiple::GB,
                 sound: Sound::None,
                 previous_threshold: 3,
+                sustain: 5,
+                cooldown: 0,
             }
         );
         assert_eq!(
@@ -1134,6 +1878,7 @@ fn test_correctly_update_notification_settings() {
             FavoriteNotification {
                 notify_on_favorite: false,
                 sound: Sound::Swhoosh,
+                cooldown: 0,
             }
         );
         // change favorite notifications
@@ -1141,6 +1886,7 @@ fn test_correctly_update_notification_settings() {
             Notification::Favorite(FavoriteNotification {
                 notify_on_favorite: true,
                 sound: Sound::Pop,
+                cooldown: 15,
             }),
             true,
         ));
@@ -1150,7 +1896,9 @@ fn test_correctly_update_notification_settings() {
             PacketsNotification {
                 threshold: Some(1122),
                 sound: Sound::None,
-                previous_threshold: 1122
+                previous_threshold: 1122,
+                sustain: 2,
+                cooldown: 30
             }
         );
         assert_eq!(
@@ -1160,19 +1908,22 @@ fn test_correctly_update_notification_settings() {
                 byte_multiple: ByteMultiple::GB,
                 sound: Sound::None,
                 previous_threshold: 3,
+                sustain: 5,
+                cooldown: 0,
             }
         );
         assert_eq!(
             sniffer.notifications.favorite_notification,
             FavoriteNotification {
                 notify_on_favorite: true,
-                sound: Sound::Pop
+                sound: Sound::Pop,
+                cooldown: 15,
             }
         );
     }
 
     #[test]
-    fn test_clear_all_notifications() {
+    fn test_add_and_remove_notification_rules() {
         let mut sniffer = Sniffer::new(
             Arc::new(Mutex::new(0)),
             Arc::new(Mutex::new(InfoTraffic::new())),
@@ -1180,16 +1931,110 @@ fn test_clear_all_notifications() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
-        sniffer.runtime_data.logged_notifications =
-            VecDeque::from([LoggedNotification::PacketsThresholdExceeded(
-                PacketsThresholdExceeded {
-                    threshold: 0,
-                    incoming: 0,
-                    outgoing: 0,
-                    timestamp: "".to_string(),
+
+        assert!(sniffer.notifications.rules.is_empty());
+        assert_eq!(sniffer.rule_draft, NotificationRuleDraft::default());
+
+        // invalid draft isn't added
+        sniffer.update(Message::UpdateRuleDraft(NotificationRuleDraft {
+            matcher_kind: RuleMatcherKind::Port,
+            matcher_value: "not a port".to_string(),
+            ..NotificationRuleDraft::default()
+        }));
+        sniffer.update(Message::AddNotificationRule);
+        assert!(sniffer.notifications.rules.is_empty());
+        assert_eq!(sniffer.rule_draft.matcher_value, "not a port".to_string());
+
+        // valid drafts are added and the draft is reset
+        sniffer.update(Message::UpdateRuleDraft(NotificationRuleDraft {
+            matcher_kind: RuleMatcherKind::Port,
+            matcher_value: "443".to_string(),
+            condition_kind: RuleConditionKind::NewConnection,
+            ..NotificationRuleDraft::default()
+        }));
+        sniffer.update(Message::AddNotificationRule);
+        assert_eq!(sniffer.rule_draft, NotificationRuleDraft::default());
+        sniffer.update(Message::UpdateRuleDraft(NotificationRuleDraft {
+            matcher_kind: RuleMatcherKind::Country,
+            matcher_value: "it".to_string(),
+            cooldown: "5".to_string(),
+            sound: Sound::None,
+            ..NotificationRuleDraft::default()
+        }));
+        sniffer.update(Message::AddNotificationRule);
+        assert_eq!(
+            sniffer.notifications.rules,
+            vec![
+                NotificationRule {
+                    sound: Sound::Pop,
+                    cooldown: 60,
+                    condition: RuleCondition::NewConnection,
+                    matcher: RuleMatcher::Port(443),
                 },
-            )]);
+                NotificationRule {
+                    sound: Sound::None,
+                    cooldown: 5,
+                    condition: RuleCondition::AnyTraffic,
+                    matcher: RuleMatcher::Country("IT".to_string()),
+                }
+            ]
+        );
+
+        // no traffic: no notification is emitted, but the state of the rules is tracked
+        sniffer.notifications.history_path =
+            std::env::temp_dir().join("sniffnet_test_rules_history.jsonl");
+        let check_rules = |sniffer: &mut Sniffer| {
+            notify_and_log(
+                &mut sniffer.runtime_data,
+                &sniffer.notifications,
+                &sniffer.info_traffic,
+                Language::EN,
+            )
+        };
+        assert!(check_rules(&mut sniffer).is_empty());
+        assert_eq!(sniffer.runtime_data.rules_state.len(), 2);
+
+        sniffer.update(Message::RemoveNotificationRule(7));
+        assert_eq!(sniffer.notifications.rules.len(), 2);
+        sniffer.update(Message::RemoveNotificationRule(0));
+        assert_eq!(
+            sniffer.notifications.rules,
+            vec![NotificationRule {
+                sound: Sound::None,
+                cooldown: 5,
+                condition: RuleCondition::AnyTraffic,
+                matcher: RuleMatcher::Country("IT".to_string()),
+            }]
+        );
+        // the state of the removed rule is dropped
+        check_rules(&mut sniffer);
+        assert_eq!(
+            Vec::from_iter(sniffer.runtime_data.rules_state.keys()),
+            vec![&sniffer.notifications.rules[0]]
+        );
+    }
+
+    #[test]
+    fn test_clear_all_notifications() {
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(InfoTraffic::new())),
+            Arc::new((Mutex::new(Status::Init), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        sniffer.runtime_data.logged_notifications = VecDeque::from([NotificationGroup::new(
+            LoggedNotification::PacketsThresholdExceeded(PacketsThresholdExceeded {
+                threshold: 0,
+                incoming: 0,
+                outgoing: 0,
+                timestamp: "".to_string(),
+            }),
+        )]);
 
         assert_eq!(sniffer.modal, None);
         sniffer.update(Message::ShowModal(MyModal::ClearAll));
@@ -1200,6 +2045,38 @@ fn test_clear_all_notifications() {
         assert_eq!(sniffer.runtime_data.logged_notifications.len(), 0);
     }
 
+    #[test]
+    fn test_notifications_history_updated_in_memory() {
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(InfoTraffic::new())),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let history_path = std::env::temp_dir().join("sniffnet_test_sniffer_history.jsonl");
+        let _ = std::fs::remove_file(&history_path);
+        sniffer.notifications.history_path = history_path.clone();
+        sniffer.notifications.packets_notification = PacketsNotification {
+            threshold: Some(5),
+            sound: Sound::None,
+            ..PacketsNotification::default()
+        };
+        sniffer.last_device_name_sniffed = sniffer.device.name.clone();
+        sniffer.info_traffic.lock().unwrap().tot_received_packets = 10;
+
+        sniffer.update(Message::ShowNotificationsHistory(true));
+        assert_eq!(sniffer.notifications_history, Some(Vec::new()));
+        sniffer.refresh_data();
+        // the new event is appended to the displayed history, and logged to the given file
+        let history = sniffer.notifications_history.clone().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, "packets_threshold_exceeded");
+        assert_eq!(read_history(&history_path), history);
+    }
+
     #[test]
     fn test_correctly_switch_running_and_notification_pages() {
         let mut sniffer = Sniffer::new(
@@ -1209,6 +2086,7 @@ fn test_correctly_switch_running_and_notification_pages() {
             &Default::default(),
             &Default::default(),
             Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
         );
         sniffer.last_focus_time = std::time::Instant::now().sub(Duration::from_millis(400));
 
@@ -1267,4 +2145,877 @@ fn test_correctly_switch_running_and_notification_pages() {
         assert_eq!(sniffer.running_page, RunningPage::Inspect);
         assert_eq!(sniffer.settings_page, Some(SettingsPage::Appearance));
     }
+
+    #[test]
+    fn test_filter_autocompletion() {
+        let mut info_traffic = InfoTraffic::new();
+        for (name, bytes) in [("GOOGLE", 100), ("AMAZON", 500), ("GOOGLE-FIBER", 300)] {
+            let host = Host {
+                domain: format!("{}.com", name.to_lowercase()),
+                asn: Asn {
+                    number: 1,
+                    name: name.to_string(),
+                },
+                country: Country::US,
+            };
+            let data_info_host = DataInfoHost {
+                data_info: DataInfo::new_with_first_packet(bytes, TrafficDirection::Outgoing),
+                ..DataInfoHost::default()
+            };
+            info_traffic.hosts.insert(host, data_info_host);
+        }
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(info_traffic)),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+
+        // suggestions are ranked by traffic volume and don't include the typed value itself
+        sniffer.update(Message::Search(SearchParameters {
+            as_name: "goo".to_string(),
+            ..SearchParameters::default()
+        }));
+        assert_eq!(
+            sniffer.autocomplete,
+            Some(Autocomplete {
+                filter: FilterInputType::AS,
+                selected: None
+            })
+        );
+        assert_eq!(
+            sniffer.filter_suggestions(FilterInputType::AS),
+            vec!["GOOGLE-FIBER".to_string(), "GOOGLE".to_string()]
+        );
+        assert_eq!(
+            sniffer.filter_suggestions(FilterInputType::Domain),
+            Vec::<String>::new()
+        );
+
+        // navigate the suggestions with the arrow keys and apply the selected one
+        sniffer.update(Message::VerticalArrowPressed(true));
+        sniffer.update(Message::VerticalArrowPressed(true));
+        sniffer.update(Message::VerticalArrowPressed(true));
+        sniffer.update(Message::VerticalArrowPressed(false));
+        assert_eq!(sniffer.autocomplete.unwrap().selected, Some(1));
+        sniffer.update(Message::ReturnKeyPressed);
+        assert_eq!(sniffer.search.as_name, "GOOGLE");
+        assert_eq!(sniffer.autocomplete, None);
+
+        // esc hides the suggestions
+        sniffer.update(Message::Search(SearchParameters {
+            domain: "o".to_string(),
+            ..sniffer.search.clone()
+        }));
+        assert_eq!(
+            sniffer.filter_suggestions(FilterInputType::Domain),
+            vec![
+                "amazon.com".to_string(),
+                "google-fiber.com".to_string(),
+                "google.com".to_string()
+            ]
+        );
+        sniffer.update(Message::EscKeyPressed);
+        assert_eq!(sniffer.autocomplete, None);
+    }
+
+    #[test]
+    fn test_hosts_page_search_sort_and_pages() {
+        let mut info_traffic = InfoTraffic::new();
+        for i in 1..=25 {
+            let host = Host {
+                domain: format!("host{i:02}.com"),
+                asn: Asn {
+                    number: 1,
+                    name: if i % 5 == 0 { "AMAZON" } else { "GOOGLE" }.to_string(),
+                },
+                country: Country::US,
+            };
+            let data_info_host = DataInfoHost {
+                data_info: DataInfo::new_with_first_packet(i, TrafficDirection::Outgoing),
+                ..DataInfoHost::default()
+            };
+            info_traffic.hosts.insert(host, data_info_host);
+        }
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(info_traffic)),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        sniffer.update(Message::ChangeRunningPage(RunningPage::Hosts));
+        assert_eq!(sniffer.hosts_sort_type, HostSortType::MostBytes);
+
+        // pages are changed with the arrows, without going past the last one
+        sniffer.update(Message::ArrowPressed(true));
+        sniffer.update(Message::ArrowPressed(true));
+        assert_eq!(sniffer.hosts_page_number, 2);
+        let (entries, results_number) = get_searched_hosts(
+            &sniffer.info_traffic.lock().unwrap(),
+            &sniffer.hosts_search,
+            sniffer.hosts_sort_type,
+            sniffer.host_grouping,
+            sniffer.hosts_page_number,
+        );
+        assert_eq!(results_number, 25);
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0].0.domain, "host05.com");
+
+        // searching and sorting go back to the first page
+        sniffer.update(Message::HostsSortSelection(HostSortType::DomainName));
+        assert_eq!(sniffer.hosts_page_number, 1);
+        sniffer.update(Message::ArrowPressed(true));
+        sniffer.update(Message::SearchHosts("amazon".to_string()));
+        assert_eq!(sniffer.hosts_page_number, 1);
+        let (entries, results_number) = get_searched_hosts(
+            &sniffer.info_traffic.lock().unwrap(),
+            &sniffer.hosts_search,
+            sniffer.hosts_sort_type,
+            sniffer.host_grouping,
+            sniffer.hosts_page_number,
+        );
+        assert_eq!(results_number, 5);
+        assert_eq!(entries[0].0.domain, "host05.com");
+        assert_eq!(entries[4].0.domain, "host25.com");
+
+        // hosts operated by the same organization are aggregated
+        sniffer.update(Message::HostGroupingSelection(HostGrouping::Organization));
+        let (entries, results_number) = get_searched_hosts(
+            &sniffer.info_traffic.lock().unwrap(),
+            &sniffer.hosts_search,
+            sniffer.hosts_sort_type,
+            sniffer.host_grouping,
+            sniffer.hosts_page_number,
+        );
+        assert_eq!(results_number, 1);
+        assert_eq!(entries[0].0.domain, "");
+        assert_eq!(entries[0].0.asn.name, "AMAZON");
+        assert_eq!(entries[0].1.data_info.outgoing_bytes, 5 + 10 + 15 + 20 + 25);
+        assert_eq!(entries[0].1.data_info.outgoing_packets, 5);
+        sniffer.update(Message::SearchHosts(String::new()));
+        sniffer.update(Message::HostGroupingSelection(HostGrouping::Country));
+        assert_eq!(sniffer.searched_hosts_number(), 1);
+    }
+
+    #[test]
+    fn test_favorite_patterns() {
+        let google = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let cloudflare = Host {
+            domain: "one.one".to_string(),
+            asn: Asn {
+                number: 13335,
+                name: "CLOUDFLARENET".to_string(),
+            },
+            country: Country::AU,
+        };
+        let mut info_traffic = InfoTraffic::new();
+        for (address, r_dns, host) in [
+            ("142.250.180.4", "mil04s44-in-f4.1e100.net", &google),
+            ("1.1.1.1", "one.one.one.one", &cloudflare),
+        ] {
+            info_traffic
+                .addresses_resolved
+                .insert(address.to_string(), (r_dns.to_string(), host.clone()));
+            info_traffic
+                .hosts
+                .insert(host.clone(), DataInfoHost::default());
+        }
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(info_traffic)),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let is_favorite = |sniffer: &Sniffer, host: &Host| {
+            sniffer.info_traffic.lock().unwrap().hosts[host].is_favorite
+        };
+
+        // invalid patterns are not added
+        sniffer.update(Message::UpdateFavoriteDraft("not a pattern".to_string()));
+        sniffer.update(Message::AddFavoritePattern);
+        assert!(sniffer.favorites.is_empty());
+        assert_eq!(sniffer.favorite_draft, "not a pattern");
+
+        // patterns are applied to the hosts already seen
+        sniffer.update(Message::UpdateFavoriteDraft("*.1E100.net".to_string()));
+        sniffer.update(Message::AddFavoritePattern);
+        sniffer.update(Message::UpdateFavoriteDraft("AS13335".to_string()));
+        sniffer.update(Message::AddFavoritePattern);
+        assert_eq!(
+            sniffer.favorites,
+            vec![
+                HostPattern::Domain("*.1e100.net".to_string()),
+                HostPattern::Asn(13335)
+            ]
+        );
+        assert_eq!(sniffer.favorite_draft, "");
+        assert!(is_favorite(&sniffer, &google));
+        assert!(is_favorite(&sniffer, &cloudflare));
+
+        // duplicates are not added
+        sniffer.update(Message::UpdateFavoriteDraft("as13335".to_string()));
+        sniffer.update(Message::AddFavoritePattern);
+        assert_eq!(sniffer.favorites.len(), 2);
+
+        sniffer.update(Message::RemoveFavoritePattern(0));
+        sniffer.update(Message::RemoveFavoritePattern(5));
+        assert_eq!(sniffer.favorites, vec![HostPattern::Asn(13335)]);
+        assert!(!is_favorite(&sniffer, &google));
+        assert!(is_favorite(&sniffer, &cloudflare));
+
+        // favorites are kept when starting a new capture
+        let info_traffic = InfoTraffic::with_user_lists(&sniffer.favorites, &[], &[]);
+        assert_eq!(info_traffic.favorite_patterns, sniffer.favorites);
+        assert!(info_traffic.is_favorite("1.0.0.1", "one.one.one.one", &cloudflare));
+        assert!(!info_traffic.is_favorite("142.250.180.4", "", &google));
+
+        // address patterns are matched since the first packet, before the rDNS resolution
+        sniffer.update(Message::UpdateFavoriteDraft("9.9.9.0/24".to_string()));
+        sniffer.update(Message::AddFavoritePattern);
+        let mut info_traffic = sniffer.info_traffic.lock().unwrap();
+        for address in ["9.9.9.9", "8.8.8.8"] {
+            info_traffic.map.insert(
+                AddressPortPair::new(
+                    "192.168.1.10".to_string(),
+                    50000,
+                    address.to_string(),
+                    53,
+                    TransProtocol::UDP,
+                ),
+                InfoAddressPortPair {
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+        }
+        assert!(info_traffic.is_favorite_connection("9.9.9.9"));
+        assert!(!info_traffic.is_favorite_connection("8.8.8.8"));
+        for search in [
+            SearchParameters {
+                only_favorites: true,
+                ..SearchParameters::default()
+            },
+            SearchParameters {
+                query: "favorite:true".to_string(),
+                ..SearchParameters::default()
+            },
+        ] {
+            let results =
+                get_searched_connections(&info_traffic, &search, ReportSortType::MostRecent);
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].0.address2, "9.9.9.9");
+        }
+    }
+
+    #[test]
+    fn test_ignore_list() {
+        let google = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let printer = Host {
+            domain: "printer.local".to_string(),
+            asn: Asn::default(),
+            country: Country::ZZ,
+        };
+        let mut info_traffic = InfoTraffic::new();
+        for (address, app_protocol, host) in [
+            ("142.250.180.4", AppProtocol::HTTPS, &google),
+            ("192.168.1.20", AppProtocol::mDNS, &printer),
+        ] {
+            info_traffic
+                .addresses_resolved
+                .insert(address.to_string(), (host.domain.clone(), host.clone()));
+            info_traffic
+                .hosts
+                .insert(host.clone(), DataInfoHost::default());
+            info_traffic.map.insert(
+                AddressPortPair::new(
+                    "192.168.1.10".to_string(),
+                    5353,
+                    address.to_string(),
+                    443,
+                    TransProtocol::UDP,
+                ),
+                InfoAddressPortPair {
+                    app_protocol,
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+            info_traffic
+                .app_protocols
+                .insert(app_protocol, DataInfo::default());
+        }
+        let info_traffic = Arc::new(Mutex::new(info_traffic));
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            info_traffic.clone(),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let visible_hosts = || {
+            get_host_entries(&info_traffic, ChartType::Bytes, HostGrouping::None)
+                .into_iter()
+                .map(|(host, _)| host.domain)
+                .collect::<Vec<String>>()
+        };
+        let visible_connections = || {
+            get_searched_connections(
+                &info_traffic.lock().unwrap(),
+                &SearchParameters::default(),
+                ReportSortType::MostRecent,
+            )
+            .len()
+        };
+        assert_eq!(visible_hosts().len(), 2);
+        assert_eq!(visible_connections(), 2);
+
+        // invalid patterns are not added
+        sniffer.update(Message::UpdateIgnoreDraft("Other".to_string()));
+        sniffer.update(Message::AddIgnorePattern);
+        assert!(sniffer.ignored.is_empty());
+
+        // ignoring an application protocol hides its traffic
+        sniffer.update(Message::UpdateIgnoreDraft("MDNS".to_string()));
+        sniffer.update(Message::AddIgnorePattern);
+        assert_eq!(
+            sniffer.ignored,
+            vec![IgnorePattern::AppProtocol(AppProtocol::mDNS)]
+        );
+        assert_eq!(sniffer.ignore_draft, "");
+        assert_eq!(visible_connections(), 1);
+        assert_eq!(
+            get_app_entries(&info_traffic, ChartType::Bytes)
+                .into_iter()
+                .map(|(app_protocol, _)| app_protocol)
+                .collect::<Vec<AppProtocol>>(),
+            vec![AppProtocol::HTTPS]
+        );
+        assert!(info_traffic
+            .lock()
+            .unwrap()
+            .is_ignored("192.168.1.20", AppProtocol::mDNS));
+        assert!(!info_traffic
+            .lock()
+            .unwrap()
+            .is_ignored("192.168.1.20", AppProtocol::HTTP));
+
+        // ignoring a host from its row hides it together with its connections
+        sniffer.update(Message::IgnoreHost(google.clone()));
+        assert_eq!(visible_hosts(), vec!["printer.local".to_string()]);
+        assert_eq!(visible_connections(), 0);
+
+        // address patterns match addresses not resolved yet
+        sniffer.update(Message::UpdateIgnoreDraft("10.0.0.0/8".to_string()));
+        sniffer.update(Message::AddIgnorePattern);
+        assert!(info_traffic
+            .lock()
+            .unwrap()
+            .is_ignored("10.1.2.3", AppProtocol::HTTPS));
+        assert!(!info_traffic
+            .lock()
+            .unwrap()
+            .is_ignored("11.1.2.3", AppProtocol::HTTPS));
+
+        sniffer.update(Message::RemoveIgnorePattern(1));
+        sniffer.update(Message::RemoveIgnorePattern(5));
+        assert_eq!(sniffer.ignored.len(), 2);
+        assert_eq!(visible_hosts().len(), 2);
+        assert_eq!(visible_connections(), 1);
+    }
+
+    #[test]
+    fn test_ignore_list_recomputes_totals() {
+        let mut info_traffic = InfoTraffic::new();
+        for (address, packets) in [("142.250.180.4", 3), ("1.1.1.1", 2)] {
+            info_traffic.map.insert(
+                AddressPortPair::new(
+                    "192.168.1.10".to_string(),
+                    50000,
+                    address.to_string(),
+                    443,
+                    TransProtocol::TCP,
+                ),
+                InfoAddressPortPair {
+                    transmitted_packets: packets,
+                    transmitted_bytes: packets * 100,
+                    app_protocol: AppProtocol::HTTPS,
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+        }
+        info_traffic.tot_sent_packets = 5;
+        info_traffic.tot_sent_bytes = 500;
+        let info_traffic = Arc::new(Mutex::new(info_traffic));
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            info_traffic.clone(),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+
+        // traffic counted before being ignored is removed from the totals
+        sniffer.update(Message::UpdateIgnoreDraft("142.250.180.4".to_string()));
+        sniffer.update(Message::AddIgnorePattern);
+        assert_eq!(info_traffic.lock().unwrap().tot_sent_packets, 2);
+        assert_eq!(
+            info_traffic.lock().unwrap().app_protocols[&AppProtocol::HTTPS].tot_bytes(),
+            200
+        );
+        assert_eq!(sniffer.runtime_data.tot_sent_bytes, 200);
+        assert_eq!(sniffer.runtime_data.tot_sent_bytes_prev, 200);
+
+        sniffer.update(Message::RemoveIgnorePattern(0));
+        assert_eq!(info_traffic.lock().unwrap().tot_sent_packets, 5);
+        assert_eq!(sniffer.runtime_data.tot_sent_bytes, 500);
+    }
+
+    #[test]
+    fn test_search_exact_host() {
+        let host = |domain: &str, asn_name: &str| Host {
+            domain: domain.to_string(),
+            asn: Asn {
+                number: 1,
+                name: asn_name.to_string(),
+            },
+            country: Country::US,
+        };
+        let google = host("google.com", "GOOGLE");
+        let not_google = host("notgoogle.com", "GOOGLE");
+        let mut info_traffic = InfoTraffic::new();
+        for (i, host) in [&google, &not_google].into_iter().enumerate() {
+            let address = format!("10.0.0.{i}");
+            info_traffic.map.insert(
+                AddressPortPair::new(
+                    "192.168.1.2".to_string(),
+                    5000,
+                    address.clone(),
+                    443,
+                    TransProtocol::TCP,
+                ),
+                InfoAddressPortPair {
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+            info_traffic
+                .addresses_resolved
+                .insert(address, (host.domain.clone(), host.clone()));
+            info_traffic
+                .hosts
+                .insert(host.clone(), DataInfoHost::default());
+        }
+        let searched = |host: Option<(HostGrouping, Host)>| {
+            let search = SearchParameters {
+                host,
+                ..SearchParameters::default()
+            };
+            assert!(search.is_some_filter_active());
+            get_searched_connections(&info_traffic, &search, ReportSortType::MostRecent)
+                .iter()
+                .map(|(key, _)| key.address2.clone())
+                .collect::<Vec<String>>()
+        };
+
+        // the domain filter would also match notgoogle.com
+        assert_eq!(
+            searched(Some((HostGrouping::None, google.clone()))),
+            vec!["10.0.0.0".to_string()]
+        );
+        // groups of hosts match all their hosts
+        let mut addresses = searched(Some((
+            HostGrouping::Organization,
+            HostGrouping::Organization.group_of(&google),
+        )));
+        addresses.sort();
+        assert_eq!(
+            addresses,
+            vec!["10.0.0.0".to_string(), "10.0.0.1".to_string()]
+        );
+        assert!(searched(Some((HostGrouping::None, host("google.com", "OTHER")))).is_empty());
+    }
+
+    #[test]
+    fn test_ignore_single_address() {
+        let google = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let mut info_traffic = InfoTraffic::new();
+        for address in ["142.250.180.4", "142.250.180.5"] {
+            info_traffic
+                .addresses_resolved
+                .insert(address.to_string(), (google.domain.clone(), google.clone()));
+        }
+        info_traffic
+            .hosts
+            .insert(google.clone(), DataInfoHost::default());
+
+        // an address pattern only hides the matched addresses, not the whole host
+        info_traffic.set_ignored(&[IgnorePattern::from_str("142.250.180.4").unwrap()]);
+        assert!(info_traffic.is_ignored("142.250.180.4", AppProtocol::HTTPS));
+        assert!(!info_traffic.is_ignored("142.250.180.5", AppProtocol::HTTPS));
+        assert!(!info_traffic.hosts[&google].is_ignored);
+        // addresses not resolved yet are matched too
+        info_traffic.set_ignored(&[IgnorePattern::from_str("10.0.0.0/8").unwrap()]);
+        assert!(info_traffic.is_ignored("10.1.2.3", AppProtocol::HTTPS));
+        assert!(!info_traffic.is_ignored("142.250.180.4", AppProtocol::HTTPS));
+
+        // a host pattern hides all the addresses of the host
+        info_traffic.set_ignored(&[IgnorePattern::from_str("AS15169").unwrap()]);
+        assert!(info_traffic.hosts[&google].is_ignored);
+        assert!(info_traffic.is_ignored("142.250.180.4", AppProtocol::HTTPS));
+        assert!(info_traffic.is_ignored("142.250.180.5", AppProtocol::HTTPS));
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.map.insert(
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                50000,
+                "142.250.180.4".to_string(),
+                443,
+                TransProtocol::TCP,
+            ),
+            InfoAddressPortPair {
+                mac_address1: "aa:bb:cc:dd:ee:ff".to_string(),
+                traffic_direction: TrafficDirection::Outgoing,
+                ..InfoAddressPortPair::default()
+            },
+        );
+        let info_traffic = Arc::new(Mutex::new(info_traffic));
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            info_traffic.clone(),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let searched_connections = |query: &str| {
+            get_searched_connections(
+                &info_traffic.lock().unwrap(),
+                &SearchParameters {
+                    query: query.to_string(),
+                    ..SearchParameters::default()
+                },
+                ReportSortType::MostRecent,
+            )
+            .len()
+        };
+        assert!(sniffer.labels.is_empty());
+        assert_eq!(searched_connections("tag:office"), 0);
+
+        // the label editor opened from the connection details returns to them
+        let address = LabelTarget::Address("192.168.1.10".to_string());
+        sniffer.update(Message::ShowModal(MyModal::ConnectionDetails(0)));
+        sniffer.update(Message::EditLabel(address.clone()));
+        assert_eq!(sniffer.modal, Some(MyModal::EditLabel(Some(0))));
+        let mut label_draft = sniffer.label_draft.clone().unwrap();
+        assert_eq!(label_draft.label, Label::new(address.clone()));
+        label_draft.label.alias = "Workstation".to_string();
+        label_draft.tag_name = "office".to_string();
+        label_draft.tag_color = TagColor::Blue;
+        sniffer.update(Message::UpdateLabelDraft(label_draft));
+        // the tag being inserted is saved together with the label
+        sniffer.update(Message::SaveLabel);
+        assert_eq!(sniffer.modal, Some(MyModal::ConnectionDetails(0)));
+        assert!(sniffer.label_draft.is_none());
+        assert_eq!(sniffer.labels.len(), 1);
+        assert_eq!(sniffer.labels[0].alias, "Workstation");
+        assert!(sniffer.labels[0].has_tag("office"));
+        assert_eq!(info_traffic.lock().unwrap().labels, sniffer.labels);
+        assert_eq!(searched_connections("tag:office"), 1);
+        assert_eq!(searched_connections("workstation"), 1);
+
+        // labels without alias, notes and tags are not saved
+        sniffer.update(Message::HideModal);
+        sniffer.update(Message::UpdateLabelTargetDraft(
+            "AA:BB:CC:DD:EE:FF".to_string(),
+        ));
+        sniffer.update(Message::EditLabel(
+            LabelTarget::from_str(&sniffer.label_target_draft).unwrap(),
+        ));
+        assert_eq!(sniffer.modal, Some(MyModal::EditLabel(None)));
+        assert_eq!(sniffer.label_target_draft, "");
+        sniffer.update(Message::SaveLabel);
+        assert_eq!(sniffer.modal, None);
+        assert_eq!(sniffer.labels.len(), 1);
+
+        // existing labels are edited in place
+        sniffer.update(Message::EditLabel(address.clone()));
+        assert_eq!(
+            sniffer.label_draft.clone().unwrap().label.alias,
+            "Workstation"
+        );
+        sniffer.update(Message::RemoveLabel(address));
+        assert_eq!(sniffer.modal, None);
+        assert!(sniffer.labels.is_empty());
+        assert_eq!(searched_connections("tag:office"), 0);
+    }
+
+    #[test]
+    fn test_connect_to_agent_in_background() {
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            Arc::new(Mutex::new(InfoTraffic::new())),
+            Arc::new((Mutex::new(Status::Init), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+
+        // the connection outcome is only displayed when it arrives
+        sniffer.update(Message::ConnectToAgent);
+        assert_eq!(*sniffer.status_pair.0.lock().unwrap(), Status::Running);
+        assert_eq!(sniffer.pcap_error, None);
+        assert_eq!(sniffer.remote_adapter, None);
+        sniffer.update(Message::AgentConnected(
+            0,
+            Ok("eth0 (10.0.0.1:9000)".to_string()),
+        ));
+        assert_eq!(
+            sniffer.remote_adapter,
+            Some("eth0 (10.0.0.1:9000)".to_string())
+        );
+
+        // outcomes of connections made for a previous capture are discarded
+        sniffer.reset();
+        sniffer.update(Message::ConnectToAgent);
+        sniffer.update(Message::AgentConnected(0, Err("refused".to_string())));
+        assert_eq!(sniffer.pcap_error, None);
+        sniffer.update(Message::AgentConnected(1, Err("refused".to_string())));
+        assert_eq!(sniffer.pcap_error, Some("refused".to_string()));
+    }
+
+    #[test]
+    fn test_display_filters() {
+        let now = Local::now().timestamp();
+        let series = |packets: u128, bytes: u128, traffic_direction: TrafficDirection| {
+            let mut series = TrafficSeries::default();
+            for _ in 0..packets {
+                series.add_packet(now, bytes / packets, traffic_direction);
+            }
+            series
+        };
+        let google = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn::default(),
+            country: Country::US,
+        };
+        let mut info_traffic = InfoTraffic::new();
+        // an outgoing IPv4 TCP connection with a resolved host
+        info_traffic.map.insert(
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                50000,
+                "142.250.180.4".to_string(),
+                443,
+                TransProtocol::TCP,
+            ),
+            InfoAddressPortPair {
+                transmitted_packets: 3,
+                transmitted_bytes: 300,
+                app_protocol: AppProtocol::HTTPS,
+                traffic_direction: TrafficDirection::Outgoing,
+                traffic_series: series(3, 300, TrafficDirection::Outgoing),
+                ..InfoAddressPortPair::default()
+            },
+        );
+        info_traffic.addresses_resolved.insert(
+            "142.250.180.4".to_string(),
+            ("1e100.net".to_string(), google.clone()),
+        );
+        info_traffic.hosts.insert(
+            google.clone(),
+            DataInfoHost {
+                data_info: DataInfo::new_with_packets(3, 300, TrafficDirection::Outgoing),
+                ..DataInfoHost::default()
+            },
+        );
+        // an incoming IPv6 UDP connection waiting for the rDNS resolution
+        info_traffic.map.insert(
+            AddressPortPair::new(
+                "2001:db8::53".to_string(),
+                53,
+                "2001:db8::10".to_string(),
+                50001,
+                TransProtocol::UDP,
+            ),
+            InfoAddressPortPair {
+                transmitted_packets: 2,
+                transmitted_bytes: 100,
+                app_protocol: AppProtocol::DNS,
+                traffic_direction: TrafficDirection::Incoming,
+                traffic_series: series(2, 100, TrafficDirection::Incoming),
+                ..InfoAddressPortPair::default()
+            },
+        );
+        info_traffic.addresses_waiting_resolution.insert(
+            "2001:db8::53".to_string(),
+            DataInfo::new_with_packets(2, 100, TrafficDirection::Incoming),
+        );
+        info_traffic.tot_sent_packets = 3;
+        info_traffic.tot_sent_bytes = 300;
+        info_traffic.tot_received_packets = 2;
+        info_traffic.tot_received_bytes = 100;
+        let info_traffic = Arc::new(Mutex::new(info_traffic));
+        let mut sniffer = Sniffer::new(
+            Arc::new(Mutex::new(0)),
+            info_traffic.clone(),
+            Arc::new((Mutex::new(Status::Running), Default::default())),
+            &Default::default(),
+            &Default::default(),
+            Arc::new(Mutex::new(Err(String::new()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+        let visible_hosts =
+            || get_host_entries(&info_traffic, ChartType::Bytes, HostGrouping::None).len();
+        let visible_connections = || {
+            get_searched_connections(
+                &info_traffic.lock().unwrap(),
+                &SearchParameters::default(),
+                ReportSortType::MostRecent,
+            )
+            .len()
+        };
+
+        let chart_total = |samples: &VecDeque<(u32, i64)>| -> i64 {
+            samples.iter().map(|(_, value)| value).sum()
+        };
+
+        // only the IPv6 traffic is displayed
+        sniffer.traffic_chart.ticks = 70;
+        sniffer.traffic_chart.sent_bytes = (0..70).map(|tick| (tick, -5)).collect();
+        sniffer.update(Message::DisplayFiltersSelection(Filters {
+            ip: IpVersion::IPv6,
+            ..Filters::default()
+        }));
+        assert_eq!(sniffer.display_filters.ip, IpVersion::IPv6);
+        // the last minute of the chart is rebuilt with the filtered traffic, the rest is discarded
+        let traffic_chart = &sniffer.traffic_chart;
+        assert_eq!(traffic_chart.sent_bytes.len(), 60);
+        assert_eq!(traffic_chart.sent_bytes.front(), Some(&(10, 0)));
+        assert_eq!(traffic_chart.received_packets.back().unwrap().0, 69);
+        assert_eq!(chart_total(&traffic_chart.sent_bytes), 0);
+        assert_eq!(chart_total(&traffic_chart.received_bytes), 100);
+        assert_eq!(chart_total(&traffic_chart.received_packets), 2);
+        assert_eq!(
+            traffic_chart
+                .app_protocols_history
+                .top_keys(0, 69, ChartType::Bytes),
+            vec![BreakdownKey::Single("DNS".to_string())]
+        );
+        assert_eq!(visible_connections(), 1);
+        assert_eq!(visible_hosts(), 0);
+        {
+            let info_traffic = info_traffic.lock().unwrap();
+            assert_eq!(info_traffic.tot_sent_packets, 0);
+            assert_eq!(info_traffic.tot_received_packets, 2);
+            assert_eq!(info_traffic.tot_received_bytes, 100);
+            assert_eq!(
+                info_traffic.app_protocols.keys().collect::<Vec<_>>(),
+                vec![&AppProtocol::DNS]
+            );
+            assert_eq!(
+                info_traffic.addresses_waiting_resolution["2001:db8::53"].tot_bytes(),
+                100
+            );
+            assert_eq!(info_traffic.hosts[&google].data_info.tot_packets(), 0);
+        }
+        // the next chart interval starts from the recomputed totals
+        assert_eq!(sniffer.runtime_data.tot_received_packets, 2);
+        assert_eq!(sniffer.runtime_data.tot_received_packets_prev, 2);
+        assert_eq!(sniffer.runtime_data.tot_sent_bytes_prev, 0);
+
+        // only the IPv4 TCP traffic is displayed
+        sniffer.update(Message::DisplayFiltersSelection(Filters {
+            ip: IpVersion::IPv4,
+            transport: TransProtocol::TCP,
+            ..Filters::default()
+        }));
+        assert_eq!(visible_connections(), 1);
+        assert_eq!(visible_hosts(), 1);
+        assert_eq!(
+            info_traffic.lock().unwrap().addresses_waiting_resolution["2001:db8::53"].tot_bytes(),
+            0
+        );
+        assert_eq!(sniffer.runtime_data.tot_sent_bytes, 300);
+        assert_eq!(sniffer.runtime_data.tot_received_bytes, 0);
+        assert_eq!(chart_total(&sniffer.traffic_chart.sent_bytes), -300);
+        assert_eq!(chart_total(&sniffer.traffic_chart.received_bytes), 0);
+        assert_eq!(
+            sniffer
+                .traffic_chart
+                .hosts_history
+                .top_keys(0, 69, ChartType::Packets),
+            vec![BreakdownKey::Single("1e100.net".to_string())]
+        );
+
+        // nothing is displayed
+        sniffer.update(Message::DisplayFiltersSelection(Filters {
+            application: AppProtocol::SSH,
+            ..Filters::default()
+        }));
+        assert_eq!(visible_connections(), 0);
+        assert_eq!(visible_hosts(), 0);
+        assert!(info_traffic.lock().unwrap().app_protocols.is_empty());
+
+        // all the traffic is displayed again, without restarting the capture
+        sniffer.update(Message::DisplayFiltersSelection(Filters::default()));
+        assert_eq!(visible_connections(), 2);
+        assert_eq!(visible_hosts(), 1);
+        assert_eq!(sniffer.runtime_data.tot_sent_packets, 3);
+        assert_eq!(sniffer.runtime_data.tot_received_packets, 2);
+        assert_eq!(
+            info_traffic.lock().unwrap().hosts[&google]
+                .data_info
+                .tot_bytes(),
+            300
+        );
+
+        // display filters are cleared when going back to the initial page
+        sniffer.update(Message::DisplayFiltersSelection(Filters {
+            ip: IpVersion::IPv4,
+            ..Filters::default()
+        }));
+        sniffer.update(Message::Reset);
+        assert_eq!(sniffer.display_filters, Filters::default());
+    }
 }
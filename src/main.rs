@@ -10,8 +10,12 @@
 use iced::window::Position;
 use iced::{window, Application, Font, Settings};
 
+#[cfg(not(target_arch = "powerpc64"))]
+use agent::server::spawn_agent_server;
+use api::server::{serve_api_loop, ApiContext};
 use chart::types::chart_type::ChartType;
 use chart::types::traffic_chart::TrafficChart;
+use cli::headless::{export_chart_loop, run_headless};
 use cli::parse_cli_args;
 use configs::types::config_device::ConfigDevice;
 use configs::types::config_settings::ConfigSettings;
@@ -28,12 +32,16 @@
 use networking::types::ip_version::IpVersion;
 use networking::types::trans_protocol::TransProtocol;
 use report::types::report_sort_type::ReportSortType;
+use secondary_threads::metrics_server::serve_metrics_loop;
 use secondary_threads::write_report_file::sleep_and_write_report_loop;
 use translations::types::language::Language;
+use tui::run_tui::run_tui;
 use utils::formatted_strings::print_cli_welcome_message;
 
 use crate::secondary_threads::check_updates::set_newer_release_status;
 
+mod agent;
+mod api;
 mod chart;
 mod cli;
 mod configs;
@@ -44,23 +52,23 @@
 mod report;
 mod secondary_threads;
 mod translations;
+mod tui;
 mod utils;
 
 /// Entry point of application execution
 ///
 /// It initializes shared variables and loads configuration parameters
 pub fn main() -> iced::Result {
-    parse_cli_args();
+    let cli_args = parse_cli_args();
 
     let current_capture_id1 = Arc::new(Mutex::new(0));
     let current_capture_id2 = current_capture_id1.clone();
 
-    let mutex_map1 = Arc::new(Mutex::new(InfoTraffic::new()));
-    let mutex_map2 = mutex_map1.clone();
-
     let status_pair1 = Arc::new((Mutex::new(Status::Init), Condvar::new()));
     let status_pair2 = status_pair1.clone();
 
+    let api_commands1 = Arc::new(Mutex::new(Vec::new()));
+
     let newer_release_available1 = Arc::new(Mutex::new(Err(String::new())));
     let newer_release_available2 = newer_release_available1.clone();
 
@@ -80,6 +88,14 @@ pub fn main() -> iced::Result {
         ConfigSettings::default()
     };
 
+    let mutex_map1 = Arc::new(Mutex::new(InfoTraffic::with_user_lists(
+        &config_settings.favorites,
+        &config_settings.ignored,
+        &config_settings.labels,
+    )));
+    let mutex_map2 = mutex_map1.clone();
+    let mutex_map3 = mutex_map1.clone();
+
     let config_device = if let Ok(device) = confy::load::<ConfigDevice>("sniffnet", "device") {
         device
     } else {
@@ -101,8 +117,92 @@ pub fn main() -> iced::Result {
         })
         .unwrap();
 
+    if let Some(metrics_address) = cli_args.metrics {
+        thread::Builder::new()
+            .name("thread_metrics_server".to_string())
+            .spawn(move || {
+                serve_metrics_loop(metrics_address, &mutex_map3);
+            })
+            .unwrap();
+    }
+
+    if let Some(api_args) = cli_args.api {
+        let context = ApiContext {
+            token: api_args.token.clone(),
+            current_capture_id: current_capture_id1.clone(),
+            info_traffic: mutex_map1.clone(),
+            status_pair: status_pair1.clone(),
+            // only the graphical interface applies the capture controls
+            // (headless and terminal sessions can't stop and restart their capture)
+            api_commands: (!cli_args.headless && !cli_args.tui).then(|| api_commands1.clone()),
+            throughput: Arc::new(Mutex::new(Default::default())),
+        };
+        thread::Builder::new()
+            .name("thread_api_server".to_string())
+            .spawn(move || {
+                serve_api_loop(&api_args, context);
+            })
+            .unwrap();
+    }
+
+    if cli_args.tui {
+        let adapter = cli_args.adapter.unwrap_or(config_device.device_name);
+        run_tui(
+            &adapter,
+            &current_capture_id1,
+            &mutex_map1,
+            &status_pair1,
+            config_settings.notifications,
+            config_settings.language,
+        );
+        return Ok(());
+    }
+
     print_cli_welcome_message();
 
+    if cli_args.headless {
+        let adapter = cli_args.adapter.unwrap_or(config_device.device_name);
+        if let Some(agent_args) = cli_args.agent {
+            #[cfg(not(target_arch = "powerpc64"))]
+            match spawn_agent_server(&agent_args, &adapter, &mutex_map1) {
+                Ok(fingerprint) => println!(
+                    "Agent listening on {} (certificate fingerprint: {fingerprint})",
+                    agent_args.listen
+                ),
+                Err(e) => {
+                    eprintln!("sniffnet: cannot start the agent: {e}");
+                    process::exit(1);
+                }
+            }
+            #[cfg(target_arch = "powerpc64")]
+            {
+                let _ = agent_args;
+                eprintln!("sniffnet: agent mode is not supported on this platform");
+                process::exit(1);
+            }
+        }
+        if let Some(export_args) = cli_args.export_chart {
+            println!(
+                "Exporting the traffic chart to {}",
+                export_args.path.display()
+            );
+            let info_traffic = mutex_map1.clone();
+            thread::Builder::new()
+                .name("thread_export_chart".to_string())
+                .spawn(move || {
+                    export_chart_loop(
+                        &export_args,
+                        &info_traffic,
+                        config_settings.style,
+                        config_settings.language,
+                    );
+                })
+                .unwrap();
+        }
+        run_headless(&adapter, &current_capture_id1, &mutex_map1, &status_pair1);
+        return Ok(());
+    }
+
     Sniffer::run(Settings {
         // id needed for Linux Wayland; should match StartupWMClass in .desktop file; see issue #292
         id: Some("sniffnet".to_string()),
@@ -129,6 +229,7 @@ pub fn main() -> iced::Result {
             &config_settings,
             &config_device,
             newer_release_available1,
+            api_commands1,
         ),
         default_font: Font::with_name("Sarasa Mono SC"),
         default_text_size: FONT_SIZE_BODY,
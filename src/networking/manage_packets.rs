@@ -17,6 +17,7 @@
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::networking::types::traffic_series::TrafficSeries;
 use crate::networking::types::traffic_type::TrafficType;
 use crate::utils::asn::asn;
 use crate::utils::formatted_strings::get_domain_from_r_dns;
@@ -212,29 +213,45 @@ pub fn modify_or_insert_in_map(
             info.transmitted_bytes += exchanged_bytes;
             info.transmitted_packets += 1;
             info.final_timestamp = now;
+            info.traffic_series.add_packet(
+                now.timestamp(),
+                exchanged_bytes,
+                info.traffic_direction,
+            );
         })
-        .or_insert(InfoAddressPortPair {
-            mac_address1: mac_addresses.0,
-            mac_address2: mac_addresses.1,
-            transmitted_bytes: exchanged_bytes,
-            transmitted_packets: 1,
-            initial_timestamp: now,
-            final_timestamp: now,
-            app_protocol: application_protocol,
-            very_long_address,
-            traffic_direction,
-            index,
+        .or_insert_with(|| {
+            let mut traffic_series = TrafficSeries::default();
+            traffic_series.add_packet(now.timestamp(), exchanged_bytes, traffic_direction);
+            InfoAddressPortPair {
+                mac_address1: mac_addresses.0,
+                mac_address2: mac_addresses.1,
+                transmitted_bytes: exchanged_bytes,
+                transmitted_packets: 1,
+                initial_timestamp: now,
+                final_timestamp: now,
+                app_protocol: application_protocol,
+                very_long_address,
+                traffic_direction,
+                index,
+                traffic_series,
+            }
         })
         .clone();
 
     info_traffic.addresses_last_interval.insert(index);
 
+    let address_to_lookup = get_address_to_lookup(key, new_info.traffic_direction);
     if let Some(host_info) = info_traffic
         .addresses_resolved
-        .get(&get_address_to_lookup(key, traffic_direction))
+        .get(&address_to_lookup)
+        .filter(|_| !info_traffic.is_ignored(&address_to_lookup, application_protocol))
         .cloned()
     {
-        if info_traffic.favorite_hosts.contains(&host_info.1) {
+        if info_traffic
+            .hosts
+            .get(&host_info.1)
+            .is_some_and(|data_info_host| data_info_host.is_favorite)
+        {
             info_traffic.favorites_last_interval.insert(host_info.1);
         }
     }
@@ -286,6 +303,10 @@ pub fn reverse_dns_lookup(
         .addresses_waiting_resolution
         .remove(&address_to_lookup)
         .unwrap_or(DataInfo::default());
+    // check if the newly resolved host is matched by the favorites
+    let is_favorite = info_traffic_lock.is_favorite(&address_to_lookup, &r_dns, &new_host);
+    // check if the newly resolved host is matched by the ignore list
+    let is_ignored = info_traffic_lock.is_ignored_host(&address_to_lookup, &r_dns, &new_host);
     // insert the newly resolved host in the collections, with the data it exchanged so far
     info_traffic_lock
         .addresses_resolved
@@ -295,15 +316,26 @@ pub fn reverse_dns_lookup(
         .entry(new_host.clone())
         .and_modify(|data_info_host| {
             data_info_host.data_info += other_data;
+            data_info_host.is_favorite |= is_favorite;
+            data_info_host.is_ignored |= is_ignored;
         })
         .or_insert(DataInfoHost {
             data_info: other_data,
-            is_favorite: false,
+            is_favorite,
+            is_ignored,
             is_local,
             traffic_type,
+            traffic_series: TrafficSeries::default(),
         });
-    // check if the newly resolved host was featured in the favorites (possible in case of already existing host)
-    if info_traffic_lock.favorite_hosts.contains(&new_host) {
+    // the data exchanged before the resolution was counted since the host wasn't known to be ignored
+    if is_ignored && other_data.tot_packets() > 0 {
+        info_traffic_lock.apply_display_filters();
+    }
+    if info_traffic_lock
+        .hosts
+        .get(&new_host)
+        .is_some_and(|data_info_host| data_info_host.is_favorite && !data_info_host.is_ignored)
+    {
         info_traffic_lock.favorites_last_interval.insert(new_host);
     }
 
@@ -0,0 +1,165 @@
+//! Module defining the filters on IP addresses and ports used to search connections.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::ip_cidr::IpCidr;
+
+/// Filter matching a set of IP addresses, expressed as a single address (e.g. `192.168.1.7`),
+/// a network in CIDR notation (e.g. `10.0.0.0/8`, `2001:db8::/32`),
+/// or a range of addresses (e.g. `192.168.1.10-192.168.1.20`).
+///
+/// It's serialized as a string in the same form it's parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum IpFilter {
+    Network(IpCidr),
+    Range { start: IpAddr, end: IpAddr },
+}
+
+impl IpFilter {
+    /// Parses a string in one of the supported forms; returns `None` if invalid
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some((start, end)) = value.split_once('-') {
+            let start = start.trim().parse::<IpAddr>().ok()?;
+            let end = end.trim().parse::<IpAddr>().ok()?;
+            if start.is_ipv4() != end.is_ipv4() || start > end {
+                return None;
+            }
+            return Some(Self::Range { start, end });
+        }
+        IpCidr::from_str(value).map(Self::Network)
+    }
+
+    /// Checks whether the given address is matched by this filter
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match self {
+            IpFilter::Network(cidr) => cidr.contains(address),
+            IpFilter::Range { start, end } => {
+                start.is_ipv4() == address.is_ipv4() && start <= address && address <= end
+            }
+        }
+    }
+
+    /// Checks whether the given address string is matched by this filter
+    pub fn contains_str(&self, address: &str) -> bool {
+        address
+            .parse::<IpAddr>()
+            .is_ok_and(|address| self.contains(&address))
+    }
+}
+
+impl fmt::Display for IpFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpFilter::Network(cidr) => write!(f, "{cidr}"),
+            IpFilter::Range { start, end } => write!(f, "{start}-{end}"),
+        }
+    }
+}
+
+impl TryFrom<String> for IpFilter {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        IpFilter::from_str(&value).ok_or(format!("invalid IP address filter: {value}"))
+    }
+}
+
+impl From<IpFilter> for String {
+    fn from(ip_filter: IpFilter) -> Self {
+        ip_filter.to_string()
+    }
+}
+
+/// Filter matching a single port (e.g. `443`) or a range of ports (e.g. `8000-8080`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortFilter {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortFilter {
+    /// Parses a string in the form `port` or `start-end`; returns `None` if invalid
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (start, end) = value.split_once('-').unwrap_or((value, value));
+        let start = start.trim().parse::<u16>().ok()?;
+        let end = end.trim().parse::<u16>().ok()?;
+        if start > end {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+
+    pub fn contains(&self, port: u16) -> bool {
+        (self.start..=self.end).contains(&port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("10.0.0.0/8", "10.255.1.2", true)]
+    #[case("192.168.1.7", "192.168.1.7", true)]
+    #[case("192.168.1.7", "192.168.1.70", false)]
+    #[case("192.168.1.10-192.168.1.20", "192.168.1.10", true)]
+    #[case("192.168.1.10 - 192.168.1.20", "192.168.1.15", true)]
+    #[case("192.168.1.10-192.168.1.20", "192.168.1.21", false)]
+    #[case("10.0.0.255-10.0.1.1", "10.0.1.0", true)]
+    #[case("2001:db8::/32", "2001:db8:abcd::1", true)]
+    #[case("fe80::1-fe80::ff", "fe80::a", true)]
+    #[case("fe80::1-fe80::ff", "fe80::100", false)]
+    #[case("0.0.0.0-255.255.255.255", "::1", false)]
+    fn test_ip_filter_contains(
+        #[case] filter: &str,
+        #[case] address: &str,
+        #[case] expected: bool,
+    ) {
+        let filter = IpFilter::from_str(filter).unwrap();
+        assert_eq!(filter.contains_str(address), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("example.com")]
+    #[case("10.0.0.0/33")]
+    #[case("10.0.0.20-10.0.0.10")]
+    #[case("10.0.0.1-::1")]
+    #[case("10.0.0.1-")]
+    fn test_invalid_ip_filter(#[case] value: &str) {
+        assert_eq!(IpFilter::from_str(value), None);
+    }
+
+    #[rstest]
+    #[case("10.0.0.0/8", "10.0.0.0/8")]
+    #[case(" 192.168.1.7 ", "192.168.1.7")]
+    #[case("2001:db8::/32", "2001:db8::/32")]
+    #[case("192.168.1.10 - 192.168.1.20", "192.168.1.10-192.168.1.20")]
+    fn test_ip_filter_string_representation(#[case] value: &str, #[case] expected: &str) {
+        let filter = IpFilter::try_from(value.to_string()).unwrap();
+        assert_eq!(String::from(filter), expected);
+        assert_eq!(IpFilter::try_from(expected.to_string()), Ok(filter));
+    }
+
+    #[rstest]
+    #[case("443", Some((443, 443)))]
+    #[case("8000-8080", Some((8000, 8080)))]
+    #[case(" 0 - 1023 ", Some((0, 1023)))]
+    #[case("8080-8000", None)]
+    #[case("65536", None)]
+    #[case("http", None)]
+    fn test_port_filter(#[case] value: &str, #[case] expected: Option<(u16, u16)>) {
+        assert_eq!(
+            PortFilter::from_str(value),
+            expected.map(|(start, end)| PortFilter { start, end })
+        );
+    }
+}
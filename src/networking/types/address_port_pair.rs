@@ -2,10 +2,12 @@
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 use crate::TransProtocol;
 
 /// Struct representing a network address:port pair.
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct AddressPortPair {
     /// Network layer IPv4 or IPv6 source address.
     pub address1: String,
@@ -42,10 +44,6 @@ pub fn new(
             trans_protocol,
         }
     }
-
-    pub fn print_gui(&self) -> String {
-        self.to_string().replace('|', "")
-    }
 }
 
 impl fmt::Display for AddressPortPair {
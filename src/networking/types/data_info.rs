@@ -2,10 +2,12 @@
 
 use std::ops::AddAssign;
 
+use serde::{Deserialize, Serialize};
+
 use crate::networking::types::traffic_direction::TrafficDirection;
 
 /// Amount of exchanged data (packets and bytes) incoming and outgoing
-#[derive(Clone, Default, Copy)]
+#[derive(Clone, Default, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DataInfo {
     /// Incoming packets
     pub incoming_packets: u128,
@@ -53,6 +55,27 @@ pub fn new_with_first_packet(bytes: u128, traffic_direction: TrafficDirection) -
             }
         }
     }
+
+    /// Data consisting of the given packets and bytes, all exchanged in the same direction
+    pub fn new_with_packets(
+        packets: u128,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+    ) -> Self {
+        if traffic_direction.eq(&TrafficDirection::Outgoing) {
+            Self {
+                outgoing_packets: packets,
+                outgoing_bytes: bytes,
+                ..Self::default()
+            }
+        } else {
+            Self {
+                incoming_packets: packets,
+                incoming_bytes: bytes,
+                ..Self::default()
+            }
+        }
+    }
 }
 
 impl AddAssign for DataInfo {
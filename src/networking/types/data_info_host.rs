@@ -1,17 +1,26 @@
 //! Module defining the `DataInfoHost` struct related to hosts.
 
+use serde::{Deserialize, Serialize};
+
 use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::traffic_series::TrafficSeries;
 use crate::networking::types::traffic_type::TrafficType;
 
 /// Host-related information.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct DataInfoHost {
     /// Incoming and outgoing packets and bytes
     pub data_info: DataInfo,
     /// Determine if this host is one of the favorites
     pub is_favorite: bool,
+    /// Determine if this host is matched by the ignore list
+    #[serde(default)]
+    pub is_ignored: bool,
     /// Determine if the connection with this host is local
     pub is_local: bool,
     /// Determine if the connection with this host is unicast, multicast, or broadcast
     pub traffic_type: TrafficType,
+    /// Incoming and outgoing packets and bytes per second in the recent history
+    #[serde(default)]
+    pub traffic_series: TrafficSeries,
 }
@@ -1,9 +1,12 @@
 //! Module defining the `Filters` struct, which represents the possible filters applicable on network traffic.
 
+use std::net::IpAddr;
+
+use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::{AppProtocol, IpVersion, TransProtocol};
 
 /// Possible filters applicable to network traffic
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Filters {
     /// Internet Protocol version
     pub ip: IpVersion,
@@ -30,4 +33,52 @@ pub fn matches(self, rhs: Self) -> bool {
             && (self.transport.eq(&TransProtocol::Other) || self.transport.eq(&rhs.transport))
             && (self.application.eq(&AppProtocol::Other) || self.application.eq(&rhs.application))
     }
+
+    /// Returns the protocols of an already collected connection, to be checked against the filters
+    pub fn of_connection(key: &AddressPortPair, app_protocol: AppProtocol) -> Self {
+        let ip = match key.address1.parse::<IpAddr>() {
+            Ok(IpAddr::V4(_)) => IpVersion::IPv4,
+            Ok(IpAddr::V6(_)) => IpVersion::IPv6,
+            Err(_) => IpVersion::Other,
+        };
+        Self {
+            ip,
+            transport: key.trans_protocol,
+            application: app_protocol,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_of_connection() {
+        let key = AddressPortPair::new(
+            "2001:db8::1".to_string(),
+            443,
+            "2001:db8::2".to_string(),
+            50000,
+            TransProtocol::TCP,
+        );
+        let protocols = Filters::of_connection(&key, AppProtocol::HTTPS);
+        assert_eq!(protocols.ip, IpVersion::IPv6);
+        assert!(Filters::default().matches(protocols));
+        assert!(Filters {
+            transport: TransProtocol::TCP,
+            ..Filters::default()
+        }
+        .matches(protocols));
+        assert!(!Filters {
+            ip: IpVersion::IPv4,
+            ..Filters::default()
+        }
+        .matches(protocols));
+        assert!(!Filters {
+            application: AppProtocol::DNS,
+            ..Filters::default()
+        }
+        .matches(protocols));
+    }
 }
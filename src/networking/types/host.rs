@@ -1,13 +1,16 @@
+use serde::{Deserialize, Serialize};
+
 use crate::countries::types::country::Country;
 use crate::networking::types::asn::Asn;
 
 /// Struct to represent a network host
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(Default, PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
 pub struct Host {
     /// Hostname (domain). Obtained from the reverse DNS.
     pub domain: String,
-    /// Autonomous System which operates the host
-    pub asn: Asn,
     /// Country
     pub country: Country,
+    // declared last since the TOML serializer doesn't support values after tables
+    /// Autonomous System which operates the host
+    pub asn: Asn,
 }
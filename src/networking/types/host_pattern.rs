@@ -0,0 +1,177 @@
+//! Module defining the `HostPattern` enum, used for the favorites and the ignore list set by the user.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::countries::types::country::Country;
+use crate::networking::types::address_filter::IpFilter;
+use crate::networking::types::host::Host;
+use crate::utils::formatted_strings::get_host_label;
+
+/// A pattern matching one or more hosts.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+// adjacently tagged since the TOML serializer doesn't support newtype variants
+#[serde(tag = "kind", content = "value")]
+pub enum HostPattern {
+    /// A single host, selected from the overview or the hosts page
+    Host(Host),
+    /// Domain name, possibly containing `*` wildcards (e.g. `*.example.com`)
+    Domain(String),
+    /// Autonomous System number
+    Asn(u32),
+    /// Country code
+    Country(String),
+    /// IP address, CIDR network or range of addresses
+    Address(IpFilter),
+}
+
+impl HostPattern {
+    /// Parses a pattern inserted by the user; returns `None` if not valid.
+    ///
+    /// Accepted forms are `AS<number>`, two letters country codes,
+    /// IP addresses, networks and ranges, and domain names possibly containing `*` wildcards.
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(number) = value
+            .strip_prefix("AS")
+            .or_else(|| value.strip_prefix("as"))
+            .and_then(|number| number.parse::<u32>().ok())
+        {
+            return Some(HostPattern::Asn(number));
+        }
+        if value.len() == 2 && value.chars().all(|c| c.is_ascii_alphabetic()) {
+            let code = value.to_uppercase();
+            return Country::from_str(&code)
+                .ne(&Country::ZZ)
+                .then_some(HostPattern::Country(code));
+        }
+        if let Some(ip_filter) = IpFilter::from_str(value) {
+            return Some(HostPattern::Address(ip_filter));
+        }
+        let is_domain = (value.contains('.') || value.contains('*'))
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ['.', '-', '_', '*'].contains(&c));
+        is_domain.then(|| HostPattern::Domain(value.to_lowercase()))
+    }
+
+    /// Checks whether a host is matched.
+    ///
+    /// `address` is one of the addresses of the host, and `r_dns` the full reverse DNS of that address
+    pub fn matches(&self, address: &str, r_dns: &str, host: &Host) -> bool {
+        match self {
+            HostPattern::Host(favorite) => favorite.eq(host),
+            HostPattern::Domain(pattern) => {
+                wildcard_matches(pattern, &r_dns.to_lowercase())
+                    || wildcard_matches(pattern, &host.domain.to_lowercase())
+            }
+            HostPattern::Asn(number) => !host.asn.name.is_empty() && host.asn.number.eq(number),
+            HostPattern::Country(code) => host.country.to_string().eq(code),
+            HostPattern::Address(ip_filter) => ip_filter.contains_str(address),
+        }
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostPattern::Host(host) => write!(f, "{}", get_host_label(host)),
+            HostPattern::Domain(value) | HostPattern::Country(value) => write!(f, "{value}"),
+            HostPattern::Address(ip_filter) => write!(f, "{ip_filter}"),
+            HostPattern::Asn(number) => write!(f, "AS{number}"),
+        }
+    }
+}
+
+/// Checks whether the text is matched by the pattern, where `*` matches any sequence of characters
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // no wildcards
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::networking::types::asn::Asn;
+
+    use super::*;
+
+    #[rstest]
+    #[case("AS15169", Some(HostPattern::Asn(15169)))]
+    #[case(" as13335 ", Some(HostPattern::Asn(13335)))]
+    #[case("us", Some(HostPattern::Country("US".to_string())))]
+    #[case("XY", None)]
+    #[case("10.0.0.0/8", Some(HostPattern::Address(IpFilter::from_str("10.0.0.0/8").unwrap())))]
+    #[case("1.1.1.1", Some(HostPattern::Address(IpFilter::from_str("1.1.1.1").unwrap())))]
+    #[case("*.Google.com", Some(HostPattern::Domain("*.google.com".to_string())))]
+    #[case("example.com", Some(HostPattern::Domain("example.com".to_string())))]
+    #[case("example", None)]
+    #[case("exa mple.com", None)]
+    #[case("", None)]
+    fn test_host_pattern_from_str(#[case] value: &str, #[case] expected: Option<HostPattern>) {
+        assert_eq!(HostPattern::from_str(value), expected);
+    }
+
+    #[rstest]
+    #[case("*.example.com", "www.example.com", true)]
+    #[case("*.example.com", "example.com", false)]
+    #[case("*example.com", "example.com", true)]
+    #[case("a*b*c", "abc", true)]
+    #[case("a*b*c", "a-b-b-c", true)]
+    #[case("a*bc", "abc-bc", true)]
+    #[case("a*b*c", "acb", false)]
+    #[case("ab*ba", "aba", false)]
+    #[case("example.com", "example.com", true)]
+    #[case("example.com", "www.example.com", false)]
+    fn test_wildcard_matches(#[case] pattern: &str, #[case] text: &str, #[case] expected: bool) {
+        assert_eq!(wildcard_matches(pattern, text), expected);
+    }
+
+    #[test]
+    fn test_host_pattern_matches() {
+        let host = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let r_dns = "mil04s44-in-f4.1e100.net";
+        let address = "142.250.180.4";
+        let matches = |pattern: &str| {
+            HostPattern::from_str(pattern)
+                .unwrap()
+                .matches(address, r_dns, &host)
+        };
+        assert!(matches("*.1e100.net"));
+        assert!(matches("mil04s44-*"));
+        assert!(matches("1e100.net"));
+        assert!(!matches("*.google.com"));
+        assert!(matches("AS15169"));
+        assert!(!matches("AS13335"));
+        assert!(matches("US"));
+        assert!(!matches("IT"));
+        assert!(matches("142.250.0.0/16"));
+        assert!(matches("142.250.180.1-142.250.180.9"));
+        assert!(!matches("142.251.0.0/16"));
+        assert!(HostPattern::Host(host.clone()).matches(address, r_dns, &host));
+    }
+}
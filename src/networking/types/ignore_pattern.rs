@@ -0,0 +1,66 @@
+//! Module defining the `IgnorePattern` enum, which represents an entry of the ignore list.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::host_pattern::HostPattern;
+use crate::AppProtocol;
+
+/// An entry of the ignore list, whose matching traffic is hidden from the views and notifications.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+// adjacently tagged since the TOML serializer doesn't support newtype variants
+#[serde(tag = "kind", content = "value")]
+pub enum IgnorePattern {
+    /// Application layer protocol
+    AppProtocol(AppProtocol),
+    /// Pattern matching one or more hosts
+    Host(HostPattern),
+}
+
+impl IgnorePattern {
+    /// Parses a pattern inserted by the user; returns `None` if not valid.
+    ///
+    /// Accepted forms are the names of the application protocols, and all the host patterns.
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(app_protocol) = AppProtocol::ALL.iter().find(|app_protocol| {
+            app_protocol.ne(&&AppProtocol::Other)
+                && format!("{app_protocol:?}").eq_ignore_ascii_case(value)
+        }) {
+            return Some(IgnorePattern::AppProtocol(*app_protocol));
+        }
+        HostPattern::from_str(value).map(IgnorePattern::Host)
+    }
+}
+
+impl fmt::Display for IgnorePattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IgnorePattern::AppProtocol(app_protocol) => write!(f, "{app_protocol}"),
+            IgnorePattern::Host(host_pattern) => write!(f, "{host_pattern}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::networking::types::address_filter::IpFilter;
+
+    use super::*;
+
+    #[rstest]
+    #[case("mdns", Some(IgnorePattern::AppProtocol(AppProtocol::mDNS)))]
+    #[case(" SSDP ", Some(IgnorePattern::AppProtocol(AppProtocol::SSDP)))]
+    #[case("Other", None)]
+    #[case("-", None)]
+    #[case("*.windowsupdate.com", Some(IgnorePattern::Host(HostPattern::Domain("*.windowsupdate.com".to_string()))))]
+    #[case("224.0.0.0/4", Some(IgnorePattern::Host(HostPattern::Address(IpFilter::from_str("224.0.0.0/4").unwrap()))))]
+    #[case("AS8075", Some(IgnorePattern::Host(HostPattern::Asn(8075))))]
+    #[case("not valid", None)]
+    fn test_ignore_pattern_from_str(#[case] value: &str, #[case] expected: Option<IgnorePattern>) {
+        assert_eq!(IgnorePattern::from_str(value), expected);
+    }
+}
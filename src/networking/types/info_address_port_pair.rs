@@ -4,15 +4,17 @@
 use std::fmt;
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::networking::types::traffic_series::TrafficSeries;
 use crate::utils::formatted_strings::get_formatted_bytes_string;
 use crate::AppProtocol;
 
 /// Struct useful to format the output report file and to keep track of statistics about the sniffed traffic.
 ///
 /// Each `InfoAddressPortPair` struct is associated to a single address:port pair.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InfoAddressPortPair {
     /// Source MAC address
     pub mac_address1: String,
@@ -34,6 +36,9 @@ pub struct InfoAddressPortPair {
     pub index: usize,
     /// Determines if the connection is incoming or outgoing
     pub traffic_direction: TrafficDirection,
+    /// Packets and bytes transmitted per second in the recent history
+    #[serde(default)]
+    pub traffic_series: TrafficSeries,
 }
 
 impl Default for InfoAddressPortPair {
@@ -49,20 +54,11 @@ fn default() -> Self {
             very_long_address: false,
             traffic_direction: TrafficDirection::default(),
             index: 0,
+            traffic_series: TrafficSeries::default(),
         }
     }
 }
 
-impl InfoAddressPortPair {
-    pub fn print_gui(&self) -> String {
-        self.to_string()
-            .get(0..35)
-            .unwrap()
-            .to_string()
-            .replace('|', "")
-    }
-}
-
 impl fmt::Display for InfoAddressPortPair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let bytes_string = get_formatted_bytes_string(self.transmitted_bytes);
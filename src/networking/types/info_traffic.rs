@@ -5,12 +5,19 @@
 
 use indexmap::IndexMap;
 
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_filter::IpFilter;
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
+use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
+use crate::networking::types::host_pattern::HostPattern;
+use crate::networking::types::ignore_pattern::IgnorePattern;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::label::Label;
 use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::networking::types::traffic_series::TrafficSeries;
 use crate::AppProtocol;
 
 /// Struct to be shared between the threads in charge of parsing packets and update reports.
@@ -35,6 +42,19 @@ pub struct InfoTraffic {
     pub addresses_last_interval: HashSet<usize>,
     /// Collection of the favorite hosts
     pub favorite_hosts: HashSet<Host>,
+    /// Favorites matching hosts by domain, Autonomous System, country or address
+    pub favorite_patterns: Vec<HostPattern>,
+    /// Application protocols of the ignore list
+    pub ignored_app_protocols: HashSet<AppProtocol>,
+    /// Patterns of the ignore list matching hosts by domain, Autonomous System or country
+    pub ignored_hosts: Vec<HostPattern>,
+    /// IP addresses, networks and ranges of the ignore list
+    ///
+    /// Kept apart from the host patterns since they only hide the matched addresses,
+    /// and not the other addresses of the same host
+    pub ignored_addresses: Vec<IpFilter>,
+    /// Aliases, tags and notes assigned to addresses and hosts
+    pub labels: Vec<Label>,
     /// Collection of favorite hosts that exchanged data in the last interval
     pub favorites_last_interval: HashSet<Host>,
     /// Map of the application layer protocols with their data info
@@ -45,6 +65,9 @@ pub struct InfoTraffic {
     pub addresses_resolved: HashMap<String, (String, Host)>,
     /// Map of the hosts with their data info
     pub hosts: HashMap<Host, DataInfoHost>,
+    /// Filters on the collected traffic: connections not matching them are kept in the map,
+    /// but are excluded from the totals, application protocols and hosts data
+    pub display_filters: Filters,
 }
 
 impl InfoTraffic {
@@ -61,11 +84,213 @@ pub fn new() -> Self {
             map: IndexMap::new(),
             addresses_last_interval: HashSet::new(),
             favorite_hosts: HashSet::new(),
+            favorite_patterns: Vec::new(),
+            ignored_app_protocols: HashSet::new(),
+            ignored_hosts: Vec::new(),
+            ignored_addresses: Vec::new(),
+            labels: Vec::new(),
             favorites_last_interval: HashSet::new(),
             app_protocols: HashMap::new(),
             addresses_waiting_resolution: HashMap::new(),
             addresses_resolved: HashMap::new(),
             hosts: HashMap::new(),
+            display_filters: Filters::default(),
+        }
+    }
+
+    /// Constructs a new `InfoTraffic` element with the given favorites, ignore list and labels.
+    pub fn with_user_lists(
+        favorites: &[HostPattern],
+        ignored: &[IgnorePattern],
+        labels: &[Label],
+    ) -> Self {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.set_favorites(favorites);
+        info_traffic.set_ignored(ignored);
+        info_traffic.labels = labels.to_vec();
+        info_traffic
+    }
+
+    /// Replaces the favorites, and updates the favorite status of the hosts accordingly
+    pub fn set_favorites(&mut self, favorites: &[HostPattern]) {
+        self.favorite_hosts = HashSet::new();
+        self.favorite_patterns = Vec::new();
+        for favorite in favorites {
+            match favorite {
+                HostPattern::Host(host) => {
+                    self.favorite_hosts.insert(host.clone());
+                }
+                pattern => self.favorite_patterns.push(pattern.clone()),
+            }
+        }
+        self.update_hosts_status();
+    }
+
+    /// Replaces the ignore list, and updates the ignored status of the hosts
+    /// and the traffic statistics accordingly
+    pub fn set_ignored(&mut self, ignored: &[IgnorePattern]) {
+        self.ignored_app_protocols = HashSet::new();
+        self.ignored_hosts = Vec::new();
+        self.ignored_addresses = Vec::new();
+        for pattern in ignored {
+            match pattern {
+                IgnorePattern::AppProtocol(app_protocol) => {
+                    self.ignored_app_protocols.insert(*app_protocol);
+                }
+                IgnorePattern::Host(HostPattern::Address(ip_filter)) => {
+                    self.ignored_addresses.push(*ip_filter);
+                }
+                IgnorePattern::Host(host_pattern) => self.ignored_hosts.push(host_pattern.clone()),
+            }
+        }
+        self.update_hosts_status();
+        self.apply_display_filters();
+    }
+
+    /// Updates the favorite and ignored status of all the hosts, according to the current favorites and ignore list
+    pub fn update_hosts_status(&mut self) {
+        let mut favorite_hosts: HashSet<&Host> = HashSet::new();
+        let mut ignored_hosts: HashSet<&Host> = HashSet::new();
+        for (address, (r_dns, host)) in &self.addresses_resolved {
+            if self.is_favorite(address, r_dns, host) {
+                favorite_hosts.insert(host);
+            }
+            if self.is_ignored_host(address, r_dns, host) {
+                ignored_hosts.insert(host);
+            }
+        }
+        for (host, data_info_host) in &mut self.hosts {
+            data_info_host.is_favorite =
+                self.favorite_hosts.contains(host) || favorite_hosts.contains(host);
+            data_info_host.is_ignored = ignored_hosts.contains(host);
+        }
+    }
+
+    /// Checks whether a host is one of the favorites, or is matched by a favorite pattern
+    ///
+    /// `address` is one of the addresses of the host, and `r_dns` the full reverse DNS of that address
+    pub fn is_favorite(&self, address: &str, r_dns: &str, host: &Host) -> bool {
+        self.favorite_hosts.contains(host)
+            || self
+                .favorite_patterns
+                .iter()
+                .any(|pattern| pattern.matches(address, r_dns, host))
+    }
+
+    /// Checks whether an address is matched by the address patterns of the favorites
+    /// (unlike the other patterns, these are known to match since the first packet,
+    /// without waiting for the rDNS resolution)
+    pub fn is_favorite_address(&self, address: &str) -> bool {
+        self.favorite_patterns.iter().any(|pattern| {
+            matches!(pattern, HostPattern::Address(ip_filter) if ip_filter.contains_str(address))
+        })
+    }
+
+    /// Checks whether the traffic exchanged with the given address involves a favorite,
+    /// matched either by address or through the host of the address, once resolved
+    pub fn is_favorite_connection(&self, address_to_lookup: &str) -> bool {
+        self.is_favorite_address(address_to_lookup)
+            || self
+                .addresses_resolved
+                .get(address_to_lookup)
+                .and_then(|(_, host)| self.hosts.get(host))
+                .is_some_and(|data_info_host| data_info_host.is_favorite)
+    }
+
+    /// Checks whether a host is matched by the host patterns of the ignore list
+    /// (address patterns are checked for each address by [`InfoTraffic::is_ignored`])
+    ///
+    /// `address` is one of the addresses of the host, and `r_dns` the full reverse DNS of that address
+    pub fn is_ignored_host(&self, address: &str, r_dns: &str, host: &Host) -> bool {
+        self.ignored_hosts
+            .iter()
+            .any(|pattern| pattern.matches(address, r_dns, host))
+    }
+
+    /// Checks whether the traffic exchanged with the given address using the given application protocol
+    /// is matched by the ignore list
+    pub fn is_ignored(&self, address_to_lookup: &str, app_protocol: AppProtocol) -> bool {
+        if self.ignored_app_protocols.contains(&app_protocol)
+            || self
+                .ignored_addresses
+                .iter()
+                .any(|ip_filter| ip_filter.contains_str(address_to_lookup))
+        {
+            return true;
+        }
+        self.addresses_resolved
+            .get(address_to_lookup)
+            .and_then(|(_, host)| self.hosts.get(host))
+            .is_some_and(|data_info_host| data_info_host.is_ignored)
+    }
+
+    /// Checks whether a host is shown in the views: it must not be ignored and,
+    /// if some display filters are active, part of its traffic must be matched by them
+    pub fn is_host_displayed(&self, data_info_host: &DataInfoHost) -> bool {
+        !data_info_host.is_ignored
+            && (self.display_filters.eq(&Filters::default())
+                || data_info_host.data_info.tot_packets() > 0)
+    }
+
+    /// Checks whether a connection is matched by the display filters
+    pub fn is_displayed(&self, key: &AddressPortPair, app_protocol: AppProtocol) -> bool {
+        self.display_filters
+            .matches(Filters::of_connection(key, app_protocol))
+    }
+
+    /// Replaces the display filters, and recomputes the traffic statistics accordingly
+    pub fn set_display_filters(&mut self, display_filters: Filters) {
+        self.display_filters = display_filters;
+        self.apply_display_filters();
+    }
+
+    /// Recomputes the totals, the application protocols and the hosts data
+    /// from the collected connections matching the display filters (and not ignored)
+    pub fn apply_display_filters(&mut self) {
+        let mut totals = DataInfo::default();
+        let mut app_protocols: HashMap<AppProtocol, DataInfo> = HashMap::new();
+        let mut hosts: HashMap<Host, (DataInfo, TrafficSeries)> = HashMap::new();
+        let mut addresses_waiting_resolution: HashMap<String, DataInfo> = self
+            .addresses_waiting_resolution
+            .keys()
+            .map(|address| (address.clone(), DataInfo::default()))
+            .collect();
+        for (key, val) in &self.map {
+            let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
+            if !self.is_displayed(key, val.app_protocol)
+                || self.is_ignored(&address_to_lookup, val.app_protocol)
+            {
+                continue;
+            }
+            let data_info = DataInfo::new_with_packets(
+                val.transmitted_packets,
+                val.transmitted_bytes,
+                val.traffic_direction,
+            );
+            totals += data_info;
+            *app_protocols.entry(val.app_protocol).or_default() += data_info;
+            if let Some((_, host)) = self.addresses_resolved.get(&address_to_lookup) {
+                let (host_data_info, host_series) = hosts.entry(host.clone()).or_default();
+                *host_data_info += data_info;
+                host_series.merge(&val.traffic_series);
+            } else if let Some(waiting_data_info) =
+                addresses_waiting_resolution.get_mut(&address_to_lookup)
+            {
+                *waiting_data_info += data_info;
+            }
+        }
+
+        self.tot_received_packets = totals.incoming_packets;
+        self.tot_sent_packets = totals.outgoing_packets;
+        self.tot_received_bytes = totals.incoming_bytes;
+        self.tot_sent_bytes = totals.outgoing_bytes;
+        self.app_protocols = app_protocols;
+        self.addresses_waiting_resolution = addresses_waiting_resolution;
+        // hosts without displayed traffic are kept (with no data) to preserve their properties
+        for (host, data_info_host) in &mut self.hosts {
+            let (data_info, traffic_series) = hosts.remove(host).unwrap_or_default();
+            data_info_host.data_info = data_info;
+            data_info_host.traffic_series = traffic_series;
         }
     }
 
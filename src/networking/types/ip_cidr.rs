@@ -0,0 +1,103 @@
+use std::fmt;
+use std::net::IpAddr;
+
+/// Struct representing an IP network in CIDR notation (e.g. `192.168.1.0/24`).
+///
+/// A plain IP address is interpreted as a network containing only that address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IpCidr {
+    /// Network address
+    pub address: IpAddr,
+    /// Number of bits of the network prefix
+    pub prefix: u8,
+}
+
+impl IpCidr {
+    /// Parses a string in the form `address` or `address/prefix`; returns `None` if invalid
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (address_str, prefix_str) = match value.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (value, None),
+        };
+        let address = address_str.trim().parse::<IpAddr>().ok()?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix_str {
+            Some(prefix) => prefix.trim().parse::<u8>().ok()?,
+            None => max_prefix,
+        };
+        if prefix > max_prefix {
+            return None;
+        }
+        Some(Self { address, prefix })
+    }
+
+    /// Checks whether the given address belongs to this network
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.address, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(*address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(*address) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks whether the given address string belongs to this network
+    pub fn contains_str(&self, address: &str) -> bool {
+        address
+            .parse::<IpAddr>()
+            .is_ok_and(|address| self.contains(&address))
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_prefix = if self.address.is_ipv4() { 32 } else { 128 };
+        if self.prefix == max_prefix {
+            // plain IP address
+            write!(f, "{}", self.address)
+        } else {
+            write!(f, "{}/{}", self.address, self.prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("10.0.0.0/8", "10.255.1.2", true)]
+    #[case("10.0.0.0/8", "11.0.0.1", false)]
+    #[case("192.168.1.7", "192.168.1.7", true)]
+    #[case("192.168.1.7", "192.168.1.8", false)]
+    #[case("0.0.0.0/0", "8.8.8.8", true)]
+    #[case("2001:db8::/32", "2001:db8:abcd::1", true)]
+    #[case("2001:db8::/32", "2001:db9::1", false)]
+    #[case("2001:db8::/32", "10.0.0.1", false)]
+    fn test_cidr_contains(#[case] cidr: &str, #[case] address: &str, #[case] expected: bool) {
+        let cidr = IpCidr::from_str(cidr).unwrap();
+        assert_eq!(cidr.contains_str(address), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("google.com")]
+    #[case("10.0.0.0/33")]
+    #[case("::1/129")]
+    #[case("10.0.0.0/")]
+    fn test_invalid_cidr(#[case] value: &str) {
+        assert_eq!(IpCidr::from_str(value), None);
+    }
+}
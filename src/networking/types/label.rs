@@ -0,0 +1,278 @@
+//! Module defining the `Label` struct, which permits to assign an alias, tags and notes
+//! to IP addresses, MAC addresses or hosts.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::gui::styles::types::tag_color::TagColor;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::utils::formatted_strings::get_host_label;
+
+/// Element a label is assigned to.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+// adjacently tagged since the TOML serializer doesn't support newtype variants
+#[serde(tag = "kind", content = "value")]
+pub enum LabelTarget {
+    /// IP address
+    Address(String),
+    /// MAC address
+    Mac(String),
+    /// Remote host
+    Host(Host),
+}
+
+impl LabelTarget {
+    /// Parses an IP or MAC address inserted by the user; returns `None` if not valid.
+    pub fn from_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(address) = value.parse::<IpAddr>() {
+            return Some(LabelTarget::Address(address.to_string()));
+        }
+        let is_mac = value.split(':').count() == 6
+            && value
+                .split(':')
+                .all(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_hexdigit()));
+        is_mac.then(|| LabelTarget::Mac(value.to_lowercase()))
+    }
+}
+
+impl fmt::Display for LabelTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LabelTarget::Address(address) | LabelTarget::Mac(address) => write!(f, "{address}"),
+            LabelTarget::Host(host) => write!(f, "{}", get_host_label(host)),
+        }
+    }
+}
+
+/// A tag, with its color.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct Tag {
+    pub name: String,
+    pub color: TagColor,
+}
+
+/// Alias, tags and notes assigned by the user to an IP address, a MAC address or a host.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct Label {
+    /// Friendly name (e.g., "NAS")
+    pub alias: String,
+    /// Free-text notes
+    pub note: String,
+    // tags and target are declared last since the TOML serializer
+    // doesn't support values after tables
+    pub tags: Vec<Tag>,
+    pub target: LabelTarget,
+}
+
+impl Label {
+    pub fn new(target: LabelTarget) -> Self {
+        Label {
+            alias: String::new(),
+            note: String::new(),
+            tags: Vec::new(),
+            target,
+        }
+    }
+
+    /// Whether the label doesn't carry any information
+    pub fn is_empty(&self) -> bool {
+        self.alias.trim().is_empty() && self.note.trim().is_empty() && self.tags.is_empty()
+    }
+
+    /// Whether the label has a tag containing the given lowercase text
+    pub fn has_tag(&self, text: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tag.name.to_lowercase().contains(text))
+    }
+}
+
+/// Returns the label assigned to the given target, if any
+pub fn get_label<'a>(labels: &'a [Label], target: &LabelTarget) -> Option<&'a Label> {
+    labels.iter().find(|label| label.target.eq(target))
+}
+
+/// Returns the label of a host: the one assigned to the host itself or,
+/// for hosts identified by their IP address, the one assigned to that address
+pub fn get_host_label_entry<'a>(labels: &'a [Label], host: &Host) -> Option<&'a Label> {
+    get_label(labels, &LabelTarget::Host(host.clone()))
+        .or_else(|| get_label(labels, &LabelTarget::Address(host.domain.clone())))
+}
+
+/// Returns the label of an endpoint: the one assigned to its IP address or, if missing,
+/// the one assigned to its MAC address
+pub fn get_address_label_entry<'a>(
+    labels: &'a [Label],
+    address: &str,
+    mac: &str,
+) -> Option<&'a Label> {
+    get_label(labels, &LabelTarget::Address(address.to_string()))
+        .or_else(|| get_label(labels, &LabelTarget::Mac(mac.to_lowercase())))
+}
+
+/// Returns all the labels applying to a connection: the ones of its addresses,
+/// of its MAC addresses, and of its remote host
+pub fn get_connection_labels<'a>(
+    labels: &'a [Label],
+    key: &AddressPortPair,
+    val: &InfoAddressPortPair,
+    host: Option<&Host>,
+) -> Vec<&'a Label> {
+    labels
+        .iter()
+        .filter(|label| match &label.target {
+            LabelTarget::Address(address) => key.address1.eq(address) || key.address2.eq(address),
+            LabelTarget::Mac(mac) => {
+                val.mac_address1.eq_ignore_ascii_case(mac)
+                    || val.mac_address2.eq_ignore_ascii_case(mac)
+            }
+            LabelTarget::Host(labeled_host) => host.is_some_and(|host| host.eq(labeled_host)),
+        })
+        .collect()
+}
+
+/// Returns the given text preceded by the alias of the label, if any
+pub fn with_alias(label: Option<&Label>, text: &str) -> String {
+    match label.map(|label| label.alias.trim()) {
+        Some(alias) if !alias.is_empty() => {
+            if text.is_empty() {
+                alias.to_string()
+            } else {
+                format!("{alias} ({text})")
+            }
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Label being composed in the label editor
+#[derive(Clone, Debug)]
+pub struct LabelDraft {
+    pub label: Label,
+    /// Name of the tag being inserted
+    pub tag_name: String,
+    /// Color of the tag being inserted
+    pub tag_color: TagColor,
+}
+
+impl LabelDraft {
+    pub fn new(label: Label) -> Self {
+        LabelDraft {
+            label,
+            tag_name: String::new(),
+            tag_color: TagColor::default(),
+        }
+    }
+
+    /// Returns the draft with the tag being inserted added to the label
+    /// (if not empty and not already present)
+    #[must_use]
+    pub fn with_new_tag(mut self) -> Self {
+        let name = self.tag_name.trim().to_string();
+        if !name.is_empty() && !self.label.tags.iter().any(|tag| tag.name.eq(&name)) {
+            self.label.tags.push(Tag {
+                name,
+                color: self.tag_color,
+            });
+        }
+        self.tag_name = String::new();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::countries::types::country::Country;
+    use crate::TransProtocol;
+
+    use super::*;
+
+    fn label(target: LabelTarget, alias: &str) -> Label {
+        Label {
+            alias: alias.to_string(),
+            ..Label::new(target)
+        }
+    }
+
+    #[rstest]
+    #[case("192.168.1.5", Some(LabelTarget::Address("192.168.1.5".to_string())))]
+    #[case(" ::1 ", Some(LabelTarget::Address("::1".to_string())))]
+    #[case("AA:bb:cc:dd:ee:ff", Some(LabelTarget::Mac("aa:bb:cc:dd:ee:ff".to_string())))]
+    #[case("aa:bb:cc:dd:ee", None)]
+    #[case("nas.local", None)]
+    fn test_label_target_from_str(#[case] value: &str, #[case] expected: Option<LabelTarget>) {
+        assert_eq!(LabelTarget::from_str(value), expected);
+    }
+
+    #[test]
+    fn test_labels_lookup() {
+        let host = Host {
+            domain: "192.168.1.5".to_string(),
+            asn: Default::default(),
+            country: Country::ZZ,
+        };
+        let labels = vec![
+            label(LabelTarget::Address("192.168.1.5".to_string()), "NAS"),
+            label(LabelTarget::Mac("aa:bb:cc:dd:ee:ff".to_string()), "Router"),
+        ];
+
+        assert_eq!(
+            get_host_label_entry(&labels, &host).map(|label| label.alias.as_str()),
+            Some("NAS")
+        );
+        assert_eq!(
+            get_address_label_entry(&labels, "10.0.0.1", "AA:BB:CC:DD:EE:FF")
+                .map(|label| label.alias.as_str()),
+            Some("Router")
+        );
+        assert!(get_address_label_entry(&labels, "10.0.0.1", "").is_none());
+
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            50000,
+            "192.168.1.5".to_string(),
+            445,
+            TransProtocol::TCP,
+        );
+        let val = InfoAddressPortPair {
+            mac_address2: "aa:bb:cc:dd:ee:ff".to_string(),
+            ..InfoAddressPortPair::default()
+        };
+        assert_eq!(get_connection_labels(&labels, &key, &val, None).len(), 2);
+
+        assert_eq!(
+            with_alias(labels.first(), "192.168.1.5"),
+            "NAS (192.168.1.5)"
+        );
+        assert_eq!(with_alias(None, "192.168.1.5"), "192.168.1.5");
+    }
+
+    #[test]
+    fn test_label_draft_with_new_tag() {
+        let mut draft = LabelDraft::new(Label::new(LabelTarget::Address("::1".to_string())));
+        draft.tag_name = " backup ".to_string();
+        draft.tag_color = TagColor::Green;
+        let mut draft = draft.with_new_tag();
+        assert_eq!(
+            draft.label.tags,
+            vec![Tag {
+                name: "backup".to_string(),
+                color: TagColor::Green
+            }]
+        );
+        assert!(draft.tag_name.is_empty());
+        // duplicated tags are not added
+        draft.tag_name = "backup".to_string();
+        let draft = draft.with_new_tag();
+        assert_eq!(draft.label.tags.len(), 1);
+        assert!(draft.label.has_tag("back"));
+        assert!(!draft.label.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod address_filter;
 pub mod address_port_pair;
 pub mod app_protocol;
 pub mod asn;
@@ -6,11 +7,17 @@
 pub mod data_info_host;
 pub mod filters;
 pub mod host;
+pub mod host_pattern;
+pub mod ignore_pattern;
 pub mod info_address_port_pair;
 pub mod info_traffic;
+pub mod ip_cidr;
 pub mod ip_version;
+pub mod label;
 pub mod my_device;
 pub mod search_parameters;
+pub mod search_query;
 pub mod traffic_direction;
+pub mod traffic_series;
 pub mod traffic_type;
 pub mod trans_protocol;
@@ -1,3 +1,7 @@
+use crate::networking::types::address_filter::{IpFilter, PortFilter};
+use crate::networking::types::host::Host;
+use crate::report::types::host_grouping::HostGrouping;
+
 /// Used to express the search filters applied to GUI inspect page
 #[derive(Clone, Debug, Default, Hash)]
 pub struct SearchParameters {
@@ -11,21 +15,136 @@ pub struct SearchParameters {
     pub as_name: String,
     /// Whether to display only favorites
     pub only_favorites: bool,
+    /// Exact host (or group of hosts, according to the grouping) selected in the hosts page
+    pub host: Option<(HostGrouping, Host)>,
+    /// IP address, network or range of either the source or the destination
+    pub address: String,
+    /// IP address, network or range of the source
+    pub source: String,
+    /// IP address, network or range of the destination
+    pub destination: String,
+    /// Port or range of ports of either the source or the destination
+    pub port: String,
+    /// Search expression (e.g., `country:US and bytes>10MB`)
+    pub query: String,
 }
 
 impl SearchParameters {
     pub fn is_some_filter_active(&self) -> bool {
         self.only_favorites
+            || self.host.is_some()
             || !self.app.is_empty()
             || !self.domain.is_empty()
             || !self.country.is_empty()
             || !self.as_name.is_empty()
+            || !self.address.is_empty()
+            || !self.source.is_empty()
+            || !self.destination.is_empty()
+            || !self.port.is_empty()
+            || !self.query.trim().is_empty()
+    }
+
+    /// Value of the given filter
+    pub fn get(&self, filter: FilterInputType) -> &str {
+        match filter {
+            FilterInputType::App => &self.app,
+            FilterInputType::Domain => &self.domain,
+            FilterInputType::Country => &self.country,
+            FilterInputType::AS => &self.as_name,
+            FilterInputType::Address => &self.address,
+            FilterInputType::Source => &self.source,
+            FilterInputType::Destination => &self.destination,
+            FilterInputType::Port => &self.port,
+            FilterInputType::Query => &self.query,
+        }
+    }
+
+    /// Mutable reference to the value of the given filter
+    pub fn get_mut(&mut self, filter: FilterInputType) -> &mut String {
+        match filter {
+            FilterInputType::App => &mut self.app,
+            FilterInputType::Domain => &mut self.domain,
+            FilterInputType::Country => &mut self.country,
+            FilterInputType::AS => &mut self.as_name,
+            FilterInputType::Address => &mut self.address,
+            FilterInputType::Source => &mut self.source,
+            FilterInputType::Destination => &mut self.destination,
+            FilterInputType::Port => &mut self.port,
+            FilterInputType::Query => &mut self.query,
+        }
+    }
+
+    /// Returns the filter supporting autocompletion whose value differs in the given parameters, if any
+    pub fn edited_autocomplete_filter(&self, other: &SearchParameters) -> Option<FilterInputType> {
+        FilterInputType::AUTOCOMPLETE
+            .into_iter()
+            .find(|filter| self.get(*filter).ne(other.get(*filter)))
+    }
+
+    /// Describes the first address or port filter with an invalid value, if any
+    pub fn invalid_address_filter(&self) -> Option<String> {
+        for address in [&self.address, &self.source, &self.destination] {
+            if !address.is_empty() && IpFilter::from_str(address).is_none() {
+                return Some(format!(
+                    "invalid IP address, network or range '{address}' (e.g. 10.0.0.1, 10.0.0.0/8, 10.0.0.1-10.0.0.9)"
+                ));
+            }
+        }
+        if !self.port.is_empty() && PortFilter::from_str(&self.port).is_none() {
+            return Some(format!(
+                "invalid port or range of ports '{}' (e.g. 443, 8000-8080)",
+                self.port
+            ));
+        }
+        None
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 pub enum FilterInputType {
     App,
     Domain,
     Country,
     AS,
+    Address,
+    Source,
+    Destination,
+    Port,
+    Query,
+}
+
+impl FilterInputType {
+    /// Filters whose values can be autocompleted with the ones observed in the captured traffic
+    pub const AUTOCOMPLETE: [FilterInputType; 4] = [
+        FilterInputType::App,
+        FilterInputType::Domain,
+        FilterInputType::Country,
+        FilterInputType::AS,
+    ];
+}
+
+/// Filter input of the inspect page whose suggestions are displayed,
+/// with the suggestion currently selected by means of the arrow keys
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Autocomplete {
+    pub filter: FilterInputType,
+    pub selected: Option<usize>,
+}
+
+impl Autocomplete {
+    /// Moves the selection to the next (previous) suggestion if true (false), wrapping around
+    pub fn move_selection(&mut self, next: bool, suggestions_len: usize) {
+        if suggestions_len == 0 {
+            self.selected = None;
+            return;
+        }
+        self.selected = Some(match (self.selected, next) {
+            (None, true) => 0,
+            (None, false) => suggestions_len - 1,
+            (Some(i), true) => (i + 1) % suggestions_len,
+            (Some(i), false) => {
+                (i.min(suggestions_len - 1) + suggestions_len - 1) % suggestions_len
+            }
+        });
+    }
 }
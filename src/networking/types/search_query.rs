@@ -0,0 +1,926 @@
+//! Module defining the search language of the inspect page, which permits to filter connections
+//! with expressions like `country:US and bytes>10MB and port:443 and not asn:"Google"`.
+//!
+//! An expression is made of conditions in the form `field operator value`, combined with
+//! `and` (also implied between adjacent conditions), `or`, `not` and parentheses.
+//! A word without field matches the connections having it in their domain, AS name,
+//! addresses, application protocol, country, or in the aliases and tags of their labels.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::networking::types::address_filter::IpFilter;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::byte_multiple::{from_char_to_multiple, ByteMultiple};
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::label::Label;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Fields that can be used in the conditions of a search expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchField {
+    Country,
+    Domain,
+    Asn,
+    App,
+    Proto,
+    Ip,
+    SrcIp,
+    DstIp,
+    Port,
+    SrcPort,
+    DstPort,
+    Bytes,
+    Packets,
+    Direction,
+    Favorite,
+    Tag,
+}
+
+impl SearchField {
+    pub(crate) const ALL: [SearchField; 16] = [
+        SearchField::Country,
+        SearchField::Domain,
+        SearchField::Asn,
+        SearchField::App,
+        SearchField::Proto,
+        SearchField::Ip,
+        SearchField::SrcIp,
+        SearchField::DstIp,
+        SearchField::Port,
+        SearchField::SrcPort,
+        SearchField::DstPort,
+        SearchField::Bytes,
+        SearchField::Packets,
+        SearchField::Direction,
+        SearchField::Favorite,
+        SearchField::Tag,
+    ];
+
+    /// Name of the field in search expressions
+    pub fn name(self) -> &'static str {
+        match self {
+            SearchField::Country => "country",
+            SearchField::Domain => "domain",
+            SearchField::Asn => "asn",
+            SearchField::App => "app",
+            SearchField::Proto => "proto",
+            SearchField::Ip => "ip",
+            SearchField::SrcIp => "src",
+            SearchField::DstIp => "dst",
+            SearchField::Port => "port",
+            SearchField::SrcPort => "sport",
+            SearchField::DstPort => "dport",
+            SearchField::Bytes => "bytes",
+            SearchField::Packets => "packets",
+            SearchField::Direction => "dir",
+            SearchField::Favorite => "fav",
+            SearchField::Tag => "tag",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let field = match name.as_str() {
+            "cc" => SearchField::Country,
+            "host" => SearchField::Domain,
+            "as" => SearchField::Asn,
+            "l7" => SearchField::App,
+            "l4" => SearchField::Proto,
+            "addr" => SearchField::Ip,
+            "direction" => SearchField::Direction,
+            "favorite" => SearchField::Favorite,
+            "label" => SearchField::Tag,
+            _ => *SearchField::ALL
+                .iter()
+                .find(|field| field.name().eq(&name))?,
+        };
+        Some(field)
+    }
+
+    /// Whether the field supports the ordering operators (`<`, `<=`, `>`, `>=`)
+    fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            SearchField::Asn
+                | SearchField::Port
+                | SearchField::SrcPort
+                | SearchField::DstPort
+                | SearchField::Bytes
+                | SearchField::Packets
+        )
+    }
+}
+
+/// Operators comparing a field with a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchOperator {
+    /// `:` (contains for text fields, equals for the others)
+    Matches,
+    /// `=`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+impl SearchOperator {
+    fn symbol(self) -> &'static str {
+        match self {
+            SearchOperator::Matches => ":",
+            SearchOperator::Equal => "=",
+            SearchOperator::NotEqual => "!=",
+            SearchOperator::Less => "<",
+            SearchOperator::LessOrEqual => "<=",
+            SearchOperator::Greater => ">",
+            SearchOperator::GreaterOrEqual => ">=",
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        matches!(
+            self,
+            SearchOperator::Less
+                | SearchOperator::LessOrEqual
+                | SearchOperator::Greater
+                | SearchOperator::GreaterOrEqual
+        )
+    }
+
+    fn compare<T: Ord>(self, value: &T, other: &T) -> bool {
+        let ordering = value.cmp(other);
+        match self {
+            SearchOperator::Matches | SearchOperator::Equal => ordering.eq(&Ordering::Equal),
+            SearchOperator::NotEqual => ordering.ne(&Ordering::Equal),
+            SearchOperator::Less => ordering.eq(&Ordering::Less),
+            SearchOperator::LessOrEqual => ordering.ne(&Ordering::Greater),
+            SearchOperator::Greater => ordering.eq(&Ordering::Greater),
+            SearchOperator::GreaterOrEqual => ordering.ne(&Ordering::Less),
+        }
+    }
+}
+
+/// Value of a condition, already validated for its field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchValue {
+    /// Lowercase text
+    Text(String),
+    Number(u128),
+    Network(IpFilter),
+    Direction(TrafficDirection),
+    Bool(bool),
+}
+
+/// A condition in the form `field operator value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchCondition {
+    pub field: SearchField,
+    pub operator: SearchOperator,
+    pub value: SearchValue,
+}
+
+/// A parsed search expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchQuery {
+    And(Box<SearchQuery>, Box<SearchQuery>),
+    Or(Box<SearchQuery>, Box<SearchQuery>),
+    Not(Box<SearchQuery>),
+    Condition(SearchCondition),
+    /// Lowercase text to be found in any of the main fields
+    Text(String),
+}
+
+/// Error found while parsing a search expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQueryError {
+    pub message: String,
+    /// Position (in characters) of the error in the expression
+    pub position: usize,
+}
+
+impl fmt::Display for SearchQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.position + 1)
+    }
+}
+
+/// Connection against which a search expression is evaluated
+pub struct SearchTarget<'a> {
+    pub key: &'a AddressPortPair,
+    pub val: &'a InfoAddressPortPair,
+    /// Reverse DNS and host of the remote address, if already resolved
+    pub r_dns_host: Option<&'a (String, Host)>,
+    /// Whether the connection involves a favorite (matched by address, or through its host)
+    pub is_favorite: bool,
+    /// Labels of the addresses and of the host of the connection
+    pub labels: Vec<&'a Label>,
+}
+
+impl SearchQuery {
+    /// Parses a search expression; returns `None` if the expression is empty
+    pub fn parse(input: &str) -> Result<Option<Self>, SearchQueryError> {
+        let mut parser = Parser {
+            input,
+            chars: input.char_indices().peekable(),
+            depth: 0,
+            operands: 0,
+        };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Ok(None);
+        }
+        let query = parser.parse_or()?;
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            let index = parser.index();
+            return Err(parser.error_at(index, "unexpected ')'".to_string()));
+        }
+        Ok(Some(query))
+    }
+
+    /// Checks whether the given connection satisfies this expression
+    pub fn matches(&self, target: &SearchTarget) -> bool {
+        match self {
+            SearchQuery::And(a, b) => a.matches(target) && b.matches(target),
+            SearchQuery::Or(a, b) => a.matches(target) || b.matches(target),
+            SearchQuery::Not(a) => !a.matches(target),
+            SearchQuery::Condition(condition) => condition.matches(target),
+            SearchQuery::Text(text) => {
+                let (r_dns, host) = target.r_dns_host.cloned().unwrap_or_default();
+                [
+                    r_dns,
+                    host.asn.name,
+                    host.country.to_string(),
+                    target.key.address1.clone(),
+                    target.key.address2.clone(),
+                    format!("{:?}", target.val.app_protocol),
+                ]
+                .iter()
+                .any(|value| value.to_lowercase().contains(text))
+                    || target.labels.iter().any(|label| {
+                        label.alias.to_lowercase().contains(text) || label.has_tag(text)
+                    })
+            }
+        }
+    }
+
+    /// Whether the expression refers to the remote hosts (which are known only once resolved)
+    ///
+    /// The favorite status isn't included, since address patterns are matched even before the resolution
+    pub fn needs_host(&self) -> bool {
+        match self {
+            SearchQuery::And(a, b) | SearchQuery::Or(a, b) => a.needs_host() || b.needs_host(),
+            SearchQuery::Not(a) => a.needs_host(),
+            SearchQuery::Condition(condition) => matches!(
+                condition.field,
+                SearchField::Country | SearchField::Domain | SearchField::Asn
+            ),
+            SearchQuery::Text(_) => false,
+        }
+    }
+}
+
+impl SearchCondition {
+    fn matches(&self, target: &SearchTarget) -> bool {
+        let operator = self.operator;
+        let (r_dns, host) = target.r_dns_host.cloned().unwrap_or_default();
+        let key = target.key;
+        let val = target.val;
+        match (&self.value, self.field) {
+            (SearchValue::Text(text), SearchField::Country) => {
+                let country = host.country.to_string().to_lowercase();
+                match operator {
+                    SearchOperator::Matches => country.starts_with(text),
+                    _ => operator.compare(&country, text),
+                }
+            }
+            (SearchValue::Text(text), SearchField::Domain) => compare_text(operator, &r_dns, text),
+            (SearchValue::Text(text), SearchField::Asn) => {
+                compare_text(operator, &host.asn.name, text)
+            }
+            (SearchValue::Number(number), SearchField::Asn) => {
+                operator.compare(&u128::from(host.asn.number), number)
+            }
+            (SearchValue::Text(text), SearchField::App) => {
+                operator.compare(&format!("{:?}", val.app_protocol).to_lowercase(), text)
+            }
+            (SearchValue::Text(text), SearchField::Proto) => {
+                operator.compare(&key.trans_protocol.to_string().to_lowercase(), text)
+            }
+            (SearchValue::Network(network), field) => {
+                let addresses = match field {
+                    SearchField::SrcIp => vec![&key.address1],
+                    SearchField::DstIp => vec![&key.address2],
+                    _ => vec![&key.address1, &key.address2],
+                };
+                let contained = addresses
+                    .iter()
+                    .any(|address| network.contains_str(address));
+                contained != operator.eq(&SearchOperator::NotEqual)
+            }
+            (SearchValue::Number(number), SearchField::Port) => {
+                if operator.eq(&SearchOperator::NotEqual) {
+                    u128::from(key.port1).ne(number) && u128::from(key.port2).ne(number)
+                } else {
+                    operator.compare(&u128::from(key.port1), number)
+                        || operator.compare(&u128::from(key.port2), number)
+                }
+            }
+            (SearchValue::Number(number), SearchField::SrcPort) => {
+                operator.compare(&u128::from(key.port1), number)
+            }
+            (SearchValue::Number(number), SearchField::DstPort) => {
+                operator.compare(&u128::from(key.port2), number)
+            }
+            (SearchValue::Number(number), SearchField::Bytes) => {
+                operator.compare(&val.transmitted_bytes, number)
+            }
+            (SearchValue::Number(number), SearchField::Packets) => {
+                operator.compare(&val.transmitted_packets, number)
+            }
+            (SearchValue::Direction(direction), _) => {
+                val.traffic_direction.eq(direction) != operator.eq(&SearchOperator::NotEqual)
+            }
+            (SearchValue::Text(text), SearchField::Tag) => {
+                let has_tag = target.labels.iter().any(|label| match operator {
+                    SearchOperator::Matches => label.has_tag(text),
+                    _ => label
+                        .tags
+                        .iter()
+                        .any(|tag| tag.name.to_lowercase().eq(text)),
+                });
+                has_tag != operator.eq(&SearchOperator::NotEqual)
+            }
+            (SearchValue::Bool(is_favorite), _) => {
+                target.is_favorite.eq(is_favorite) != operator.eq(&SearchOperator::NotEqual)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Compares a text field: `:` checks whether it contains the value, the other operators
+/// compare them ignoring case
+fn compare_text(operator: SearchOperator, field_value: &str, text: &str) -> bool {
+    let field_value = field_value.to_lowercase();
+    match operator {
+        SearchOperator::Matches => field_value.contains(text),
+        _ => operator.compare(&field_value, &text.to_string()),
+    }
+}
+
+/// Maximum nesting of parentheses and negations in a search expression
+const MAX_QUERY_DEPTH: usize = 64;
+/// Maximum number of operands in a search expression
+const MAX_QUERY_OPERANDS: usize = 256;
+
+/// Recursive descent parser of search expressions.
+///
+/// Nesting and operands are bounded, so that neither parsing nor evaluating
+/// (which are both recursive) can overflow the stack.
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    /// Current nesting of parentheses and negations
+    depth: usize,
+    /// Number of operands parsed so far
+    operands: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    /// Byte index of the next character
+    fn index(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map_or(self.input.len(), |(index, _)| *index)
+    }
+
+    fn error_at(&self, index: usize, message: String) -> SearchQueryError {
+        SearchQueryError {
+            message,
+            position: self.input[..index].chars().count(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.chars.next();
+        }
+    }
+
+    /// Consumes the given keyword (case insensitive) if it's the next word
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let is_keyword = self.is_keyword_next(keyword);
+        if is_keyword {
+            for _ in 0..keyword.len() {
+                self.chars.next();
+            }
+        }
+        is_keyword
+    }
+
+    fn parse_or(&mut self) -> Result<SearchQuery, SearchQueryError> {
+        let mut query = self.parse_and()?;
+        while self.eat_keyword("or") || self.eat_symbol("||") {
+            let right = self.parse_operand("or")?;
+            query = SearchQuery::Or(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(&mut self) -> Result<SearchQuery, SearchQueryError> {
+        let mut query = self.parse_unary()?;
+        loop {
+            let right = if self.eat_keyword("and") || self.eat_symbol("&&") {
+                self.parse_operand("and")?
+            } else {
+                self.skip_whitespace();
+                // conditions one after the other are implicitly combined with 'and'
+                match self.peek() {
+                    None | Some(')') => break,
+                    _ if self.is_keyword_next("or")
+                        || self.input[self.index()..].starts_with("||") =>
+                    {
+                        break
+                    }
+                    _ => self.parse_unary()?,
+                }
+            };
+            query = SearchQuery::And(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    /// Whether the given keyword (case insensitive) is the next word
+    fn is_keyword_next(&mut self, keyword: &str) -> bool {
+        let start = self.index();
+        let rest = &self.input[start..];
+        rest.get(..keyword.len())
+            .is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+            && rest[keyword.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c.eq(&'('))
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        self.skip_whitespace();
+        let start = self.index();
+        if self.input[start..].starts_with(symbol) {
+            for _ in 0..symbol.chars().count() {
+                self.chars.next();
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Increases the nesting level, failing if it's too deep
+    fn enter_nested(&mut self, start: usize) -> Result<(), SearchQueryError> {
+        self.depth += 1;
+        if self.depth > MAX_QUERY_DEPTH {
+            return Err(self.error_at(
+                start,
+                format!("expression nested too deeply (at most {MAX_QUERY_DEPTH} levels)"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses the expression following a boolean operator
+    fn parse_operand(&mut self, operator: &str) -> Result<SearchQuery, SearchQueryError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None | Some(')') => {
+                let index = self.index();
+                Err(self.error_at(index, format!("expected an expression after '{operator}'")))
+            }
+            _ => self.parse_unary(),
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<SearchQuery, SearchQueryError> {
+        self.skip_whitespace();
+        let start = self.index();
+        self.operands += 1;
+        if self.operands > MAX_QUERY_OPERANDS {
+            return Err(self.error_at(
+                start,
+                format!("too many conditions (at most {MAX_QUERY_OPERANDS})"),
+            ));
+        }
+        if self.eat_keyword("not") || self.eat_symbol("!") {
+            self.enter_nested(start)?;
+            let operand = self.parse_operand("not")?;
+            self.depth -= 1;
+            return Ok(SearchQuery::Not(Box::new(operand)));
+        }
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                self.skip_whitespace();
+                if self.peek().eq(&Some(')')) {
+                    return Err(self.error_at(start, "empty parentheses".to_string()));
+                }
+                self.enter_nested(start)?;
+                let query = self.parse_or()?;
+                self.depth -= 1;
+                self.skip_whitespace();
+                if self.peek().eq(&Some(')')) {
+                    self.chars.next();
+                    Ok(query)
+                } else {
+                    Err(self.error_at(start, "missing closing parenthesis".to_string()))
+                }
+            }
+            Some(')') => Err(self.error_at(start, "unexpected ')'".to_string())),
+            Some('"') => Ok(SearchQuery::Text(self.parse_quoted()?.to_lowercase())),
+            _ => self.parse_term(),
+        }
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, SearchQueryError> {
+        let start = self.index();
+        self.chars.next();
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(value),
+                Some((_, '\\')) => {
+                    if let Some((_, c)) = self.chars.next() {
+                        value.push(c);
+                    }
+                }
+                Some((_, c)) => value.push(c),
+                None => {
+                    return Err(self.error_at(start, "unterminated quoted string".to_string()));
+                }
+            }
+        }
+    }
+
+    /// Parses a condition, or a word to be searched in all the main fields
+    fn parse_term(&mut self) -> Result<SearchQuery, SearchQueryError> {
+        let start = self.index();
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || "_-.*".contains(c) {
+                word.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        let operator_start = self.index();
+        let Some(operator) = self.parse_operator() else {
+            return match self.peek() {
+                Some(c) if word.is_empty() => {
+                    Err(self.error_at(start, format!("unexpected character '{c}'")))
+                }
+                Some(c) if !c.is_whitespace() && c.ne(&')') => {
+                    Err(self.error_at(operator_start, format!("unexpected character '{c}'")))
+                }
+                _ => Ok(SearchQuery::Text(word.to_lowercase())),
+            };
+        };
+        if word.is_empty() {
+            return Err(self.error_at(
+                start,
+                format!("missing field before '{}'", operator.symbol()),
+            ));
+        }
+        let Some(field) = SearchField::from_name(&word) else {
+            let fields: Vec<&str> = SearchField::ALL.iter().map(|f| f.name()).collect();
+            return Err(self.error_at(
+                start,
+                format!(
+                    "unknown field '{word}' (valid fields: {})",
+                    fields.join(", ")
+                ),
+            ));
+        };
+        if operator.is_ordering() && !field.is_numeric() {
+            return Err(self.error_at(
+                operator_start,
+                format!(
+                    "operator '{}' can't be used with field '{}'",
+                    operator.symbol(),
+                    field.name()
+                ),
+            ));
+        }
+        let value_start = self.index();
+        let value = match self.peek() {
+            Some('"') => self.parse_quoted()?,
+            _ => {
+                let mut value = String::new();
+                while let Some(c) = self.peek() {
+                    if c.is_whitespace() || c.eq(&')') {
+                        break;
+                    }
+                    value.push(c);
+                    self.chars.next();
+                }
+                value
+            }
+        };
+        if value.is_empty() {
+            return Err(self.error_at(
+                value_start,
+                format!("missing value after '{word}{}'", operator.symbol()),
+            ));
+        }
+        let value = parse_value(field, operator, &value)
+            .map_err(|message| self.error_at(value_start, message))?;
+        Ok(SearchQuery::Condition(SearchCondition {
+            field,
+            operator,
+            value,
+        }))
+    }
+
+    fn parse_operator(&mut self) -> Option<SearchOperator> {
+        let operator = match self.peek()? {
+            ':' => SearchOperator::Matches,
+            '=' => SearchOperator::Equal,
+            '!' => SearchOperator::NotEqual,
+            '<' => SearchOperator::Less,
+            '>' => SearchOperator::Greater,
+            _ => return None,
+        };
+        let start = self.index();
+        let rest = &self.input[start..];
+        if operator.eq(&SearchOperator::NotEqual) && !rest.starts_with("!=") {
+            return None;
+        }
+        self.chars.next();
+        if self.peek().eq(&Some('=')) {
+            match operator {
+                SearchOperator::NotEqual => {
+                    self.chars.next();
+                }
+                SearchOperator::Less => {
+                    self.chars.next();
+                    return Some(SearchOperator::LessOrEqual);
+                }
+                SearchOperator::Greater => {
+                    self.chars.next();
+                    return Some(SearchOperator::GreaterOrEqual);
+                }
+                _ => {}
+            }
+        }
+        Some(operator)
+    }
+}
+
+/// Validates the value of a condition according to its field
+fn parse_value(
+    field: SearchField,
+    operator: SearchOperator,
+    value: &str,
+) -> Result<SearchValue, String> {
+    let lowercase = value.to_lowercase();
+    match field {
+        SearchField::Country
+        | SearchField::Domain
+        | SearchField::App
+        | SearchField::Proto
+        | SearchField::Tag => Ok(SearchValue::Text(lowercase)),
+        SearchField::Asn => {
+            let number = lowercase.strip_prefix("as").unwrap_or(&lowercase);
+            match number.parse::<u32>() {
+                Ok(number) => Ok(SearchValue::Number(u128::from(number))),
+                Err(_) if operator.is_ordering() => Err(format!("invalid AS number '{value}'")),
+                Err(_) => Ok(SearchValue::Text(lowercase)),
+            }
+        }
+        SearchField::Ip | SearchField::SrcIp | SearchField::DstIp => IpFilter::from_str(value)
+            .map(SearchValue::Network)
+            .ok_or_else(|| format!("invalid IP address, network or range '{value}'")),
+        SearchField::Port | SearchField::SrcPort | SearchField::DstPort => value
+            .parse::<u16>()
+            .map(|port| SearchValue::Number(u128::from(port)))
+            .map_err(|_| format!("invalid port '{value}'")),
+        SearchField::Bytes | SearchField::Packets => {
+            parse_amount(value).map(SearchValue::Number).ok_or_else(|| {
+                format!(
+                    "invalid number '{value}' for field '{}' (e.g. 500, 10K, 1.5MB)",
+                    field.name()
+                )
+            })
+        }
+        SearchField::Direction => match lowercase.as_str() {
+            "in" | "incoming" => Ok(SearchValue::Direction(TrafficDirection::Incoming)),
+            "out" | "outgoing" => Ok(SearchValue::Direction(TrafficDirection::Outgoing)),
+            _ => Err(format!("invalid direction '{value}' (use in or out)")),
+        },
+        SearchField::Favorite => match lowercase.as_str() {
+            "true" | "yes" => Ok(SearchValue::Bool(true)),
+            "false" | "no" => Ok(SearchValue::Bool(false)),
+            _ => Err(format!("invalid value '{value}' (use true or false)")),
+        },
+    }
+}
+
+/// Parses an amount with an optional multiple (e.g. `10MB`, `1.5k`, `500`)
+fn parse_amount(value: &str) -> Option<u128> {
+    let value = value.strip_suffix(['b', 'B']).unwrap_or(value);
+    let (number, multiple) = match value.chars().last()? {
+        c if c.is_ascii_alphabetic() => {
+            let multiple = from_char_to_multiple(c);
+            if multiple.eq(&ByteMultiple::B) {
+                return None;
+            }
+            (&value[..value.len() - 1], multiple)
+        }
+        _ => (value, ByteMultiple::B),
+    };
+    let multiplier = u128::from(multiple.get_multiplier());
+    if let Ok(integer) = number.parse::<u128>() {
+        return integer.checked_mul(multiplier);
+    }
+    let decimal = number.parse::<f64>().ok()?;
+    if !decimal.is_finite() || decimal < 0.0 {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((decimal * multiplier as f64).round() as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::countries::types::country::Country;
+    use crate::gui::styles::types::tag_color::TagColor;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::label::{LabelTarget, Tag};
+    use crate::{AppProtocol, TransProtocol};
+
+    use super::*;
+
+    fn matches(query: &str) -> bool {
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            52000,
+            "142.250.180.14".to_string(),
+            443,
+            TransProtocol::TCP,
+        );
+        let val = InfoAddressPortPair {
+            transmitted_bytes: 20_000_000,
+            transmitted_packets: 15_000,
+            app_protocol: AppProtocol::HTTPS,
+            traffic_direction: TrafficDirection::Outgoing,
+            ..InfoAddressPortPair::default()
+        };
+        let host = Host {
+            domain: "1e100.net".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let label = Label {
+            alias: "Workstation".to_string(),
+            tags: vec![Tag {
+                name: "Office".to_string(),
+                color: TagColor::Blue,
+            }],
+            ..Label::new(LabelTarget::Address("192.168.1.10".to_string()))
+        };
+        SearchQuery::parse(query)
+            .unwrap()
+            .unwrap()
+            .matches(&SearchTarget {
+                key: &key,
+                val: &val,
+                r_dns_host: Some(&("mil04s43-in-f14.1e100.net".to_string(), host)),
+                is_favorite: false,
+                labels: vec![&label],
+            })
+    }
+
+    #[rstest]
+    #[case("country:US and bytes>10MB and port:443", true)]
+    #[case("country:US and bytes>10MB and port:443 and not asn:\"Google\"", false)]
+    #[case("country:it or asn:15169", true)]
+    #[case("cc:u port:80", false)]
+    #[case("(port:80 or port:443) and proto:tcp", true)]
+    #[case("not (port:80 or port:443)", false)]
+    #[case("ip:192.168.0.0/16 and dst:142.250.0.0/15", true)]
+    #[case("src:142.250.0.0/15", false)]
+    #[case("src:192.168.1.1-192.168.1.20", true)]
+    #[case("ip!=10.0.0.0/8", true)]
+    #[case("sport>=50000 dport<1024", true)]
+    #[case("port!=443", false)]
+    #[case("bytes<=20M packets>1.5k", true)]
+    #[case("packets=15000 and app:https", true)]
+    #[case("dir:out and fav:false", true)]
+    #[case("direction:in", false)]
+    #[case("1e100", true)]
+    #[case("\"google\" && domain:net || port:1", true)]
+    #[case("domain=google.com", false)]
+    #[case("asn>=AS15000 asn<16000", true)]
+    #[case("tag:off", true)]
+    #[case("tag=off", false)]
+    #[case("label=office and not tag:home", true)]
+    #[case("tag!=office", false)]
+    #[case("workstation", true)]
+    fn test_search_query_matches(#[case] query: &str, #[case] expected: bool) {
+        assert_eq!(matches(query), expected);
+    }
+
+    #[rstest]
+    #[case("contry:US", "unknown field 'contry'", 0)]
+    #[case("bytes>", "missing value after 'bytes>'", 6)]
+    #[case("bytes>10XB", "invalid number '10XB'", 6)]
+    #[case("domain>abc", "operator '>' can't be used with field 'domain'", 6)]
+    #[case("ip:10.0.0.0/33", "invalid IP address, network or range", 3)]
+    #[case("port:80 and", "expected an expression after 'and'", 11)]
+    #[case("(port:80 or port:443", "missing closing parenthesis", 0)]
+    #[case("port:80)", "unexpected ')'", 7)]
+    #[case("asn:\"Google", "unterminated quoted string", 4)]
+    #[case("port:99999", "invalid port '99999'", 5)]
+    #[case("dir:up", "invalid direction 'up'", 4)]
+    #[case("tag>a", "operator '>' can't be used with field 'tag'", 3)]
+    fn test_search_query_errors(
+        #[case] query: &str,
+        #[case] message: &str,
+        #[case] position: usize,
+    ) {
+        let error = SearchQuery::parse(query).unwrap_err();
+        assert!(error.message.starts_with(message), "{}", error.message);
+        assert_eq!(error.position, position);
+    }
+
+    #[test]
+    fn test_search_query_limits() {
+        let nested = |levels: usize, prefix: &str, suffix: &str| {
+            format!("{}port:80{}", prefix.repeat(levels), suffix.repeat(levels))
+        };
+        assert!(SearchQuery::parse(&nested(MAX_QUERY_DEPTH, "(", ")")).is_ok());
+        assert!(SearchQuery::parse(&nested(MAX_QUERY_DEPTH, "not ", "")).is_ok());
+        // deeply nested expressions are rejected instead of overflowing the stack
+        for query in [nested(100_000, "(", ")"), nested(100_000, "not ", "")] {
+            let error = SearchQuery::parse(&query).unwrap_err();
+            assert!(error.message.starts_with("expression nested too deeply"));
+        }
+        // ...as well as very long chains of conditions
+        let chain = vec!["port:80"; 100_000].join(" or ");
+        let error = SearchQuery::parse(&chain).unwrap_err();
+        assert!(error.message.starts_with("too many conditions"));
+        assert!(SearchQuery::parse(&vec!["port:80"; MAX_QUERY_OPERANDS].join(" and ")).is_ok());
+    }
+
+    #[test]
+    fn test_empty_search_query() {
+        assert_eq!(SearchQuery::parse("   "), Ok(None));
+        assert_eq!(
+            SearchQuery::parse("port:53 or not fav:yes"),
+            Ok(Some(SearchQuery::Or(
+                Box::new(SearchQuery::Condition(SearchCondition {
+                    field: SearchField::Port,
+                    operator: SearchOperator::Matches,
+                    value: SearchValue::Number(53),
+                })),
+                Box::new(SearchQuery::Not(Box::new(SearchQuery::Condition(
+                    SearchCondition {
+                        field: SearchField::Favorite,
+                        operator: SearchOperator::Matches,
+                        value: SearchValue::Bool(true),
+                    }
+                ))))
+            )))
+        );
+    }
+
+    #[rstest]
+    #[case("500", Some(500))]
+    #[case("10MB", Some(10_000_000))]
+    #[case("1.5k", Some(1_500))]
+    #[case("2G", Some(2_000_000_000))]
+    #[case("10XB", None)]
+    #[case("-1", None)]
+    fn test_parse_amount(#[case] value: &str, #[case] expected: Option<u128>) {
+        assert_eq!(parse_amount(value), expected);
+    }
+}
@@ -0,0 +1,131 @@
+//! Module defining the `TrafficSeries` struct, which keeps the recent per-second traffic
+//! of a connection or a host.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Number of seconds of traffic history kept for each connection and host
+pub const SERIES_SECONDS: u32 = 60;
+
+/// Incoming and outgoing packets and bytes exchanged per second, limited to the last [`SERIES_SECONDS`] seconds.
+///
+/// Only the seconds with some traffic are stored, so that idle connections take no space.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrafficSeries {
+    /// Seconds (as Unix timestamps) with some traffic, and the data exchanged in them
+    samples: VecDeque<(i64, DataInfo)>,
+}
+
+impl TrafficSeries {
+    /// Records a packet exchanged in the given second
+    pub fn add_packet(&mut self, second: i64, bytes: u128, traffic_direction: TrafficDirection) {
+        match self.samples.back_mut() {
+            Some((last_second, data_info)) if *last_second == second => {
+                data_info.add_packet(bytes, traffic_direction);
+            }
+            _ => self.samples.push_back((
+                second,
+                DataInfo::new_with_first_packet(bytes, traffic_direction),
+            )),
+        }
+        while self
+            .samples
+            .front()
+            .is_some_and(|(first_second, _)| *first_second <= second - i64::from(SERIES_SECONDS))
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Adds the traffic of another series to this one (used to aggregate the series of many hosts)
+    pub fn merge(&mut self, other: &TrafficSeries) {
+        let mut samples: BTreeMap<i64, DataInfo> = self.samples.drain(..).collect();
+        for (second, data_info) in &other.samples {
+            *samples.entry(*second).or_default() += *data_info;
+        }
+        self.samples = samples.into_iter().collect();
+    }
+
+    /// Seconds with some traffic among the last [`SERIES_SECONDS`] seconds (up to `now`),
+    /// and the data exchanged in them
+    pub fn recent_samples(&self, now: i64) -> impl Iterator<Item = &(i64, DataInfo)> {
+        let first_second = now - i64::from(SERIES_SECONDS) + 1;
+        self.samples
+            .iter()
+            .filter(move |(second, _)| (first_second..=now).contains(second))
+    }
+
+    /// Incoming and outgoing bytes for each of the last [`SERIES_SECONDS`] seconds
+    /// (from the oldest to `now`)
+    pub fn last_seconds(&self, now: i64) -> Vec<(u128, u128)> {
+        let first_second = now - i64::from(SERIES_SECONDS) + 1;
+        let mut values = vec![(0, 0); SERIES_SECONDS as usize];
+        for (second, data_info) in self.recent_samples(now) {
+            let index = usize::try_from(second - first_second).unwrap_or_default();
+            values[index] = (data_info.incoming_bytes, data_info.outgoing_bytes);
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Seconds, incoming bytes and outgoing bytes of the stored samples
+    fn stored_bytes(series: &TrafficSeries) -> Vec<(i64, u128, u128)> {
+        series
+            .samples
+            .iter()
+            .map(|(second, data_info)| {
+                (*second, data_info.incoming_bytes, data_info.outgoing_bytes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_traffic_series() {
+        let mut series = TrafficSeries::default();
+        series.add_packet(100, 500, TrafficDirection::Incoming);
+        series.add_packet(100, 200, TrafficDirection::Outgoing);
+        series.add_packet(100, 100, TrafficDirection::Incoming);
+        series.add_packet(103, 50, TrafficDirection::Outgoing);
+
+        let values = series.last_seconds(104);
+        assert_eq!(values.len(), 60);
+        assert_eq!(values[55], (600, 200));
+        assert_eq!(values[56], (0, 0));
+        assert_eq!(values[58], (0, 50));
+        assert_eq!(values[59], (0, 0));
+        // old samples are no longer displayed...
+        assert_eq!(series.last_seconds(200), vec![(0, 0); 60]);
+        // ...and are discarded when new traffic arrives
+        series.add_packet(160, 10, TrafficDirection::Incoming);
+        assert_eq!(stored_bytes(&series), vec![(103, 0, 50), (160, 10, 0)]);
+
+        let mut other = TrafficSeries::default();
+        other.add_packet(120, 5, TrafficDirection::Outgoing);
+        other.add_packet(160, 7, TrafficDirection::Outgoing);
+        series.merge(&other);
+        assert_eq!(
+            stored_bytes(&series),
+            vec![(103, 0, 50), (120, 0, 5), (160, 10, 7)]
+        );
+        // packets are counted as well
+        let recent: Vec<(i64, u128, u128)> = series
+            .recent_samples(161)
+            .map(|(second, data_info)| {
+                (
+                    *second,
+                    data_info.incoming_packets,
+                    data_info.outgoing_packets,
+                )
+            })
+            .collect();
+        assert_eq!(recent, vec![(103, 0, 1), (120, 0, 1), (160, 1, 1)]);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::notifications::types::logged_notification::LoggedNotification;
+use crate::Language;
+
+/// Shows the given notification event through the desktop notification service
+/// (`org.freedesktop.Notifications`), in a separate thread.
+///
+/// Errors (e.g. no notification daemon running) are silently ignored.
+#[cfg(target_os = "linux")]
+pub fn send_desktop_notification(notification: &LoggedNotification, language: Language) {
+    let summary = notification.get_summary(language);
+    let body = notification.get_body(language);
+    let _ = std::thread::Builder::new()
+        .name("thread_desktop_notification".to_string())
+        .spawn(move || {
+            let _ = notify_rust::Notification::new()
+                .appname("Sniffnet")
+                .summary(&summary)
+                .body(&body)
+                .icon("sniffnet")
+                .show();
+        });
+}
+
+/// Desktop notifications are only supported on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn send_desktop_notification(_notification: &LoggedNotification, _language: Language) {}
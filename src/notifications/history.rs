@@ -0,0 +1,144 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::notifications::types::notification_record::NotificationRecord;
+
+/// Size in bytes above which the history file is rotated
+const MAX_HISTORY_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Appends the given notification event to the history file, as a JSON line.
+///
+/// When the file exceeds `MAX_HISTORY_SIZE` it's renamed to `<file>.1` (replacing the previous one),
+/// so that at most two files are kept on disk
+pub fn append_to_history(path: &Path, record: &NotificationRecord) -> std::io::Result<()> {
+    append_to_history_with_limit(path, record, MAX_HISTORY_SIZE)
+}
+
+fn append_to_history_with_limit(
+    path: &Path,
+    record: &NotificationRecord,
+    max_size: u64,
+) -> std::io::Result<()> {
+    if std::fs::metadata(path).is_ok_and(|metadata| metadata.len() >= max_size) {
+        std::fs::rename(path, rotated_path(path))?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(record)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all the notification events stored in the history files (the rotated one and the current one),
+/// from the oldest to the newest.
+///
+/// Lines that can't be parsed are skipped
+pub fn read_history(path: &Path) -> Vec<NotificationRecord> {
+    let mut records = read_history_file(&rotated_path(path));
+    records.extend(read_history_file(path));
+    records
+}
+
+fn read_history_file(path: &Path) -> Vec<NotificationRecord> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Path of the history file containing the oldest events, after a rotation
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut file_name = path.as_os_str().to_owned();
+    file_name.push(".1");
+    PathBuf::from(file_name)
+}
+
+/// Exports the given notification events to a CSV file
+pub fn export_history(path: &Path, records: &[&NotificationRecord]) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "timestamp,kind,summary,body")?;
+    for record in records {
+        writeln!(
+            file,
+            "{},{},{},{}",
+            csv_field(&record.timestamp),
+            csv_field(&record.kind),
+            csv_field(&record.summary),
+            csv_field(&record.body)
+        )?;
+    }
+    file.flush()
+}
+
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn record(kind: &str, body: &str) -> NotificationRecord {
+        NotificationRecord {
+            kind: kind.to_string(),
+            timestamp: "2023-09-01T03:00:00+02:00".to_string(),
+            summary: "Summary".to_string(),
+            body: body.to_string(),
+            text: format!("Summary\n{body}"),
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("sniffnet_test_{name}"));
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(rotated_path(&path));
+        path
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = temp_path("history.jsonl");
+        assert!(read_history(&path).is_empty());
+        let first = record("packets_threshold_exceeded", "120 packets\nIncoming: 80");
+        let second = record("favorite_transmitted", "example.com");
+        append_to_history(&path, &first).unwrap();
+        append_to_history(&path, &second).unwrap();
+        // malformed lines are skipped
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "not json").unwrap();
+        assert_eq!(read_history(&path), vec![first, second]);
+    }
+
+    #[test]
+    fn test_history_rotation() {
+        let path = temp_path("rotated_history.jsonl");
+        let records: Vec<NotificationRecord> = (0..5)
+            .map(|i| record("favorite_transmitted", &format!("host{i}.com")))
+            .collect();
+        let line_len = serde_json::to_string(&records[0]).unwrap().len() as u64 + 1;
+        // rotate after two lines
+        for record in &records {
+            append_to_history_with_limit(&path, record, 2 * line_len).unwrap();
+        }
+        assert_eq!(read_history_file(&path), records[4..]);
+        assert_eq!(read_history_file(&rotated_path(&path)), records[2..4]);
+        // only the last rotation is kept, the oldest events are dropped
+        assert_eq!(read_history(&path), records[2..]);
+    }
+
+    #[test]
+    fn test_export_history() {
+        let path = temp_path("export.csv");
+        let first = record("rule_triggered", "Port: 443\n\"quoted\"");
+        export_history(&path, &[&first]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "timestamp,kind,summary,body\n\"2023-09-01T03:00:00+02:00\",\"rule_triggered\",\"Summary\",\"Port: 443\n\"\"quoted\"\"\"\n"
+        );
+    }
+}
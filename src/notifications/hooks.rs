@@ -0,0 +1,294 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::notifications::types::notification_hook::{HookKind, NotificationHook};
+use crate::notifications::types::notification_record::NotificationRecord;
+
+/// Time to wait before retrying a failed action
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Executes the configured hook for the given notification event, in a separate thread
+pub fn run_hook(hook: &NotificationHook, record: NotificationRecord) {
+    if !hook.is_active() {
+        return;
+    }
+    let hook = hook.clone();
+    let _ = thread::Builder::new()
+        .name("thread_notification_hook".to_string())
+        .spawn(move || {
+            let _ = execute_hook(&hook, &record, RETRY_DELAY);
+        });
+}
+
+/// Executes the hook, retrying up to the configured number of times if it fails
+fn execute_hook(
+    hook: &NotificationHook,
+    record: &NotificationRecord,
+    retry_delay: Duration,
+) -> Result<(), String> {
+    let payload = serde_json::to_string(record).map_err(|e| e.to_string())?;
+    let timeout = Duration::from_secs(hook.timeout);
+    let mut result = Ok(());
+    for attempt in 0..=hook.retries {
+        if attempt > 0 {
+            thread::sleep(retry_delay);
+        }
+        result = match hook.kind {
+            HookKind::None => Ok(()),
+            HookKind::Webhook => post_webhook(hook.target.trim(), &payload, timeout),
+            HookKind::Script => run_script(hook.target.trim(), record, &payload, timeout),
+        };
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
+}
+
+/// Sends the JSON payload to the given URL with an HTTP POST request
+fn post_webhook(url: &str, payload: &str, timeout: Duration) -> Result<(), String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(payload.to_string())
+        .send()
+        .map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("webhook replied with status {}", response.status()))
+    }
+}
+
+/// Runs the given script, passing the event as environment variables and the JSON payload on stdin
+fn run_script(
+    path: &str,
+    record: &NotificationRecord,
+    payload: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut child = Command::new(path)
+        .env("SNIFFNET_EVENT_KIND", &record.kind)
+        .env("SNIFFNET_EVENT_TIMESTAMP", &record.timestamp)
+        .env("SNIFFNET_EVENT_SUMMARY", &record.summary)
+        .env("SNIFFNET_EVENT_BODY", &record.body)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // written from a separate thread, since the script is free to ignore its input
+        // and a full pipe would otherwise block before the timeout is checked
+        let payload = payload.to_string();
+        let _ = thread::Builder::new()
+            .name("thread_notification_hook_stdin".to_string())
+            .spawn(move || {
+                let _ = stdin.write_all(payload.as_bytes());
+            });
+    }
+    let start = Instant::now();
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(format!("script exited with {status}")),
+            None if start.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("script timed out".to_string());
+            }
+            None => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    fn record() -> NotificationRecord {
+        NotificationRecord {
+            kind: "packets_threshold_exceeded".to_string(),
+            timestamp: "2023-09-01T10:00:00+02:00".to_string(),
+            summary: "Packets threshold exceeded!".to_string(),
+            body: "120 packets have been exchanged".to_string(),
+            text: "Packets threshold exceeded!\n120 packets have been exchanged".to_string(),
+        }
+    }
+
+    /// Starts a local HTTP stub replying with the given status codes (one per connection),
+    /// and returns its URL and the bodies of the received requests
+    fn http_stub(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let bodies2 = bodies.clone();
+        thread::spawn(move || {
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                loop {
+                    let n = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                        let content_length = headers
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= content_length {
+                            bodies2.lock().unwrap().push(body.to_string());
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, bodies)
+    }
+
+    #[test]
+    fn test_webhook_is_retried_until_success() {
+        let (url, bodies) = http_stub(vec![500, 503, 200]);
+        let hook = NotificationHook {
+            kind: HookKind::Webhook,
+            target: url,
+            retries: 3,
+            timeout: 5,
+        };
+        assert_eq!(execute_hook(&hook, &record(), Duration::ZERO), Ok(()));
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 3);
+        let sent: NotificationRecord = serde_json::from_str(&bodies[2]).unwrap();
+        assert_eq!(sent, record());
+    }
+
+    #[test]
+    fn test_webhook_gives_up_after_retries() {
+        let (url, bodies) = http_stub(vec![500, 500, 200]);
+        let hook = NotificationHook {
+            kind: HookKind::Webhook,
+            target: url,
+            retries: 1,
+            timeout: 5,
+        };
+        assert!(execute_hook(&hook, &record(), Duration::ZERO).is_err());
+        assert_eq!(bodies.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_webhook_timeout() {
+        // accepts connections but never replies
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let hook = NotificationHook {
+            kind: HookKind::Webhook,
+            target: format!("http://{}/hook", listener.local_addr().unwrap()),
+            retries: 0,
+            timeout: 1,
+        };
+        let start = Instant::now();
+        assert!(execute_hook(&hook, &record(), Duration::ZERO).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[cfg(unix)]
+    fn script(name: &str, content: &str) -> String {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("sniffnet_hook_{name}.sh"));
+        std::fs::write(&path, content).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_script_receives_event() {
+        let output = std::env::temp_dir().join("sniffnet_hook_output.txt");
+        let path = script(
+            "event",
+            &format!(
+                "#!/bin/sh\necho \"$SNIFFNET_EVENT_KIND\" > {0}\ncat >> {0}\n",
+                output.display()
+            ),
+        );
+        let hook = NotificationHook {
+            kind: HookKind::Script,
+            target: path,
+            retries: 0,
+            timeout: 5,
+        };
+        assert_eq!(execute_hook(&hook, &record(), Duration::ZERO), Ok(()));
+        let written = std::fs::read_to_string(output).unwrap();
+        let (kind, payload) = written.split_once('\n').unwrap();
+        assert_eq!(kind, "packets_threshold_exceeded");
+        let received: NotificationRecord = serde_json::from_str(payload).unwrap();
+        assert_eq!(received, record());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_script_failure_and_timeout() {
+        let hook = NotificationHook {
+            kind: HookKind::Script,
+            target: script("failure", "#!/bin/sh\nexit 1\n"),
+            retries: 2,
+            timeout: 5,
+        };
+        assert!(execute_hook(&hook, &record(), Duration::ZERO).is_err());
+
+        let hook = NotificationHook {
+            kind: HookKind::Script,
+            target: script("timeout", "#!/bin/sh\nsleep 10\n"),
+            retries: 0,
+            timeout: 1,
+        };
+        let start = Instant::now();
+        assert_eq!(
+            execute_hook(&hook, &record(), Duration::ZERO),
+            Err("script timed out".to_string())
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // a script not reading a payload larger than the pipe buffer is still timed out
+        let hook = NotificationHook {
+            kind: HookKind::Script,
+            target: script("ignore_stdin", "#!/bin/sh\nsleep 10\n"),
+            retries: 0,
+            timeout: 1,
+        };
+        let large_record = NotificationRecord {
+            // not passed as environment variable, only on stdin
+            text: "x".repeat(1_000_000),
+            ..record()
+        };
+        let start = Instant::now();
+        assert_eq!(
+            execute_hook(&hook, &large_record, Duration::ZERO),
+            Err("script timed out".to_string())
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
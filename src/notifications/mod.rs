@@ -1,2 +1,5 @@
+pub mod desktop_notification;
+pub mod history;
+pub mod hooks;
 pub mod notify_and_log;
 pub mod types;
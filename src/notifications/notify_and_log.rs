@@ -1,45 +1,62 @@
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 
+use crate::networking::types::label::get_host_label_entry;
+use crate::notifications::desktop_notification::send_desktop_notification;
+use crate::notifications::history::append_to_history;
+use crate::notifications::hooks::run_hook;
 use crate::notifications::types::logged_notification::{
-    BytesThresholdExceeded, FavoriteTransmitted, LoggedNotification, PacketsThresholdExceeded,
+    log_notification, BytesThresholdExceeded, FavoriteTransmitted, LoggedNotification,
+    PacketsThresholdExceeded, RuleTriggered,
 };
+use crate::notifications::types::notification_record::NotificationRecord;
+use crate::notifications::types::notification_rule::{NotificationRule, RuleState};
 use crate::notifications::types::notifications::Notifications;
 use crate::notifications::types::sound::{play, Sound};
-use crate::{InfoTraffic, RunTimeData};
+use crate::{InfoTraffic, Language, RunTimeData};
 
 /// Checks if one or more notifications have to be emitted and logs them.
 ///
-/// It returns the number of new notifications emitted
+/// The new notification events are forwarded only once all the checks are done,
+/// so that no file is written and no hook is run while the traffic data is locked.
+///
+/// It returns the new notification events, already appended to the history file
 pub fn notify_and_log(
     runtime_data: &mut RunTimeData,
-    notifications: Notifications,
+    notifications: &Notifications,
     info_traffic: &Arc<Mutex<InfoTraffic>>,
-) -> usize {
+    language: Language,
+) -> Vec<NotificationRecord> {
     let mut already_emitted_sound = false;
-    let mut emitted_notifications = 0;
+    // notifications to forward, along with whether they must be sent to the desktop
+    let mut pending_notifications = Vec::new();
     // packets threshold
-    if notifications.packets_notification.threshold.is_some() {
-        let sent_packets_entry = runtime_data.tot_sent_packets - runtime_data.tot_sent_packets_prev;
-        let received_packets_entry =
-            runtime_data.tot_received_packets - runtime_data.tot_received_packets_prev;
-        if received_packets_entry + sent_packets_entry
-            > u128::from(notifications.packets_notification.threshold.unwrap())
-        {
+    if let Some(threshold) = notifications.packets_notification.threshold {
+        // totals can decrease when recomputed (e.g., when a newly resolved host is ignored)
+        let sent_packets_entry = runtime_data
+            .tot_sent_packets
+            .saturating_sub(runtime_data.tot_sent_packets_prev);
+        let received_packets_entry = runtime_data
+            .tot_received_packets
+            .saturating_sub(runtime_data.tot_received_packets_prev);
+        let exceeded = received_packets_entry + sent_packets_entry > u128::from(threshold);
+        if runtime_data.packets_threshold_state.update(
+            exceeded,
+            notifications.packets_notification.sustain,
+            notifications.packets_notification.cooldown,
+        ) {
             // log this notification
-            emitted_notifications += 1;
-            if runtime_data.logged_notifications.len() >= 30 {
-                runtime_data.logged_notifications.pop_back();
-            }
-            runtime_data.logged_notifications.push_front(
+            let notification =
                 LoggedNotification::PacketsThresholdExceeded(PacketsThresholdExceeded {
                     threshold: notifications.packets_notification.previous_threshold,
                     incoming: received_packets_entry.try_into().unwrap(),
                     outgoing: sent_packets_entry.try_into().unwrap(),
                     timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
-                }),
-            );
+                });
+            pending_notifications.push((notification.clone(), notifications.desktop.packets));
+            log_notification(&mut runtime_data.logged_notifications, notification);
             if notifications.packets_notification.sound.ne(&Sound::None) {
                 // emit sound
                 play(
@@ -51,26 +68,28 @@ pub fn notify_and_log(
         }
     }
     // bytes threshold
-    if notifications.bytes_notification.threshold.is_some() {
-        let sent_bytes_entry = runtime_data.tot_sent_bytes - runtime_data.tot_sent_bytes_prev;
-        let received_bytes_entry =
-            runtime_data.tot_received_bytes - runtime_data.tot_received_bytes_prev;
-        if received_bytes_entry + sent_bytes_entry
-            > u128::from(notifications.bytes_notification.threshold.unwrap())
-        {
+    if let Some(threshold) = notifications.bytes_notification.threshold {
+        let sent_bytes_entry = runtime_data
+            .tot_sent_bytes
+            .saturating_sub(runtime_data.tot_sent_bytes_prev);
+        let received_bytes_entry = runtime_data
+            .tot_received_bytes
+            .saturating_sub(runtime_data.tot_received_bytes_prev);
+        let exceeded = received_bytes_entry + sent_bytes_entry > u128::from(threshold);
+        if runtime_data.bytes_threshold_state.update(
+            exceeded,
+            notifications.bytes_notification.sustain,
+            notifications.bytes_notification.cooldown,
+        ) {
             //log this notification
-            emitted_notifications += 1;
-            if runtime_data.logged_notifications.len() >= 30 {
-                runtime_data.logged_notifications.pop_back();
-            }
-            runtime_data.logged_notifications.push_front(
-                LoggedNotification::BytesThresholdExceeded(BytesThresholdExceeded {
-                    threshold: notifications.bytes_notification.previous_threshold,
-                    incoming: received_bytes_entry.try_into().unwrap(),
-                    outgoing: sent_bytes_entry.try_into().unwrap(),
-                    timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
-                }),
-            );
+            let notification = LoggedNotification::BytesThresholdExceeded(BytesThresholdExceeded {
+                threshold: notifications.bytes_notification.previous_threshold,
+                incoming: received_bytes_entry.try_into().unwrap(),
+                outgoing: sent_bytes_entry.try_into().unwrap(),
+                timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
+            });
+            pending_notifications.push((notification.clone(), notifications.desktop.bytes));
+            log_notification(&mut runtime_data.logged_notifications, notification);
             if !already_emitted_sound && notifications.bytes_notification.sound.ne(&Sound::None) {
                 // emit sound
                 play(notifications.bytes_notification.sound, notifications.volume);
@@ -86,33 +105,124 @@ pub fn notify_and_log(
             .favorites_last_interval
             .is_empty()
     {
+        let cooldown = Duration::from_secs(notifications.favorite_notification.cooldown);
+        let mut logged_favorites = false;
         let info_traffic_lock = info_traffic.lock().unwrap();
         for host in &info_traffic_lock.favorites_last_interval.clone() {
-            //log this notification
-            emitted_notifications += 1;
-            if runtime_data.logged_notifications.len() >= 30 {
-                runtime_data.logged_notifications.pop_back();
+            let cooled_down = runtime_data
+                .favorites_last_emitted
+                .get(host)
+                .is_none_or(|last| last.elapsed() >= cooldown);
+            if !cooled_down {
+                continue;
             }
-
             runtime_data
-                .logged_notifications
-                .push_front(LoggedNotification::FavoriteTransmitted(
-                    FavoriteTransmitted {
-                        host: host.clone(),
-                        data_info_host: info_traffic_lock.hosts.get(host).unwrap().clone(),
-                        timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
-                    },
-                ));
+                .favorites_last_emitted
+                .insert(host.clone(), Instant::now());
+            //log this notification
+            logged_favorites = true;
+            let notification = LoggedNotification::FavoriteTransmitted(FavoriteTransmitted {
+                host: host.clone(),
+                data_info_host: info_traffic_lock.hosts.get(host).unwrap().clone(),
+                alias: get_host_label_entry(&info_traffic_lock.labels, host)
+                    .map(|label| label.alias.trim().to_string())
+                    .filter(|alias| !alias.is_empty()),
+                timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
+            });
+            pending_notifications.push((notification.clone(), notifications.desktop.favorites));
+            log_notification(&mut runtime_data.logged_notifications, notification);
         }
         drop(info_traffic_lock);
-        if !already_emitted_sound && notifications.favorite_notification.sound.ne(&Sound::None) {
+        if logged_favorites
+            && !already_emitted_sound
+            && notifications.favorite_notification.sound.ne(&Sound::None)
+        {
             // emit sound
             play(
                 notifications.favorite_notification.sound,
                 notifications.volume,
             );
+            already_emitted_sound = true;
         }
     }
+    // custom rules
+    // forget the state of the rules edited or removed in the meantime
+    runtime_data
+        .rules_state
+        .retain(|rule, _| notifications.rules.contains(rule));
+    if !notifications.rules.is_empty() {
+        // rules added in the middle of a capture only consider traffic from now on
+        let is_first_interval =
+            runtime_data.tot_sent_packets_prev + runtime_data.tot_received_packets_prev == 0;
+        let info_traffic_lock = info_traffic.lock().unwrap();
+        let matched_traffic =
+            NotificationRule::matched_traffic(&notifications.rules, &info_traffic_lock);
+        drop(info_traffic_lock);
+        for (rule, (bytes, connections)) in notifications.rules.iter().zip(matched_traffic) {
+            let state = runtime_data
+                .rules_state
+                .entry(rule.clone())
+                .or_insert_with(|| {
+                    if is_first_interval {
+                        RuleState::default()
+                    } else {
+                        RuleState {
+                            bytes,
+                            connections,
+                            last_emitted: None,
+                        }
+                    }
+                });
+            let bytes_entry = bytes.saturating_sub(state.bytes);
+            let new_connections = connections.saturating_sub(state.connections);
+            state.bytes = bytes;
+            state.connections = connections;
+            let cooled_down = state
+                .last_emitted
+                .is_none_or(|last| last.elapsed() >= Duration::from_secs(rule.cooldown));
+            if cooled_down && rule.condition.is_satisfied(bytes_entry, new_connections) {
+                state.last_emitted = Some(Instant::now());
+                //log this notification
+                let notification = LoggedNotification::RuleTriggered(RuleTriggered {
+                    rule: rule.clone(),
+                    bytes: bytes_entry,
+                    new_connections,
+                    timestamp: Local::now().to_string().get(11..19).unwrap().to_string(),
+                });
+                pending_notifications.push((notification.clone(), notifications.desktop.rules));
+                log_notification(&mut runtime_data.logged_notifications, notification);
+                if !already_emitted_sound && rule.sound.ne(&Sound::None) {
+                    // emit sound
+                    play(rule.sound, notifications.volume);
+                    already_emitted_sound = true;
+                }
+            }
+        }
+    }
+
+    pending_notifications
+        .iter()
+        .map(|(notification, desktop)| {
+            forward_notification(notification, notifications, *desktop, language)
+        })
+        .collect()
+}
 
-    emitted_notifications
+/// Appends the notification event to the history file, and sends it to the desktop
+/// notification service (if enabled) and to the configured hook
+fn forward_notification(
+    notification: &LoggedNotification,
+    notifications: &Notifications,
+    desktop: bool,
+    language: Language,
+) -> NotificationRecord {
+    let record = NotificationRecord::new(notification, language);
+    append_to_history(&notifications.history_path, &record).unwrap_or(());
+    if desktop {
+        send_desktop_notification(notification, language);
+    }
+    if notifications.hook.is_active() {
+        run_hook(&notifications.hook, record.clone());
+    }
+    record
 }
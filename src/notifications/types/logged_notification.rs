@@ -1,7 +1,25 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::Host;
+use crate::notifications::types::notification_rule::{NotificationRule, RuleCondition};
+use crate::translations::translations::{
+    bytes_exceeded_translation, bytes_exceeded_value_translation, favorite_transmitted_translation,
+    incoming_translation, outgoing_translation, packets_exceeded_translation,
+    packets_exceeded_value_translation,
+};
+use crate::translations::translations_3::{
+    matched_bytes_value_translation, new_connections_value_translation, rule_triggered_translation,
+};
+use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
+use crate::Language;
+
+/// Similar events occurred within this interval from the previous one are grouped together
+pub const GROUPING_INTERVAL: Duration = Duration::from_secs(60);
 
 /// Enum representing the possible notification events.
+#[derive(Clone)]
 pub enum LoggedNotification {
     /// Packets threshold exceeded
     PacketsThresholdExceeded(PacketsThresholdExceeded),
@@ -9,6 +27,8 @@ pub enum LoggedNotification {
     BytesThresholdExceeded(BytesThresholdExceeded),
     /// Favorite connection exchanged data
     FavoriteTransmitted(FavoriteTransmitted),
+    /// Custom rule condition satisfied
+    RuleTriggered(RuleTriggered),
 }
 
 #[derive(Clone)]
@@ -31,5 +51,323 @@ pub struct BytesThresholdExceeded {
 pub struct FavoriteTransmitted {
     pub(crate) host: Host,
     pub(crate) data_info_host: DataInfoHost,
+    /// Alias assigned by the user to the host, if any
+    pub(crate) alias: Option<String>,
+    pub(crate) timestamp: String,
+}
+
+impl FavoriteTransmitted {
+    /// Describes the host with its alias (if any), domain and Autonomous System
+    pub fn get_host_description(&self) -> String {
+        let mut description = match &self.alias {
+            Some(alias) => format!("{alias} ({})", self.host.domain),
+            None => self.host.domain.clone(),
+        };
+        if !self.host.asn.name.is_empty() {
+            description.push_str(&format!(" - {}", self.host.asn.name));
+        }
+        description
+    }
+}
+
+#[derive(Clone)]
+pub struct RuleTriggered {
+    pub(crate) rule: NotificationRule,
+    pub(crate) bytes: u128,
+    pub(crate) new_connections: usize,
     pub(crate) timestamp: String,
 }
+
+impl RuleTriggered {
+    /// Describes the traffic that triggered the rule
+    pub fn get_value_description(&self, language: Language) -> String {
+        if self.rule.condition.eq(&RuleCondition::NewConnection) {
+            new_connections_value_translation(language, self.new_connections)
+        } else {
+            matched_bytes_value_translation(
+                language,
+                &get_formatted_bytes_string_with_b(self.bytes),
+            )
+        }
+    }
+}
+
+impl LoggedNotification {
+    pub fn get_timestamp(&self) -> &str {
+        match self {
+            LoggedNotification::PacketsThresholdExceeded(n) => &n.timestamp,
+            LoggedNotification::BytesThresholdExceeded(n) => &n.timestamp,
+            LoggedNotification::FavoriteTransmitted(n) => &n.timestamp,
+            LoggedNotification::RuleTriggered(n) => &n.timestamp,
+        }
+    }
+
+    /// Identifier of the kind of event, used in the payloads sent to external tools
+    pub fn get_kind_id(&self) -> &'static str {
+        match self {
+            LoggedNotification::PacketsThresholdExceeded(_) => "packets_threshold_exceeded",
+            LoggedNotification::BytesThresholdExceeded(_) => "bytes_threshold_exceeded",
+            LoggedNotification::FavoriteTransmitted(_) => "favorite_transmitted",
+            LoggedNotification::RuleTriggered(_) => "rule_triggered",
+        }
+    }
+
+    /// Short textual title of the event
+    pub fn get_summary(&self, language: Language) -> String {
+        match self {
+            LoggedNotification::PacketsThresholdExceeded(_) => {
+                packets_exceeded_translation(language)
+            }
+            LoggedNotification::BytesThresholdExceeded(_) => bytes_exceeded_translation(language),
+            LoggedNotification::FavoriteTransmitted(_) => {
+                favorite_transmitted_translation(language)
+            }
+            LoggedNotification::RuleTriggered(_) => rule_triggered_translation(language),
+        }
+        .to_string()
+    }
+
+    /// Textual details of the event
+    pub fn get_body(&self, language: Language) -> String {
+        match self {
+            LoggedNotification::PacketsThresholdExceeded(n) => format!(
+                "{}\n{}: {} - {}: {}",
+                packets_exceeded_value_translation(language, n.incoming + n.outgoing),
+                incoming_translation(language),
+                n.incoming,
+                outgoing_translation(language),
+                n.outgoing
+            ),
+            LoggedNotification::BytesThresholdExceeded(n) => format!(
+                "{}\n{}: {} - {}: {}",
+                bytes_exceeded_value_translation(
+                    language,
+                    &get_formatted_bytes_string_with_b(u128::from(n.incoming + n.outgoing))
+                ),
+                incoming_translation(language),
+                get_formatted_bytes_string_with_b(u128::from(n.incoming)),
+                outgoing_translation(language),
+                get_formatted_bytes_string_with_b(u128::from(n.outgoing))
+            ),
+            LoggedNotification::FavoriteTransmitted(n) => {
+                let mut body = n.get_host_description();
+                let country = n.host.country.to_string();
+                if !country.is_empty() {
+                    body.push_str(&format!(" ({country})"));
+                }
+                body
+            }
+            LoggedNotification::RuleTriggered(n) => format!(
+                "{}\n{}",
+                n.rule.get_description(language),
+                n.get_value_description(language)
+            ),
+        }
+    }
+
+    /// Checks whether two events are related to the same threshold, favorite host, or custom rule
+    pub fn is_similar(&self, other: &LoggedNotification) -> bool {
+        match (self, other) {
+            (
+                LoggedNotification::PacketsThresholdExceeded(_),
+                LoggedNotification::PacketsThresholdExceeded(_),
+            )
+            | (
+                LoggedNotification::BytesThresholdExceeded(_),
+                LoggedNotification::BytesThresholdExceeded(_),
+            ) => true,
+            (
+                LoggedNotification::FavoriteTransmitted(a),
+                LoggedNotification::FavoriteTransmitted(b),
+            ) => a.host.eq(&b.host),
+            (LoggedNotification::RuleTriggered(a), LoggedNotification::RuleTriggered(b)) => {
+                a.rule.eq(&b.rule)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Group of similar notification events, displayed as a single entry
+#[derive(Clone)]
+pub struct NotificationGroup {
+    /// Most recent event of the group
+    pub notification: LoggedNotification,
+    /// Number of events in the group
+    pub count: usize,
+    /// Timestamp of the first event of the group
+    pub first_timestamp: String,
+    /// Time of the most recent event of the group
+    pub last_occurrence: Instant,
+}
+
+impl NotificationGroup {
+    pub fn new(notification: LoggedNotification) -> Self {
+        NotificationGroup {
+            first_timestamp: notification.get_timestamp().to_string(),
+            notification,
+            count: 1,
+            last_occurrence: Instant::now(),
+        }
+    }
+
+    /// Returns the time span of the group, followed by the number of events if more than one
+    pub fn get_time_span(&self) -> String {
+        let last_timestamp = self.notification.get_timestamp();
+        if self.count > 1 {
+            format!(
+                "{} - {last_timestamp}   (×{})",
+                self.first_timestamp, self.count
+            )
+        } else {
+            last_timestamp.to_string()
+        }
+    }
+}
+
+/// Logs a notification event, merging it into a group of similar recent events if present.
+///
+/// At most 30 groups are kept
+pub fn log_notification(
+    logged_notifications: &mut VecDeque<NotificationGroup>,
+    notification: LoggedNotification,
+) {
+    let similar_group_index = logged_notifications.iter().position(|group| {
+        group.last_occurrence.elapsed() < GROUPING_INTERVAL
+            && group.notification.is_similar(&notification)
+    });
+    let group = if let Some(index) = similar_group_index {
+        let mut group = logged_notifications.remove(index).unwrap();
+        group.notification = notification;
+        group.count += 1;
+        group.last_occurrence = Instant::now();
+        group
+    } else {
+        if logged_notifications.len() >= 30 {
+            logged_notifications.pop_back();
+        }
+        NotificationGroup::new(notification)
+    };
+    logged_notifications.push_front(group);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::notifications::types::notification_rule::{RuleCondition, RuleMatcher};
+    use crate::notifications::types::sound::Sound;
+
+    use super::*;
+
+    fn packets(timestamp: &str) -> LoggedNotification {
+        LoggedNotification::PacketsThresholdExceeded(PacketsThresholdExceeded {
+            threshold: 100,
+            incoming: 80,
+            outgoing: 40,
+            timestamp: timestamp.to_string(),
+        })
+    }
+
+    fn rule(port: u16, timestamp: &str) -> LoggedNotification {
+        LoggedNotification::RuleTriggered(RuleTriggered {
+            rule: NotificationRule {
+                sound: Sound::None,
+                cooldown: 0,
+                condition: RuleCondition::AnyTraffic,
+                matcher: RuleMatcher::Port(port),
+            },
+            bytes: 10,
+            new_connections: 0,
+            timestamp: timestamp.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_similar_events_are_grouped() {
+        let mut logged_notifications = VecDeque::new();
+        log_notification(&mut logged_notifications, packets("10:00:00"));
+        log_notification(&mut logged_notifications, rule(443, "10:00:01"));
+        log_notification(&mut logged_notifications, rule(80, "10:00:02"));
+        log_notification(&mut logged_notifications, packets("10:00:03"));
+        log_notification(&mut logged_notifications, packets("10:00:04"));
+
+        assert_eq!(logged_notifications.len(), 3);
+        let packets_group = logged_notifications.front().unwrap();
+        assert_eq!(packets_group.count, 3);
+        assert_eq!(packets_group.notification.get_timestamp(), "10:00:04");
+        assert_eq!(
+            packets_group.get_time_span(),
+            "10:00:00 - 10:00:04   (×3)".to_string()
+        );
+        assert_eq!(logged_notifications.get(1).unwrap().count, 1);
+        assert_eq!(
+            logged_notifications.get(1).unwrap().get_time_span(),
+            "10:00:02".to_string()
+        );
+    }
+
+    #[test]
+    fn test_old_events_are_not_grouped() {
+        let mut logged_notifications = VecDeque::new();
+        log_notification(&mut logged_notifications, packets("10:00:00"));
+        logged_notifications.front_mut().unwrap().last_occurrence -= GROUPING_INTERVAL;
+        log_notification(&mut logged_notifications, packets("10:01:00"));
+        assert_eq!(logged_notifications.len(), 2);
+    }
+
+    #[test]
+    fn test_at_most_30_groups() {
+        let mut logged_notifications = VecDeque::new();
+        for port in 0..40 {
+            log_notification(&mut logged_notifications, rule(port, "10:00:00"));
+        }
+        assert_eq!(logged_notifications.len(), 30);
+        log_notification(&mut logged_notifications, rule(39, "10:00:01"));
+        assert_eq!(logged_notifications.len(), 30);
+        assert_eq!(logged_notifications.front().unwrap().count, 2);
+    }
+
+    #[test]
+    fn test_packets_notification_text() {
+        let notification = packets("10:00:00");
+        assert_eq!(
+            notification.get_summary(Language::EN),
+            "Packets threshold exceeded!"
+        );
+        assert_eq!(
+            notification.get_body(Language::EN),
+            "120 packets have been exchanged\nIncoming: 80 - Outgoing: 40"
+        );
+    }
+
+    #[test]
+    fn test_favorite_notification_text() {
+        let notification = LoggedNotification::FavoriteTransmitted(FavoriteTransmitted {
+            host: Host {
+                domain: "example.com".to_string(),
+                asn: Asn {
+                    number: 15169,
+                    name: "GOOGLE".to_string(),
+                },
+                country: Country::US,
+            },
+            data_info_host: DataInfoHost::default(),
+            alias: None,
+            timestamp: "10:00:00".to_string(),
+        });
+        assert_eq!(
+            notification.get_body(Language::EN),
+            "example.com - GOOGLE (US)"
+        );
+
+        if let LoggedNotification::FavoriteTransmitted(mut favorite_transmitted) = notification {
+            favorite_transmitted.alias = Some("Search".to_string());
+            assert_eq!(
+                LoggedNotification::FavoriteTransmitted(favorite_transmitted)
+                    .get_body(Language::EN),
+                "Search (example.com) - GOOGLE (US)"
+            );
+        }
+    }
+}
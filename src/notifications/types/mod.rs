@@ -1,3 +1,6 @@
 pub mod logged_notification;
+pub mod notification_hook;
+pub mod notification_record;
+pub mod notification_rule;
 pub mod notifications;
 pub mod sound;
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use crate::translations::translations::none_translation;
+use crate::translations::translations_3::script_translation;
+use crate::Language;
+
+/// Action executed for every notification event, to integrate Sniffnet with external tools
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+pub struct NotificationHook {
+    /// Kind of action to execute
+    pub kind: HookKind,
+    /// URL of the webhook, or path of the script
+    pub target: String,
+    /// Number of further attempts if the action fails
+    pub retries: u64,
+    /// Maximum number of seconds an attempt can last
+    pub timeout: u64,
+}
+
+impl Default for NotificationHook {
+    fn default() -> Self {
+        NotificationHook {
+            kind: HookKind::None,
+            target: String::new(),
+            retries: 3,
+            timeout: 5,
+        }
+    }
+}
+
+impl NotificationHook {
+    /// Checks whether the hook is fully configured
+    pub fn is_active(&self) -> bool {
+        self.kind.ne(&HookKind::None) && !self.target.trim().is_empty()
+    }
+}
+
+/// Enum representing the possible kinds of notification hooks.
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug, Default)]
+pub enum HookKind {
+    /// No action
+    #[default]
+    None,
+    /// HTTP POST of a JSON payload to a URL
+    Webhook,
+    /// Execution of a local script, receiving the event as environment variables and JSON on stdin
+    Script,
+}
+
+impl HookKind {
+    pub(crate) const ALL: [HookKind; 3] = [HookKind::None, HookKind::Webhook, HookKind::Script];
+
+    pub fn get_radio_label(self, language: Language) -> String {
+        match self {
+            HookKind::None => none_translation(language),
+            HookKind::Webhook => "Webhook".to_string(),
+            HookKind::Script => script_translation(language).to_string(),
+        }
+    }
+
+    pub fn get_placeholder(self) -> &'static str {
+        match self {
+            HookKind::None => "",
+            HookKind::Webhook => "https://hooks.example.com/sniffnet",
+            HookKind::Script => "/path/to/script.sh",
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::notifications::types::logged_notification::LoggedNotification;
+use crate::Language;
+
+/// Serializable representation of a notification event, shared with external tools
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+pub struct NotificationRecord {
+    /// Kind of event (e.g. `packets_threshold_exceeded`)
+    pub kind: String,
+    /// Date and time of the event, in RFC 3339 format
+    pub timestamp: String,
+    /// Short textual title of the event
+    pub summary: String,
+    /// Textual details of the event
+    pub body: String,
+    /// Summary followed by body; this is the field displayed by Slack and Matrix webhooks
+    pub text: String,
+}
+
+impl NotificationRecord {
+    pub fn new(notification: &LoggedNotification, language: Language) -> Self {
+        let summary = notification.get_summary(language);
+        let body = notification.get_body(language);
+        NotificationRecord {
+            kind: notification.get_kind_id().to_string(),
+            timestamp: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+            text: format!("{summary}\n{body}"),
+            summary,
+            body,
+        }
+    }
+
+    /// Checks whether the record contains the given text (case insensitive) in any of its fields
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || [&self.kind, &self.timestamp, &self.summary, &self.body]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", true)]
+    #[case("  ", true)]
+    #[case("favorite", true)]
+    #[case("EXAMPLE.COM", true)]
+    #[case("2023-09-01T03", true)]
+    #[case("google", false)]
+    fn test_record_matches(#[case] filter: &str, #[case] expected: bool) {
+        let record = NotificationRecord {
+            kind: "favorite_transmitted".to_string(),
+            timestamp: "2023-09-01T03:12:45+02:00".to_string(),
+            summary: "Favorite connection exchanged data!".to_string(),
+            body: "example.com".to_string(),
+            text: "Favorite connection exchanged data!\nexample.com".to_string(),
+        };
+        assert_eq!(record.matches(filter), expected);
+    }
+}
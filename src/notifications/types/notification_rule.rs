@@ -0,0 +1,627 @@
+//! Module defining the custom notification rules, which permit to be notified about the traffic
+//! exchanged with specific hosts, countries, Autonomous Systems, application protocols or ports.
+
+use std::fmt;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::countries::types::country::Country;
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::byte_multiple::from_char_to_multiple;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::ip_cidr::IpCidr;
+use crate::notifications::types::sound::Sound;
+use crate::translations::translations::{application_protocol_translation, per_second_translation};
+use crate::translations::translations_2::country_translation;
+use crate::translations::translations_3::{
+    any_traffic_translation, bytes_over_translation, new_connection_translation, port_translation,
+    rule_host_translation,
+};
+use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
+use crate::{AppProtocol, InfoTraffic, Language};
+
+/// A custom notification rule set by the user
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct NotificationRule {
+    /// The sound to emit
+    pub sound: Sound,
+    /// Minimum number of seconds between two notifications emitted by this rule
+    pub cooldown: u64,
+    /// Condition that the matched traffic must satisfy to emit a notification
+    pub condition: RuleCondition,
+    /// Defines which connections are considered by this rule
+    pub matcher: RuleMatcher,
+}
+
+/// Defines which connections are considered by a notification rule.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+// adjacently tagged since the TOML serializer doesn't support newtype variants
+#[serde(tag = "kind", content = "value")]
+pub enum RuleMatcher {
+    /// Remote host domain name (including its subdomains), IP address, or CIDR network
+    Host(HostMatcher),
+    /// Autonomous System number or name
+    Asn(String),
+    /// Country code of the remote host
+    Country(String),
+    /// Application protocol
+    AppProtocol(AppProtocol),
+    /// Source or destination port
+    Port(u16),
+}
+
+/// Remote host matched by a notification rule.
+///
+/// It's serialized as a string in the same form it's parsed from.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(from = "String", into = "String")]
+pub enum HostMatcher {
+    /// Domain name, including its subdomains
+    Domain(String),
+    /// IP address or CIDR network
+    Network(IpCidr),
+}
+
+/// Condition that the matched traffic must satisfy to emit a notification.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", content = "value")]
+pub enum RuleCondition {
+    /// Any data exchanged in the last second
+    AnyTraffic,
+    /// Bytes exchanged in the last second are more than the specified value
+    BytesPerSecond(u64),
+    /// At least a new connection appeared in the last second
+    NewConnection,
+}
+
+/// The kinds of matcher selectable in the rule editor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RuleMatcherKind {
+    #[default]
+    Host,
+    Asn,
+    Country,
+    AppProtocol,
+    Port,
+}
+
+/// The kinds of condition selectable in the rule editor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RuleConditionKind {
+    #[default]
+    AnyTraffic,
+    BytesPerSecond,
+    NewConnection,
+}
+
+/// Traffic matched by a rule up to the last time interval, and the last time the rule was triggered
+#[derive(Default, Clone, Copy)]
+pub struct RuleState {
+    pub bytes: u128,
+    pub connections: usize,
+    pub last_emitted: Option<Instant>,
+}
+
+/// Rule being composed by the user in the notifications settings page
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NotificationRuleDraft {
+    pub matcher_kind: RuleMatcherKind,
+    pub matcher_value: String,
+    pub condition_kind: RuleConditionKind,
+    /// Bytes per second threshold, possibly with a multiple (e.g. 500K)
+    pub threshold: String,
+    /// Cooldown in seconds
+    pub cooldown: String,
+    pub sound: Sound,
+}
+
+impl Default for NotificationRuleDraft {
+    fn default() -> Self {
+        NotificationRuleDraft {
+            matcher_kind: RuleMatcherKind::default(),
+            matcher_value: String::new(),
+            condition_kind: RuleConditionKind::default(),
+            threshold: String::new(),
+            cooldown: "60".to_string(),
+            sound: Sound::Pop,
+        }
+    }
+}
+
+impl NotificationRuleDraft {
+    /// Builds the corresponding rule; returns `None` if some of the inserted values are not valid
+    pub fn to_rule(&self) -> Option<NotificationRule> {
+        let matcher = RuleMatcher::from(self.matcher_kind, &self.matcher_value)?;
+        let condition = match self.condition_kind {
+            RuleConditionKind::AnyTraffic => RuleCondition::AnyTraffic,
+            RuleConditionKind::BytesPerSecond => {
+                RuleCondition::BytesPerSecond(parse_bytes(&self.threshold)?)
+            }
+            RuleConditionKind::NewConnection => RuleCondition::NewConnection,
+        };
+        let cooldown = if self.cooldown.trim().is_empty() {
+            0
+        } else {
+            self.cooldown.trim().parse::<u64>().ok()?
+        };
+        Some(NotificationRule {
+            sound: self.sound,
+            cooldown,
+            condition,
+            matcher,
+        })
+    }
+}
+
+impl NotificationRule {
+    /// Computes the total bytes and the number of connections currently matched by each of the given rules,
+    /// with a single pass over the connections
+    pub fn matched_traffic(rules: &[Self], info_traffic: &InfoTraffic) -> Vec<(u128, usize)> {
+        let mut matched = vec![(0, 0); rules.len()];
+        if rules.is_empty() {
+            return matched;
+        }
+        for (key, val) in &info_traffic.map {
+            let address = get_address_to_lookup(key, val.traffic_direction);
+            if info_traffic.is_ignored(&address, val.app_protocol) {
+                continue;
+            }
+            let host = info_traffic.addresses_resolved.get(&address);
+            for (rule, (bytes, connections)) in rules.iter().zip(&mut matched) {
+                if rule.matcher.matches(key, val, &address, host) {
+                    *bytes += val.transmitted_bytes;
+                    *connections += 1;
+                }
+            }
+        }
+        matched
+    }
+
+    /// Returns a short human-readable description of this rule
+    pub fn get_description(&self, language: Language) -> String {
+        format!(
+            "{} → {}",
+            self.matcher.get_description(language),
+            self.condition.get_description(language)
+        )
+    }
+}
+
+impl RuleMatcher {
+    /// Arbitrary string constructor; returns `None` if the value is not valid for the given kind
+    pub fn from(kind: RuleMatcherKind, value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        match kind {
+            RuleMatcherKind::Host => Some(RuleMatcher::Host(HostMatcher::new(value))),
+            RuleMatcherKind::Asn => Some(RuleMatcher::Asn(value.to_string())),
+            RuleMatcherKind::Country => {
+                let code = value.to_uppercase();
+                if Country::from_str(&code).eq(&Country::ZZ) {
+                    None
+                } else {
+                    Some(RuleMatcher::Country(code))
+                }
+            }
+            RuleMatcherKind::AppProtocol => AppProtocol::ALL
+                .iter()
+                .find(|app| {
+                    app.ne(&&AppProtocol::Other) && app.to_string().eq_ignore_ascii_case(value)
+                })
+                .map(|app| RuleMatcher::AppProtocol(*app)),
+            RuleMatcherKind::Port => value.parse::<u16>().ok().map(RuleMatcher::Port),
+        }
+    }
+
+    /// Checks whether a connection is matched.
+    ///
+    /// `address` is the remote address of the connection, and `host` its resolved host (if any)
+    pub fn matches(
+        &self,
+        key: &AddressPortPair,
+        val: &InfoAddressPortPair,
+        address: &str,
+        host: Option<&(String, Host)>,
+    ) -> bool {
+        match self {
+            RuleMatcher::Host(HostMatcher::Network(cidr)) => cidr.contains_str(address),
+            RuleMatcher::Host(HostMatcher::Domain(pattern)) => host.is_some_and(|(r_dns, host)| {
+                domain_matches(r_dns, pattern) || domain_matches(&host.domain, pattern)
+            }),
+            RuleMatcher::Asn(pattern) => host.is_some_and(|(_, host)| {
+                !host.asn.name.is_empty()
+                    && (host.asn.number.to_string().eq(pattern)
+                        || host
+                            .asn
+                            .name
+                            .to_lowercase()
+                            .contains(&pattern.to_lowercase()))
+            }),
+            RuleMatcher::Country(code) => {
+                host.is_some_and(|(_, host)| host.country.to_string().eq(code))
+            }
+            RuleMatcher::AppProtocol(app) => val.app_protocol.eq(app),
+            RuleMatcher::Port(port) => key.port1.eq(port) || key.port2.eq(port),
+        }
+    }
+
+    pub fn get_kind(&self) -> RuleMatcherKind {
+        match self {
+            RuleMatcher::Host(_) => RuleMatcherKind::Host,
+            RuleMatcher::Asn(_) => RuleMatcherKind::Asn,
+            RuleMatcher::Country(_) => RuleMatcherKind::Country,
+            RuleMatcher::AppProtocol(_) => RuleMatcherKind::AppProtocol,
+            RuleMatcher::Port(_) => RuleMatcherKind::Port,
+        }
+    }
+
+    fn get_description(&self, language: Language) -> String {
+        let value = match self {
+            RuleMatcher::Host(host) => host.to_string(),
+            RuleMatcher::Asn(value) | RuleMatcher::Country(value) => value.clone(),
+            RuleMatcher::AppProtocol(app) => app.to_string(),
+            RuleMatcher::Port(port) => port.to_string(),
+        };
+        format!("{}: {value}", self.get_kind().get_radio_label(language))
+    }
+}
+
+impl HostMatcher {
+    /// Parses an IP address or CIDR network, falling back to a (lowercase) domain name
+    pub fn new(value: &str) -> Self {
+        let value = value.trim();
+        match IpCidr::from_str(value) {
+            Some(cidr) => HostMatcher::Network(cidr),
+            None => HostMatcher::Domain(value.to_lowercase()),
+        }
+    }
+}
+
+impl fmt::Display for HostMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostMatcher::Domain(domain) => write!(f, "{domain}"),
+            HostMatcher::Network(cidr) => write!(f, "{cidr}"),
+        }
+    }
+}
+
+impl From<String> for HostMatcher {
+    fn from(value: String) -> Self {
+        HostMatcher::new(&value)
+    }
+}
+
+impl From<HostMatcher> for String {
+    fn from(host_matcher: HostMatcher) -> Self {
+        host_matcher.to_string()
+    }
+}
+
+impl RuleCondition {
+    /// Checks whether the condition is satisfied, given the bytes and the new connections
+    /// observed in the last time interval
+    pub fn is_satisfied(self, bytes: u128, new_connections: usize) -> bool {
+        match self {
+            RuleCondition::AnyTraffic => bytes > 0,
+            RuleCondition::BytesPerSecond(threshold) => bytes > u128::from(threshold),
+            RuleCondition::NewConnection => new_connections > 0,
+        }
+    }
+
+    pub fn get_kind(self) -> RuleConditionKind {
+        match self {
+            RuleCondition::AnyTraffic => RuleConditionKind::AnyTraffic,
+            RuleCondition::BytesPerSecond(_) => RuleConditionKind::BytesPerSecond,
+            RuleCondition::NewConnection => RuleConditionKind::NewConnection,
+        }
+    }
+
+    fn get_description(self, language: Language) -> String {
+        match self {
+            RuleCondition::BytesPerSecond(threshold) => format!(
+                "{} {} {}",
+                bytes_over_translation(language),
+                get_formatted_bytes_string_with_b(u128::from(threshold)),
+                per_second_translation(language)
+            ),
+            _ => self.get_kind().get_radio_label(language).to_string(),
+        }
+    }
+}
+
+impl RuleMatcherKind {
+    pub(crate) const ALL: [RuleMatcherKind; 5] = [
+        RuleMatcherKind::Host,
+        RuleMatcherKind::Asn,
+        RuleMatcherKind::Country,
+        RuleMatcherKind::AppProtocol,
+        RuleMatcherKind::Port,
+    ];
+
+    pub fn get_radio_label(self, language: Language) -> &'static str {
+        match self {
+            RuleMatcherKind::Host => rule_host_translation(language),
+            RuleMatcherKind::Asn => "ASN",
+            RuleMatcherKind::Country => country_translation(language),
+            RuleMatcherKind::AppProtocol => application_protocol_translation(language),
+            RuleMatcherKind::Port => port_translation(language),
+        }
+    }
+
+    /// Example value, displayed as placeholder of the rule editor input
+    pub fn get_placeholder(self) -> &'static str {
+        match self {
+            RuleMatcherKind::Host => "example.com, 1.1.1.1, 10.0.0.0/8",
+            RuleMatcherKind::Asn => "15169, Google",
+            RuleMatcherKind::Country => "US",
+            RuleMatcherKind::AppProtocol => "HTTPS",
+            RuleMatcherKind::Port => "443",
+        }
+    }
+}
+
+impl RuleConditionKind {
+    pub(crate) const ALL: [RuleConditionKind; 3] = [
+        RuleConditionKind::AnyTraffic,
+        RuleConditionKind::BytesPerSecond,
+        RuleConditionKind::NewConnection,
+    ];
+
+    pub fn get_radio_label(self, language: Language) -> &'static str {
+        match self {
+            RuleConditionKind::AnyTraffic => any_traffic_translation(language),
+            RuleConditionKind::BytesPerSecond => bytes_over_translation(language),
+            RuleConditionKind::NewConnection => new_connection_translation(language),
+        }
+    }
+}
+
+/// Checks whether the domain is equal to the pattern or is one of its subdomains
+fn domain_matches(domain: &str, pattern: &str) -> bool {
+    let domain = domain.to_lowercase();
+    domain.eq(pattern) || domain.ends_with(&format!(".{pattern}"))
+}
+
+/// Parses a bytes amount, optionally followed by a multiple (e.g. 500K)
+fn parse_bytes(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let last_char = value.chars().last()?;
+    if last_char.is_ascii_digit() {
+        return value.parse::<u64>().ok();
+    }
+    let multiple = from_char_to_multiple(last_char);
+    let without_multiple = value[0..value.len() - last_char.len_utf8()].trim();
+    without_multiple
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(multiple.get_multiplier())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+
+    use super::*;
+
+    fn host(domain: &str, asn_number: u32, asn_name: &str, country: Country) -> (String, Host) {
+        (
+            format!("www.{domain}"),
+            Host {
+                domain: domain.to_string(),
+                asn: Asn {
+                    number: asn_number,
+                    name: asn_name.to_string(),
+                },
+                country,
+            },
+        )
+    }
+
+    fn key(port1: u16, port2: u16) -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.2".to_string(),
+            port1,
+            "142.250.180.4".to_string(),
+            port2,
+            crate::TransProtocol::TCP,
+        )
+    }
+
+    #[rstest]
+    #[case(
+        RuleMatcherKind::Host,
+        " Example.COM ",
+        Some(RuleMatcher::Host(HostMatcher::new("example.com")))
+    )]
+    #[case(RuleMatcherKind::Host, "", None)]
+    #[case(
+        RuleMatcherKind::Host,
+        "10.0.0.0/8",
+        Some(RuleMatcher::Host(HostMatcher::Network(IpCidr::from_str("10.0.0.0/8").unwrap())))
+    )]
+    #[case(RuleMatcherKind::Asn, "Google", Some(RuleMatcher::Asn("Google".to_string())))]
+    #[case(RuleMatcherKind::Country, "it", Some(RuleMatcher::Country("IT".to_string())))]
+    #[case(RuleMatcherKind::Country, "XY", None)]
+    #[case(
+        RuleMatcherKind::AppProtocol,
+        "https",
+        Some(RuleMatcher::AppProtocol(AppProtocol::HTTPS))
+    )]
+    #[case(RuleMatcherKind::AppProtocol, "foo", None)]
+    #[case(RuleMatcherKind::Port, "443", Some(RuleMatcher::Port(443)))]
+    #[case(RuleMatcherKind::Port, "70000", None)]
+    fn test_matcher_from_string(
+        #[case] kind: RuleMatcherKind,
+        #[case] value: &str,
+        #[case] expected: Option<RuleMatcher>,
+    ) {
+        assert_eq!(RuleMatcher::from(kind, value), expected);
+    }
+
+    #[rstest]
+    #[case("123", Some(123))]
+    #[case("500k", Some(500_000))]
+    #[case(" 2 M", Some(2_000_000))]
+    #[case("", None)]
+    #[case("k", None)]
+    #[case("foo", None)]
+    fn test_parse_bytes(#[case] value: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_bytes(value), expected);
+    }
+
+    #[test]
+    fn test_draft_to_rule() {
+        let mut draft = NotificationRuleDraft {
+            matcher_kind: RuleMatcherKind::Country,
+            matcher_value: "us".to_string(),
+            condition_kind: RuleConditionKind::BytesPerSecond,
+            threshold: "1M".to_string(),
+            cooldown: "30".to_string(),
+            sound: Sound::Gulp,
+        };
+        assert_eq!(
+            draft.to_rule(),
+            Some(NotificationRule {
+                sound: Sound::Gulp,
+                cooldown: 30,
+                condition: RuleCondition::BytesPerSecond(1_000_000),
+                matcher: RuleMatcher::Country("US".to_string()),
+            })
+        );
+        draft.cooldown = "abc".to_string();
+        assert_eq!(draft.to_rule(), None);
+        draft.cooldown = String::new();
+        draft.threshold = String::new();
+        assert_eq!(draft.to_rule(), None);
+        draft.condition_kind = RuleConditionKind::NewConnection;
+        assert_eq!(draft.to_rule().unwrap().cooldown, 0);
+    }
+
+    #[test]
+    fn test_matched_traffic() {
+        let mut info_traffic = InfoTraffic::new();
+        for (port, app_protocol, bytes) in [
+            (443, AppProtocol::HTTPS, 1000),
+            (8443, AppProtocol::HTTPS, 500),
+            (53, AppProtocol::DNS, 100),
+        ] {
+            info_traffic.map.insert(
+                key(50_000, port),
+                InfoAddressPortPair {
+                    app_protocol,
+                    transmitted_bytes: bytes,
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+        }
+        let rule = |matcher: RuleMatcher| NotificationRule {
+            sound: Sound::None,
+            cooldown: 0,
+            condition: RuleCondition::NewConnection,
+            matcher,
+        };
+        let rules = [
+            rule(RuleMatcher::AppProtocol(AppProtocol::HTTPS)),
+            rule(RuleMatcher::Port(53)),
+            rule(RuleMatcher::Port(22)),
+        ];
+        assert_eq!(
+            NotificationRule::matched_traffic(&rules, &info_traffic),
+            vec![(1500, 2), (100, 1), (0, 0)]
+        );
+
+        // ignored traffic is never matched
+        info_traffic
+            .ignored_app_protocols
+            .insert(AppProtocol::HTTPS);
+        assert_eq!(
+            NotificationRule::matched_traffic(&rules, &info_traffic),
+            vec![(0, 0), (100, 1), (0, 0)]
+        );
+        assert!(NotificationRule::matched_traffic(&[], &info_traffic).is_empty());
+    }
+
+    #[test]
+    fn test_matcher_matches() {
+        let key = key(50_000, 443);
+        let val = InfoAddressPortPair {
+            app_protocol: AppProtocol::HTTPS,
+            ..InfoAddressPortPair::default()
+        };
+        let address = "142.250.180.4";
+        let host = host("google.com", 15169, "GOOGLE", Country::US);
+        let host = Some(&host);
+
+        let matches = |matcher: RuleMatcher| matcher.matches(&key, &val, address, host);
+        assert!(matches(RuleMatcher::Host(HostMatcher::new("google.com"))));
+        assert!(matches(RuleMatcher::Host(HostMatcher::new(
+            "www.google.com"
+        ))));
+        assert!(!matches(RuleMatcher::Host(HostMatcher::new("gle.com"))));
+        assert!(matches(RuleMatcher::Host(HostMatcher::new(
+            "142.250.0.0/16"
+        ))));
+        assert!(!matches(RuleMatcher::Host(HostMatcher::new(
+            "142.251.0.0/16"
+        ))));
+        assert!(matches(RuleMatcher::Asn("15169".to_string())));
+        assert!(matches(RuleMatcher::Asn("google".to_string())));
+        assert!(!matches(RuleMatcher::Asn("amazon".to_string())));
+        assert!(matches(RuleMatcher::Country("US".to_string())));
+        assert!(!matches(RuleMatcher::Country("IT".to_string())));
+        assert!(matches(RuleMatcher::AppProtocol(AppProtocol::HTTPS)));
+        assert!(!matches(RuleMatcher::AppProtocol(AppProtocol::DNS)));
+        assert!(matches(RuleMatcher::Port(443)));
+        assert!(matches(RuleMatcher::Port(50_000)));
+        assert!(!matches(RuleMatcher::Port(80)));
+
+        // unresolved hosts are only matched by address
+        assert!(!RuleMatcher::Country("US".to_string()).matches(&key, &val, address, None));
+        assert!(
+            RuleMatcher::Host(HostMatcher::new("142.250.180.4")).matches(&key, &val, address, None)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        HostMatcher::new("10.0.0.0/8"),
+        r#"{"kind":"Host","value":"10.0.0.0/8"}"#
+    )]
+    #[case(HostMatcher::new("1.1.1.1/32"), r#"{"kind":"Host","value":"1.1.1.1"}"#)]
+    #[case(
+        HostMatcher::new("Example.com"),
+        r#"{"kind":"Host","value":"example.com"}"#
+    )]
+    fn test_host_matcher_serialized_as_string(#[case] host: HostMatcher, #[case] json: &str) {
+        let matcher = RuleMatcher::Host(host);
+        assert_eq!(serde_json::to_string(&matcher).unwrap(), json);
+        assert_eq!(serde_json::from_str::<RuleMatcher>(json).unwrap(), matcher);
+    }
+
+    #[rstest]
+    #[case(RuleCondition::AnyTraffic, 0, 0, false)]
+    #[case(RuleCondition::AnyTraffic, 1, 0, true)]
+    #[case(RuleCondition::BytesPerSecond(1000), 1000, 0, false)]
+    #[case(RuleCondition::BytesPerSecond(1000), 1001, 0, true)]
+    #[case(RuleCondition::NewConnection, 5000, 0, false)]
+    #[case(RuleCondition::NewConnection, 0, 1, true)]
+    fn test_condition_is_satisfied(
+        #[case] condition: RuleCondition,
+        #[case] bytes: u128,
+        #[case] new_connections: usize,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(condition.is_satisfied(bytes, new_connections), expected);
+    }
+}
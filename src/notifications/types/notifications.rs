@@ -1,31 +1,56 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
 
 use crate::networking::types::byte_multiple::from_char_to_multiple;
+use crate::notifications::types::notification_hook::NotificationHook;
+use crate::notifications::types::notification_rule::NotificationRule;
 use crate::notifications::types::sound::Sound;
+use crate::utils::formatted_strings::get_notifications_history_path;
 use crate::ByteMultiple;
 
 /// Used to contain the notifications configuration set by the user
-#[derive(Clone, Serialize, Deserialize, Copy)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Notifications {
     pub volume: u8,
+    /// File where all the emitted notifications are logged (JSON lines)
+    #[serde(default = "get_notifications_history_path")]
+    pub history_path: PathBuf,
+    /// Custom rules about specific hosts, countries, ASNs, application protocols or ports
+    ///
+    /// Declared before the other notifications since the TOML serializer
+    /// doesn't support values (like an empty list) after tables
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
     pub packets_notification: PacketsNotification,
     pub bytes_notification: BytesNotification,
     pub favorite_notification: FavoriteNotification,
+    /// Which kinds of events are also shown as desktop notifications
+    #[serde(default)]
+    pub desktop: DesktopNotifications,
+    /// Action executed for every notification event
+    #[serde(default)]
+    pub hook: NotificationHook,
 }
 
 impl Default for Notifications {
     fn default() -> Self {
         Notifications {
             volume: 60,
+            history_path: get_notifications_history_path(),
+            rules: Vec::new(),
             packets_notification: PacketsNotification::default(),
             bytes_notification: BytesNotification::default(),
             favorite_notification: FavoriteNotification::default(),
+            desktop: DesktopNotifications::default(),
+            hook: NotificationHook::default(),
         }
     }
 }
 
 /// Enum representing the possible notifications.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Notification {
     /// Packets notification
     Packets(PacketsNotification),
@@ -33,6 +58,12 @@ pub enum Notification {
     Bytes(BytesNotification),
     /// Favorites notification
     Favorite(FavoriteNotification),
+    /// Desktop notifications
+    Desktop(DesktopNotifications),
+    /// Notification hook
+    Hook(NotificationHook),
+    /// File of the notifications history
+    HistoryPath(PathBuf),
 }
 
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy)]
@@ -43,6 +74,12 @@ pub struct PacketsNotification {
     pub sound: Sound,
     /// The last used Some value for the threshold field
     pub previous_threshold: u32,
+    /// Number of consecutive seconds the threshold must be exceeded before emitting a notification
+    #[serde(default)]
+    pub sustain: u64,
+    /// Minimum number of seconds between two notifications
+    #[serde(default)]
+    pub cooldown: u64,
 }
 
 impl Default for PacketsNotification {
@@ -51,6 +88,8 @@ fn default() -> Self {
             threshold: None,
             sound: Sound::Gulp,
             previous_threshold: 750,
+            sustain: 1,
+            cooldown: 0,
         }
     }
 }
@@ -83,6 +122,12 @@ pub struct BytesNotification {
     pub sound: Sound,
     /// The last used Some value for the threshold field
     pub previous_threshold: u64,
+    /// Number of consecutive seconds the threshold must be exceeded before emitting a notification
+    #[serde(default)]
+    pub sustain: u64,
+    /// Minimum number of seconds between two notifications
+    #[serde(default)]
+    pub cooldown: u64,
 }
 
 impl Default for BytesNotification {
@@ -92,6 +137,8 @@ fn default() -> Self {
             byte_multiple: ByteMultiple::KB,
             sound: Sound::Pop,
             previous_threshold: 800_000,
+            sustain: 1,
+            cooldown: 0,
         }
     }
 }
@@ -143,6 +190,9 @@ pub struct FavoriteNotification {
     pub notify_on_favorite: bool,
     /// The sound to emit
     pub sound: Sound,
+    /// Minimum number of seconds between two notifications related to the same host
+    #[serde(default)]
+    pub cooldown: u64,
 }
 
 impl Default for FavoriteNotification {
@@ -150,24 +200,86 @@ fn default() -> Self {
         FavoriteNotification {
             notify_on_favorite: false,
             sound: Sound::Swhoosh,
+            cooldown: 0,
         }
     }
 }
 
 impl FavoriteNotification {
     /// Constructor when the notification is in use
-    pub fn on(sound: Sound) -> Self {
+    pub fn on(sound: Sound, cooldown: u64) -> Self {
         FavoriteNotification {
             notify_on_favorite: true,
             sound,
+            cooldown,
         }
     }
 
-    /// Constructor when the notification is not in use. Note that sound is used here for caching, although it won't actively be used.
-    pub fn off(sound: Sound) -> Self {
+    /// Constructor when the notification is not in use. Note that sound and cooldown are used here for caching, although they won't actively be used.
+    pub fn off(sound: Sound, cooldown: u64) -> Self {
         FavoriteNotification {
             notify_on_favorite: false,
             sound,
+            cooldown,
+        }
+    }
+}
+
+/// Kinds of events to be also shown through the desktop notification service (Linux only)
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug, Copy, Default)]
+pub struct DesktopNotifications {
+    /// Packets threshold exceeded
+    pub packets: bool,
+    /// Bytes threshold exceeded
+    pub bytes: bool,
+    /// Favorite connection exchanged data
+    pub favorites: bool,
+    /// Custom rule condition satisfied
+    pub rules: bool,
+}
+
+/// Keeps track of how long a threshold has been exceeded, to apply hysteresis and cooldown.
+#[derive(Clone, Copy, Debug)]
+pub struct ThresholdState {
+    /// Number of consecutive seconds the threshold has been exceeded
+    pub seconds_over: u64,
+    /// Whether a notification can be emitted; it's disarmed after a notification
+    /// and re-armed only when the observed value drops below the threshold
+    pub armed: bool,
+    /// Last time a notification was emitted
+    pub last_emitted: Option<Instant>,
+}
+
+impl Default for ThresholdState {
+    fn default() -> Self {
+        ThresholdState {
+            seconds_over: 0,
+            armed: true,
+            last_emitted: None,
+        }
+    }
+}
+
+impl ThresholdState {
+    /// Updates the state at the end of a time interval.
+    ///
+    /// It returns true if a notification has to be emitted
+    pub fn update(&mut self, exceeded: bool, sustain: u64, cooldown: u64) -> bool {
+        if !exceeded {
+            self.seconds_over = 0;
+            self.armed = true;
+            return false;
+        }
+        self.seconds_over += 1;
+        let cooled_down = self
+            .last_emitted
+            .is_none_or(|last| last.elapsed() >= Duration::from_secs(cooldown));
+        if self.armed && self.seconds_over >= sustain && cooled_down {
+            self.armed = false;
+            self.last_emitted = Some(Instant::now());
+            true
+        } else {
+            false
         }
     }
 }
@@ -176,8 +288,49 @@ pub fn off(sound: Sound) -> Self {
 mod tests {
     use rstest::rstest;
 
+    use crate::notifications::types::notification_rule::{RuleCondition, RuleMatcher};
+    use crate::ConfigSettings;
+
     use super::*;
 
+    #[test]
+    fn test_settings_serialized_as_toml() {
+        let mut config_settings = ConfigSettings::default();
+        let serialized = toml::to_string_pretty(&config_settings).unwrap();
+        let deserialized: ConfigSettings = toml::from_str(&serialized).unwrap();
+        assert!(deserialized.notifications.rules.is_empty());
+
+        let rule = NotificationRule {
+            sound: Sound::Pop,
+            cooldown: 30,
+            condition: RuleCondition::BytesPerSecond(1000),
+            matcher: RuleMatcher::Country("IT".to_string()),
+        };
+        config_settings.notifications.rules = vec![rule.clone()];
+        config_settings.notifications.history_path = PathBuf::from("/tmp/sniffnet_history.jsonl");
+        let serialized = toml::to_string_pretty(&config_settings).unwrap();
+        let deserialized: ConfigSettings = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.notifications.rules, vec![rule]);
+        assert_eq!(deserialized.notifications.volume, 60);
+        assert_eq!(
+            deserialized.notifications.history_path,
+            PathBuf::from("/tmp/sniffnet_history.jsonl")
+        );
+
+        // configurations stored without the history path use the default one
+        let legacy: String = serialized
+            .lines()
+            .filter(|line| !line.starts_with("history_path"))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        assert_ne!(legacy, serialized);
+        let deserialized: ConfigSettings = toml::from_str(&legacy).unwrap();
+        assert_eq!(
+            deserialized.notifications.history_path,
+            get_notifications_history_path()
+        );
+    }
+
     #[rstest]
     #[case("123", BytesNotification {
         previous_threshold: 123, threshold: Some(123), byte_multiple: ByteMultiple::B, ..BytesNotification::default() })]
@@ -222,31 +375,35 @@ fn test_will_reuse_previous_value_if_cannot_parse(#[case] input: &str) {
     #[test]
     fn test_can_instantiate_favourite_notification() {
         assert_eq!(
-            FavoriteNotification::on(Sound::Gulp),
+            FavoriteNotification::on(Sound::Gulp, 0),
             FavoriteNotification {
                 notify_on_favorite: true,
-                sound: Sound::Gulp
+                sound: Sound::Gulp,
+                cooldown: 0
             }
         );
         assert_eq!(
-            FavoriteNotification::on(Sound::Swhoosh),
+            FavoriteNotification::on(Sound::Swhoosh, 30),
             FavoriteNotification {
                 notify_on_favorite: true,
-                sound: Sound::Swhoosh
+                sound: Sound::Swhoosh,
+                cooldown: 30
             }
         );
         assert_eq!(
-            FavoriteNotification::off(Sound::Pop),
+            FavoriteNotification::off(Sound::Pop, 10),
             FavoriteNotification {
                 notify_on_favorite: false,
-                sound: Sound::Pop
+                sound: Sound::Pop,
+                cooldown: 10
             }
         );
         assert_eq!(
-            FavoriteNotification::off(Sound::None),
+            FavoriteNotification::off(Sound::None, 0),
             FavoriteNotification {
                 notify_on_favorite: false,
-                sound: Sound::None
+                sound: Sound::None,
+                cooldown: 0
             }
         );
     }
@@ -272,4 +429,41 @@ fn test_can_instantiate_packet_notification_from_string(
     ) {
         assert_eq!(expected, PacketsNotification::from(input, None));
     }
+
+    #[test]
+    fn test_threshold_state_hysteresis() {
+        let mut state = ThresholdState::default();
+        // needs 3 consecutive seconds over the threshold
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(false, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(state.update(true, 3, 0));
+        // not re-armed until the value drops below the threshold
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(false, 3, 0));
+        assert!(state.armed);
+        assert!(!state.update(true, 3, 0));
+        assert!(!state.update(true, 3, 0));
+        assert!(state.update(true, 3, 0));
+    }
+
+    #[test]
+    fn test_threshold_state_cooldown() {
+        let mut state = ThresholdState::default();
+        assert!(state.update(true, 1, 3600));
+        assert!(!state.update(false, 1, 3600));
+        // re-armed but still cooling down
+        assert!(!state.update(true, 1, 3600));
+        assert!(state.armed);
+        assert!(!state.update(true, 1, 3600));
+        // a zero cooldown never blocks notifications
+        let mut state = ThresholdState::default();
+        assert!(state.update(true, 0, 0));
+        assert!(!state.update(false, 0, 0));
+        assert!(state.update(true, 0, 0));
+    }
 }
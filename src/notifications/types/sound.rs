@@ -9,7 +9,7 @@
 use crate::Language;
 
 /// Enum representing the possible notification sounds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Sound {
     Gulp,
     Pop,
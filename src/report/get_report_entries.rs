@@ -1,48 +1,89 @@
 use std::cmp::{min, Ordering};
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 
 use crate::countries::country_utils::get_flag_tooltip;
 use crate::countries::flags_pictures::FLAGS_WIDTH_SMALL;
+use crate::countries::types::country::Country;
 use crate::gui::styles::style_constants::get_font;
 use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_filter::{IpFilter, PortFilter};
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::label::get_connection_labels;
+use crate::networking::types::search_parameters::{FilterInputType, SearchParameters};
+use crate::networking::types::search_query::{SearchQuery, SearchTarget};
+use crate::networking::types::traffic_type::TrafficType;
+use crate::report::types::host_grouping::HostGrouping;
+use crate::report::types::host_sort_type::HostSortType;
+use crate::report::types::report_column::ReportSort;
 use crate::report::types::report_entry::ReportEntry;
 use crate::{AppProtocol, ChartType, InfoTraffic, ReportSortType, Sniffer};
 
-/// Returns the elements which satisfy the search constraints and belong to the given page,
-/// and the total number of elements which satisfy the search constraints
-pub fn get_searched_entries(sniffer: &Sniffer) -> (Vec<ReportEntry>, usize) {
-    let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
-    let mut all_results: Vec<(&AddressPortPair, &InfoAddressPortPair)> = info_traffic_lock
+/// Maximum number of values suggested while typing in a filter input
+const MAX_FILTER_SUGGESTIONS: usize = 6;
+
+/// Returns the connections satisfying the search parameters, sorted according to the given criterion
+pub fn get_searched_connections<'a>(
+    info_traffic: &'a InfoTraffic,
+    search: &SearchParameters,
+    sort_type: ReportSortType,
+) -> Vec<(&'a AddressPortPair, &'a InfoAddressPortPair)> {
+    // invalid address filters and search expressions are ignored (the error is reported where they're typed)
+    let address = IpFilter::from_str(&search.address);
+    let source = IpFilter::from_str(&search.source);
+    let destination = IpFilter::from_str(&search.destination);
+    let port = PortFilter::from_str(&search.port);
+    let query = SearchQuery::parse(&search.query).ok().flatten();
+    let mut all_results: Vec<(&AddressPortPair, &InfoAddressPortPair)> = info_traffic
         .map
         .iter()
         .filter(|(key, value)| {
             let address_to_lookup = &get_address_to_lookup(key, value.traffic_direction);
-            let r_dns_host = info_traffic_lock.addresses_resolved.get(address_to_lookup);
+            // connections matched by the ignore list or not matched by the display filters are never shown
+            if info_traffic.is_ignored(address_to_lookup, value.app_protocol)
+                || !info_traffic.is_displayed(key, value.app_protocol)
+            {
+                return false;
+            }
+            let r_dns_host = info_traffic.addresses_resolved.get(address_to_lookup);
 
-            let searched_domain = &*sniffer.search.domain.to_lowercase();
-            let searched_country = &*sniffer.search.country.to_lowercase();
-            let searched_as_name = &*sniffer.search.as_name.to_lowercase();
-            let searched_only_fav = sniffer.search.only_favorites;
+            let searched_domain = &*search.domain.to_lowercase();
+            let searched_country = &*search.country.to_lowercase();
+            let searched_as_name = &*search.as_name.to_lowercase();
+            // address patterns of the favorites are matched even before the rDNS resolution
+            let is_favorite = info_traffic.is_favorite_connection(address_to_lookup);
             // if a host-related filter is active and this address has not been resolved yet => false
             if r_dns_host.is_none()
                 && (!searched_domain.is_empty()
                     || !searched_country.is_empty()
                     || !searched_as_name.is_empty()
-                    || searched_only_fav)
+                    || search.host.is_some())
             {
                 return false;
             }
             // check application protocol filter
-            let searched_app = &*sniffer.search.app.to_lowercase();
+            let searched_app = &*search.app.to_lowercase();
             let app = format!("{:?}", value.app_protocol).to_lowercase();
             if !searched_app.is_empty() && app.ne(searched_app) {
                 return false;
             }
+            // check address filters
+            if address.is_some_and(|address| {
+                !address.contains_str(&key.address1) && !address.contains_str(&key.address2)
+            }) || source.is_some_and(|source| !source.contains_str(&key.address1))
+                || destination.is_some_and(|destination| !destination.contains_str(&key.address2))
+            {
+                return false;
+            }
+            // check port filter
+            if port.is_some_and(|port| !port.contains(key.port1) && !port.contains(key.port2)) {
+                return false;
+            }
             // check domain filter
             if !searched_domain.is_empty() {
                 let domain = r_dns_host.unwrap().0.to_lowercase();
@@ -64,25 +105,88 @@ pub fn get_searched_entries(sniffer: &Sniffer) -> (Vec<ReportEntry>, usize) {
                     return false;
                 }
             }
+            // check exact host filter
+            if let Some((grouping, host)) = &search.host {
+                if grouping.group_of(&r_dns_host.unwrap().1).ne(host) {
+                    return false;
+                }
+            }
             // check favorites filter
-            if searched_only_fav
-                && !info_traffic_lock
-                    .hosts
-                    .get(&r_dns_host.unwrap().1)
-                    .unwrap()
-                    .is_favorite
-            {
+            if search.only_favorites && !is_favorite {
                 return false;
             }
+            // check search expression
+            if let Some(query) = &query {
+                if r_dns_host.is_none() && query.needs_host() {
+                    return false;
+                }
+                let target = SearchTarget {
+                    key,
+                    val: value,
+                    r_dns_host,
+                    is_favorite,
+                    labels: get_connection_labels(
+                        &info_traffic.labels,
+                        key,
+                        value,
+                        r_dns_host.map(|(_, host)| host),
+                    ),
+                };
+                if !query.matches(&target) {
+                    return false;
+                }
+            }
             // if arrived at this point all filters are satisfied => return true
             true
         })
         .collect();
-    all_results.sort_by(|&(_, a), &(_, b)| match sniffer.report_sort_type {
+    all_results.sort_by(|&(_, a), &(_, b)| match sort_type {
         ReportSortType::MostRecent => b.final_timestamp.cmp(&a.final_timestamp),
         ReportSortType::MostBytes => b.transmitted_bytes.cmp(&a.transmitted_bytes),
         ReportSortType::MostPackets => b.transmitted_packets.cmp(&a.transmitted_packets),
     });
+    all_results
+}
+
+/// Sorts the connections according to a column of the connections table
+///
+/// The sort is stable, so that connections with equal values keep their previous order
+pub fn sort_connections_by_column(
+    connections: &mut [(&AddressPortPair, &InfoAddressPortPair)],
+    info_traffic: &InfoTraffic,
+    sort: ReportSort,
+) {
+    let default_host = Host::default();
+    let get_host = |key: &AddressPortPair, val: &InfoAddressPortPair| {
+        let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
+        info_traffic
+            .addresses_resolved
+            .get(&address_to_lookup)
+            .map_or(&default_host, |(_, host)| host)
+    };
+    connections.sort_by(|&(key_a, val_a), &(key_b, val_b)| {
+        let ordering = sort.column.compare(
+            (key_a, val_a, get_host(key_a, val_a)),
+            (key_b, val_b, get_host(key_b, val_b)),
+        );
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// Returns the elements which satisfy the search constraints and belong to the given page,
+/// and the total number of elements which satisfy the search constraints
+pub fn get_searched_entries(sniffer: &Sniffer) -> (Vec<ReportEntry>, usize) {
+    let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
+    let mut all_results = get_searched_connections(
+        &info_traffic_lock,
+        &sniffer.search,
+        ReportSortType::MostRecent,
+    );
+    sort_connections_by_column(&mut all_results, &info_traffic_lock, sniffer.report_sort);
 
     let upper_bound = min(sniffer.page_number * 20, all_results.len());
 
@@ -116,6 +220,7 @@ pub fn get_searched_entries(sniffer: &Sniffer) -> (Vec<ReportEntry>, usize) {
                 ReportEntry {
                     key: key_val.0.clone(),
                     val: key_val.1.clone(),
+                    host,
                     tooltip: flag,
                 }
             })
@@ -127,19 +232,159 @@ pub fn get_searched_entries(sniffer: &Sniffer) -> (Vec<ReportEntry>, usize) {
 pub fn get_host_entries(
     info_traffic: &Arc<Mutex<InfoTraffic>>,
     chart_type: ChartType,
+    grouping: HostGrouping,
 ) -> Vec<(Host, DataInfoHost)> {
-    let info_traffic_lock = info_traffic.lock().unwrap();
-    let mut sorted_vec: Vec<(&Host, &DataInfoHost)> = info_traffic_lock.hosts.iter().collect();
+    let mut sorted_vec = get_grouped_hosts(&info_traffic.lock().unwrap(), grouping);
 
-    sorted_vec.sort_by(|&(_, a), &(_, b)| match chart_type {
+    sorted_vec.sort_by(|(_, a), (_, b)| match chart_type {
         ChartType::Packets => b.data_info.tot_packets().cmp(&a.data_info.tot_packets()),
         ChartType::Bytes => b.data_info.tot_bytes().cmp(&a.data_info.tot_bytes()),
     });
 
-    let n_entry = min(sorted_vec.len(), 30);
-    sorted_vec[0..n_entry]
+    sorted_vec.truncate(30);
+    sorted_vec
+}
+
+/// Returns the hosts not matched by the ignore list, aggregated according to the given grouping.
+///
+/// Each group is represented by a host having only the fields identifying it
+/// (see [`HostGrouping::group_of`]), and its traffic is the sum of the traffic of its hosts.
+pub fn get_grouped_hosts(
+    info_traffic: &InfoTraffic,
+    grouping: HostGrouping,
+) -> Vec<(Host, DataInfoHost)> {
+    if grouping.eq(&HostGrouping::None) {
+        return info_traffic
+            .hosts
+            .iter()
+            .filter(|(_, data_info_host)| info_traffic.is_host_displayed(data_info_host))
+            .map(|(host, data_info_host)| (host.clone(), data_info_host.clone()))
+            .collect();
+    }
+    let mut groups: HashMap<Host, DataInfoHost> = HashMap::new();
+    for (host, data_info_host) in info_traffic
+        .hosts
         .iter()
-        .map(|e| (e.0.clone(), e.1.clone()))
+        .filter(|(_, data_info_host)| info_traffic.is_host_displayed(data_info_host))
+    {
+        groups
+            .entry(grouping.group_of(host))
+            .and_modify(|group| {
+                group.data_info += data_info_host.data_info;
+                group.is_local &= data_info_host.is_local;
+                if group.traffic_type.ne(&data_info_host.traffic_type) {
+                    group.traffic_type = TrafficType::Unicast;
+                }
+                group.traffic_series.merge(&data_info_host.traffic_series);
+            })
+            .or_insert_with(|| DataInfoHost {
+                // favorites are single hosts
+                is_favorite: false,
+                ..data_info_host.clone()
+            });
+    }
+    groups.into_iter().collect()
+}
+
+/// Returns the hosts (or groups of hosts) of the given page (20 per page) among the ones matching
+/// the searched text in their domain, Autonomous System name or country,
+/// together with the number of matching hosts
+pub fn get_searched_hosts(
+    info_traffic: &InfoTraffic,
+    search: &str,
+    sort_type: HostSortType,
+    grouping: HostGrouping,
+    page_number: usize,
+) -> (Vec<(Host, DataInfoHost)>, usize) {
+    let search = search.trim().to_lowercase();
+    let mut all_results: Vec<(Host, DataInfoHost)> = get_grouped_hosts(info_traffic, grouping)
+        .into_iter()
+        .filter(|(host, _)| {
+            search.is_empty()
+                || host.domain.to_lowercase().contains(&search)
+                || host.asn.name.to_lowercase().contains(&search)
+                || host.country.to_string().to_lowercase().eq(&search)
+        })
+        .collect();
+    all_results.sort_by(|(host_a, a), (host_b, b)| {
+        match sort_type {
+            HostSortType::MostBytes => b.data_info.tot_bytes().cmp(&a.data_info.tot_bytes()),
+            HostSortType::MostPackets => b.data_info.tot_packets().cmp(&a.data_info.tot_packets()),
+            HostSortType::DomainName => host_a.domain.cmp(&host_b.domain),
+            // hosts with unknown country are listed last
+            HostSortType::Country => (host_a.country.eq(&Country::ZZ), host_a.country.to_string())
+                .cmp(&(host_b.country.eq(&Country::ZZ), host_b.country.to_string()))
+                .then_with(|| b.data_info.tot_bytes().cmp(&a.data_info.tot_bytes())),
+        }
+        .then_with(|| host_a.domain.cmp(&host_b.domain))
+        .then_with(|| host_a.asn.name.cmp(&host_b.asn.name))
+    });
+
+    let results_number = all_results.len();
+    (
+        all_results
+            .into_iter()
+            .skip((page_number - 1) * 20)
+            .take(20)
+            .collect(),
+        results_number,
+    )
+}
+
+/// Returns the values observed in the captured traffic that match the text typed in a filter input,
+/// ranked by traffic volume (at most `MAX_FILTER_SUGGESTIONS`)
+pub fn get_filter_suggestions(
+    info_traffic: &InfoTraffic,
+    filter: FilterInputType,
+    typed: &str,
+) -> Vec<String> {
+    let typed = typed.trim().to_lowercase();
+    let mut volumes: HashMap<String, u128> = HashMap::new();
+    if filter.eq(&FilterInputType::App) {
+        for (app_protocol, data_info) in info_traffic
+            .app_protocols
+            .iter()
+            .filter(|(app_protocol, _)| !info_traffic.ignored_app_protocols.contains(app_protocol))
+        {
+            *volumes.entry(format!("{app_protocol:?}")).or_default() += data_info.tot_bytes();
+        }
+    } else {
+        for (host, data_info_host) in info_traffic
+            .hosts
+            .iter()
+            .filter(|(_, data_info_host)| info_traffic.is_host_displayed(data_info_host))
+        {
+            let value = match filter {
+                FilterInputType::Domain => host.domain.clone(),
+                FilterInputType::Country => host.country.to_string(),
+                FilterInputType::AS => host.asn.name.clone(),
+                _ => return Vec::new(),
+            };
+            *volumes.entry(value).or_default() += data_info_host.data_info.tot_bytes();
+        }
+    }
+
+    let mut suggestions: Vec<(String, u128)> = volumes
+        .into_iter()
+        .filter(|(value, _)| {
+            let value = value.to_lowercase();
+            // the country filter matches the beginning of the country code
+            let is_match = if filter.eq(&FilterInputType::Country) {
+                value.starts_with(&typed)
+            } else {
+                value.contains(&typed)
+            };
+            // hosts without a domain name are identified by their address
+            is_match && value.ne(&typed) && !value.is_empty() && value.parse::<IpAddr>().is_err()
+        })
+        .collect();
+    suggestions.sort_by(|(value_a, bytes_a), (value_b, bytes_b)| {
+        bytes_b.cmp(bytes_a).then_with(|| value_a.cmp(value_b))
+    });
+    suggestions
+        .into_iter()
+        .take(MAX_FILTER_SUGGESTIONS)
+        .map(|(value, _)| value)
         .collect()
 }
 
@@ -148,8 +393,15 @@ pub fn get_app_entries(
     chart_type: ChartType,
 ) -> Vec<(AppProtocol, DataInfo)> {
     let info_traffic_lock = info_traffic.lock().unwrap();
-    let mut sorted_vec: Vec<(&AppProtocol, &DataInfo)> =
-        info_traffic_lock.app_protocols.iter().collect();
+    let mut sorted_vec: Vec<(&AppProtocol, &DataInfo)> = info_traffic_lock
+        .app_protocols
+        .iter()
+        .filter(|(app_protocol, _)| {
+            !info_traffic_lock
+                .ignored_app_protocols
+                .contains(app_protocol)
+        })
+        .collect();
 
     sorted_vec.sort_by(|&(p1, a), &(p2, b)| {
         if p1.eq(&AppProtocol::Other) {
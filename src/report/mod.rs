@@ -1,2 +1,3 @@
 pub mod get_report_entries;
+pub mod prometheus_metrics;
 pub mod types;
@@ -0,0 +1,283 @@
+//! Module containing functions to expose the traffic statistics in Prometheus text format.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::networking::types::data_info::DataInfo;
+use crate::InfoTraffic;
+
+/// Number of hosts, countries, and Autonomous Systems exposed as labelled series
+pub const METRICS_TOP_N: usize = 20;
+
+/// Returns the current traffic statistics in Prometheus text exposition format.
+///
+/// Hosts, countries, and Autonomous Systems are limited to the `top_n` ones by exchanged bytes
+pub fn get_prometheus_metrics(info_traffic: &InfoTraffic, top_n: usize) -> String {
+    let mut metrics = String::new();
+
+    let totals = [
+        (
+            "sniffnet_received_bytes_total",
+            "Filtered bytes received",
+            info_traffic.tot_received_bytes,
+        ),
+        (
+            "sniffnet_sent_bytes_total",
+            "Filtered bytes sent",
+            info_traffic.tot_sent_bytes,
+        ),
+        (
+            "sniffnet_received_packets_total",
+            "Filtered packets received",
+            info_traffic.tot_received_packets,
+        ),
+        (
+            "sniffnet_sent_packets_total",
+            "Filtered packets sent",
+            info_traffic.tot_sent_packets,
+        ),
+        (
+            "sniffnet_all_bytes_total",
+            "Bytes observed, including the ones not filtered",
+            info_traffic.all_bytes,
+        ),
+        (
+            "sniffnet_all_packets_total",
+            "Packets observed, including the ones not filtered",
+            info_traffic.all_packets,
+        ),
+        (
+            "sniffnet_dropped_packets_total",
+            "Packets dropped by the capture",
+            u128::from(info_traffic.dropped_packets),
+        ),
+    ];
+    for (name, help, value) in totals {
+        write_header(&mut metrics, name, help, "counter");
+        let _ = writeln!(metrics, "{name} {value}");
+    }
+
+    write_header(
+        &mut metrics,
+        "sniffnet_connections",
+        "Connections observed",
+        "gauge",
+    );
+    let _ = writeln!(metrics, "sniffnet_connections {}", info_traffic.map.len());
+
+    let mut app_protocols: Vec<(String, DataInfo)> = info_traffic
+        .app_protocols
+        .iter()
+        .filter(|(app, _)| !info_traffic.ignored_app_protocols.contains(app))
+        .map(|(app, data_info)| (format!("{app:?}"), *data_info))
+        .collect();
+    app_protocols.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let app_protocols: Vec<(Vec<(&str, String)>, DataInfo)> = app_protocols
+        .into_iter()
+        .map(|(app, data_info)| (vec![("app_protocol", app)], data_info))
+        .collect();
+    write_data_info_series(
+        &mut metrics,
+        "sniffnet_app_protocol",
+        "per application protocol",
+        &app_protocols,
+    );
+
+    // ignored hosts (and, with display filters, the ones without matching traffic) are skipped as in the GUI
+    let displayed_hosts = info_traffic
+        .hosts
+        .iter()
+        .filter(|(_, data_info_host)| info_traffic.is_host_displayed(data_info_host));
+    let mut hosts: Vec<_> = displayed_hosts.clone().collect();
+    hosts.sort_by_key(|(_, data_info_host)| Reverse(data_info_host.data_info.tot_bytes()));
+    let hosts: Vec<(Vec<(&str, String)>, DataInfo)> = hosts
+        .into_iter()
+        .take(top_n)
+        .map(|(host, data_info_host)| {
+            (
+                vec![
+                    ("domain", host.domain.clone()),
+                    ("asn", host.asn.name.clone()),
+                    ("country", host.country.to_string()),
+                ],
+                data_info_host.data_info,
+            )
+        })
+        .collect();
+    write_data_info_series(&mut metrics, "sniffnet_host", "per host", &hosts);
+
+    let mut countries: HashMap<String, DataInfo> = HashMap::new();
+    let mut asns: HashMap<(u32, String), DataInfo> = HashMap::new();
+    for (host, data_info_host) in displayed_hosts {
+        *countries.entry(host.country.to_string()).or_default() += data_info_host.data_info;
+        if !host.asn.name.is_empty() {
+            *asns
+                .entry((host.asn.number, host.asn.name.clone()))
+                .or_default() += data_info_host.data_info;
+        }
+    }
+    let countries: Vec<(Vec<(&str, String)>, DataInfo)> = top_by_bytes(countries, top_n)
+        .into_iter()
+        .map(|(country, data_info)| (vec![("country", country)], data_info))
+        .collect();
+    write_data_info_series(&mut metrics, "sniffnet_country", "per country", &countries);
+    let asns: Vec<(Vec<(&str, String)>, DataInfo)> = top_by_bytes(asns, top_n)
+        .into_iter()
+        .map(|((number, name), data_info)| {
+            (
+                vec![("asn_number", number.to_string()), ("asn_name", name)],
+                data_info,
+            )
+        })
+        .collect();
+    write_data_info_series(&mut metrics, "sniffnet_asn", "per Autonomous System", &asns);
+
+    metrics
+}
+
+/// Returns the `top_n` entries by exchanged bytes, sorted in descending order
+fn top_by_bytes<K: Ord>(map: HashMap<K, DataInfo>, top_n: usize) -> Vec<(K, DataInfo)> {
+    let mut entries: Vec<(K, DataInfo)> = map.into_iter().collect();
+    entries.sort_by(|(key_a, a), (key_b, b)| {
+        b.tot_bytes()
+            .cmp(&a.tot_bytes())
+            .then_with(|| key_a.cmp(key_b))
+    });
+    entries.truncate(top_n);
+    entries
+}
+
+/// Writes bytes and packets series, split by direction, for each of the given label sets
+fn write_data_info_series(
+    metrics: &mut String,
+    prefix: &str,
+    help_suffix: &str,
+    series: &[(Vec<(&str, String)>, DataInfo)],
+) {
+    for unit in ["bytes", "packets"] {
+        let name = format!("{prefix}_{unit}_total");
+        write_header(
+            metrics,
+            &name,
+            &format!("Filtered {unit} exchanged {help_suffix}"),
+            "counter",
+        );
+        for (labels, data_info) in series {
+            let (incoming, outgoing) = if unit.eq("bytes") {
+                (data_info.incoming_bytes, data_info.outgoing_bytes)
+            } else {
+                (data_info.incoming_packets, data_info.outgoing_packets)
+            };
+            for (direction, value) in [("incoming", incoming), ("outgoing", outgoing)] {
+                let mut labels_str = String::new();
+                for (key, label_value) in labels {
+                    let _ = write!(labels_str, "{key}=\"{}\",", escape_label(label_value));
+                }
+                let _ = writeln!(
+                    metrics,
+                    "{name}{{{labels_str}direction=\"{direction}\"}} {value}"
+                );
+            }
+        }
+    }
+}
+
+fn write_header(metrics: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(metrics, "# HELP {name} {help}");
+    let _ = writeln!(metrics, "# TYPE {name} {metric_type}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::AppProtocol;
+
+    use super::*;
+
+    fn host(domain: &str, asn: &str, country: Country) -> Host {
+        Host {
+            domain: domain.to_string(),
+            asn: Asn {
+                number: 1,
+                name: asn.to_string(),
+            },
+            country,
+        }
+    }
+
+    #[test]
+    fn test_prometheus_metrics() {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.add_packet(100, TrafficDirection::Incoming);
+        info_traffic.add_packet(40, TrafficDirection::Outgoing);
+        info_traffic.dropped_packets = 3;
+        info_traffic.app_protocols.insert(
+            AppProtocol::HTTPS,
+            DataInfo::new_with_first_packet(100, TrafficDirection::Incoming),
+        );
+        info_traffic.hosts.insert(
+            host("big.com", "BIG \"AS\"", Country::US),
+            DataInfoHost {
+                data_info: DataInfo::new_with_first_packet(100, TrafficDirection::Incoming),
+                ..DataInfoHost::default()
+            },
+        );
+        info_traffic.hosts.insert(
+            host("small.com", "", Country::IT),
+            DataInfoHost {
+                data_info: DataInfo::new_with_first_packet(40, TrafficDirection::Outgoing),
+                ..DataInfoHost::default()
+            },
+        );
+
+        let metrics = get_prometheus_metrics(&info_traffic, 1);
+        let lines: Vec<&str> = metrics.lines().collect();
+        for expected in [
+            "# TYPE sniffnet_received_bytes_total counter",
+            "sniffnet_received_bytes_total 100",
+            "sniffnet_sent_bytes_total 40",
+            "sniffnet_received_packets_total 1",
+            "sniffnet_sent_packets_total 1",
+            "sniffnet_dropped_packets_total 3",
+            "sniffnet_connections 0",
+            "sniffnet_app_protocol_bytes_total{app_protocol=\"HTTPS\",direction=\"incoming\"} 100",
+            "sniffnet_app_protocol_packets_total{app_protocol=\"HTTPS\",direction=\"outgoing\"} 0",
+            "sniffnet_host_bytes_total{domain=\"big.com\",asn=\"BIG \\\"AS\\\"\",country=\"US\",direction=\"incoming\"} 100",
+            "sniffnet_country_bytes_total{country=\"US\",direction=\"outgoing\"} 0",
+            "sniffnet_asn_packets_total{asn_number=\"1\",asn_name=\"BIG \\\"AS\\\"\",direction=\"incoming\"} 1",
+        ] {
+            assert!(lines.contains(&expected), "missing line: {expected}");
+        }
+        // only the top host and country are exposed
+        assert!(!metrics.contains("small.com"));
+        assert!(!metrics.contains("country=\"IT\""));
+
+        // ignored hosts and application protocols aren't exposed
+        info_traffic
+            .hosts
+            .get_mut(&host("big.com", "BIG \"AS\"", Country::US))
+            .unwrap()
+            .is_ignored = true;
+        info_traffic
+            .ignored_app_protocols
+            .insert(AppProtocol::HTTPS);
+        let metrics = get_prometheus_metrics(&info_traffic, 1);
+        assert!(!metrics.contains("big.com"));
+        assert!(!metrics.contains("BIG"));
+        assert!(!metrics.contains("country=\"US\""));
+        assert!(!metrics.contains("HTTPS"));
+        assert!(metrics.contains("sniffnet_host_bytes_total{domain=\"small.com\""));
+    }
+}
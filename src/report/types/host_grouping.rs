@@ -0,0 +1,70 @@
+use crate::countries::types::country::Country;
+use crate::networking::types::asn::Asn;
+use crate::networking::types::host::Host;
+use crate::translations::translations_3::{
+    country_sort_translation, no_grouping_translation, organization_grouping_translation,
+    registrable_domain_translation,
+};
+use crate::Language;
+
+/// Enum representing the possible ways to aggregate the hosts in the overview and hosts pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HostGrouping {
+    /// Each host is displayed on its own
+    #[default]
+    None,
+    /// Hosts are grouped by the Autonomous System (organization) operating them
+    Organization,
+    /// Hosts are grouped by registrable domain (public suffix plus one label)
+    Domain,
+    /// Hosts are grouped by country
+    Country,
+}
+
+impl HostGrouping {
+    pub(crate) const ALL: [HostGrouping; 4] = [
+        HostGrouping::None,
+        HostGrouping::Organization,
+        HostGrouping::Domain,
+        HostGrouping::Country,
+    ];
+
+    pub fn get_picklist_label(self, language: Language) -> &'static str {
+        match self {
+            HostGrouping::None => no_grouping_translation(language),
+            HostGrouping::Organization => organization_grouping_translation(language),
+            HostGrouping::Domain => registrable_domain_translation(language),
+            HostGrouping::Country => country_sort_translation(language),
+        }
+    }
+
+    pub fn all_strings(language: Language) -> Vec<&'static str> {
+        HostGrouping::ALL
+            .iter()
+            .map(|grouping| grouping.get_picklist_label(language))
+            .collect()
+    }
+
+    /// Returns the host representing the group of the given host,
+    /// keeping only the fields identifying the group
+    pub fn group_of(self, host: &Host) -> Host {
+        match self {
+            HostGrouping::None => host.clone(),
+            HostGrouping::Organization => Host {
+                domain: String::new(),
+                asn: host.asn.clone(),
+                country: Country::ZZ,
+            },
+            HostGrouping::Domain => Host {
+                domain: host.domain.clone(),
+                asn: Asn::default(),
+                country: Country::ZZ,
+            },
+            HostGrouping::Country => Host {
+                domain: String::new(),
+                asn: Asn::default(),
+                country: host.country,
+            },
+        }
+    }
+}
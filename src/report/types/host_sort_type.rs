@@ -0,0 +1,38 @@
+use crate::translations::translations::{bytes_report_translation, packets_report_translation};
+use crate::translations::translations_3::{country_sort_translation, domain_name_sort_translation};
+use crate::Language;
+
+/// Enum representing the possible orderings of the hosts page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HostSortType {
+    #[default]
+    MostBytes,
+    MostPackets,
+    DomainName,
+    Country,
+}
+
+impl HostSortType {
+    pub(crate) const ALL: [HostSortType; 4] = [
+        HostSortType::MostBytes,
+        HostSortType::MostPackets,
+        HostSortType::DomainName,
+        HostSortType::Country,
+    ];
+
+    pub fn get_picklist_label(self, language: Language) -> &'static str {
+        match self {
+            HostSortType::MostBytes => bytes_report_translation(language),
+            HostSortType::MostPackets => packets_report_translation(language),
+            HostSortType::DomainName => domain_name_sort_translation(language),
+            HostSortType::Country => country_sort_translation(language),
+        }
+    }
+
+    pub fn all_strings(language: Language) -> Vec<&'static str> {
+        HostSortType::ALL
+            .iter()
+            .map(|sort_type| sort_type.get_picklist_label(language))
+            .collect()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod host_grouping;
+pub mod host_sort_type;
+pub mod report_column;
 pub mod report_entry;
 pub mod report_sort_type;
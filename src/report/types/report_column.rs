@@ -0,0 +1,392 @@
+//! Module defining the columns of the connections table displayed in the inspect page,
+//! with their persisted widths and visibility.
+
+use std::cmp::Ordering;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::label::{
+    get_address_label_entry, get_host_label_entry, with_alias, Label,
+};
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::translations::translations::{incoming_translation, outgoing_translation};
+use crate::translations::translations_2::{country_translation, domain_name_translation};
+use crate::translations::translations_3::{
+    bytes_column_translation, destination_address_column_translation,
+    destination_port_column_translation, direction_translation, first_seen_translation,
+    last_seen_translation, packets_column_translation, source_address_column_translation,
+    source_port_column_translation,
+};
+use crate::utils::formatted_strings::get_formatted_bytes_string_with_b;
+use crate::{AppProtocol, Language};
+
+/// Minimum width of a column, in pixels
+pub const MIN_COLUMN_WIDTH: u16 = 40;
+/// Maximum width of a column, in pixels
+pub const MAX_COLUMN_WIDTH: u16 = 600;
+
+/// Enum representing the columns of the connections table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReportColumn {
+    SourceAddress,
+    SourcePort,
+    DestinationAddress,
+    DestinationPort,
+    TransportProtocol,
+    AppProtocol,
+    Domain,
+    Country,
+    Asn,
+    Packets,
+    Bytes,
+    FirstSeen,
+    LastSeen,
+    Direction,
+}
+
+impl ReportColumn {
+    pub(crate) const ALL: [ReportColumn; 14] = [
+        ReportColumn::SourceAddress,
+        ReportColumn::SourcePort,
+        ReportColumn::DestinationAddress,
+        ReportColumn::DestinationPort,
+        ReportColumn::TransportProtocol,
+        ReportColumn::AppProtocol,
+        ReportColumn::Domain,
+        ReportColumn::Country,
+        ReportColumn::Asn,
+        ReportColumn::Packets,
+        ReportColumn::Bytes,
+        ReportColumn::FirstSeen,
+        ReportColumn::LastSeen,
+        ReportColumn::Direction,
+    ];
+
+    pub fn get_label(self, language: Language) -> &'static str {
+        match self {
+            ReportColumn::SourceAddress => source_address_column_translation(language),
+            ReportColumn::SourcePort => source_port_column_translation(language),
+            ReportColumn::DestinationAddress => destination_address_column_translation(language),
+            ReportColumn::DestinationPort => destination_port_column_translation(language),
+            // protocol layers and ASN are technical abbreviations, the same in every language
+            ReportColumn::TransportProtocol => "Layer4",
+            ReportColumn::AppProtocol => "Layer7",
+            ReportColumn::Domain => domain_name_translation(language),
+            ReportColumn::Country => country_translation(language),
+            ReportColumn::Asn => "ASN",
+            ReportColumn::Packets => packets_column_translation(language),
+            ReportColumn::Bytes => bytes_column_translation(language),
+            ReportColumn::FirstSeen => first_seen_translation(language),
+            ReportColumn::LastSeen => last_seen_translation(language),
+            ReportColumn::Direction => direction_translation(language),
+        }
+    }
+
+    fn default_settings(self) -> ReportColumnSettings {
+        let (width, visible) = match self {
+            ReportColumn::SourceAddress | ReportColumn::DestinationAddress => (210, true),
+            ReportColumn::SourcePort | ReportColumn::DestinationPort => (80, true),
+            ReportColumn::TransportProtocol => (70, true),
+            ReportColumn::AppProtocol => (90, true),
+            ReportColumn::Domain => (220, false),
+            ReportColumn::Country => (90, true),
+            ReportColumn::Asn => (200, false),
+            ReportColumn::Packets | ReportColumn::Bytes => (90, true),
+            ReportColumn::FirstSeen | ReportColumn::LastSeen => (170, false),
+            ReportColumn::Direction => (90, false),
+        };
+        ReportColumnSettings {
+            column: self,
+            width,
+            visible,
+        }
+    }
+
+    /// Whether the values of this column are numbers or dates,
+    /// which are sorted from the largest or most recent by default
+    pub fn is_quantity(self) -> bool {
+        matches!(
+            self,
+            ReportColumn::Packets
+                | ReportColumn::Bytes
+                | ReportColumn::FirstSeen
+                | ReportColumn::LastSeen
+        )
+    }
+
+    /// Text displayed in the cell of this column for the given connection
+    pub fn get_cell_text(
+        self,
+        key: &AddressPortPair,
+        val: &InfoAddressPortPair,
+        host: &Host,
+        labels: &[Label],
+        language: Language,
+    ) -> String {
+        match self {
+            ReportColumn::SourceAddress => with_alias(
+                get_address_label_entry(labels, &key.address1, &val.mac_address1),
+                &key.address1,
+            ),
+            ReportColumn::SourcePort => key.port1.to_string(),
+            ReportColumn::DestinationAddress => with_alias(
+                get_address_label_entry(labels, &key.address2, &val.mac_address2),
+                &key.address2,
+            ),
+            ReportColumn::DestinationPort => key.port2.to_string(),
+            ReportColumn::TransportProtocol => key.trans_protocol.to_string(),
+            ReportColumn::AppProtocol => match val.app_protocol {
+                AppProtocol::Other => "Other".to_string(),
+                app_protocol => app_protocol.to_string(),
+            },
+            ReportColumn::Domain => with_alias(get_host_label_entry(labels, host), &host.domain),
+            ReportColumn::Country => host.country.to_string(),
+            ReportColumn::Asn => {
+                if host.asn.number == 0 {
+                    String::new()
+                } else {
+                    format!("AS{} {}", host.asn.number, host.asn.name)
+                }
+            }
+            ReportColumn::Packets => val.transmitted_packets.to_string(),
+            ReportColumn::Bytes => get_formatted_bytes_string_with_b(val.transmitted_bytes),
+            ReportColumn::FirstSeen => val
+                .initial_timestamp
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            ReportColumn::LastSeen => val.final_timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            ReportColumn::Direction => match val.traffic_direction {
+                TrafficDirection::Incoming => incoming_translation(language).to_string(),
+                TrafficDirection::Outgoing => outgoing_translation(language).to_string(),
+            },
+        }
+    }
+
+    /// Compares two connections according to the values of this column (in ascending order)
+    pub fn compare(
+        self,
+        (key_a, val_a, host_a): (&AddressPortPair, &InfoAddressPortPair, &Host),
+        (key_b, val_b, host_b): (&AddressPortPair, &InfoAddressPortPair, &Host),
+    ) -> Ordering {
+        match self {
+            ReportColumn::SourceAddress => compare_addresses(&key_a.address1, &key_b.address1),
+            ReportColumn::SourcePort => key_a.port1.cmp(&key_b.port1),
+            ReportColumn::DestinationAddress => compare_addresses(&key_a.address2, &key_b.address2),
+            ReportColumn::DestinationPort => key_a.port2.cmp(&key_b.port2),
+            ReportColumn::Asn => host_a.asn.number.cmp(&host_b.asn.number),
+            ReportColumn::Packets => val_a.transmitted_packets.cmp(&val_b.transmitted_packets),
+            ReportColumn::Bytes => val_a.transmitted_bytes.cmp(&val_b.transmitted_bytes),
+            ReportColumn::FirstSeen => val_a.initial_timestamp.cmp(&val_b.initial_timestamp),
+            ReportColumn::LastSeen => val_a.final_timestamp.cmp(&val_b.final_timestamp),
+            _ => self
+                .get_cell_text(key_a, val_a, host_a, &[], Language::EN)
+                .to_lowercase()
+                .cmp(
+                    &self
+                        .get_cell_text(key_b, val_b, host_b, &[], Language::EN)
+                        .to_lowercase(),
+                ),
+        }
+    }
+}
+
+/// Compares two addresses numerically if they are IP addresses, or alphabetically otherwise
+fn compare_addresses(a: &str, b: &str) -> Ordering {
+    match (a.parse::<IpAddr>(), b.parse::<IpAddr>()) {
+        (Ok(ip_a), Ok(ip_b)) => ip_a.cmp(&ip_b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Criterion to sort the connections table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReportSort {
+    pub column: ReportColumn,
+    pub descending: bool,
+}
+
+impl Default for ReportSort {
+    fn default() -> Self {
+        Self {
+            column: ReportColumn::LastSeen,
+            descending: true,
+        }
+    }
+}
+
+impl ReportSort {
+    /// Sorts by the given column, inverting the order if the table is already sorted by it
+    pub fn toggle(self, column: ReportColumn) -> Self {
+        if self.column.eq(&column) {
+            Self {
+                column,
+                descending: !self.descending,
+            }
+        } else {
+            Self {
+                column,
+                descending: column.is_quantity(),
+            }
+        }
+    }
+}
+
+/// Width and visibility of a column of the connections table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReportColumnSettings {
+    pub column: ReportColumn,
+    /// Width in pixels
+    pub width: u16,
+    pub visible: bool,
+}
+
+/// Settings of all the columns of the connections table
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReportColumns {
+    columns: Vec<ReportColumnSettings>,
+}
+
+impl Default for ReportColumns {
+    fn default() -> Self {
+        Self {
+            columns: ReportColumn::ALL
+                .iter()
+                .map(|column| column.default_settings())
+                .collect(),
+        }
+    }
+}
+
+impl ReportColumns {
+    /// Settings of the given column (the default ones if not stored yet)
+    pub fn get(&self, column: ReportColumn) -> ReportColumnSettings {
+        self.columns
+            .iter()
+            .find(|settings| settings.column.eq(&column))
+            .copied()
+            .unwrap_or_else(|| column.default_settings())
+    }
+
+    /// Columns currently displayed, in table order
+    pub fn visible(&self) -> Vec<ReportColumnSettings> {
+        ReportColumn::ALL
+            .iter()
+            .map(|column| self.get(*column))
+            .filter(|settings| settings.visible)
+            .collect()
+    }
+
+    /// Shows or hides the given column; the last visible column cannot be hidden
+    pub fn toggle(&mut self, column: ReportColumn) {
+        let mut settings = self.get(column);
+        if settings.visible && self.visible().len() == 1 {
+            return;
+        }
+        settings.visible = !settings.visible;
+        self.set(settings);
+    }
+
+    /// Changes the width of the given column by the given amount of pixels
+    pub fn resize(&mut self, column: ReportColumn, delta: f32) {
+        let mut settings = self.get(column);
+        #[allow(clippy::cast_possible_truncation)]
+        let width = (f32::from(settings.width) + delta).round() as i32;
+        settings.width =
+            u16::try_from(width.clamp(i32::from(MIN_COLUMN_WIDTH), i32::from(MAX_COLUMN_WIDTH)))
+                .unwrap_or(MIN_COLUMN_WIDTH);
+        self.set(settings);
+    }
+
+    fn set(&mut self, settings: ReportColumnSettings) {
+        if let Some(stored) = self
+            .columns
+            .iter_mut()
+            .find(|stored| stored.column.eq(&settings.column))
+        {
+            *stored = settings;
+        } else {
+            self.columns.push(settings);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigSettings;
+
+    use super::*;
+
+    #[test]
+    fn test_report_columns() {
+        let mut columns = ReportColumns::default();
+        assert_eq!(columns.visible().len(), 9);
+        assert!(!columns.get(ReportColumn::Domain).visible);
+
+        columns.toggle(ReportColumn::Domain);
+        columns.toggle(ReportColumn::Bytes);
+        let visible: Vec<ReportColumn> = columns.visible().iter().map(|s| s.column).collect();
+        assert!(visible.contains(&ReportColumn::Domain));
+        assert!(!visible.contains(&ReportColumn::Bytes));
+
+        columns.resize(ReportColumn::Domain, 30.4);
+        assert_eq!(columns.get(ReportColumn::Domain).width, 250);
+        columns.resize(ReportColumn::Domain, -1000.0);
+        assert_eq!(columns.get(ReportColumn::Domain).width, MIN_COLUMN_WIDTH);
+        columns.resize(ReportColumn::Domain, 1000.0);
+        assert_eq!(columns.get(ReportColumn::Domain).width, MAX_COLUMN_WIDTH);
+
+        // columns missing from stored settings get their default ones
+        let mut stored = ReportColumns { columns: vec![] };
+        assert_eq!(stored.visible(), ReportColumns::default().visible());
+        // the last visible column can't be hidden
+        for column in ReportColumn::ALL {
+            stored.toggle(column);
+        }
+        let visible = stored.visible();
+        assert_eq!(visible.len(), 5);
+        for settings in visible {
+            stored.toggle(settings.column);
+        }
+        assert_eq!(stored.visible().len(), 1);
+    }
+
+    #[test]
+    fn test_report_columns_persisted() {
+        let mut config_settings = ConfigSettings::default();
+        config_settings.report_columns.toggle(ReportColumn::Asn);
+        config_settings
+            .report_columns
+            .resize(ReportColumn::SourceAddress, 50.0);
+        let serialized = toml::to_string(&config_settings).unwrap();
+        let deserialized: ConfigSettings = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.report_columns, config_settings.report_columns);
+
+        // settings stored before the columns were configurable get the default ones
+        let (before, after) = serialized.split_once("[[report_columns]]").unwrap();
+        let legacy = format!("{before}[style]{}", after.split_once("[style]").unwrap().1);
+        let deserialized: ConfigSettings = toml::from_str(&legacy).unwrap();
+        assert_eq!(deserialized.report_columns, ReportColumns::default());
+    }
+
+    #[test]
+    fn test_report_sort() {
+        let sort = ReportSort::default();
+        assert_eq!(sort.column, ReportColumn::LastSeen);
+        assert!(sort.descending);
+        let sort = sort.toggle(ReportColumn::LastSeen);
+        assert!(!sort.descending);
+        let sort = sort.toggle(ReportColumn::Domain);
+        assert_eq!(sort.column, ReportColumn::Domain);
+        assert!(!sort.descending);
+        let sort = sort.toggle(ReportColumn::Bytes);
+        assert!(sort.descending);
+
+        assert_eq!(compare_addresses("10.0.0.2", "9.0.0.1"), Ordering::Greater);
+        assert_eq!(compare_addresses("::1", "fe80::1"), Ordering::Less);
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::gui::types::message::Message;
 use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::StyleType;
 
@@ -10,5 +11,6 @@
 pub struct ReportEntry {
     pub key: AddressPortPair,
     pub val: InfoAddressPortPair,
+    pub host: Host,
     pub tooltip: Tooltip<'static, Message, Renderer<StyleType>>,
 }
@@ -13,14 +13,6 @@ pub enum ReportSortType {
 }
 
 impl ReportSortType {
-    pub fn all_strings(language: Language) -> Vec<&'static str> {
-        vec![
-            recent_report_translation(language),
-            bytes_report_translation(language),
-            packets_report_translation(language),
-        ]
-    }
-
     pub fn get_picklist_label(self, language: Language) -> &'static str {
         match self {
             ReportSortType::MostRecent => recent_report_translation(language),
@@ -0,0 +1,111 @@
+//! Module containing the functions executed by the thread exposing the Prometheus metrics endpoint
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::report::prometheus_metrics::{get_prometheus_metrics, METRICS_TOP_N};
+use crate::InfoTraffic;
+
+/// The calling thread listens on the given address and replies to `GET /metrics` requests
+/// with the current traffic statistics in Prometheus text format
+pub fn serve_metrics_loop(address: SocketAddr, info_traffic_mutex: &Arc<Mutex<InfoTraffic>>) {
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("sniffnet: cannot expose metrics on {address}: {e}");
+            return;
+        }
+    };
+    for stream in listener.incoming().flatten() {
+        let _ = handle_connection(stream, info_traffic_mutex);
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, content_type, body) =
+        if method.eq("GET") && (path.eq("/metrics") || path.starts_with("/metrics?")) {
+            let info_traffic = info_traffic_mutex.lock().unwrap();
+            let body = get_prometheus_metrics(&info_traffic, METRICS_TOP_N);
+            drop(info_traffic);
+            ("200 OK", "text/plain; version=0.0.4; charset=utf-8", body)
+        } else {
+            (
+                "404 Not Found",
+                "text/plain; charset=utf-8",
+                "Not Found\n".to_string(),
+            )
+        };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::thread;
+    use std::time::Instant;
+
+    use crate::networking::types::traffic_direction::TrafficDirection;
+
+    use super::*;
+
+    fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    /// Waits until the condition is satisfied, failing if it takes too long
+    fn wait_until(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition not met in time");
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_metrics_endpoint() {
+        // find a free port
+        let address = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let info_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+        info_traffic
+            .lock()
+            .unwrap()
+            .add_packet(1500, TrafficDirection::Incoming);
+        let info_traffic2 = info_traffic.clone();
+        thread::spawn(move || serve_metrics_loop(address, &info_traffic2));
+        wait_until(|| TcpStream::connect(address).is_ok());
+
+        let response = get(address, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\nsniffnet_received_bytes_total 1500\n"));
+
+        info_traffic
+            .lock()
+            .unwrap()
+            .add_packet(500, TrafficDirection::Incoming);
+        assert!(get(address, "/metrics").contains("\nsniffnet_received_bytes_total 2000\n"));
+
+        assert!(get(address, "/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
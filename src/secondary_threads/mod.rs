@@ -1,3 +1,4 @@
 pub mod check_updates;
+pub mod metrics_server;
 pub mod parse_packets;
 pub mod write_report_file;
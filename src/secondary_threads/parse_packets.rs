@@ -11,6 +11,7 @@
 use crate::networking::manage_packets::{
     analyze_headers, get_address_to_lookup, modify_or_insert_in_map, reverse_dns_lookup,
 };
+use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
@@ -20,6 +21,9 @@
 
 /// The calling thread enters in a loop in which it waits for network packets, parses them according
 /// to the user specified filters, and inserts them into the shared map variable.
+///
+/// Packets passing the capture `filters` are always inserted in the map, but are only counted
+/// in the totals, application protocols and hosts data if matched by the display filters.
 pub fn parse_packets(
     current_capture_id: &Arc<Mutex<u16>>,
     device: &MyDevice,
@@ -64,124 +68,52 @@ pub fn parse_packets(
                         }
 
                         let key = key_option.unwrap();
-                        let mut new_info = InfoAddressPortPair::default();
-
-                        let passed_filters = filters.matches(protocols);
-                        if passed_filters {
-                            new_info = modify_or_insert_in_map(
+                        let new_info = filters.matches(protocols).then(|| {
+                            modify_or_insert_in_map(
                                 info_traffic_mutex,
                                 &key,
                                 device,
                                 mac_addresses,
                                 exchanged_bytes,
                                 protocols.application,
-                            );
-                        }
+                            )
+                        });
 
                         let mut info_traffic = info_traffic_mutex
                             .lock()
                             .expect("Error acquiring mutex\n\r");
-                        //increment number of sniffed packets and bytes
-                        info_traffic.all_packets += 1;
-                        info_traffic.all_bytes += exchanged_bytes;
                         // update dropped packets number
                         if let Ok(stats) = cap.stats() {
                             info_traffic.dropped_packets = stats.dropped;
                         }
+                        let lookup_needed = count_packet(
+                            &mut info_traffic,
+                            &key,
+                            new_info.as_ref(),
+                            exchanged_bytes,
+                            protocols,
+                        );
+                        drop(info_traffic);
 
-                        if passed_filters {
-                            info_traffic.add_packet(exchanged_bytes, new_info.traffic_direction);
-
-                            // check the rDNS status of this address and act accordingly
-                            let address_to_lookup =
-                                get_address_to_lookup(&key, new_info.traffic_direction);
-                            let r_dns_already_resolved = info_traffic
-                                .addresses_resolved
-                                .contains_key(&address_to_lookup);
-                            let mut r_dns_waiting_resolution = false;
-                            if !r_dns_already_resolved {
-                                r_dns_waiting_resolution = info_traffic
-                                    .addresses_waiting_resolution
-                                    .contains_key(&address_to_lookup);
-                            }
-
-                            match (r_dns_waiting_resolution, r_dns_already_resolved) {
-                                (false, false) => {
-                                    // rDNS not requested yet (first occurrence of this address to lookup)
-
-                                    // Add this address to the map of addresses waiting for a resolution
-                                    // Useful to NOT perform again a rDNS lookup for this entry
-                                    info_traffic.addresses_waiting_resolution.insert(
-                                        address_to_lookup,
-                                        DataInfo::new_with_first_packet(
-                                            exchanged_bytes,
-                                            new_info.traffic_direction,
-                                        ),
+                        if let (true, Some(new_info)) = (lookup_needed, new_info) {
+                            // launch new thread to resolve host name
+                            let info_traffic2 = info_traffic_mutex.clone();
+                            let device2 = device.clone();
+                            let country_db_reader2 = country_db_reader.clone();
+                            let asn_db_reader2 = asn_db_reader.clone();
+                            thread::Builder::new()
+                                .name("thread_reverse_dns_lookup".to_string())
+                                .spawn(move || {
+                                    reverse_dns_lookup(
+                                        &info_traffic2,
+                                        &key,
+                                        new_info.traffic_direction,
+                                        &device2,
+                                        &country_db_reader2,
+                                        &asn_db_reader2,
                                     );
-
-                                    // launch new thread to resolve host name
-                                    let key2 = key.clone();
-                                    let info_traffic2 = info_traffic_mutex.clone();
-                                    let device2 = device.clone();
-                                    let country_db_reader2 = country_db_reader.clone();
-                                    let asn_db_reader2 = asn_db_reader.clone();
-                                    thread::Builder::new()
-                                        .name("thread_reverse_dns_lookup".to_string())
-                                        .spawn(move || {
-                                            reverse_dns_lookup(
-                                                &info_traffic2,
-                                                &key2,
-                                                new_info.traffic_direction,
-                                                &device2,
-                                                &country_db_reader2,
-                                                &asn_db_reader2,
-                                            );
-                                        })
-                                        .unwrap();
-                                }
-                                (true, false) => {
-                                    // waiting for a previously requested rDNS resolution
-                                    // update the corresponding waiting address data
-                                    info_traffic
-                                        .addresses_waiting_resolution
-                                        .entry(address_to_lookup)
-                                        .and_modify(|data_info| {
-                                            data_info.add_packet(
-                                                exchanged_bytes,
-                                                new_info.traffic_direction,
-                                            );
-                                        });
-                                }
-                                (_, true) => {
-                                    // rDNS already resolved
-                                    // update the corresponding host's data info
-                                    let host = info_traffic
-                                        .addresses_resolved
-                                        .get(&address_to_lookup)
-                                        .unwrap()
-                                        .1
-                                        .clone();
-                                    info_traffic.hosts.entry(host).and_modify(|data_info_host| {
-                                        data_info_host.data_info.add_packet(
-                                            exchanged_bytes,
-                                            new_info.traffic_direction,
-                                        );
-                                    });
-                                }
-                            }
-
-                            //increment the packet count for the sniffed app protocol
-                            info_traffic
-                                .app_protocols
-                                .entry(protocols.application)
-                                .and_modify(|data_info| {
-                                    data_info
-                                        .add_packet(exchanged_bytes, new_info.traffic_direction);
                                 })
-                                .or_insert(DataInfo::new_with_first_packet(
-                                    exchanged_bytes,
-                                    new_info.traffic_direction,
-                                ));
+                                .unwrap();
                         }
                     }
                 }
@@ -189,3 +121,195 @@ pub fn parse_packets(
         }
     }
 }
+
+/// Counts a packet in the traffic statistics.
+///
+/// `new_info` is the connection the packet belongs to, if it passed the capture filters
+/// (in that case it was already inserted in the map).
+/// Every packet is counted in the totals of all the traffic, but only the ones passing the capture filters,
+/// not ignored, and matched by the display filters are counted in the filtered totals,
+/// application protocols and hosts data.
+///
+/// Returns whether the rDNS resolution of the address involved in the packet must be requested
+/// (this happens even if the packet isn't matched by the display filters,
+/// so that the host is already known when the display filters change)
+fn count_packet(
+    info_traffic: &mut InfoTraffic,
+    key: &AddressPortPair,
+    new_info: Option<&InfoAddressPortPair>,
+    exchanged_bytes: u128,
+    protocols: Filters,
+) -> bool {
+    //increment number of sniffed packets and bytes
+    info_traffic.all_packets += 1;
+    info_traffic.all_bytes += exchanged_bytes;
+
+    let Some(new_info) = new_info else {
+        return false;
+    };
+    let address_to_lookup = get_address_to_lookup(key, new_info.traffic_direction);
+    if info_traffic.is_ignored(&address_to_lookup, protocols.application) {
+        return false;
+    }
+
+    // traffic not matched by the display filters is collected, but not counted
+    let is_displayed = info_traffic.display_filters.matches(protocols);
+    if is_displayed {
+        info_traffic.add_packet(exchanged_bytes, new_info.traffic_direction);
+    }
+
+    // check the rDNS status of this address and act accordingly
+    let r_dns_already_resolved = info_traffic
+        .addresses_resolved
+        .contains_key(&address_to_lookup);
+    let mut r_dns_waiting_resolution = false;
+    if !r_dns_already_resolved {
+        r_dns_waiting_resolution = info_traffic
+            .addresses_waiting_resolution
+            .contains_key(&address_to_lookup);
+    }
+
+    let lookup_needed = match (r_dns_waiting_resolution, r_dns_already_resolved) {
+        (false, false) => {
+            // rDNS not requested yet (first occurrence of this address to lookup)
+
+            // Add this address to the map of addresses waiting for a resolution
+            // Useful to NOT perform again a rDNS lookup for this entry
+            let data_info = if is_displayed {
+                DataInfo::new_with_first_packet(exchanged_bytes, new_info.traffic_direction)
+            } else {
+                DataInfo::default()
+            };
+            info_traffic
+                .addresses_waiting_resolution
+                .insert(address_to_lookup, data_info);
+            true
+        }
+        (_, _) if !is_displayed => false,
+        (true, false) => {
+            // waiting for a previously requested rDNS resolution
+            // update the corresponding waiting address data
+            info_traffic
+                .addresses_waiting_resolution
+                .entry(address_to_lookup)
+                .and_modify(|data_info| {
+                    data_info.add_packet(exchanged_bytes, new_info.traffic_direction);
+                });
+            false
+        }
+        (_, true) => {
+            // rDNS already resolved
+            // update the corresponding host's data info
+            let host = info_traffic
+                .addresses_resolved
+                .get(&address_to_lookup)
+                .unwrap()
+                .1
+                .clone();
+            info_traffic.hosts.entry(host).and_modify(|data_info_host| {
+                data_info_host
+                    .data_info
+                    .add_packet(exchanged_bytes, new_info.traffic_direction);
+                data_info_host.traffic_series.add_packet(
+                    new_info.final_timestamp.timestamp(),
+                    exchanged_bytes,
+                    new_info.traffic_direction,
+                );
+            });
+            false
+        }
+    };
+
+    //increment the packet count for the sniffed app protocol
+    if is_displayed {
+        info_traffic
+            .app_protocols
+            .entry(protocols.application)
+            .and_modify(|data_info| {
+                data_info.add_packet(exchanged_bytes, new_info.traffic_direction);
+            })
+            .or_insert(DataInfo::new_with_first_packet(
+                exchanged_bytes,
+                new_info.traffic_direction,
+            ));
+    }
+
+    lookup_needed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::{AppProtocol, IpVersion, TransProtocol};
+
+    use super::*;
+
+    #[test]
+    fn test_count_packet() {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic
+            .ignored_app_protocols
+            .insert(AppProtocol::HTTPS);
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            50000,
+            "8.8.8.8".to_string(),
+            443,
+            TransProtocol::TCP,
+        );
+        let new_info = InfoAddressPortPair {
+            transmitted_packets: 1,
+            transmitted_bytes: 100,
+            app_protocol: AppProtocol::HTTPS,
+            traffic_direction: TrafficDirection::Outgoing,
+            ..InfoAddressPortPair::default()
+        };
+        let protocols = Filters {
+            ip: IpVersion::IPv4,
+            transport: TransProtocol::TCP,
+            application: AppProtocol::HTTPS,
+        };
+
+        // ignored packets are only counted in the totals of all the traffic...
+        assert!(!count_packet(
+            &mut info_traffic,
+            &key,
+            Some(&new_info),
+            100,
+            protocols
+        ));
+        assert_eq!(info_traffic.all_packets, 1);
+        assert_eq!(info_traffic.all_bytes, 100);
+        assert_eq!(info_traffic.tot_sent_packets, 0);
+        assert_eq!(info_traffic.tot_sent_bytes, 0);
+        assert!(info_traffic.app_protocols.is_empty());
+        assert!(info_traffic.addresses_waiting_resolution.is_empty());
+
+        // ...as well as packets not passing the capture filters
+        assert!(!count_packet(&mut info_traffic, &key, None, 50, protocols));
+        assert_eq!(info_traffic.all_packets, 2);
+        assert_eq!(info_traffic.all_bytes, 150);
+        assert_eq!(info_traffic.tot_sent_packets, 0);
+
+        // the other packets are counted everywhere, and their address is resolved once
+        info_traffic.ignored_app_protocols.clear();
+        for lookup_needed in [true, false] {
+            assert_eq!(
+                count_packet(&mut info_traffic, &key, Some(&new_info), 100, protocols),
+                lookup_needed
+            );
+        }
+        assert_eq!(info_traffic.all_packets, 4);
+        assert_eq!(info_traffic.all_bytes, 350);
+        assert_eq!(info_traffic.tot_sent_packets, 2);
+        assert_eq!(info_traffic.tot_sent_bytes, 200);
+        assert_eq!(
+            info_traffic.app_protocols[&AppProtocol::HTTPS].outgoing_bytes,
+            200
+        );
+        assert_eq!(
+            info_traffic.addresses_waiting_resolution["8.8.8.8"].outgoing_packets,
+            2
+        );
+    }
+}
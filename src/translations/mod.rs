@@ -1,4 +1,5 @@
 #![allow(clippy::module_inception, clippy::module_name_repetitions)]
 pub mod translations;
 pub mod translations_2;
+pub mod translations_3;
 pub mod types;
@@ -0,0 +1,648 @@
+#![allow(clippy::match_same_arms, clippy::match_wildcard_for_single_variants)]
+
+use crate::gui::styles::types::tag_color::TagColor;
+use crate::Language;
+
+pub fn custom_rules_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom rules",
+        Language::IT => "Regole personalizzate",
+        _ => "Custom rules",
+    }
+}
+
+pub fn rule_host_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Domain, IP or CIDR",
+        Language::IT => "Dominio, IP o CIDR",
+        _ => "Domain, IP or CIDR",
+    }
+}
+
+pub fn port_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Port",
+        Language::IT => "Porta",
+        _ => "Port",
+    }
+}
+
+pub fn any_traffic_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Any traffic",
+        Language::IT => "Qualsiasi traffico",
+        _ => "Any traffic",
+    }
+}
+
+pub fn bytes_over_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes over",
+        Language::IT => "Byte oltre",
+        _ => "Bytes over",
+    }
+}
+
+pub fn new_connection_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "New connection",
+        Language::IT => "Nuova connessione",
+        _ => "New connection",
+    }
+}
+
+pub fn match_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Match",
+        Language::IT => "Corrispondenza",
+        _ => "Match",
+    }
+}
+
+pub fn condition_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Condition",
+        Language::IT => "Condizione",
+        _ => "Condition",
+    }
+}
+
+pub fn cooldown_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Cooldown (seconds)",
+        Language::IT => "Intervallo minimo (secondi)",
+        _ => "Cooldown (seconds)",
+    }
+}
+
+pub fn add_rule_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add rule",
+        Language::IT => "Aggiungi regola",
+        _ => "Add rule",
+    }
+}
+
+pub fn rule_triggered_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom rule triggered",
+        Language::IT => "Regola personalizzata attivata",
+        _ => "Custom rule triggered",
+    }
+}
+
+pub fn new_connections_value_translation(language: Language, value: usize) -> String {
+    match language {
+        Language::EN => format!("{value} new connections"),
+        Language::IT => format!("{value} nuove connessioni"),
+        _ => format!("{value} new connections"),
+    }
+}
+
+pub fn matched_bytes_value_translation(language: Language, value: &str) -> String {
+    match language {
+        Language::EN => format!("{value} exchanged in the last second"),
+        Language::IT => format!("{value} scambiati nell'ultimo secondo"),
+        _ => format!("{value} exchanged in the last second"),
+    }
+}
+
+pub fn sustain_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Consecutive seconds",
+        Language::IT => "Secondi consecutivi",
+        _ => "Consecutive seconds",
+    }
+}
+
+pub fn desktop_notifications_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Also show as desktop notifications",
+        Language::IT => "Mostra anche come notifiche desktop",
+        _ => "Also show as desktop notifications",
+    }
+}
+
+pub fn script_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Script",
+        Language::IT => "Script",
+        _ => "Script",
+    }
+}
+
+pub fn notification_hook_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Action to execute for each notification",
+        Language::IT => "Azione da eseguire per ogni notifica",
+        _ => "Action to execute for each notification",
+    }
+}
+
+pub fn retries_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Retries",
+        Language::IT => "Tentativi aggiuntivi",
+        _ => "Retries",
+    }
+}
+
+pub fn timeout_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Timeout (seconds)",
+        Language::IT => "Timeout (secondi)",
+        _ => "Timeout (seconds)",
+    }
+}
+
+pub fn notifications_history_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Notifications history",
+        Language::IT => "Cronologia delle notifiche",
+        _ => "Notifications history",
+    }
+}
+
+pub fn history_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "File of the notifications history",
+        Language::IT => "File della cronologia delle notifiche",
+        _ => "File of the notifications history",
+    }
+}
+
+pub fn recent_notifications_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Recent notifications",
+        Language::IT => "Notifiche recenti",
+        _ => "Recent notifications",
+    }
+}
+
+pub fn export_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Export",
+        Language::IT => "Esporta",
+        _ => "Export",
+    }
+}
+
+pub fn filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Filter",
+        Language::IT => "Filtra",
+        _ => "Filter",
+    }
+}
+
+pub fn history_entries_translation(language: Language, shown: usize, total: usize) -> String {
+    match language {
+        Language::EN => format!("Showing {shown} of {total} entries, most recent first"),
+        Language::IT => format!("Mostrate {shown} voci su {total}, dalla più recente"),
+        _ => format!("Showing {shown} of {total} entries, most recent first"),
+    }
+}
+
+pub fn remote_agent_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Remote agent",
+        Language::IT => "Agente remoto",
+        _ => "Remote agent",
+    }
+}
+
+pub fn token_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Token",
+        Language::IT => "Token",
+        _ => "Token",
+    }
+}
+
+pub fn certificate_fingerprint_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Certificate fingerprint",
+        Language::IT => "Impronta del certificato",
+        _ => "Certificate fingerprint",
+    }
+}
+
+pub fn connect_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Connect",
+        Language::IT => "Connetti",
+        _ => "Connect",
+    }
+}
+
+pub fn tui_keys_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => {
+            "q: quit | Tab/1-4: screen | c: bytes/packets | ↑↓←→: navigate | \
+            f: edit filters | /: search | *: favorites | x: clear | s: sort | g: group"
+        }
+        Language::IT => {
+            "q: esci | Tab/1-4: schermata | c: byte/pacchetti | ↑↓←→: naviga | \
+            f: modifica filtri | /: cerca | *: preferiti | x: cancella | s: ordina | g: raggruppa"
+        }
+        _ => {
+            "q: quit | Tab/1-4: screen | c: bytes/packets | ↑↓←→: navigate | \
+            f: edit filters | /: search | *: favorites | x: clear | s: sort | g: group"
+        }
+    }
+}
+
+pub fn no_notifications_logged_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "No notifications received yet",
+        Language::IT => "Nessuna notifica ricevuta finora",
+        _ => "No notifications received yet",
+    }
+}
+
+pub fn custom_themes_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom themes",
+        Language::IT => "Temi personalizzati",
+        _ => "Custom themes",
+    }
+}
+
+pub fn theme_file_path_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Path of a theme file (.toml)",
+        Language::IT => "Percorso di un file di tema (.toml)",
+        _ => "Path of a theme file (.toml)",
+    }
+}
+
+pub fn browse_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Browse...",
+        Language::IT => "Sfoglia...",
+        _ => "Browse...",
+    }
+}
+
+pub fn import_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Import",
+        Language::IT => "Importa",
+        _ => "Import",
+    }
+}
+
+pub fn theme_editor_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Theme editor",
+        Language::IT => "Editor di temi",
+        _ => "Theme editor",
+    }
+}
+
+pub fn theme_name_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Name of the new theme",
+        Language::IT => "Nome del nuovo tema",
+        _ => "Name of the new theme",
+    }
+}
+
+pub fn bold_text_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bold text",
+        Language::IT => "Testo in grassetto",
+        _ => "Bold text",
+    }
+}
+
+pub fn save_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Save",
+        Language::IT => "Salva",
+        _ => "Save",
+    }
+}
+
+pub fn cancel_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Cancel",
+        Language::IT => "Annulla",
+        _ => "Cancel",
+    }
+}
+
+pub fn whole_session_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Whole session",
+        Language::IT => "Intera sessione",
+        _ => "Whole session",
+    }
+}
+
+pub fn chart_hint_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Scroll to zoom, drag to move through time",
+        Language::IT => "Scorri per ingrandire, trascina per spostarti nel tempo",
+        _ => "Scroll to zoom, drag to move through time",
+    }
+}
+
+pub fn total_traffic_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Total traffic",
+        Language::IT => "Traffico totale",
+        _ => "Total traffic",
+    }
+}
+
+pub fn other_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Other",
+        Language::IT => "Altro",
+        _ => "Other",
+    }
+}
+
+pub fn columns_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Columns",
+        Language::IT => "Colonne",
+        _ => "Columns",
+    }
+}
+
+pub fn search_query_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Query",
+        Language::IT => "Espressione",
+        _ => "Query",
+    }
+}
+
+pub fn hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Hosts",
+        Language::IT => "Host",
+        _ => "Hosts",
+    }
+}
+
+pub fn show_all_hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Show all hosts",
+        Language::IT => "Mostra tutti gli host",
+        _ => "Show all hosts",
+    }
+}
+
+pub fn search_hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Search by domain, AS or country",
+        Language::IT => "Cerca per dominio, AS o paese",
+        _ => "Search by domain, AS or country",
+    }
+}
+
+pub fn domain_name_sort_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "domain name",
+        Language::IT => "nome di dominio",
+        _ => "domain name",
+    }
+}
+
+pub fn country_sort_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "country",
+        Language::IT => "paese",
+        _ => "country",
+    }
+}
+
+pub fn group_by_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Group by",
+        Language::IT => "Raggruppa per",
+        _ => "Group by",
+    }
+}
+
+pub fn no_grouping_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "single hosts",
+        Language::IT => "singoli host",
+        _ => "single hosts",
+    }
+}
+
+pub fn organization_grouping_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "organization (ASN)",
+        Language::IT => "organizzazione (ASN)",
+        _ => "organization (ASN)",
+    }
+}
+
+pub fn registrable_domain_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "registrable domain",
+        Language::IT => "dominio registrabile",
+        _ => "registrable domain",
+    }
+}
+
+pub fn favorite_hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Favorite hosts",
+        Language::IT => "Host preferiti",
+        _ => "Favorite hosts",
+    }
+}
+
+pub fn add_favorite_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add favorite",
+        Language::IT => "Aggiungi preferito",
+        _ => "Add favorite",
+    }
+}
+
+pub fn ignored_traffic_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Ignored traffic",
+        Language::IT => "Traffico ignorato",
+        _ => "Ignored traffic",
+    }
+}
+
+pub fn ignore_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Ignore",
+        Language::IT => "Ignora",
+        _ => "Ignore",
+    }
+}
+
+pub fn tag_color_translation(color: TagColor, language: Language) -> &'static str {
+    match (color, language) {
+        (TagColor::Gray, Language::IT) => "Grigio",
+        (TagColor::Red, Language::IT) => "Rosso",
+        (TagColor::Orange, Language::IT) => "Arancione",
+        (TagColor::Yellow, Language::IT) => "Giallo",
+        (TagColor::Green, Language::IT) => "Verde",
+        (TagColor::Blue, Language::IT) => "Blu",
+        (TagColor::Purple, Language::IT) => "Viola",
+        (TagColor::Gray, _) => "Gray",
+        (TagColor::Red, _) => "Red",
+        (TagColor::Orange, _) => "Orange",
+        (TagColor::Yellow, _) => "Yellow",
+        (TagColor::Green, _) => "Green",
+        (TagColor::Blue, _) => "Blue",
+        (TagColor::Purple, _) => "Purple",
+    }
+}
+
+pub fn labels_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Labels",
+        Language::IT => "Etichette",
+        _ => "Labels",
+    }
+}
+
+pub fn edit_label_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Edit label",
+        Language::IT => "Modifica etichetta",
+        _ => "Edit label",
+    }
+}
+
+pub fn alias_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Alias",
+        Language::IT => "Alias",
+        _ => "Alias",
+    }
+}
+
+pub fn tags_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Tags",
+        Language::IT => "Tag",
+        _ => "Tags",
+    }
+}
+
+pub fn notes_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Notes",
+        Language::IT => "Note",
+        _ => "Notes",
+    }
+}
+
+pub fn add_tag_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Add tag",
+        Language::IT => "Aggiungi tag",
+        _ => "Add tag",
+    }
+}
+
+pub fn remove_label_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Remove label",
+        Language::IT => "Rimuovi etichetta",
+        _ => "Remove label",
+    }
+}
+
+pub fn ip_or_mac_address_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "IP or MAC address",
+        Language::IT => "Indirizzo IP o MAC",
+        _ => "IP or MAC address",
+    }
+}
+
+pub fn display_filters_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Display filters",
+        Language::IT => "Filtri di visualizzazione",
+        _ => "Display filters",
+    }
+}
+
+pub fn source_address_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Src IP address",
+        Language::IT => "Indirizzo IP sorg.",
+        _ => "Src IP address",
+    }
+}
+
+pub fn source_port_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Src port",
+        Language::IT => "Porta sorg.",
+        _ => "Src port",
+    }
+}
+
+pub fn destination_address_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Dst IP address",
+        Language::IT => "Indirizzo IP dest.",
+        _ => "Dst IP address",
+    }
+}
+
+pub fn destination_port_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Dst port",
+        Language::IT => "Porta dest.",
+        _ => "Dst port",
+    }
+}
+
+pub fn packets_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Packets",
+        Language::IT => "Pacchetti",
+        _ => "Packets",
+    }
+}
+
+pub fn bytes_column_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes",
+        Language::IT => "Byte",
+        _ => "Bytes",
+    }
+}
+
+pub fn first_seen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "First seen",
+        Language::IT => "Prima volta",
+        _ => "First seen",
+    }
+}
+
+pub fn last_seen_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Last seen",
+        Language::IT => "Ultima volta",
+        _ => "Last seen",
+    }
+}
+
+pub fn direction_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Direction",
+        Language::IT => "Direzione",
+        _ => "Direction",
+    }
+}
@@ -0,0 +1,3 @@
+pub mod run_tui;
+pub mod tui_pages;
+pub mod types;
@@ -0,0 +1,113 @@
+//! Module containing the capture executed when Sniffnet runs with the terminal user interface.
+
+use std::io;
+use std::io::Stdout;
+use std::panic;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+use crate::cli::headless::open_adapter_or_exit;
+use crate::gui::types::status::Status;
+use crate::networking::types::filters::Filters;
+use crate::notifications::types::notifications::Notifications;
+use crate::secondary_threads::parse_packets::parse_packets;
+use crate::tui::tui_pages::draw;
+use crate::tui::types::tui_app::TuiApp;
+use crate::{InfoTraffic, Language};
+
+/// Interval between two consecutive refreshes of the displayed data
+const TUI_TICK: Duration = Duration::from_secs(1);
+
+/// Captures the traffic of the given network adapter, displaying it in the terminal
+/// until the user quits
+pub fn run_tui(
+    adapter: &str,
+    current_capture_id: &Arc<Mutex<u16>>,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    status_pair: &Arc<(Mutex<Status>, Condvar)>,
+    notifications: Notifications,
+    language: Language,
+) {
+    let (device, cap) = open_adapter_or_exit(adapter);
+
+    *status_pair.0.lock().unwrap() = Status::Running;
+    status_pair.1.notify_all();
+
+    let current_capture_id2 = current_capture_id.clone();
+    let info_traffic_mutex2 = info_traffic_mutex.clone();
+    thread::Builder::new()
+        .name("thread_parse_packets".to_string())
+        .spawn(move || {
+            parse_packets(
+                &current_capture_id2,
+                &device,
+                cap,
+                Filters::default(),
+                &info_traffic_mutex2,
+            );
+        })
+        .unwrap();
+
+    let mut app = TuiApp::new(
+        adapter.to_string(),
+        info_traffic_mutex.clone(),
+        notifications,
+        language,
+    );
+    let result = setup_terminal().and_then(|mut terminal| {
+        let result = run_app(&mut terminal, &mut app);
+        restore_terminal();
+        result
+    });
+
+    // stop the capture
+    *current_capture_id.lock().unwrap() += 1;
+    if let Err(e) = result {
+        eprintln!("sniffnet: terminal error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut TuiApp) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+    while !app.quit {
+        terminal.draw(|frame| draw(frame, app))?;
+        let timeout = TUI_TICK.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                app.handle_key(key);
+            }
+        }
+        if last_tick.elapsed() >= TUI_TICK {
+            app.refresh_data();
+            last_tick = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    // leave the terminal usable if a panic occurs
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        restore_terminal();
+        previous_hook(panic_info);
+    }));
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+}
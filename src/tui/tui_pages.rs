@@ -0,0 +1,582 @@
+//! Module containing the functions drawing the screens of the terminal user interface.
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Axis, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, Paragraph, Row, Table,
+    TableState, Tabs, Wrap,
+};
+use ratatui::Frame;
+
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::search_parameters::FilterInputType;
+use crate::networking::types::search_query::SearchQuery;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::report::get_report_entries::{
+    get_app_entries, get_host_entries, get_searched_connections, get_searched_hosts,
+};
+use crate::translations::translations::{
+    address_translation, application_protocol_translation, bytes_chart_translation,
+    incoming_translation, network_adapter_translation, outgoing_translation,
+    packets_chart_translation,
+};
+use crate::translations::translations_2::{
+    administrative_entity_translation, country_translation, destination_translation,
+    domain_name_translation, dropped_packets_translation, host_translation,
+    no_search_results_translation, only_show_favorites_translation, search_filters_translation,
+    showing_results_translation, sort_by_translation, source_translation,
+};
+use crate::translations::translations_3::{
+    group_by_translation, no_notifications_logged_translation, port_translation,
+    search_query_translation, tui_keys_translation,
+};
+use crate::tui::types::tui_app::{TuiApp, TUI_PAGE_SIZE};
+use crate::utils::formatted_strings::{get_formatted_bytes_string_with_b, get_percentage_string};
+use crate::{ChartType, RunningPage};
+
+const COLOR_INCOMING: Color = Color::Cyan;
+const COLOR_OUTGOING: Color = Color::Magenta;
+const COLOR_HIGHLIGHT: Color = Color::Yellow;
+const COLOR_ERROR: Color = Color::Red;
+
+/// Draws the whole interface: header with the screens tabs, current screen, and keys help
+pub fn draw(frame: &mut Frame, app: &TuiApp) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+
+    let titles: Vec<Line> = RunningPage::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, page)| Line::from(format!("{} {}", i + 1, page.get_tab_label(app.language))))
+        .collect();
+    let selected = RunningPage::ALL
+        .iter()
+        .position(|page| page.eq(&app.running_page))
+        .unwrap_or(0);
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Sniffnet - {}: {} ",
+            network_adapter_translation(app.language),
+            app.adapter
+        )))
+        .highlight_style(
+            Style::default()
+                .fg(COLOR_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_widget(tabs, chunks[0]);
+
+    match app.running_page {
+        RunningPage::Overview => draw_overview(frame, app, chunks[1]),
+        RunningPage::Inspect => draw_inspect(frame, app, chunks[1]),
+        RunningPage::Hosts => draw_hosts(frame, app, chunks[1]),
+        RunningPage::Notifications => draw_notifications(frame, app, chunks[1]),
+    }
+
+    frame.render_widget(
+        Paragraph::new(tui_keys_translation(app.language))
+            .style(Style::default().add_modifier(Modifier::DIM)),
+        chunks[2],
+    );
+}
+
+fn draw_overview(frame: &mut Frame, app: &TuiApp, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Percentage(50),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let runtime_data = &app.runtime_data;
+    let filtered_packets = runtime_data.tot_received_packets + runtime_data.tot_sent_packets;
+    let filtered_bytes = runtime_data.tot_received_bytes + runtime_data.tot_sent_bytes;
+    let mut totals = vec![
+        Line::from(vec![
+            Span::raw(format!("{}: ", packets_chart_translation(app.language))),
+            Span::styled(
+                format!("↓ {}", runtime_data.tot_received_packets),
+                Style::default().fg(COLOR_INCOMING),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("↑ {}", runtime_data.tot_sent_packets),
+                Style::default().fg(COLOR_OUTGOING),
+            ),
+            Span::raw(format!(
+                "  ({}% / {})",
+                get_percentage_string(runtime_data.all_packets, filtered_packets),
+                runtime_data.all_packets
+            )),
+        ]),
+        Line::from(vec![
+            Span::raw(format!("{}: ", bytes_chart_translation(app.language))),
+            Span::styled(
+                format!(
+                    "↓ {}",
+                    get_formatted_bytes_string_with_b(runtime_data.tot_received_bytes)
+                ),
+                Style::default().fg(COLOR_INCOMING),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!(
+                    "↑ {}",
+                    get_formatted_bytes_string_with_b(runtime_data.tot_sent_bytes)
+                ),
+                Style::default().fg(COLOR_OUTGOING),
+            ),
+            Span::raw(format!(
+                "  ({}% / {})",
+                get_percentage_string(runtime_data.all_bytes, filtered_bytes),
+                get_formatted_bytes_string_with_b(runtime_data.all_bytes)
+            )),
+        ]),
+    ];
+    if runtime_data.dropped_packets > 0 {
+        totals.push(Line::from(format!(
+            "{}: {}",
+            dropped_packets_translation(app.language),
+            runtime_data.dropped_packets
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(totals).block(Block::default().borders(Borders::ALL)),
+        chunks[0],
+    );
+
+    draw_chart(frame, app, chunks[1]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[2]);
+    let chart_type = app.traffic_chart.chart_type;
+    let hosts: Vec<ListItem> = get_host_entries(&app.info_traffic, chart_type, app.host_grouping)
+        .into_iter()
+        .map(|(host, data_info_host)| {
+            let favorite = if data_info_host.is_favorite {
+                "★ "
+            } else {
+                ""
+            };
+            let amount = match chart_type {
+                ChartType::Packets => data_info_host.data_info.tot_packets().to_string(),
+                ChartType::Bytes => {
+                    get_formatted_bytes_string_with_b(data_info_host.data_info.tot_bytes())
+                }
+            };
+            ListItem::new(format!(
+                "{favorite}{} [{}] {} - {amount}",
+                host.domain, host.country, host.asn.name
+            ))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(hosts).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", host_translation(app.language))),
+        ),
+        bottom[0],
+    );
+    let apps: Vec<ListItem> = get_app_entries(&app.info_traffic, chart_type)
+        .into_iter()
+        .map(|(app_protocol, data_info)| {
+            let amount = match chart_type {
+                ChartType::Packets => data_info.tot_packets().to_string(),
+                ChartType::Bytes => get_formatted_bytes_string_with_b(data_info.tot_bytes()),
+            };
+            ListItem::new(format!("{app_protocol} - {amount}"))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(apps).block(Block::default().borders(Borders::ALL).title(format!(
+            " {} ",
+            application_protocol_translation(app.language)
+        ))),
+        bottom[1],
+    );
+}
+
+/// Draws the traffic chart of the displayed time window, with incoming traffic above the x axis
+/// and outgoing traffic below it (as in the graphical interface)
+fn draw_chart(frame: &mut Frame, app: &TuiApp, area: Rect) {
+    let traffic_chart = &app.traffic_chart;
+    let (received, sent, min, max) = match traffic_chart.chart_type {
+        ChartType::Packets => (
+            &traffic_chart.received_packets,
+            &traffic_chart.sent_packets,
+            traffic_chart.min_sent_packets,
+            traffic_chart.max_received_packets,
+        ),
+        ChartType::Bytes => (
+            &traffic_chart.received_bytes,
+            &traffic_chart.sent_bytes,
+            traffic_chart.min_sent_bytes,
+            traffic_chart.max_received_bytes,
+        ),
+    };
+    let received = to_points(&traffic_chart.displayed_points(received));
+    let sent = to_points(&traffic_chart.displayed_points(sent));
+    let (first_tick, last_tick) = traffic_chart.displayed_range();
+    let (first_tick, last_tick) = (f64::from(first_tick), f64::from(last_tick));
+    let label = |value: i64| match traffic_chart.chart_type {
+        ChartType::Packets => value.to_string(),
+        ChartType::Bytes => {
+            let sign = if value < 0 { "-" } else { "" };
+            format!(
+                "{sign}{}",
+                get_formatted_bytes_string_with_b(u128::from(value.unsigned_abs()))
+            )
+        }
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let (min_f, max_f) = (min as f64, max as f64);
+
+    let datasets = vec![
+        Dataset::default()
+            .name(incoming_translation(app.language))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(COLOR_INCOMING))
+            .data(&received),
+        Dataset::default()
+            .name(outgoing_translation(app.language))
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(COLOR_OUTGOING))
+            .data(&sent),
+    ];
+    let title = match traffic_chart.chart_type {
+        ChartType::Packets => packets_chart_translation(app.language),
+        ChartType::Bytes => bytes_chart_translation(app.language),
+    };
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {title} ")),
+        )
+        .x_axis(Axis::default().bounds([first_tick, last_tick.max(first_tick + 1.0)]))
+        .y_axis(
+            Axis::default()
+                .bounds([min_f, max_f.max(min_f + 1.0)])
+                .labels(vec![
+                    Span::raw(label(min)),
+                    Span::raw("0"),
+                    Span::raw(label(max)),
+                ]),
+        );
+    frame.render_widget(chart, area);
+}
+
+fn to_points(values: &[(u32, i64)]) -> Vec<(f64, f64)> {
+    #[allow(clippy::cast_precision_loss)]
+    values
+        .iter()
+        .map(|(x, y)| (f64::from(*x), *y as f64))
+        .collect()
+}
+
+fn draw_inspect(frame: &mut Frame, app: &TuiApp, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .split(area);
+
+    let language = app.language;
+    let filter_span = |filter: FilterInputType, caption: &str, value: &str| {
+        let style = if app.editing_filter == Some(filter) {
+            Style::default()
+                .fg(COLOR_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let cursor = if app.editing_filter == Some(filter) {
+            "_"
+        } else {
+            ""
+        };
+        Span::styled(format!("{caption}: [{value}{cursor}]   "), style)
+    };
+    let search = &app.search;
+    let filters = vec![
+        Line::from(vec![
+            filter_span(
+                FilterInputType::App,
+                application_protocol_translation(language),
+                &search.app,
+            ),
+            filter_span(
+                FilterInputType::Country,
+                country_translation(language),
+                &search.country,
+            ),
+            Span::raw(format!(
+                "{}: [{}]",
+                only_show_favorites_translation(language),
+                if search.only_favorites { "★" } else { " " }
+            )),
+        ]),
+        Line::from(vec![
+            filter_span(
+                FilterInputType::Address,
+                address_translation(language),
+                &search.address,
+            ),
+            filter_span(
+                FilterInputType::Source,
+                source_translation(language),
+                &search.source,
+            ),
+            filter_span(
+                FilterInputType::Destination,
+                destination_translation(language),
+                &search.destination,
+            ),
+            filter_span(
+                FilterInputType::Port,
+                port_translation(language),
+                &search.port,
+            ),
+        ]),
+        Line::from(vec![
+            filter_span(
+                FilterInputType::Domain,
+                domain_name_translation(language),
+                &search.domain,
+            ),
+            filter_span(
+                FilterInputType::AS,
+                administrative_entity_translation(language),
+                &search.as_name,
+            ),
+        ]),
+        Line::from(vec![
+            filter_span(
+                FilterInputType::Query,
+                search_query_translation(language),
+                &search.query,
+            ),
+            Span::styled(
+                search
+                    .invalid_address_filter()
+                    .or_else(|| {
+                        SearchQuery::parse(&search.query)
+                            .err()
+                            .map(|error| error.to_string())
+                    })
+                    .unwrap_or_default(),
+                Style::default().fg(COLOR_ERROR),
+            ),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(filters).block(Block::default().borders(Borders::ALL).title(format!(
+            " {} - {}: {} ",
+            search_filters_translation(language),
+            sort_by_translation(language),
+            app.report_sort_type.get_picklist_label(language)
+        ))),
+        chunks[0],
+    );
+
+    let info_traffic_lock = app.info_traffic.lock().unwrap();
+    let results = get_searched_connections(&info_traffic_lock, search, app.report_sort_type);
+    if results.is_empty() {
+        frame.render_widget(
+            Paragraph::new(no_search_results_translation(language))
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        return;
+    }
+    let start = (app.page_number - 1) * TUI_PAGE_SIZE;
+    let end = results.len().min(app.page_number * TUI_PAGE_SIZE);
+    let rows: Vec<Row> = results
+        .get(start..end)
+        .unwrap_or_default()
+        .iter()
+        .map(|(key, val)| {
+            let host = info_traffic_lock
+                .addresses_resolved
+                .get(&get_address_to_lookup(key, val.traffic_direction))
+                .map(|(_, host)| format!("{} [{}]", host.domain, host.country))
+                .unwrap_or_default();
+            Row::new(vec![
+                Cell::from(key.address1.clone()),
+                Cell::from(key.port1.to_string()),
+                Cell::from(key.address2.clone()),
+                Cell::from(key.port2.to_string()),
+                Cell::from(key.trans_protocol.to_string()),
+                Cell::from(val.app_protocol.to_string()),
+                Cell::from(val.transmitted_packets.to_string()),
+                Cell::from(get_formatted_bytes_string_with_b(val.transmitted_bytes)),
+                Cell::from(host),
+            ])
+            .style(Style::default().fg(
+                if val.traffic_direction.eq(&TrafficDirection::Incoming) {
+                    COLOR_INCOMING
+                } else {
+                    COLOR_OUTGOING
+                },
+            ))
+        })
+        .collect();
+    let header = Row::new(vec![
+        "Src IP", "Src port", "Dst IP", "Dst port", "L4", "L7", "Packets", "Bytes", "Host",
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let widths = [
+        Constraint::Length(25),
+        Constraint::Length(8),
+        Constraint::Length(25),
+        Constraint::Length(8),
+        Constraint::Length(4),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Min(10),
+    ];
+    let table = Table::new(rows)
+        .header(header)
+        .widths(&widths)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " {} ",
+            showing_results_translation(language, start + 1, end, results.len())
+        )))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut table_state = TableState::default().with_selected(Some(app.selected_row));
+    frame.render_stateful_widget(table, chunks[1], &mut table_state);
+}
+
+fn draw_hosts(frame: &mut Frame, app: &TuiApp, area: Rect) {
+    let language = app.language;
+    let (entries, results_number) = get_searched_hosts(
+        &app.info_traffic.lock().unwrap(),
+        "",
+        app.hosts_sort_type,
+        app.host_grouping,
+        app.hosts_page_number,
+    );
+    if results_number == 0 {
+        frame.render_widget(
+            Paragraph::new(no_search_results_translation(language))
+                .block(Block::default().borders(Borders::ALL)),
+            area,
+        );
+        return;
+    }
+    let start = (app.hosts_page_number - 1) * TUI_PAGE_SIZE;
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|(host, data_info_host)| {
+            let data_info = &data_info_host.data_info;
+            let favorite = if data_info_host.is_favorite {
+                "★"
+            } else {
+                ""
+            };
+            Row::new(vec![
+                Cell::from(favorite),
+                Cell::from(host.domain.clone()),
+                Cell::from(host.country.to_string()),
+                Cell::from(host.asn.name.clone()),
+                Cell::from(format!(
+                    "{} ({})",
+                    get_formatted_bytes_string_with_b(data_info.incoming_bytes),
+                    data_info.incoming_packets
+                ))
+                .style(Style::default().fg(COLOR_INCOMING)),
+                Cell::from(format!(
+                    "{} ({})",
+                    get_formatted_bytes_string_with_b(data_info.outgoing_bytes),
+                    data_info.outgoing_packets
+                ))
+                .style(Style::default().fg(COLOR_OUTGOING)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        "",
+        host_translation(language),
+        country_translation(language),
+        administrative_entity_translation(language),
+        incoming_translation(language),
+        outgoing_translation(language),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Min(20),
+        Constraint::Length(8),
+        Constraint::Length(25),
+        Constraint::Length(18),
+        Constraint::Length(18),
+    ];
+    let table = Table::new(rows).header(header).widths(&widths).block(
+        Block::default().borders(Borders::ALL).title(format!(
+            " {} - {}: {} - {}: {} ",
+            showing_results_translation(language, start + 1, start + entries.len(), results_number),
+            sort_by_translation(language),
+            app.hosts_sort_type.get_picklist_label(language),
+            group_by_translation(language),
+            app.host_grouping.get_picklist_label(language)
+        )),
+    );
+    frame.render_widget(table, area);
+}
+
+fn draw_notifications(frame: &mut Frame, app: &TuiApp, area: Rect) {
+    let language = app.language;
+    let logged_notifications = &app.runtime_data.logged_notifications;
+    let block = Block::default().borders(Borders::ALL);
+    if logged_notifications.is_empty() {
+        frame.render_widget(
+            Paragraph::new(no_notifications_logged_translation(language)).block(block),
+            area,
+        );
+        return;
+    }
+    let items: Vec<Line> = logged_notifications
+        .iter()
+        .skip(app.notifications_scroll)
+        .flat_map(|group| {
+            let mut lines = vec![Line::from(vec![
+                Span::styled(
+                    group.notification.get_summary(language),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("   {}", group.get_time_span())),
+            ])];
+            lines.extend(
+                group
+                    .notification
+                    .get_body(language)
+                    .lines()
+                    .map(|line| Line::from(format!("  {line}"))),
+            );
+            lines.push(Line::from(""));
+            lines
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(items)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
@@ -0,0 +1,532 @@
+//! Module defining the `TuiApp` struct, which holds the state of the terminal user interface.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::chart::manage_chart_data::update_charts_data;
+use crate::networking::types::search_parameters::{FilterInputType, SearchParameters};
+use crate::notifications::notify_and_log::notify_and_log;
+use crate::notifications::types::notifications::Notifications;
+use crate::report::get_report_entries::{get_searched_connections, get_searched_hosts};
+use crate::report::types::host_grouping::HostGrouping;
+use crate::report::types::host_sort_type::HostSortType;
+use crate::{
+    ChartType, InfoTraffic, Language, ReportSortType, RunTimeData, RunningPage, StyleType,
+    TrafficChart,
+};
+
+/// Number of connections displayed in each page of the inspect screen
+pub const TUI_PAGE_SIZE: usize = 20;
+
+/// Struct containing the state of the terminal user interface
+pub struct TuiApp {
+    /// Name of the network adapter in use
+    pub adapter: String,
+    /// Currently displayed screen
+    pub running_page: RunningPage,
+    /// Statistics about the traffic, and notifications log
+    pub runtime_data: RunTimeData,
+    /// Traffic chart data
+    pub traffic_chart: TrafficChart,
+    /// Traffic statistics shared with the capture thread
+    pub info_traffic: Arc<Mutex<InfoTraffic>>,
+    /// Notifications configuration
+    pub notifications: Notifications,
+    /// Language of the interface
+    pub language: Language,
+    /// Search filters of the inspect screen
+    pub search: SearchParameters,
+    /// Sort criterion of the inspect screen
+    pub report_sort_type: ReportSortType,
+    /// Current page of the inspect screen (starting from 1)
+    pub page_number: usize,
+    /// Selected row in the current page of the inspect screen
+    pub selected_row: usize,
+    /// Search filter currently being edited, if any
+    pub editing_filter: Option<FilterInputType>,
+    /// Sort criterion of the hosts screen
+    pub hosts_sort_type: HostSortType,
+    /// Aggregation of the hosts in the overview and hosts screens
+    pub host_grouping: HostGrouping,
+    /// Current page of the hosts screen (starting from 1)
+    pub hosts_page_number: usize,
+    /// Number of notifications scrolled in the notifications screen
+    pub notifications_scroll: usize,
+    /// Whether the user asked to quit
+    pub quit: bool,
+}
+
+impl TuiApp {
+    pub fn new(
+        adapter: String,
+        info_traffic: Arc<Mutex<InfoTraffic>>,
+        notifications: Notifications,
+        language: Language,
+    ) -> Self {
+        Self {
+            adapter,
+            running_page: RunningPage::Overview,
+            runtime_data: RunTimeData::new(),
+            traffic_chart: TrafficChart::new(StyleType::default(), language),
+            info_traffic,
+            notifications,
+            language,
+            search: SearchParameters::default(),
+            report_sort_type: ReportSortType::MostRecent,
+            page_number: 1,
+            selected_row: 0,
+            editing_filter: None,
+            hosts_sort_type: HostSortType::default(),
+            host_grouping: HostGrouping::default(),
+            hosts_page_number: 1,
+            notifications_scroll: 0,
+            quit: false,
+        }
+    }
+
+    /// Updates the displayed data with the latest traffic statistics, to be called once per second
+    pub fn refresh_data(&mut self) {
+        let info_traffic_lock = self.info_traffic.lock().unwrap();
+        self.runtime_data.all_packets = info_traffic_lock.all_packets;
+        self.runtime_data.all_bytes = info_traffic_lock.all_bytes;
+        self.runtime_data.tot_sent_packets = info_traffic_lock.tot_sent_packets;
+        self.runtime_data.tot_received_packets = info_traffic_lock.tot_received_packets;
+        self.runtime_data.tot_received_bytes = info_traffic_lock.tot_received_bytes;
+        self.runtime_data.tot_sent_bytes = info_traffic_lock.tot_sent_bytes;
+        self.runtime_data.dropped_packets = info_traffic_lock.dropped_packets;
+        drop(info_traffic_lock);
+        let emitted_notifications = notify_and_log(
+            &mut self.runtime_data,
+            &self.notifications,
+            &self.info_traffic,
+            self.language,
+        )
+        .len();
+        self.info_traffic.lock().unwrap().favorites_last_interval = HashSet::new();
+        self.runtime_data.tot_emitted_notifications += emitted_notifications;
+        update_charts_data(&mut self.runtime_data, &mut self.traffic_chart);
+    }
+
+    /// Number of connections satisfying the current search filters
+    pub fn results_count(&self) -> usize {
+        let info_traffic_lock = self.info_traffic.lock().unwrap();
+        get_searched_connections(&info_traffic_lock, &self.search, self.report_sort_type).len()
+    }
+
+    /// Number of hosts exchanging traffic
+    pub fn hosts_count(&self) -> usize {
+        let info_traffic_lock = self.info_traffic.lock().unwrap();
+        get_searched_hosts(
+            &info_traffic_lock,
+            "",
+            self.hosts_sort_type,
+            self.host_grouping,
+            1,
+        )
+        .1
+    }
+
+    /// Updates the state according to the pressed key
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        if let Some(filter) = self.editing_filter {
+            self.edit_filter(filter, key.code);
+            return;
+        }
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Tab => self.running_page = self.running_page.next(),
+            KeyCode::BackTab => self.running_page = self.running_page.previous(),
+            KeyCode::Char('1') => self.running_page = RunningPage::Overview,
+            KeyCode::Char('2') => self.running_page = RunningPage::Inspect,
+            KeyCode::Char('3') => self.running_page = RunningPage::Hosts,
+            KeyCode::Char('4') => self.running_page = RunningPage::Notifications,
+            KeyCode::Char('c') => {
+                let chart_type = match self.traffic_chart.chart_type {
+                    ChartType::Bytes => ChartType::Packets,
+                    ChartType::Packets => ChartType::Bytes,
+                };
+                self.traffic_chart.change_kind(chart_type);
+            }
+            code => match self.running_page {
+                RunningPage::Overview => {}
+                RunningPage::Inspect => self.handle_inspect_key(code),
+                RunningPage::Hosts => self.handle_hosts_key(code),
+                RunningPage::Notifications => self.handle_notifications_key(code),
+            },
+        }
+    }
+
+    fn handle_inspect_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Up => self.selected_row = self.selected_row.saturating_sub(1),
+            KeyCode::Down => {
+                let results = self.results_count();
+                let rows_in_page = results
+                    .saturating_sub((self.page_number - 1) * TUI_PAGE_SIZE)
+                    .min(TUI_PAGE_SIZE);
+                if self.selected_row + 1 < rows_in_page {
+                    self.selected_row += 1;
+                }
+            }
+            KeyCode::Left | KeyCode::PageUp if self.page_number > 1 => {
+                self.page_number -= 1;
+                self.selected_row = 0;
+            }
+            KeyCode::Right | KeyCode::PageDown
+                if self.page_number * TUI_PAGE_SIZE < self.results_count() =>
+            {
+                self.page_number += 1;
+                self.selected_row = 0;
+            }
+            KeyCode::Char('s') => {
+                self.report_sort_type = match self.report_sort_type {
+                    ReportSortType::MostRecent => ReportSortType::MostBytes,
+                    ReportSortType::MostBytes => ReportSortType::MostPackets,
+                    ReportSortType::MostPackets => ReportSortType::MostRecent,
+                };
+                self.reset_page();
+            }
+            KeyCode::Char('f') => self.editing_filter = Some(FilterInputType::App),
+            KeyCode::Char('/') => self.editing_filter = Some(FilterInputType::Query),
+            KeyCode::Char('*') => {
+                self.search.only_favorites = !self.search.only_favorites;
+                self.reset_page();
+            }
+            KeyCode::Char('x') => {
+                self.search = SearchParameters::default();
+                self.reset_page();
+            }
+            _ => {}
+        }
+    }
+
+    fn edit_filter(&mut self, filter: FilterInputType, code: KeyCode) {
+        let value = self.search.get_mut(filter);
+        match code {
+            KeyCode::Enter | KeyCode::Esc => self.editing_filter = None,
+            KeyCode::Tab | KeyCode::Down => self.editing_filter = Some(next_filter(filter)),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.editing_filter = Some(previous_filter(filter));
+            }
+            KeyCode::Backspace => {
+                value.pop();
+                self.reset_page();
+            }
+            // only search expressions can contain spaces
+            KeyCode::Char(c) if !c.is_whitespace() || filter.eq(&FilterInputType::Query) => {
+                value.push(c);
+                self.reset_page();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_hosts_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Left | KeyCode::PageUp if self.hosts_page_number > 1 => {
+                self.hosts_page_number -= 1;
+            }
+            KeyCode::Right | KeyCode::PageDown
+                if self.hosts_page_number * TUI_PAGE_SIZE < self.hosts_count() =>
+            {
+                self.hosts_page_number += 1;
+            }
+            KeyCode::Char('s') => {
+                let index = HostSortType::ALL
+                    .iter()
+                    .position(|sort_type| sort_type.eq(&self.hosts_sort_type))
+                    .unwrap_or_default();
+                self.hosts_sort_type = HostSortType::ALL[(index + 1) % HostSortType::ALL.len()];
+                self.hosts_page_number = 1;
+            }
+            KeyCode::Char('g') => {
+                let index = HostGrouping::ALL
+                    .iter()
+                    .position(|grouping| grouping.eq(&self.host_grouping))
+                    .unwrap_or_default();
+                self.host_grouping = HostGrouping::ALL[(index + 1) % HostGrouping::ALL.len()];
+                self.hosts_page_number = 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_notifications_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Up => self.notifications_scroll = self.notifications_scroll.saturating_sub(1),
+            KeyCode::Down
+                if self.notifications_scroll + 1 < self.runtime_data.logged_notifications.len() =>
+            {
+                self.notifications_scroll += 1;
+            }
+            KeyCode::Char('x') => {
+                self.runtime_data.logged_notifications = VecDeque::new();
+                self.notifications_scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn reset_page(&mut self) {
+        self.page_number = 1;
+        self.selected_row = 0;
+    }
+}
+
+fn next_filter(filter: FilterInputType) -> FilterInputType {
+    match filter {
+        FilterInputType::App => FilterInputType::Country,
+        FilterInputType::Country => FilterInputType::Address,
+        FilterInputType::Address => FilterInputType::Source,
+        FilterInputType::Source => FilterInputType::Destination,
+        FilterInputType::Destination => FilterInputType::Port,
+        FilterInputType::Port => FilterInputType::Domain,
+        FilterInputType::Domain => FilterInputType::AS,
+        FilterInputType::AS | FilterInputType::Query => FilterInputType::App,
+    }
+}
+
+fn previous_filter(filter: FilterInputType) -> FilterInputType {
+    match filter {
+        FilterInputType::App | FilterInputType::Query => FilterInputType::AS,
+        FilterInputType::Country => FilterInputType::App,
+        FilterInputType::Address => FilterInputType::Country,
+        FilterInputType::Source => FilterInputType::Address,
+        FilterInputType::Destination => FilterInputType::Source,
+        FilterInputType::Port => FilterInputType::Destination,
+        FilterInputType::Domain => FilterInputType::Port,
+        FilterInputType::AS => FilterInputType::Domain,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::notifications::types::logged_notification::{
+        LoggedNotification, NotificationGroup, PacketsThresholdExceeded,
+    };
+    use crate::TransProtocol;
+
+    use super::*;
+
+    fn press(app: &mut TuiApp, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn new_app(connections: u16) -> TuiApp {
+        let mut info_traffic = InfoTraffic::new();
+        for port in 0..connections {
+            info_traffic.map.insert(
+                AddressPortPair::new(
+                    "10.0.0.1".to_string(),
+                    port,
+                    "10.0.0.2".to_string(),
+                    80,
+                    TransProtocol::TCP,
+                ),
+                InfoAddressPortPair::default(),
+            );
+        }
+        TuiApp::new(
+            "eth0".to_string(),
+            Arc::new(Mutex::new(info_traffic)),
+            Notifications::default(),
+            Language::EN,
+        )
+    }
+
+    #[test]
+    fn test_switch_pages_and_quit() {
+        let mut app = new_app(0);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.running_page, RunningPage::Inspect);
+        press(&mut app, KeyCode::BackTab);
+        press(&mut app, KeyCode::BackTab);
+        assert_eq!(app.running_page, RunningPage::Notifications);
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.running_page, RunningPage::Overview);
+        press(&mut app, KeyCode::Char('c'));
+        assert_eq!(app.traffic_chart.chart_type, ChartType::Packets);
+        assert!(!app.quit);
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.quit);
+
+        let mut app = new_app(0);
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(app.quit);
+    }
+
+    #[test]
+    fn test_inspect_navigation() {
+        let mut app = new_app(25);
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.selected_row, 0);
+        for _ in 0..30 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(app.selected_row, TUI_PAGE_SIZE - 1);
+        press(&mut app, KeyCode::Right);
+        assert_eq!((app.page_number, app.selected_row), (2, 0));
+        for _ in 0..30 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(app.selected_row, 4);
+        // there is no third page
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.page_number, 2);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.page_number, 1);
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.report_sort_type, ReportSortType::MostBytes);
+    }
+
+    #[test]
+    fn test_edit_filters() {
+        let mut app = new_app(25);
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('f'));
+        assert_eq!(app.editing_filter, Some(FilterInputType::App));
+        // while editing, keys are inserted in the filter instead of being shortcuts
+        for c in ['h', 't', 't', 'q', ' '] {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.search.app, "htt");
+        assert_eq!(app.page_number, 1);
+        assert!(!app.quit);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('i'));
+        press(&mut app, KeyCode::Char('t'));
+        assert_eq!(app.search.country, "it");
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.editing_filter, Some(FilterInputType::AS));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.editing_filter, None);
+        assert_eq!(app.results_count(), 0);
+        press(&mut app, KeyCode::Char('*'));
+        assert!(app.search.only_favorites);
+        press(&mut app, KeyCode::Char('x'));
+        assert!(!app.search.is_some_filter_active());
+        assert_eq!(app.results_count(), 25);
+    }
+
+    #[test]
+    fn test_edit_search_query() {
+        let mut app = new_app(25);
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Char('/'));
+        assert_eq!(app.editing_filter, Some(FilterInputType::Query));
+        for c in "sport<3 or sport>22".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.search.query, "sport<3 or sport>22");
+        assert_eq!(app.editing_filter, None);
+        assert!(app.search.is_some_filter_active());
+        assert_eq!(app.results_count(), 5);
+    }
+
+    #[test]
+    fn test_edit_address_filters() {
+        let mut app = new_app(25);
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Char('f'));
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.editing_filter, Some(FilterInputType::Address));
+        for c in "10.0.0.0/30".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.editing_filter, Some(FilterInputType::Port));
+        for c in "10-19".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.results_count(), 10);
+        press(&mut app, KeyCode::BackTab);
+        for c in "10.0.0.1".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.search.destination, "10.0.0.1");
+        assert_eq!(app.results_count(), 0);
+    }
+
+    #[test]
+    fn test_hosts_pages_and_sort() {
+        let mut app = new_app(0);
+        {
+            let mut info_traffic = app.info_traffic.lock().unwrap();
+            for i in 0..25 {
+                info_traffic.hosts.insert(
+                    Host {
+                        domain: format!("host{i}.com"),
+                        asn: Asn::default(),
+                        country: Country::ZZ,
+                    },
+                    DataInfoHost::default(),
+                );
+            }
+        }
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.running_page, RunningPage::Hosts);
+        assert_eq!(app.hosts_count(), 25);
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.hosts_page_number, 2);
+        // there is no third page
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.hosts_page_number, 2);
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.hosts_sort_type, HostSortType::MostPackets);
+        assert_eq!(app.hosts_page_number, 1);
+        // all the hosts have the same (unknown) country
+        press(&mut app, KeyCode::Char('g'));
+        press(&mut app, KeyCode::Char('g'));
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.host_grouping, HostGrouping::Country);
+        assert_eq!(app.hosts_count(), 1);
+    }
+
+    #[test]
+    fn test_notifications_scroll_and_clear() {
+        let mut app = new_app(0);
+        for _ in 0..3 {
+            app.runtime_data
+                .logged_notifications
+                .push_back(NotificationGroup::new(
+                    LoggedNotification::PacketsThresholdExceeded(PacketsThresholdExceeded {
+                        threshold: 1,
+                        incoming: 2,
+                        outgoing: 2,
+                        timestamp: String::new(),
+                    }),
+                ));
+        }
+        press(&mut app, KeyCode::Char('4'));
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Down);
+        }
+        assert_eq!(app.notifications_scroll, 2);
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.notifications_scroll, 0);
+        assert!(app.runtime_data.logged_notifications.is_empty());
+    }
+}
@@ -4,12 +4,15 @@
 use iced::widget::{Column, Text};
 use iced::{Font, Renderer};
 
+use crate::chart::export_chart::ChartImageFormat;
 use crate::gui::styles::text::TextType;
 use crate::gui::types::message::Message;
 use crate::networking::types::filters::Filters;
+use crate::networking::types::host::Host;
 use crate::translations::translations::{
     active_filters_translation, none_translation, open_report_translation,
 };
+use crate::utils::public_suffix::get_registrable_domain;
 use crate::{AppProtocol, IpVersion, Language, StyleType, TransProtocol};
 
 /// Application version number (to be displayed in gui footer)
@@ -103,14 +106,50 @@ pub fn get_formatted_bytes_string_with_b(bytes: u128) -> String {
 }
 
 pub fn get_report_path() -> PathBuf {
+    get_config_dir_file_path("report.txt")
+}
+
+/// Path of the file where all the emitted notifications are logged (JSON lines)
+pub fn get_notifications_history_path() -> PathBuf {
+    get_config_dir_file_path("notifications.jsonl")
+}
+
+/// Path of the file where the notifications history is exported
+pub fn get_notifications_export_path() -> PathBuf {
+    get_config_dir_file_path("notifications_export.csv")
+}
+
+/// Path of the file where the traffic chart is exported
+pub fn get_chart_export_path(format: ChartImageFormat) -> PathBuf {
+    get_config_dir_file_path(&format!("traffic_chart.{}", format.get_extension()))
+}
+
+/// Certificate used by the agent when not specified from the command line
+pub fn get_agent_cert_path() -> PathBuf {
+    get_config_dir_file_path("agent_cert.pem")
+}
+
+/// Private key used by the agent when not specified from the command line
+pub fn get_agent_key_path() -> PathBuf {
+    get_config_dir_file_path("agent_key.pem")
+}
+
+/// Directory containing the custom themes (TOML files)
+pub fn get_themes_dir() -> PathBuf {
+    get_config_dir_file_path("themes")
+}
+
+/// Returns the path of a file in the configuration directory,
+/// or in the home directory if the former is not available
+fn get_config_dir_file_path(file_name: &str) -> PathBuf {
     if let Ok(mut config_path) = confy::get_configuration_file_path("sniffnet", "file") {
         config_path.pop();
-        config_path.push("report.txt");
+        config_path.push(file_name);
         config_path
     } else {
-        let mut report_path = PathBuf::from(std::env::var_os("HOME").unwrap());
-        report_path.push("sniffnet_report.txt");
-        report_path
+        let mut path = PathBuf::from(std::env::var_os("HOME").unwrap());
+        path.push(format!("sniffnet_{file_name}"));
+        path
     }
 }
 
@@ -151,10 +190,22 @@ pub fn print_cli_welcome_message() {
     );
 }
 
+/// Returns the text identifying a host (or a group of hosts) in the overview and hosts pages
+pub fn get_host_label(host: &Host) -> String {
+    match (host.domain.is_empty(), host.asn.name.is_empty()) {
+        (false, false) => format!("{} - {}", host.domain, host.asn.name),
+        (false, true) => host.domain.clone(),
+        (true, false) => host.asn.name.clone(),
+        (true, true) => host.country.to_string(),
+    }
+}
+
 pub fn get_domain_from_r_dns(r_dns: String) -> String {
     if r_dns.parse::<IpAddr>().is_ok() || r_dns.is_empty() {
         // rDNS is equal to the corresponding IP address (can't be empty but checking it to be safe)
         r_dns
+    } else if let Some(registrable_domain) = get_registrable_domain(&r_dns) {
+        registrable_domain
     } else {
         let parts: Vec<&str> = r_dns.split('.').collect();
         if parts.len() >= 2 {
@@ -1,3 +1,4 @@
 pub mod asn;
 pub mod formatted_strings;
+pub mod public_suffix;
 pub mod types;
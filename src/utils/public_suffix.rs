@@ -0,0 +1,108 @@
+//! Module to find the registrable domain (eTLD+1) of a host name,
+//! based on the [Public Suffix List](https://publicsuffix.org/) embedded in the binary.
+//!
+//! The list (MPL-2.0) is `resources/DB/public_suffix_list.dat`, version 2023-02-09 23:26 UTC,
+//! downloaded from <https://publicsuffix.org/list/public_suffix_list.dat>;
+//! update the version in the README when replacing it.
+//!
+//! Only the ICANN section of the list is used: the private section contains the domains
+//! under which cloud and CDN providers hand out subdomains to their customers
+//! (e.g. `cloudfront.net`), and honouring it would split the traffic of those providers.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../../resources/DB/public_suffix_list.dat");
+
+/// Rules of the Public Suffix List
+struct PublicSuffixRules {
+    /// Public suffixes, including the wildcard ones (e.g. `*.ck`)
+    rules: HashSet<&'static str>,
+    /// Exceptions to the wildcard rules, without the leading `!` (e.g. `www.ck`)
+    exceptions: HashSet<&'static str>,
+}
+
+fn public_suffix_rules() -> &'static PublicSuffixRules {
+    static RULES: OnceLock<PublicSuffixRules> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let mut rules = HashSet::new();
+        let mut exceptions = HashSet::new();
+        for line in PUBLIC_SUFFIX_LIST
+            .lines()
+            .take_while(|line| !line.starts_with("// ===END ICANN DOMAINS==="))
+        {
+            let Some(rule) = line.split_whitespace().next() else {
+                continue;
+            };
+            if rule.starts_with("//") {
+                continue;
+            }
+            if let Some(exception) = rule.strip_prefix('!') {
+                exceptions.insert(exception);
+            } else {
+                rules.insert(rule);
+            }
+        }
+        PublicSuffixRules { rules, exceptions }
+    })
+}
+
+/// Returns the number of labels of the public suffix of the given host name labels
+fn public_suffix_len(labels: &[&str]) -> usize {
+    let PublicSuffixRules { rules, exceptions } = public_suffix_rules();
+    // the longest matching rule prevails, and exceptions prevail over wildcards
+    for i in 0..labels.len() {
+        let candidate = labels[i..].join(".");
+        if exceptions.contains(candidate.as_str()) {
+            return labels.len() - i - 1;
+        }
+        if rules.contains(candidate.as_str())
+            || (i + 1 < labels.len()
+                && rules.contains(format!("*.{}", labels[i + 1..].join(".")).as_str()))
+        {
+            return labels.len() - i;
+        }
+    }
+    // if no rule matches, the public suffix is the top level domain
+    1
+}
+
+/// Returns the registrable domain (public suffix plus one label) of the given host name,
+/// e.g. `bbc.co.uk` for `news.bbc.co.uk`.
+///
+/// Returns `None` if the name is itself a public suffix.
+pub fn get_registrable_domain(name: &str) -> Option<String> {
+    let name = name.trim_end_matches('.').to_lowercase();
+    let labels: Vec<&str> = name.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) {
+        return None;
+    }
+    let suffix_len = public_suffix_len(&labels);
+    if labels.len() <= suffix_len {
+        return None;
+    }
+    Some(labels[labels.len() - suffix_len - 1..].join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("www.example.com", Some("example.com"))]
+    #[case("example.com.", Some("example.com"))]
+    #[case("lhr25s34-in-f14.1e100.net", Some("1e100.net"))]
+    #[case("news.BBC.co.uk", Some("bbc.co.uk"))]
+    #[case("a.b.c.example.unknowntld", Some("example.unknowntld"))]
+    #[case("d111111abcdef8.cloudfront.net", Some("cloudfront.net"))]
+    #[case("foo.bar.kawasaki.jp", Some("foo.bar.kawasaki.jp"))]
+    #[case("www.city.kawasaki.jp", Some("city.kawasaki.jp"))]
+    #[case("co.uk", None)]
+    #[case("com", None)]
+    #[case("", None)]
+    fn test_registrable_domain(#[case] name: &str, #[case] expected: Option<&str>) {
+        assert_eq!(get_registrable_domain(name), expected.map(str::to_string));
+    }
+}